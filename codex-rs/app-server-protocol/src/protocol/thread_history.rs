@@ -8,6 +8,7 @@ use codex_protocol::protocol::AgentReasoningRawContentEvent;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ThreadRolledBackEvent;
 use codex_protocol::protocol::TurnAbortedEvent;
+use codex_protocol::protocol::TurnSteeredEvent;
 use codex_protocol::protocol::UserMessageEvent;
 
 /// Convert persisted [`EventMsg`] entries into a sequence of [`Turn`] values.
@@ -59,6 +60,10 @@ impl ThreadHistoryBuilder {
             EventMsg::EnteredReviewMode(_) => {}
             EventMsg::ExitedReviewMode(_) => {}
             EventMsg::ThreadRolledBack(payload) => self.handle_thread_rollback(payload),
+            EventMsg::TurnSteered(payload) => self.handle_turn_steered(payload),
+            // Kept for transcript export; file changes are not replayed as items.
+            EventMsg::PatchApplyEnd(_) => {}
+            EventMsg::TurnDiff(_) => {}
             EventMsg::UndoCompleted(_) => {}
             EventMsg::TurnAborted(payload) => self.handle_turn_aborted(payload),
             _ => {}
@@ -74,6 +79,16 @@ impl ThreadHistoryBuilder {
         self.current_turn = Some(turn);
     }
 
+    /// Steered input joins the turn it was delivered to instead of starting a
+    /// new one.
+    fn handle_turn_steered(&mut self, payload: &TurnSteeredEvent) {
+        let id = self.next_item_id();
+        let content = payload.items.iter().cloned().map(UserInput::from).collect();
+        self.ensure_turn()
+            .items
+            .push(ThreadItem::UserMessage { id, content });
+    }
+
     fn handle_agent_message(&mut self, text: String) {
         if text.is_empty() {
             return;
@@ -521,4 +536,48 @@ mod tests {
         let turns = build_turns_from_event_msgs(&events);
         assert_eq!(turns, Vec::<Turn>::new());
     }
+
+    #[test]
+    fn steered_input_stays_in_the_running_turn() {
+        let events = vec![
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "Refactor the parser".into(),
+                images: None,
+            }),
+            EventMsg::TurnSteered(TurnSteeredEvent {
+                items: vec![codex_protocol::user_input::UserInput::Text {
+                    text: "Keep the public API".into(),
+                }],
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Done".into(),
+            }),
+        ];
+
+        let turns = build_turns_from_event_msgs(&events);
+        let expected = vec![Turn {
+            id: "turn-1".into(),
+            status: TurnStatus::Completed,
+            error: None,
+            items: vec![
+                ThreadItem::UserMessage {
+                    id: "item-1".into(),
+                    content: vec![UserInput::Text {
+                        text: "Refactor the parser".into(),
+                    }],
+                },
+                ThreadItem::UserMessage {
+                    id: "item-2".into(),
+                    content: vec![UserInput::Text {
+                        text: "Keep the public API".into(),
+                    }],
+                },
+                ThreadItem::AgentMessage {
+                    id: "item-3".into(),
+                    text: "Done".into(),
+                },
+            ],
+        }];
+        assert_eq!(turns, expected);
+    }
 }
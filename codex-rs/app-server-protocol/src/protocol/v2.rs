@@ -4,6 +4,8 @@ use std::path::PathBuf;
 use crate::protocol::common::AuthMode;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ExecPolicyAmendment as CoreExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyExplanation as CoreExecPolicyExplanation;
+use codex_protocol::approvals::ExecPolicyRuleMatch as CoreExecPolicyRuleMatch;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode as CoreSandboxMode;
@@ -90,6 +92,7 @@ pub enum CodexErrorInfo {
     Unauthorized,
     BadRequest,
     ThreadRollbackFailed,
+    TurnSteerFailed,
     SandboxError,
     /// The response SSE stream disconnected in the middle of a turn before completion.
     ResponseStreamDisconnected {
@@ -121,6 +124,7 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::Unauthorized => CodexErrorInfo::Unauthorized,
             CoreCodexErrorInfo::BadRequest => CodexErrorInfo::BadRequest,
             CoreCodexErrorInfo::ThreadRollbackFailed => CodexErrorInfo::ThreadRollbackFailed,
+            CoreCodexErrorInfo::TurnSteerFailed => CodexErrorInfo::TurnSteerFailed,
             CoreCodexErrorInfo::SandboxError => CodexErrorInfo::SandboxError,
             CoreCodexErrorInfo::ResponseStreamDisconnected { http_status_code } => {
                 CodexErrorInfo::ResponseStreamDisconnected { http_status_code }
@@ -306,6 +310,10 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    #[serde(default)]
+    pub deny_read: Vec<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -650,6 +658,66 @@ impl From<CoreExecPolicyAmendment> for ExecPolicyAmendment {
     }
 }
 
+v2_enum_from_core!(
+    pub enum ExecPolicyDecision from codex_protocol::approvals::ExecPolicyDecision {
+        Allow, Prompt, Forbidden
+    }
+);
+
+v2_enum_from_core!(
+    pub enum ExecPolicyLayer from codex_protocol::approvals::ExecPolicyLayer {
+        System, User, Project
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ExecPolicyRuleMatch {
+    /// Leading command tokens the rule matched.
+    pub matched_prefix: Vec<String>,
+    pub decision: ExecPolicyDecision,
+    pub justification: Option<String>,
+    /// Rules file that defined the rule; null for rules added during the session.
+    pub source: Option<PathBuf>,
+    pub layer: Option<ExecPolicyLayer>,
+}
+
+impl From<CoreExecPolicyRuleMatch> for ExecPolicyRuleMatch {
+    fn from(value: CoreExecPolicyRuleMatch) -> Self {
+        Self {
+            matched_prefix: value.matched_prefix,
+            decision: value.decision.into(),
+            justification: value.justification,
+            source: value.source,
+            layer: value.layer.map(ExecPolicyLayer::from),
+        }
+    }
+}
+
+/// Why a command needs approval: the execpolicy rules that decided, or, when no rule matched,
+/// a note on the built-in safety heuristic that did.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ExecPolicyExplanation {
+    pub matched_rules: Vec<ExecPolicyRuleMatch>,
+    pub heuristic: Option<String>,
+}
+
+impl From<CoreExecPolicyExplanation> for ExecPolicyExplanation {
+    fn from(value: CoreExecPolicyExplanation) -> Self {
+        Self {
+            matched_rules: value
+                .matched_rules
+                .into_iter()
+                .map(ExecPolicyRuleMatch::from)
+                .collect(),
+            heuristic: value.heuristic,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "camelCase")]
 #[ts(tag = "type")]
@@ -1133,6 +1201,75 @@ pub struct ThreadArchiveParams {
 #[ts(export_to = "v2/")]
 pub struct ThreadArchiveResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUnarchiveParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUnarchiveResponse {
+    /// The restored thread. `turns` is empty.
+    pub thread: Thread,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUpdateParams {
+    pub thread_id: String,
+    /// New user-visible title. An empty string clears the title; when omitted
+    /// the title is left unchanged.
+    pub title: Option<String>,
+    /// Replacement set of tags. Tags are trimmed and de-duplicated; when
+    /// omitted the tags are left unchanged.
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUpdateResponse {
+    /// The updated thread. `turns` is empty.
+    pub thread: Thread,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ThreadExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadExportParams {
+    pub thread_id: String,
+    pub format: ThreadExportFormat,
+    /// Mask likely secrets and replace the home directory with `~`.
+    #[serde(default)]
+    pub redact: bool,
+    /// Lines kept from each tool call's output; `0` keeps everything. Defaults
+    /// to 40.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub max_output_lines: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadExportResponse {
+    /// The rendered transcript.
+    pub content: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1180,6 +1317,46 @@ pub struct ThreadListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Whitespace-separated terms that must all appear (case-insensitively) in
+    /// the thread title or in one of its user or agent messages.
+    pub query: Option<String>,
+    /// Only threads whose working directory is exactly this path.
+    pub cwd: Option<PathBuf>,
+    /// Only threads started on this git branch.
+    pub git_branch: Option<String>,
+    /// Only threads created at or after this Unix timestamp (in seconds).
+    #[ts(type = "number | null")]
+    pub created_after: Option<i64>,
+    /// Only threads created before this Unix timestamp (in seconds).
+    #[ts(type = "number | null")]
+    pub created_before: Option<i64>,
+    /// Only threads from these sources. When omitted or empty, all sources match.
+    pub sources: Option<Vec<SessionSource>>,
+    /// Only threads carrying all of these tags.
+    pub tags: Option<Vec<String>>,
+    /// Optional provider filter with the same semantics as in `thread/list`.
+    pub model_providers: Option<Vec<String>>,
+    /// Opaque pagination cursor returned by a previous call.
+    pub cursor: Option<String>,
+    /// Optional page size; defaults to a reasonable server-side value.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads, newest first. `turns` is empty.
+    pub data: Vec<Thread>,
+    /// Opaque cursor to pass to the next call to continue after the last item.
+    /// if None, there are no more items to return.
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1201,6 +1378,34 @@ pub struct ThreadLoadedListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSubscribeParams {
+    /// Id of a thread that is currently loaded in memory.
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSubscribeResponse {
+    /// The thread, including the turns recorded so far.
+    pub thread: Thread,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUnsubscribeParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUnsubscribeResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1316,6 +1521,10 @@ pub struct Thread {
     pub source: SessionSource,
     /// Optional Git metadata captured when the thread was created.
     pub git_info: Option<GitInfo>,
+    /// User-visible title set with `thread/update`.
+    pub title: Option<String>,
+    /// Tags set with `thread/update`.
+    pub tags: Vec<String>,
     /// Only populated on `thread/resume`, `thread/rollback`, `thread/fork` responses.
     /// For all other responses and notifications returning a Thread,
     /// the turns field will be an empty list.
@@ -1529,6 +1738,21 @@ pub struct TurnInterruptParams {
 #[ts(export_to = "v2/")]
 pub struct TurnInterruptResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnSteerParams {
+    pub thread_id: String,
+    /// Id of the running turn. The steer is rejected if another turn is running.
+    pub turn_id: String,
+    pub input: Vec<UserInput>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnSteerResponse {}
+
 // User input types
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -1752,6 +1976,16 @@ pub struct TurnStartedNotification {
     pub turn: Turn,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnSteeredNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// The steer input, now delivered to the model.
+    pub input: Vec<UserInput>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1960,6 +2194,33 @@ pub struct WindowsWorldWritableWarningNotification {
     pub failed_scan: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum SubAgentStatus {
+    Running,
+    Completed,
+    Interrupted,
+    Failed,
+    Shutdown,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification that a sub-agent spawned by `threadId` changed status.
+/// The sub-agent's own turns and items are emitted with `threadId` set to `agentThreadId`;
+/// its approval requests are raised on the parent thread.
+pub struct SubAgentUpdatedNotification {
+    pub thread_id: String,
+    pub agent_thread_id: String,
+    /// Name of the `[agents]` profile the sub-agent was spawned with, if any.
+    pub agent: Option<String>,
+    pub status: SubAgentStatus,
+    pub last_agent_message: Option<String>,
+    pub error: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1979,6 +2240,9 @@ pub struct CommandExecutionRequestApprovalParams {
     pub reason: Option<String>,
     /// Optional proposed execpolicy amendment to allow similar commands without prompting.
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    /// Optional explanation of which execpolicy rules or heuristic required approval.
+    #[serde(default)]
+    pub explanation: Option<ExecPolicyExplanation>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
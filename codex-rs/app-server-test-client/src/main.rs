@@ -833,6 +833,7 @@ impl CodexClient {
             item_id,
             reason,
             proposed_execpolicy_amendment,
+            explanation,
         } = params;
 
         println!(
@@ -844,6 +845,9 @@ impl CodexClient {
         if let Some(execpolicy_amendment) = proposed_execpolicy_amendment.as_ref() {
             println!("< proposed execpolicy amendment: {execpolicy_amendment:?}");
         }
+        if let Some(explanation) = explanation.as_ref() {
+            println!("< explanation: {explanation:?}");
+        }
 
         let response = CommandExecutionRequestApprovalResponse {
             decision: CommandExecutionApprovalDecision::Accept,
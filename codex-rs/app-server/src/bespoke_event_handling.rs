@@ -26,6 +26,7 @@ use codex_app_server_protocol::ErrorNotification;
 use codex_app_server_protocol::ExecCommandApprovalParams;
 use codex_app_server_protocol::ExecCommandApprovalResponse;
 use codex_app_server_protocol::ExecPolicyAmendment as V2ExecPolicyAmendment;
+use codex_app_server_protocol::ExecPolicyExplanation as V2ExecPolicyExplanation;
 use codex_app_server_protocol::FileChangeApprovalDecision;
 use codex_app_server_protocol::FileChangeOutputDeltaNotification;
 use codex_app_server_protocol::FileChangeRequestApprovalParams;
@@ -46,6 +47,8 @@ use codex_app_server_protocol::ReasoningSummaryTextDeltaNotification;
 use codex_app_server_protocol::ReasoningTextDeltaNotification;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
+use codex_app_server_protocol::SubAgentStatus;
+use codex_app_server_protocol::SubAgentUpdatedNotification;
 use codex_app_server_protocol::TerminalInteractionNotification;
 use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadRollbackResponse;
@@ -59,6 +62,8 @@ use codex_app_server_protocol::TurnInterruptResponse;
 use codex_app_server_protocol::TurnPlanStep;
 use codex_app_server_protocol::TurnPlanUpdatedNotification;
 use codex_app_server_protocol::TurnStatus;
+use codex_app_server_protocol::TurnSteeredNotification;
+use codex_app_server_protocol::UserInput as V2UserInput;
 use codex_app_server_protocol::build_turns_from_event_msgs;
 use codex_core::CodexThread;
 use codex_core::parse_command::shlex_join;
@@ -73,6 +78,7 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SubAgentActivityEvent;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::review_format::format_review_findings_block;
@@ -196,6 +202,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             cwd,
             reason,
             proposed_execpolicy_amendment,
+            explanation,
             parsed_cmd,
         }) => match api_version {
             ApiVersion::V1 => {
@@ -233,6 +240,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     item_id: item_id.clone(),
                     reason,
                     proposed_execpolicy_amendment: proposed_execpolicy_amendment_v2,
+                    explanation: explanation.map(V2ExecPolicyExplanation::from),
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::CommandExecutionRequestApproval(
@@ -289,6 +297,16 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .send_server_notification(ServerNotification::AgentMessageDelta(notification))
                 .await;
         }
+        EventMsg::TurnSteered(event) => {
+            let notification = TurnSteeredNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id.clone(),
+                input: event.items.into_iter().map(V2UserInput::from).collect(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::TurnSteered(notification))
+                .await;
+        }
         EventMsg::ContextCompacted(..) => {
             let notification = ContextCompactedNotification {
                 thread_id: conversation_id.to_string(),
@@ -376,6 +394,18 @@ pub(crate) async fn apply_bespoke_event_handling(
                 codex_error_info: ev.codex_error_info.map(V2CodexErrorInfo::from),
                 additional_details: None,
             };
+            // A rejected `turn/steer` leaves the running turn alone, so only notify.
+            if matches!(codex_error_info, Some(CoreCodexErrorInfo::TurnSteerFailed)) {
+                outgoing
+                    .send_server_notification(ServerNotification::Error(ErrorNotification {
+                        error: turn_error,
+                        will_retry: false,
+                        thread_id: conversation_id.to_string(),
+                        turn_id: event_turn_id.clone(),
+                    }))
+                    .await;
+                return;
+            }
             handle_error(conversation_id, turn_error.clone(), &turn_summary_store).await;
             outgoing
                 .send_server_notification(ServerNotification::Error(ErrorNotification {
@@ -958,6 +988,37 @@ async fn find_and_remove_turn_summary(
     map.remove(&conversation_id).unwrap_or_default()
 }
 
+/// Map a forwarded sub-agent event to a `thread/subAgent/updated` notification, or `None` when
+/// the event does not change the sub-agent's status.
+pub(crate) fn sub_agent_updated_notification(
+    parent_id: ThreadId,
+    activity: &SubAgentActivityEvent,
+) -> Option<SubAgentUpdatedNotification> {
+    let (status, last_agent_message, error) = match activity.msg.as_ref() {
+        EventMsg::TurnStarted(_) => (SubAgentStatus::Running, None, None),
+        EventMsg::TurnComplete(ev) => (
+            SubAgentStatus::Completed,
+            ev.last_agent_message.clone(),
+            None,
+        ),
+        EventMsg::TurnAborted(_) => (SubAgentStatus::Interrupted, None, None),
+        EventMsg::Error(ev) if ev.codex_error_info == Some(CoreCodexErrorInfo::TurnSteerFailed) => {
+            return None;
+        }
+        EventMsg::Error(ev) => (SubAgentStatus::Failed, None, Some(ev.message.clone())),
+        EventMsg::ShutdownComplete => (SubAgentStatus::Shutdown, None, None),
+        _ => return None,
+    };
+    Some(SubAgentUpdatedNotification {
+        thread_id: parent_id.to_string(),
+        agent_thread_id: activity.agent_id.to_string(),
+        agent: activity.agent.clone(),
+        status,
+        last_agent_message,
+        error,
+    })
+}
+
 async fn handle_turn_complete(
     conversation_id: ThreadId,
     event_turn_id: String,
@@ -1106,6 +1167,15 @@ async fn on_exec_approval_response(
         Ok(value) => value,
         Err(err) => {
             error!("request failed: {err:?}");
+            if let Err(submit_err) = conversation
+                .submit(Op::ExecApproval {
+                    id: event_turn_id,
+                    decision: ReviewDecision::Denied,
+                })
+                .await
+            {
+                error!("failed to submit denied ExecApproval after request failure: {submit_err}");
+            }
             return;
         }
     };
@@ -1433,6 +1503,7 @@ mod tests {
     use codex_core::protocol::RateLimitWindow;
     use codex_core::protocol::TokenUsage;
     use codex_core::protocol::TokenUsageInfo;
+    use codex_core::protocol::TurnCompleteEvent;
     use codex_protocol::plan_tool::PlanItemArg;
     use codex_protocol::plan_tool::StepStatus;
     use mcp_types::CallToolResult;
@@ -1457,6 +1528,32 @@ mod tests {
         assert_eq!(completion_status, None);
     }
 
+    #[test]
+    fn sub_agent_completion_maps_to_updated_notification() {
+        let parent_id = ThreadId::new();
+        let agent_id = ThreadId::new();
+        let activity = SubAgentActivityEvent {
+            agent_id,
+            turn_id: "1".to_string(),
+            agent: Some("reviewer".to_string()),
+            msg: Box::new(EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message: Some("done".to_string()),
+            })),
+        };
+
+        assert_eq!(
+            sub_agent_updated_notification(parent_id, &activity),
+            Some(SubAgentUpdatedNotification {
+                thread_id: parent_id.to_string(),
+                agent_thread_id: agent_id.to_string(),
+                agent: Some("reviewer".to_string()),
+                status: SubAgentStatus::Completed,
+                last_agent_message: Some("done".to_string()),
+                error: None,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_error_records_message() -> Result<()> {
         let conversation_id = ThreadId::new();
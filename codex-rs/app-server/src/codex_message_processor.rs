@@ -1,4 +1,5 @@
 use crate::bespoke_event_handling::apply_bespoke_event_handling;
+use crate::bespoke_event_handling::sub_agent_updated_notification;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
@@ -88,6 +89,9 @@ use codex_app_server_protocol::SkillsListResponse;
 use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
+use codex_app_server_protocol::ThreadExportFormat;
+use codex_app_server_protocol::ThreadExportParams;
+use codex_app_server_protocol::ThreadExportResponse;
 use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadForkResponse;
 use codex_app_server_protocol::ThreadItem;
@@ -98,9 +102,19 @@ use codex_app_server_protocol::ThreadLoadedListResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::ThreadStartedNotification;
+use codex_app_server_protocol::ThreadSubscribeParams;
+use codex_app_server_protocol::ThreadSubscribeResponse;
+use codex_app_server_protocol::ThreadUnarchiveParams;
+use codex_app_server_protocol::ThreadUnarchiveResponse;
+use codex_app_server_protocol::ThreadUnsubscribeParams;
+use codex_app_server_protocol::ThreadUnsubscribeResponse;
+use codex_app_server_protocol::ThreadUpdateParams;
+use codex_app_server_protocol::ThreadUpdateResponse;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnError;
 use codex_app_server_protocol::TurnInterruptParams;
@@ -108,6 +122,8 @@ use codex_app_server_protocol::TurnStartParams;
 use codex_app_server_protocol::TurnStartResponse;
 use codex_app_server_protocol::TurnStartedNotification;
 use codex_app_server_protocol::TurnStatus;
+use codex_app_server_protocol::TurnSteerParams;
+use codex_app_server_protocol::TurnSteerResponse;
 use codex_app_server_protocol::UserInfoResponse;
 use codex_app_server_protocol::UserInput as V2UserInput;
 use codex_app_server_protocol::UserSavedConfig;
@@ -121,7 +137,9 @@ use codex_core::InitialHistory;
 use codex_core::NewThread;
 use codex_core::RolloutRecorder;
 use codex_core::SessionMeta;
+use codex_core::SessionMetaUpdate;
 use codex_core::ThreadManager;
+use codex_core::ThreadSearchFilter;
 use codex_core::auth::CLIENT_ID;
 use codex_core::auth::login_with_api_key;
 use codex_core::config::Config;
@@ -133,12 +151,16 @@ use codex_core::default_client::get_codex_user_agent;
 use codex_core::error::CodexErr;
 use codex_core::exec::ExecParams;
 use codex_core::exec_env::create_env;
+use codex_core::export::ExportFormat;
+use codex_core::export::ExportOptions;
+use codex_core::export::export_rollout;
 use codex_core::features::Feature;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::mcp::collect_mcp_snapshot;
 use codex_core::mcp::group_tools_by_server;
 use codex_core::parse_cursor;
+use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDelivery as CoreReviewDelivery;
@@ -147,6 +169,7 @@ use codex_core::protocol::ReviewTarget as CoreReviewTarget;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::read_head_for_summary;
 use codex_core::sandboxing::SandboxPermissions;
+use codex_core::update_session_meta;
 use codex_feedback::CodexFeedback;
 use codex_login::ServerOptions as LoginServerOptions;
 use codex_login::ShutdownHandle;
@@ -160,6 +183,7 @@ use codex_protocol::protocol::McpAuthStatus as CoreMcpAuthStatus;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::SessionSource as CoreSessionSource;
 use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 use codex_protocol::user_input::UserInput as CoreInputItem;
 use codex_rmcp_client::perform_oauth_login_return_url;
@@ -227,6 +251,9 @@ pub(crate) struct CodexMessageProcessor {
     config: Arc<Config>,
     cli_overrides: Vec<(String, TomlValue)>,
     conversation_listeners: HashMap<Uuid, oneshot::Sender<()>>,
+    // Most recent v2 listener per thread, so `thread/subscribe` can reuse it instead of
+    // attaching a second listener that would compete for the same events.
+    thread_listeners: HashMap<ThreadId, Uuid>,
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
@@ -284,6 +311,7 @@ impl CodexMessageProcessor {
             config,
             cli_overrides,
             conversation_listeners: HashMap::new(),
+            thread_listeners: HashMap::new(),
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             pending_rollbacks: Arc::new(Mutex::new(HashMap::new())),
@@ -380,15 +408,33 @@ impl CodexMessageProcessor {
             ClientRequest::ThreadArchive { request_id, params } => {
                 self.thread_archive(request_id, params).await;
             }
+            ClientRequest::ThreadUnarchive { request_id, params } => {
+                self.thread_unarchive(request_id, params).await;
+            }
+            ClientRequest::ThreadUpdate { request_id, params } => {
+                self.thread_update(request_id, params).await;
+            }
+            ClientRequest::ThreadExport { request_id, params } => {
+                self.thread_export(request_id, params).await;
+            }
             ClientRequest::ThreadRollback { request_id, params } => {
                 self.thread_rollback(request_id, params).await;
             }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(request_id, params).await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(request_id, params).await;
+            }
             ClientRequest::ThreadLoadedList { request_id, params } => {
                 self.thread_loaded_list(request_id, params).await;
             }
+            ClientRequest::ThreadSubscribe { request_id, params } => {
+                self.thread_subscribe(request_id, params).await;
+            }
+            ClientRequest::ThreadUnsubscribe { request_id, params } => {
+                self.thread_unsubscribe(request_id, params).await;
+            }
             ClientRequest::SkillsList { request_id, params } => {
                 self.skills_list(request_id, params).await;
             }
@@ -398,6 +444,9 @@ impl CodexMessageProcessor {
             ClientRequest::TurnInterrupt { request_id, params } => {
                 self.turn_interrupt(request_id, params).await;
             }
+            ClientRequest::TurnSteer { request_id, params } => {
+                self.turn_steer(request_id, params).await;
+            }
             ClientRequest::ReviewStart { request_id, params } => {
                 self.review_start(request_id, params).await;
             }
@@ -1235,6 +1284,7 @@ impl CodexMessageProcessor {
         let outgoing = self.outgoing.clone();
         let req_id = request_id;
         let sandbox_cwd = self.config.cwd.clone();
+        let deny_read = self.config.sandbox_deny_read.clone();
 
         tokio::spawn(async move {
             match codex_core::exec::process_exec_tool_call(
@@ -1242,6 +1292,7 @@ impl CodexMessageProcessor {
                 &effective_policy,
                 sandbox_cwd.as_path(),
                 &codex_linux_sandbox_exe,
+                &deny_read,
                 None,
             )
             .await
@@ -1537,6 +1588,199 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_unarchive(&mut self, request_id: RequestId, params: ThreadUnarchiveParams) {
+        let thread_id = match ThreadId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        let rollout_path = match self.unarchive_thread_common(thread_id).await {
+            Ok(path) => path,
+            Err(err) => {
+                self.outgoing.send_error(request_id, err).await;
+                return;
+            }
+        };
+
+        let fallback_provider = self.config.model_provider_id.as_str();
+        match read_summary_from_rollout(&rollout_path, fallback_provider).await {
+            Ok(summary) => {
+                let response = ThreadUnarchiveResponse {
+                    thread: summary_to_thread(summary),
+                };
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!(
+                        "failed to load rollout `{}` for thread {thread_id}: {err}",
+                        rollout_path.display()
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn thread_update(&self, request_id: RequestId, params: ThreadUpdateParams) {
+        let ThreadUpdateParams {
+            thread_id,
+            title,
+            tags,
+        } = params;
+        let thread_id = match ThreadId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        let update = SessionMetaUpdate { title, tags };
+
+        // Loaded threads are still being appended to, so route the rewrite through
+        // their recorder; otherwise edit the rollout on disk directly.
+        let (rollout_path, result) = match self.thread_manager.get_thread(thread_id).await {
+            Ok(thread) => (
+                thread.rollout_path(),
+                thread.update_session_meta(update).await,
+            ),
+            Err(_) => {
+                let path = match find_thread_path_by_id_str(
+                    &self.config.codex_home,
+                    &thread_id.to_string(),
+                )
+                .await
+                {
+                    Ok(Some(path)) => path,
+                    Ok(None) => {
+                        self.send_invalid_request_error(
+                            request_id,
+                            format!("no rollout found for thread id {thread_id}"),
+                        )
+                        .await;
+                        return;
+                    }
+                    Err(err) => {
+                        self.send_invalid_request_error(
+                            request_id,
+                            format!("failed to locate thread id {thread_id}: {err}"),
+                        )
+                        .await;
+                        return;
+                    }
+                };
+                let result = update_session_meta(&path, update).await;
+                (path, result)
+            }
+        };
+        if let Err(err) = result {
+            self.send_internal_error(
+                request_id,
+                format!("failed to update thread {thread_id}: {err}"),
+            )
+            .await;
+            return;
+        }
+
+        let fallback_provider = self.config.model_provider_id.as_str();
+        match read_summary_from_rollout(&rollout_path, fallback_provider).await {
+            Ok(summary) => {
+                let response = ThreadUpdateResponse {
+                    thread: summary_to_thread(summary),
+                };
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!(
+                        "failed to load rollout `{}` for thread {thread_id}: {err}",
+                        rollout_path.display()
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn thread_export(&self, request_id: RequestId, params: ThreadExportParams) {
+        let ThreadExportParams {
+            thread_id,
+            format,
+            redact,
+            max_output_lines,
+        } = params;
+        let thread_id = match ThreadId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        let rollout_path = match self.thread_manager.get_thread(thread_id).await {
+            Ok(thread) => thread.rollout_path(),
+            Err(_) => {
+                match find_thread_path_by_id_str(&self.config.codex_home, &thread_id.to_string())
+                    .await
+                {
+                    Ok(Some(path)) => path,
+                    Ok(None) => {
+                        self.send_invalid_request_error(
+                            request_id,
+                            format!("no rollout found for thread id {thread_id}"),
+                        )
+                        .await;
+                        return;
+                    }
+                    Err(err) => {
+                        self.send_invalid_request_error(
+                            request_id,
+                            format!("failed to locate thread id {thread_id}: {err}"),
+                        )
+                        .await;
+                        return;
+                    }
+                }
+            }
+        };
+
+        let format = match format {
+            ThreadExportFormat::Markdown => ExportFormat::Markdown,
+            ThreadExportFormat::Html => ExportFormat::Html,
+            ThreadExportFormat::Json => ExportFormat::Json,
+        };
+        let mut options = ExportOptions::new(format);
+        options.redact = redact;
+        if let Some(max_output_lines) = max_output_lines {
+            options.max_output_lines = max_output_lines as usize;
+        }
+        match export_rollout(&rollout_path, &options).await {
+            Ok(content) => {
+                self.outgoing
+                    .send_response(request_id, ThreadExportResponse { content })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!(
+                        "failed to export rollout `{}` for thread {thread_id}: {err}",
+                        rollout_path.display()
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
     async fn thread_rollback(&mut self, request_id: RequestId, params: ThreadRollbackParams) {
         let ThreadRollbackParams {
             thread_id,
@@ -1594,7 +1838,13 @@ impl CodexMessageProcessor {
             .unwrap_or(THREAD_LIST_DEFAULT_LIMIT)
             .clamp(1, THREAD_LIST_MAX_LIMIT);
         let (summaries, next_cursor) = match self
-            .list_threads_common(requested_page_size, cursor, model_providers)
+            .list_threads_common(
+                requested_page_size,
+                cursor,
+                model_providers,
+                INTERACTIVE_SESSION_SOURCES,
+                None,
+            )
             .await
         {
             Ok(r) => r,
@@ -1609,6 +1859,58 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_search(&self, request_id: RequestId, params: ThreadSearchParams) {
+        let ThreadSearchParams {
+            query,
+            cwd,
+            git_branch,
+            created_after,
+            created_before,
+            sources,
+            tags,
+            model_providers,
+            cursor,
+            limit,
+        } = params;
+
+        let requested_page_size = limit
+            .map(|value| value as usize)
+            .unwrap_or(THREAD_LIST_DEFAULT_LIMIT)
+            .clamp(1, THREAD_LIST_MAX_LIMIT);
+        let allowed_sources: Vec<CoreSessionSource> = match sources {
+            Some(sources) => sources.into_iter().map(Into::into).collect(),
+            None => INTERACTIVE_SESSION_SOURCES.to_vec(),
+        };
+        let filter = ThreadSearchFilter {
+            query,
+            cwd,
+            git_branch,
+            created_after,
+            created_before,
+            tags: tags.unwrap_or_default(),
+        };
+        let (summaries, next_cursor) = match self
+            .list_threads_common(
+                requested_page_size,
+                cursor,
+                model_providers,
+                &allowed_sources,
+                Some(&filter),
+            )
+            .await
+        {
+            Ok(r) => r,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let data = summaries.into_iter().map(summary_to_thread).collect();
+        let response = ThreadSearchResponse { data, next_cursor };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
     async fn thread_loaded_list(&self, request_id: RequestId, params: ThreadLoadedListParams) {
         let ThreadLoadedListParams { cursor, limit } = params;
         let mut data = self
@@ -1664,6 +1966,82 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_subscribe(&mut self, request_id: RequestId, params: ThreadSubscribeParams) {
+        let ThreadSubscribeParams { thread_id } = params;
+        let (thread_id, thread) = match self.load_thread(&thread_id).await {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        // Which connection receives the thread's notifications is decided by the transport
+        // layer; here we only make sure the thread is being listened to at all.
+        let has_listener = self
+            .thread_listeners
+            .get(&thread_id)
+            .and_then(|subscription_id| self.conversation_listeners.get(subscription_id))
+            .is_some_and(|cancel_tx| !cancel_tx.is_closed());
+        if !has_listener
+            && let Err(err) = self
+                .attach_conversation_listener(thread_id, false, ApiVersion::V2)
+                .await
+        {
+            self.outgoing.send_error(request_id, err).await;
+            return;
+        }
+
+        let rollout_path = thread.rollout_path();
+        let fallback_provider = self.config.model_provider_id.as_str();
+        let mut thread =
+            match read_summary_from_rollout(rollout_path.as_path(), fallback_provider).await {
+                Ok(summary) => summary_to_thread(summary),
+                Err(err) => {
+                    self.send_internal_error(
+                        request_id,
+                        format!(
+                            "failed to load rollout `{}` for thread {thread_id}: {err}",
+                            rollout_path.display()
+                        ),
+                    )
+                    .await;
+                    return;
+                }
+            };
+        match RolloutRecorder::get_rollout_history(&rollout_path).await {
+            Ok(history) => {
+                thread.turns = history
+                    .get_event_msgs()
+                    .as_deref()
+                    .map_or_else(Vec::new, build_turns_from_event_msgs);
+            }
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!("failed to load rollout `{}`: {err}", rollout_path.display()),
+                )
+                .await;
+                return;
+            }
+        }
+
+        let response = ThreadSubscribeResponse { thread };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn thread_unsubscribe(&self, request_id: RequestId, params: ThreadUnsubscribeParams) {
+        // The listener stays attached for other connections; the transport layer stops
+        // forwarding this thread's notifications to the caller once it sees the response.
+        if let Err(error) = self.load_thread(&params.thread_id).await {
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+        self.outgoing
+            .send_response(request_id, ThreadUnsubscribeResponse {})
+            .await;
+    }
+
     async fn thread_resume(&mut self, request_id: RequestId, params: ThreadResumeParams) {
         let ThreadResumeParams {
             thread_id,
@@ -2134,7 +2512,13 @@ impl CodexMessageProcessor {
             .clamp(1, THREAD_LIST_MAX_LIMIT);
 
         match self
-            .list_threads_common(requested_page_size, cursor, model_providers)
+            .list_threads_common(
+                requested_page_size,
+                cursor,
+                model_providers,
+                INTERACTIVE_SESSION_SOURCES,
+                None,
+            )
             .await
         {
             Ok((items, next_cursor)) => {
@@ -2152,6 +2536,8 @@ impl CodexMessageProcessor {
         requested_page_size: usize,
         cursor: Option<String>,
         model_providers: Option<Vec<String>>,
+        allowed_sources: &[CoreSessionSource],
+        search: Option<&ThreadSearchFilter>,
     ) -> Result<(Vec<ConversationSummary>, Option<String>), JSONRPCErrorError> {
         let mut cursor_obj: Option<RolloutCursor> = cursor.as_ref().and_then(|s| parse_cursor(s));
         let mut last_cursor = cursor_obj.clone();
@@ -2173,15 +2559,31 @@ impl CodexMessageProcessor {
 
         while remaining > 0 {
             let page_size = remaining.min(THREAD_LIST_MAX_LIMIT);
-            let page = RolloutRecorder::list_threads(
-                &self.config.codex_home,
-                page_size,
-                cursor_obj.as_ref(),
-                INTERACTIVE_SESSION_SOURCES,
-                model_provider_filter.as_deref(),
-                fallback_provider.as_str(),
-            )
-            .await
+            let page = match search {
+                Some(search) => {
+                    RolloutRecorder::search_threads(
+                        &self.config.codex_home,
+                        page_size,
+                        cursor_obj.as_ref(),
+                        allowed_sources,
+                        model_provider_filter.as_deref(),
+                        fallback_provider.as_str(),
+                        search,
+                    )
+                    .await
+                }
+                None => {
+                    RolloutRecorder::list_threads(
+                        &self.config.codex_home,
+                        page_size,
+                        cursor_obj.as_ref(),
+                        allowed_sources,
+                        model_provider_filter.as_deref(),
+                        fallback_provider.as_str(),
+                    )
+                    .await
+                }
+            }
             .map_err(|err| JSONRPCErrorError {
                 code: INTERNAL_ERROR_CODE,
                 message: format!("failed to list threads: {err}"),
@@ -3006,7 +3408,9 @@ impl CodexMessageProcessor {
                 .codex_home
                 .join(codex_core::ARCHIVED_SESSIONS_SUBDIR);
             tokio::fs::create_dir_all(&archive_folder).await?;
-            tokio::fs::rename(&canonical_rollout_path, &archive_folder.join(&file_name)).await?;
+            let archived_path = archive_folder.join(&file_name);
+            tokio::fs::rename(&canonical_rollout_path, &archived_path).await?;
+            codex_core::move_saved_tool_output(&canonical_rollout_path, &archived_path).await?;
             Ok(())
         }
         .await;
@@ -3018,6 +3422,84 @@ impl CodexMessageProcessor {
         })
     }
 
+    /// Moves an archived rollout back to its dated directory under `sessions`
+    /// and returns its new path.
+    async fn unarchive_thread_common(
+        &self,
+        thread_id: ThreadId,
+    ) -> Result<PathBuf, JSONRPCErrorError> {
+        let archive_folder = self
+            .config
+            .codex_home
+            .join(codex_core::ARCHIVED_SESSIONS_SUBDIR);
+        let required_suffix = format!("{thread_id}.jsonl");
+        let internal_error = |err: std::io::Error| JSONRPCErrorError {
+            code: INTERNAL_ERROR_CODE,
+            message: format!("failed to unarchive thread: {err}"),
+            data: None,
+        };
+
+        let mut file_name = None;
+        match tokio::fs::read_dir(&archive_folder).await {
+            Ok(mut entries) => {
+                while let Some(entry) = entries.next_entry().await.map_err(internal_error)? {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    if name.starts_with("rollout-") && name.ends_with(required_suffix.as_str()) {
+                        file_name = Some(name);
+                        break;
+                    }
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(internal_error(err)),
+        }
+        let Some(file_name) = file_name else {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("no archived rollout found for thread id {thread_id}"),
+                data: None,
+            });
+        };
+        let Some(day_dir) = rollout_day_dir(&file_name) else {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("archived rollout `{file_name}` has an unexpected file name"),
+                data: None,
+            });
+        };
+
+        let destination_dir = self
+            .config
+            .codex_home
+            .join(codex_core::SESSIONS_SUBDIR)
+            .join(day_dir);
+        let destination = destination_dir.join(&file_name);
+        if tokio::fs::try_exists(&destination)
+            .await
+            .map_err(internal_error)?
+        {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!(
+                    "rollout `{}` already exists; thread {thread_id} is not archived",
+                    destination.display()
+                ),
+                data: None,
+            });
+        }
+        tokio::fs::create_dir_all(&destination_dir)
+            .await
+            .map_err(internal_error)?;
+        let archived_path = archive_folder.join(&file_name);
+        tokio::fs::rename(&archived_path, &destination)
+            .await
+            .map_err(internal_error)?;
+        codex_core::move_saved_tool_output(&archived_path, &destination)
+            .await
+            .map_err(internal_error)?;
+        Ok(destination)
+    }
+
     async fn send_user_message(&self, request_id: RequestId, params: SendUserMessageParams) {
         let SendUserMessageParams {
             conversation_id,
@@ -3473,6 +3955,59 @@ impl CodexMessageProcessor {
         let _ = thread.submit(Op::Interrupt).await;
     }
 
+    async fn turn_steer(&mut self, request_id: RequestId, params: TurnSteerParams) {
+        let TurnSteerParams {
+            thread_id,
+            turn_id,
+            input,
+        } = params;
+
+        if input.is_empty() {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "input must not be empty".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        // The steer is queued behind any earlier submissions, so a turn started
+        // just before is already running when core handles it. Core reports a
+        // rejected steer as an `error` notification and a delivered one as
+        // `turn/steered`.
+        let items = input.into_iter().map(V2UserInput::into_core).collect();
+        match thread
+            .submit(Op::SteerTurn {
+                items,
+                expected_turn_id: Some(turn_id),
+            })
+            .await
+        {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, TurnSteerResponse {})
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to steer turn: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     async fn add_conversation_listener(
         &mut self,
         request_id: RequestId,
@@ -3541,6 +4076,10 @@ impl CodexMessageProcessor {
         let (cancel_tx, mut cancel_rx) = oneshot::channel();
         self.conversation_listeners
             .insert(subscription_id, cancel_tx);
+        if matches!(api_version, ApiVersion::V2) {
+            self.thread_listeners
+                .insert(conversation_id, subscription_id);
+        }
 
         let outgoing_for_task = self.outgoing.clone();
         let pending_interrupts = self.pending_interrupts.clone();
@@ -3548,6 +4087,7 @@ impl CodexMessageProcessor {
         let turn_summary_store = self.turn_summary_store.clone();
         let api_version_for_task = api_version;
         let fallback_model_provider = self.config.model_provider_id.clone();
+        let thread_manager = self.thread_manager.clone();
         tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -3601,6 +4141,44 @@ impl CodexMessageProcessor {
                             })
                             .await;
 
+                        // Sub-agent activity is reported on the parent as a status update and
+                        // replayed as the sub-agent's own thread events.
+                        if let EventMsg::SubAgentActivity(activity) = &event.msg {
+                            if !matches!(api_version_for_task, ApiVersion::V2) {
+                                continue;
+                            }
+                            if let Some(notification) =
+                                sub_agent_updated_notification(conversation_id, activity)
+                            {
+                                outgoing_for_task
+                                    .send_server_notification(ServerNotification::SubAgentUpdated(
+                                        notification,
+                                    ))
+                                    .await;
+                            }
+                            let Ok(agent_thread) =
+                                thread_manager.get_thread(activity.agent_id).await
+                            else {
+                                continue;
+                            };
+                            apply_bespoke_event_handling(
+                                Event {
+                                    id: activity.turn_id.clone(),
+                                    msg: (*activity.msg).clone(),
+                                },
+                                activity.agent_id,
+                                agent_thread,
+                                outgoing_for_task.clone(),
+                                pending_interrupts.clone(),
+                                pending_rollbacks.clone(),
+                                turn_summary_store.clone(),
+                                api_version_for_task,
+                                fallback_model_provider.clone(),
+                            )
+                            .await;
+                            continue;
+                        }
+
                         apply_bespoke_event_handling(
                             event.clone(),
                             conversation_id,
@@ -3890,6 +4468,8 @@ pub(crate) async fn read_summary_from_rollout(
         cli_version: session_meta.cli_version,
         source: session_meta.source,
         git_info,
+        title: session_meta.title,
+        tags: session_meta.tags,
     })
 }
 
@@ -3953,9 +4533,28 @@ fn extract_conversation_summary(
         cli_version: session_meta.cli_version.clone(),
         source: session_meta.source.clone(),
         git_info,
+        title: session_meta.title.clone(),
+        tags: session_meta.tags.clone(),
     })
 }
 
+/// Returns the `YYYY/MM/DD` directory a rollout named
+/// `rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl` is stored under.
+fn rollout_day_dir(file_name: &str) -> Option<PathBuf> {
+    let date = file_name.strip_prefix("rollout-")?.get(..10)?;
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return None;
+    };
+    let well_formed = year.len() == 4
+        && month.len() == 2
+        && day.len() == 2
+        && parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()));
+    well_formed.then(|| [*year, *month, *day].iter().collect())
+}
+
 fn map_git_info(git_info: &CoreGitInfo) -> ConversationGitInfo {
     ConversationGitInfo {
         sha: git_info.commit_hash.clone(),
@@ -3983,6 +4582,8 @@ pub(crate) fn summary_to_thread(summary: ConversationSummary) -> Thread {
         cli_version,
         source,
         git_info,
+        title,
+        tags,
     } = summary;
 
     let created_at = parse_datetime(timestamp.as_deref());
@@ -4002,6 +4603,8 @@ pub(crate) fn summary_to_thread(summary: ConversationSummary) -> Thread {
         cli_version,
         source: source.into(),
         git_info,
+        title,
+        tags,
         turns: Vec::new(),
     }
 }
@@ -4065,6 +4668,8 @@ mod tests {
             cli_version: "0.0.0".to_string(),
             source: SessionSource::VSCode,
             git_info: None,
+            title: None,
+            tags: Vec::new(),
         };
 
         assert_eq!(summary, expected);
@@ -4113,9 +4718,23 @@ mod tests {
             cli_version: String::new(),
             source: SessionSource::VSCode,
             git_info: None,
+            title: None,
+            tags: Vec::new(),
         };
 
         assert_eq!(summary, expected);
         Ok(())
     }
+
+    #[test]
+    fn rollout_day_dir_follows_file_name_date() {
+        assert_eq!(
+            rollout_day_dir(
+                "rollout-2025-01-05T12-00-00-bfd12a78-5900-467b-9bc5-d3d35df08191.jsonl"
+            ),
+            Some(PathBuf::from("2025").join("01").join("05"))
+        );
+        assert_eq!(rollout_day_dir("rollout-latest.jsonl"), None);
+        assert_eq!(rollout_day_dir("notes-2025-01-05.jsonl"), None);
+    }
 }
@@ -6,20 +6,18 @@ use codex_core::config_loader::LoaderOverrides;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::router::IncomingEvent;
+use crate::router::Router;
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_feedback::CodexFeedback;
-use tokio::io::AsyncBufReadExt;
-use tokio::io::AsyncWriteExt;
-use tokio::io::BufReader;
-use tokio::io::{self};
 use tokio::sync::mpsc;
 use toml::Value as TomlValue;
-use tracing::debug;
-use tracing::error;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::Layer;
@@ -34,6 +32,12 @@ mod fuzzy_file_search;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod router;
+mod transport;
+mod websocket;
+
+pub use transport::APP_SERVER_TOKEN_ENV_VAR;
+pub use transport::TransportArgs;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
@@ -44,33 +48,12 @@ pub async fn run_main(
     codex_linux_sandbox_exe: Option<PathBuf>,
     cli_config_overrides: CliConfigOverrides,
     loader_overrides: LoaderOverrides,
+    transport: TransportArgs,
 ) -> IoResult<()> {
     // Set up channels.
-    let (incoming_tx, mut incoming_rx) = mpsc::channel::<JSONRPCMessage>(CHANNEL_CAPACITY);
+    let (incoming_tx, mut incoming_rx) = mpsc::channel::<IncomingEvent>(CHANNEL_CAPACITY);
     let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
-
-    // Task: read from stdin, push to `incoming_tx`.
-    let stdin_reader_handle = tokio::spawn({
-        async move {
-            let stdin = io::stdin();
-            let reader = BufReader::new(stdin);
-            let mut lines = reader.lines();
-
-            while let Some(line) = lines.next_line().await.unwrap_or_default() {
-                match serde_json::from_str::<JSONRPCMessage>(&line) {
-                    Ok(msg) => {
-                        if incoming_tx.send(msg).await.is_err() {
-                            // Receiver gone – nothing left to do.
-                            break;
-                        }
-                    }
-                    Err(e) => error!("Failed to deserialize JSONRPCMessage: {e}"),
-                }
-            }
-
-            debug!("stdin reader finished (EOF)");
-        }
-    });
+    let router = Arc::new(Mutex::new(Router::default()));
 
     // Parse CLI overrides once and derive the base Config eagerly so later
     // components do not need to work with raw TOML values.
@@ -90,6 +73,7 @@ pub async fn run_main(
             std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
         })?;
 
+    let codex_home = config.codex_home.clone();
     let feedback = CodexFeedback::new();
 
     let otel = codex_core::otel_init::build_provider(
@@ -141,12 +125,27 @@ pub async fn run_main(
             feedback.clone(),
         );
         async move {
-            while let Some(msg) = incoming_rx.recv().await {
-                match msg {
-                    JSONRPCMessage::Request(r) => processor.process_request(r).await,
-                    JSONRPCMessage::Response(r) => processor.process_response(r).await,
-                    JSONRPCMessage::Notification(n) => processor.process_notification(n).await,
-                    JSONRPCMessage::Error(e) => processor.process_error(e),
+            while let Some(event) = incoming_rx.recv().await {
+                match event {
+                    IncomingEvent::Message {
+                        connection_id,
+                        message,
+                    } => match message {
+                        JSONRPCMessage::Request(r) => {
+                            processor.process_request(connection_id, r).await
+                        }
+                        JSONRPCMessage::Response(r) => processor.process_response(r).await,
+                        JSONRPCMessage::Notification(n) => processor.process_notification(n).await,
+                        JSONRPCMessage::Error(e) => processor.process_error(e).await,
+                    },
+                    IncomingEvent::Closed {
+                        connection_id,
+                        abandoned_requests,
+                    } => {
+                        processor
+                            .connection_closed(connection_id, abandoned_requests)
+                            .await
+                    }
                 }
             }
 
@@ -154,33 +153,31 @@ pub async fn run_main(
         }
     });
 
-    // Task: write outgoing messages to stdout.
-    let stdout_writer_handle = tokio::spawn(async move {
-        let mut stdout = io::stdout();
-        while let Some(outgoing_message) = outgoing_rx.recv().await {
-            let Ok(value) = serde_json::to_value(outgoing_message) else {
-                error!("Failed to convert OutgoingMessage to JSON value");
-                continue;
-            };
-            match serde_json::to_string(&value) {
-                Ok(mut json) => {
-                    json.push('\n');
-                    if let Err(e) = stdout.write_all(json.as_bytes()).await {
-                        error!("Failed to write to stdout: {e}");
-                        break;
-                    }
-                }
-                Err(e) => error!("Failed to serialize JSONRPCMessage: {e}"),
+    // Task: route outgoing messages to the connections that should see them.
+    let router_handle = tokio::spawn({
+        let router = router.clone();
+        async move {
+            while let Some(outgoing_message) = outgoing_rx.recv().await {
+                let deliveries = router
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .outgoing(outgoing_message);
+                transport::send_deliveries(&router, deliveries).await;
             }
-        }
 
-        info!("stdout writer exited (channel closed)");
+            info!("outgoing router exited (channel closed)");
+        }
     });
 
-    // Wait for all tasks to finish.  The typical exit path is the stdin reader
-    // hitting EOF which, once it drops `incoming_tx`, propagates shutdown to
-    // the processor and then to the stdout task.
-    let _ = tokio::join!(stdin_reader_handle, processor_handle, stdout_writer_handle);
+    if transport.is_daemon() {
+        return transport::serve_daemon(transport, &codex_home, router, incoming_tx).await;
+    }
+
+    // Wait for all tasks to finish.  The typical exit path is stdin hitting
+    // EOF which, once it drops `incoming_tx`, propagates shutdown to the
+    // processor and then to the router and the stdout writer.
+    let stdio_handle = tokio::spawn(transport::serve_stdio(router, incoming_tx));
+    let _ = tokio::join!(stdio_handle, processor_handle, router_handle);
 
     Ok(())
 }
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -5,6 +6,7 @@ use crate::codex_message_processor::CodexMessageProcessor;
 use crate::config_api::ConfigApi;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::router::ConnectionId;
 use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::ClientRequest;
 use codex_app_server_protocol::ConfigBatchWriteParams;
@@ -33,7 +35,7 @@ pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     codex_message_processor: CodexMessageProcessor,
     config_api: ConfigApi,
-    initialized: bool,
+    initialized_connections: HashSet<ConnectionId>,
 }
 
 impl MessageProcessor {
@@ -73,11 +75,15 @@ impl MessageProcessor {
             outgoing,
             codex_message_processor,
             config_api,
-            initialized: false,
+            initialized_connections: HashSet::new(),
         }
     }
 
-    pub(crate) async fn process_request(&mut self, request: JSONRPCRequest) {
+    pub(crate) async fn process_request(
+        &mut self,
+        connection_id: ConnectionId,
+        request: JSONRPCRequest,
+    ) {
         let request_id = request.id.clone();
         let request_json = match serde_json::to_value(&request) {
             Ok(request_json) => request_json,
@@ -107,9 +113,9 @@ impl MessageProcessor {
 
         match codex_request {
             // Handle Initialize internally so CodexMessageProcessor does not have to concern
-            // itself with the `initialized` bool.
+            // itself with which connections are initialized.
             ClientRequest::Initialize { request_id, params } => {
-                if self.initialized {
+                if self.initialized_connections.contains(&connection_id) {
                     let error = JSONRPCErrorError {
                         code: INVALID_REQUEST_ERROR_CODE,
                         message: "Already initialized".to_string(),
@@ -153,13 +159,13 @@ impl MessageProcessor {
                     let response = InitializeResponse { user_agent };
                     self.outgoing.send_response(request_id, response).await;
 
-                    self.initialized = true;
+                    self.initialized_connections.insert(connection_id);
 
                     return;
                 }
             }
             _ => {
-                if !self.initialized {
+                if !self.initialized_connections.contains(&connection_id) {
                     let error = JSONRPCErrorError {
                         code: INVALID_REQUEST_ERROR_CODE,
                         message: "Not initialized".to_string(),
@@ -193,6 +199,19 @@ impl MessageProcessor {
         }
     }
 
+    /// Drop per-connection state once a client disconnects, and give up on
+    /// the server requests no remaining client can answer.
+    pub(crate) async fn connection_closed(
+        &mut self,
+        connection_id: ConnectionId,
+        abandoned_requests: Vec<RequestId>,
+    ) {
+        self.initialized_connections.remove(&connection_id);
+        for id in abandoned_requests {
+            self.outgoing.cancel_request(&id).await;
+        }
+    }
+
     pub(crate) async fn process_notification(&self, notification: JSONRPCNotification) {
         // Currently, we do not expect to receive any notifications from the
         // client, so we just log them.
@@ -206,9 +225,11 @@ impl MessageProcessor {
         self.outgoing.notify_client_response(id, result).await
     }
 
-    /// Handle an error object received from the peer.
-    pub(crate) fn process_error(&mut self, err: JSONRPCError) {
+    /// Handle an error object received from the peer. A server request the
+    /// client answered with an error is treated as failed.
+    pub(crate) async fn process_error(&mut self, err: JSONRPCError) {
         tracing::error!("<- error: {:?}", err);
+        self.outgoing.cancel_request(&err.id).await;
     }
 
     async fn handle_config_read(&self, request_id: RequestId, params: ConfigReadParams) {
@@ -75,6 +75,14 @@ impl OutgoingMessageSender {
         }
     }
 
+    /// Drops the callback of a request that will not be answered. The
+    /// receiver returned by [`Self::send_request`] then sees the request as
+    /// failed, which approval handlers treat as a denial.
+    pub(crate) async fn cancel_request(&self, id: &RequestId) {
+        let mut request_id_to_callback = self.request_id_to_callback.lock().await;
+        request_id_to_callback.remove(id);
+    }
+
     pub(crate) async fn send_response<T: Serialize>(&self, id: RequestId, response: T) {
         match serde_json::to_value(response) {
             Ok(result) => {
@@ -0,0 +1,634 @@
+//! Multiplexes several client connections onto the single [`MessageProcessor`].
+//!
+//! Request ids are only unique per connection, so every incoming request is
+//! rewritten to a server-wide id before it reaches the processor and mapped
+//! back when the response goes out. The router also remembers which threads
+//! each connection has started, resumed or subscribed to, and delivers thread
+//! notifications and approval requests only to those connections.
+//!
+//! Server requests such as approvals stay tracked until a client answers
+//! them. When the last connection that was shown one goes away, it is sent to
+//! the remaining connections that would receive it now, or reported as
+//! abandoned so the processor can deny it instead of waiting forever.
+//!
+//! [`MessageProcessor`]: crate::message_processor::MessageProcessor
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::RequestId;
+use serde_json::Value;
+use tokio::sync::Notify;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::outgoing_message::OutgoingMessage;
+
+pub(crate) type ConnectionId = u64;
+
+/// A message read from a client connection, tagged with its origin.
+#[derive(Debug)]
+pub(crate) enum IncomingEvent {
+    Message {
+        connection_id: ConnectionId,
+        message: JSONRPCMessage,
+    },
+    Closed {
+        connection_id: ConnectionId,
+        /// Server requests that no remaining connection can answer.
+        abandoned_requests: Vec<RequestId>,
+    },
+}
+
+/// A serialized message queued for one connection.
+#[derive(Debug)]
+pub(crate) struct Delivery {
+    pub connection_id: ConnectionId,
+    pub sender: mpsc::Sender<String>,
+    pub line: String,
+    /// Whether to wait for room in the connection's queue instead of
+    /// disconnecting it when the queue is full.
+    pub wait_when_full: bool,
+}
+
+/// What is left to do after a connection is removed.
+#[derive(Debug, Default)]
+pub(crate) struct ClosedConnection {
+    /// Server requests the connection did not answer, sent on to the
+    /// connections that can still answer them.
+    pub resend: Vec<Delivery>,
+    pub abandoned_requests: Vec<RequestId>,
+}
+
+#[derive(Default)]
+pub(crate) struct Router {
+    next_request_id: i64,
+    connections: HashMap<ConnectionId, Connection>,
+    pending_requests: HashMap<RequestId, PendingRequest>,
+    server_requests: HashMap<RequestId, UnansweredServerRequest>,
+}
+
+struct Connection {
+    sender: mpsc::Sender<String>,
+    wait_when_full: bool,
+    disconnect: Arc<Notify>,
+    initialized: bool,
+    threads: HashSet<String>,
+}
+
+struct UnansweredServerRequest {
+    line: String,
+    thread_id: Option<String>,
+    recipients: HashSet<ConnectionId>,
+}
+
+struct PendingRequest {
+    connection_id: ConnectionId,
+    id: RequestId,
+    method: String,
+    thread_id: Option<String>,
+}
+
+impl Router {
+    /// Registers a connection. Serialized JSON-RPC messages for it are sent
+    /// on `sender`, one message per string.
+    ///
+    /// Unless `wait_when_full` is set, a connection that does not keep up
+    /// with its messages is disconnected: the returned [`Notify`] fires and
+    /// its reader should stop and remove the connection.
+    pub(crate) fn add_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        sender: mpsc::Sender<String>,
+        wait_when_full: bool,
+    ) -> Arc<Notify> {
+        let disconnect = Arc::new(Notify::new());
+        self.connections.insert(
+            connection_id,
+            Connection {
+                sender,
+                wait_when_full,
+                disconnect: disconnect.clone(),
+                initialized: false,
+                threads: HashSet::new(),
+            },
+        );
+        disconnect
+    }
+
+    /// Asks the reader of a connection whose queue is full to disconnect it.
+    pub(crate) fn disconnect_lagging(&self, connection_id: ConnectionId) {
+        if let Some(connection) = self.connections.get(&connection_id) {
+            warn!("disconnecting client {connection_id}, which is not reading its messages");
+            connection.disconnect.notify_one();
+        }
+    }
+
+    pub(crate) fn remove_connection(&mut self, connection_id: ConnectionId) -> ClosedConnection {
+        if self.connections.remove(&connection_id).is_none() {
+            return ClosedConnection::default();
+        }
+        self.pending_requests
+            .retain(|_, pending| pending.connection_id != connection_id);
+
+        let mut closed = ClosedConnection::default();
+        let mut abandoned = Vec::new();
+        for (id, request) in &mut self.server_requests {
+            if !request.recipients.remove(&connection_id) || !request.recipients.is_empty() {
+                continue;
+            }
+            let recipients = Self::recipients(&self.connections, request.thread_id.as_deref());
+            if recipients.is_empty() {
+                abandoned.push(id.clone());
+                continue;
+            }
+            for recipient in recipients {
+                request.recipients.insert(recipient);
+                if let Some(delivery) =
+                    Self::delivery(&self.connections, recipient, request.line.clone())
+                {
+                    closed.resend.push(delivery);
+                }
+            }
+        }
+        for id in &abandoned {
+            self.server_requests.remove(id);
+        }
+        closed.abandoned_requests = abandoned;
+        closed
+    }
+
+    /// Rewrites the id of a client request so it is unique across
+    /// connections. Other messages pass through unchanged: responses to
+    /// server requests already carry ids minted by the server.
+    pub(crate) fn incoming(
+        &mut self,
+        connection_id: ConnectionId,
+        message: JSONRPCMessage,
+    ) -> JSONRPCMessage {
+        let mut request = match message {
+            JSONRPCMessage::Request(request) => request,
+            JSONRPCMessage::Response(response) => {
+                self.server_requests.remove(&response.id);
+                return JSONRPCMessage::Response(response);
+            }
+            JSONRPCMessage::Error(error) => {
+                self.server_requests.remove(&error.id);
+                return JSONRPCMessage::Error(error);
+            }
+            message => return message,
+        };
+
+        let internal_id = RequestId::Integer(self.next_request_id);
+        self.next_request_id += 1;
+        let id = std::mem::replace(&mut request.id, internal_id.clone());
+        self.pending_requests.insert(
+            internal_id,
+            PendingRequest {
+                connection_id,
+                id,
+                method: request.method.clone(),
+                thread_id: request.params.as_ref().and_then(thread_id_from_params),
+            },
+        );
+        JSONRPCMessage::Request(request)
+    }
+
+    /// Decides which connections receive `message` and serializes it for
+    /// each of them.
+    pub(crate) fn outgoing(&mut self, message: OutgoingMessage) -> Vec<Delivery> {
+        match message {
+            OutgoingMessage::Response(mut response) => {
+                let Some(pending) = self.pending_requests.remove(&response.id) else {
+                    warn!("dropping response for unknown request {:?}", response.id);
+                    return Vec::new();
+                };
+                response.id = pending.id.clone();
+                self.record_response(&pending, &response.result);
+                self.deliver_to(pending.connection_id, &OutgoingMessage::Response(response))
+            }
+            OutgoingMessage::Error(mut error) => {
+                let Some(pending) = self.pending_requests.remove(&error.id) else {
+                    warn!("dropping error for unknown request {:?}", error.id);
+                    return Vec::new();
+                };
+                error.id = pending.id;
+                self.deliver_to(pending.connection_id, &OutgoingMessage::Error(error))
+            }
+            OutgoingMessage::Request(request) => {
+                let message = OutgoingMessage::Request(request);
+                let deliveries = self.broadcast(&message);
+                if let Some(id) = request_id(&message)
+                    && let Some(delivery) = deliveries.first()
+                {
+                    self.server_requests.insert(
+                        id,
+                        UnansweredServerRequest {
+                            line: delivery.line.clone(),
+                            thread_id: thread_id(&message),
+                            recipients: deliveries
+                                .iter()
+                                .map(|delivery| delivery.connection_id)
+                                .collect(),
+                        },
+                    );
+                }
+                deliveries
+            }
+            message => self.broadcast(&message),
+        }
+    }
+
+    fn record_response(&mut self, pending: &PendingRequest, result: &Value) {
+        let Some(connection) = self.connections.get_mut(&pending.connection_id) else {
+            return;
+        };
+        let subscribed_thread = match pending.method.as_str() {
+            "initialize" => {
+                connection.initialized = true;
+                None
+            }
+            "thread/start" | "thread/resume" | "thread/fork" | "thread/subscribe" => result
+                .get("thread")
+                .and_then(|thread| thread.get("id"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            "newConversation" | "resumeConversation" | "forkConversation" => result
+                .get("conversationId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            "review/start" => result
+                .get("reviewThreadId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            "addConversationListener" => pending.thread_id.clone(),
+            "thread/unsubscribe" => {
+                if let Some(thread_id) = &pending.thread_id {
+                    connection.threads.remove(thread_id);
+                }
+                None
+            }
+            _ => None,
+        };
+        if let Some(thread_id) = subscribed_thread {
+            connection.threads.insert(thread_id);
+        }
+    }
+
+    /// Notifications and server requests about a thread go to the
+    /// connections subscribed to it. Everything else, and messages about
+    /// threads nobody is subscribed to, go to every initialized connection.
+    fn broadcast(&self, message: &OutgoingMessage) -> Vec<Delivery> {
+        let Some(line) = serialize(message) else {
+            return Vec::new();
+        };
+        let thread_id = thread_id(message);
+        Self::recipients(&self.connections, thread_id.as_deref())
+            .into_iter()
+            .filter_map(|connection_id| {
+                Self::delivery(&self.connections, connection_id, line.clone())
+            })
+            .collect()
+    }
+
+    fn recipients(
+        connections: &HashMap<ConnectionId, Connection>,
+        thread_id: Option<&str>,
+    ) -> Vec<ConnectionId> {
+        let subscribers: Vec<ConnectionId> = match thread_id {
+            Some(thread_id) => connections
+                .iter()
+                .filter(|(_, connection)| connection.threads.contains(thread_id))
+                .map(|(connection_id, _)| *connection_id)
+                .collect(),
+            None => Vec::new(),
+        };
+        if !subscribers.is_empty() {
+            return subscribers;
+        }
+        connections
+            .iter()
+            .filter(|(_, connection)| connection.initialized)
+            .map(|(connection_id, _)| *connection_id)
+            .collect()
+    }
+
+    fn deliver_to(&self, connection_id: ConnectionId, message: &OutgoingMessage) -> Vec<Delivery> {
+        serialize(message)
+            .and_then(|line| Self::delivery(&self.connections, connection_id, line))
+            .into_iter()
+            .collect()
+    }
+
+    fn delivery(
+        connections: &HashMap<ConnectionId, Connection>,
+        connection_id: ConnectionId,
+        line: String,
+    ) -> Option<Delivery> {
+        let connection = connections.get(&connection_id)?;
+        Some(Delivery {
+            connection_id,
+            sender: connection.sender.clone(),
+            line,
+            wait_when_full: connection.wait_when_full,
+        })
+    }
+}
+
+fn thread_id(message: &OutgoingMessage) -> Option<String> {
+    serde_json::to_value(message)
+        .ok()
+        .and_then(|value| value.get("params").and_then(thread_id_from_params))
+}
+
+fn request_id(message: &OutgoingMessage) -> Option<RequestId> {
+    let value = serde_json::to_value(message).ok()?;
+    serde_json::from_value(value.get("id")?.clone()).ok()
+}
+
+fn thread_id_from_params(params: &Value) -> Option<String> {
+    params
+        .get("threadId")
+        .or_else(|| params.get("conversationId"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn serialize(message: &OutgoingMessage) -> Option<String> {
+    match serde_json::to_string(message) {
+        Ok(line) => Some(line),
+        Err(err) => {
+            warn!("failed to serialize outgoing message: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_app_server_protocol::CommandExecutionRequestApprovalParams;
+    use codex_app_server_protocol::JSONRPCErrorError;
+    use codex_app_server_protocol::JSONRPCRequest;
+    use codex_app_server_protocol::JSONRPCResponse;
+    use codex_app_server_protocol::ServerRequestPayload;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::outgoing_message::OutgoingError;
+    use crate::outgoing_message::OutgoingNotification;
+    use crate::outgoing_message::OutgoingResponse;
+
+    fn connect(router: &mut Router, connection_id: ConnectionId) -> mpsc::Receiver<String> {
+        let (tx, rx) = mpsc::channel(16);
+        router.add_connection(connection_id, tx, false);
+        rx
+    }
+
+    /// Sends a request from `connection_id`, answers it with `result` and
+    /// returns what the connection received.
+    fn round_trip(
+        router: &mut Router,
+        connection_id: ConnectionId,
+        method: &str,
+        params: Option<Value>,
+        result: Value,
+    ) -> Value {
+        let JSONRPCMessage::Request(request) = router.incoming(
+            connection_id,
+            JSONRPCMessage::Request(JSONRPCRequest {
+                id: RequestId::Integer(1),
+                method: method.to_string(),
+                params,
+            }),
+        ) else {
+            panic!("requests stay requests");
+        };
+        let deliveries = router.outgoing(OutgoingMessage::Response(OutgoingResponse {
+            id: request.id,
+            result,
+        }));
+        assert_eq!(deliveries.len(), 1);
+        serde_json::from_str(&deliveries[0].line).expect("valid json")
+    }
+
+    fn notification(params: Value) -> OutgoingMessage {
+        OutgoingMessage::Notification(OutgoingNotification {
+            method: "turn/started".to_string(),
+            params: Some(params),
+        })
+    }
+
+    fn recipients(deliveries: &[Delivery], senders: &[&mpsc::Sender<String>]) -> Vec<bool> {
+        senders
+            .iter()
+            .map(|sender| {
+                deliveries
+                    .iter()
+                    .any(|delivery| delivery.sender.same_channel(sender))
+            })
+            .collect()
+    }
+
+    fn approval_request(id: i64, thread_id: &str) -> OutgoingMessage {
+        OutgoingMessage::Request(
+            ServerRequestPayload::CommandExecutionRequestApproval(
+                CommandExecutionRequestApprovalParams {
+                    thread_id: thread_id.to_string(),
+                    turn_id: "turn-1".to_string(),
+                    item_id: "item-1".to_string(),
+                    reason: None,
+                    proposed_execpolicy_amendment: None,
+                    explanation: None,
+                },
+            )
+            .request_with_id(RequestId::Integer(id)),
+        )
+    }
+
+    fn delivered_ids(deliveries: &[Delivery]) -> Vec<ConnectionId> {
+        let mut ids: Vec<ConnectionId> = deliveries
+            .iter()
+            .map(|delivery| delivery.connection_id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn request_ids_are_rewritten_and_restored() {
+        let mut router = Router::default();
+        let _a = connect(&mut router, 1);
+        let _b = connect(&mut router, 2);
+
+        let first = router.incoming(
+            1,
+            JSONRPCMessage::Request(JSONRPCRequest {
+                id: RequestId::Integer(7),
+                method: "thread/list".to_string(),
+                params: None,
+            }),
+        );
+        let second = router.incoming(
+            2,
+            JSONRPCMessage::Request(JSONRPCRequest {
+                id: RequestId::Integer(7),
+                method: "thread/list".to_string(),
+                params: None,
+            }),
+        );
+        let (JSONRPCMessage::Request(first), JSONRPCMessage::Request(second)) = (first, second)
+        else {
+            panic!("requests stay requests");
+        };
+        assert_ne!(first.id, second.id);
+
+        let deliveries = router.outgoing(OutgoingMessage::Error(OutgoingError {
+            id: second.id,
+            error: JSONRPCErrorError {
+                code: -1,
+                message: "boom".to_string(),
+                data: None,
+            },
+        }));
+        assert_eq!(deliveries.len(), 1);
+        let delivered: Value = serde_json::from_str(&deliveries[0].line).expect("valid json");
+        assert_eq!(delivered["id"], json!(7));
+        assert!(
+            router
+                .outgoing(OutgoingMessage::Error(OutgoingError {
+                    id: RequestId::Integer(12345),
+                    error: JSONRPCErrorError {
+                        code: -1,
+                        message: "unknown".to_string(),
+                        data: None,
+                    },
+                }))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn thread_notifications_go_to_subscribers_only() {
+        let mut router = Router::default();
+        let _a = connect(&mut router, 1);
+        let _b = connect(&mut router, 2);
+        let _c = connect(&mut router, 3);
+        for connection_id in [1, 2, 3] {
+            round_trip(&mut router, connection_id, "initialize", None, json!({}));
+        }
+        let senders: Vec<mpsc::Sender<String>> = [1, 2, 3]
+            .iter()
+            .map(|id| router.connections[id].sender.clone())
+            .collect();
+        let senders: Vec<&mpsc::Sender<String>> = senders.iter().collect();
+
+        round_trip(
+            &mut router,
+            1,
+            "thread/start",
+            None,
+            json!({ "thread": { "id": "thread-1" } }),
+        );
+        round_trip(
+            &mut router,
+            2,
+            "thread/subscribe",
+            Some(json!({ "threadId": "thread-1" })),
+            json!({ "thread": { "id": "thread-1" } }),
+        );
+
+        let deliveries = router.outgoing(notification(json!({ "threadId": "thread-1" })));
+        assert_eq!(recipients(&deliveries, &senders), vec![true, true, false]);
+
+        // Threads without subscribers and thread-less notifications reach everyone.
+        let deliveries = router.outgoing(notification(json!({ "threadId": "thread-2" })));
+        assert_eq!(recipients(&deliveries, &senders), vec![true, true, true]);
+        let deliveries = router.outgoing(notification(json!({})));
+        assert_eq!(recipients(&deliveries, &senders), vec![true, true, true]);
+
+        round_trip(
+            &mut router,
+            2,
+            "thread/unsubscribe",
+            Some(json!({ "threadId": "thread-1" })),
+            json!({}),
+        );
+        let deliveries = router.outgoing(notification(json!({ "threadId": "thread-1" })));
+        assert_eq!(recipients(&deliveries, &senders), vec![true, false, false]);
+    }
+
+    #[test]
+    fn uninitialized_and_closed_connections_receive_nothing() {
+        let mut router = Router::default();
+        let _a = connect(&mut router, 1);
+        let _b = connect(&mut router, 2);
+        round_trip(&mut router, 1, "initialize", None, json!({}));
+
+        let deliveries = router.outgoing(notification(json!({})));
+        assert_eq!(deliveries.len(), 1);
+
+        router.remove_connection(1);
+        assert!(router.outgoing(notification(json!({}))).is_empty());
+    }
+
+    #[test]
+    fn unanswered_server_requests_move_to_remaining_clients_or_are_abandoned() {
+        let mut router = Router::default();
+        let _a = connect(&mut router, 1);
+        let _b = connect(&mut router, 2);
+        for connection_id in [1, 2] {
+            round_trip(&mut router, connection_id, "initialize", None, json!({}));
+        }
+        round_trip(
+            &mut router,
+            1,
+            "thread/start",
+            None,
+            json!({ "thread": { "id": "thread-1" } }),
+        );
+
+        // Only the subscriber sees the approval; once it leaves, the other
+        // client is asked instead.
+        let deliveries = router.outgoing(approval_request(100, "thread-1"));
+        assert_eq!(delivered_ids(&deliveries), vec![1]);
+        let closed = router.remove_connection(1);
+        assert_eq!(delivered_ids(&closed.resend), vec![2]);
+        assert_eq!(closed.resend[0].line, deliveries[0].line);
+        assert!(closed.abandoned_requests.is_empty());
+
+        // With nobody left to ask, the request is abandoned.
+        let closed = router.remove_connection(2);
+        assert!(closed.resend.is_empty());
+        assert_eq!(closed.abandoned_requests, vec![RequestId::Integer(100)]);
+    }
+
+    #[test]
+    fn answered_server_requests_are_forgotten() {
+        let mut router = Router::default();
+        let _a = connect(&mut router, 1);
+        let _b = connect(&mut router, 2);
+        for connection_id in [1, 2] {
+            round_trip(&mut router, connection_id, "initialize", None, json!({}));
+        }
+
+        assert_eq!(
+            delivered_ids(&router.outgoing(approval_request(100, "thread-1"))),
+            vec![1, 2]
+        );
+        router.incoming(
+            2,
+            JSONRPCMessage::Response(JSONRPCResponse {
+                id: RequestId::Integer(100),
+                result: json!({ "decision": "accept" }),
+            }),
+        );
+
+        // Other recipients leaving does not matter once the request is answered.
+        let closed = router.remove_connection(1);
+        assert!(closed.resend.is_empty());
+        assert!(closed.abandoned_requests.is_empty());
+    }
+}
@@ -0,0 +1,427 @@
+//! Transports that carry JSON-RPC between clients and the app server: stdio
+//! for a single client, or a daemon accepting any number of clients on a Unix
+//! domain socket and/or a loopback WebSocket.
+
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use clap::Args;
+use codex_app_server_protocol::JSONRPCMessage;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::sync::Notify;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tracing::debug;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::CHANNEL_CAPACITY;
+use crate::router::ConnectionId;
+use crate::router::Delivery;
+use crate::router::IncomingEvent;
+use crate::router::Router;
+use crate::websocket;
+
+/// Environment variable holding the token WebSocket clients must present.
+pub const APP_SERVER_TOKEN_ENV_VAR: &str = "CODEX_APP_SERVER_TOKEN";
+
+const DEFAULT_SOCKET_FILE: &str = "app-server.sock";
+const TOKEN_FILE: &str = "app-server-token";
+
+/// How clients reach the app server. With neither flag set, the server talks
+/// to a single client over stdio.
+#[derive(Debug, Default, Clone, Args)]
+pub struct TransportArgs {
+    /// Run as a daemon accepting clients on a Unix domain socket. Defaults to
+    /// `$CODEX_HOME/app-server.sock` when no path is given.
+    #[arg(long = "unix-socket", value_name = "PATH", num_args = 0..=1)]
+    pub unix_socket: Option<Option<PathBuf>>,
+
+    /// Run as a daemon accepting WebSocket clients on this loopback address,
+    /// e.g. `127.0.0.1:4500`. Clients must present the token from
+    /// `$CODEX_APP_SERVER_TOKEN` or `$CODEX_HOME/app-server-token`.
+    #[arg(long = "websocket", value_name = "ADDR")]
+    pub websocket: Option<SocketAddr>,
+}
+
+impl TransportArgs {
+    pub(crate) fn is_daemon(&self) -> bool {
+        self.unix_socket.is_some() || self.websocket.is_some()
+    }
+}
+
+pub(crate) type SharedRouter = Arc<Mutex<Router>>;
+
+fn lock(router: &SharedRouter) -> MutexGuard<'_, Router> {
+    router
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn next_connection_id() -> ConnectionId {
+    static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Serves a single client on stdin/stdout. Returns once stdin hits EOF and
+/// everything queued for stdout has been written.
+pub(crate) async fn serve_stdio(router: SharedRouter, incoming_tx: mpsc::Sender<IncomingEvent>) {
+    let connection_id = next_connection_id();
+    let (tx, rx) = mpsc::channel::<String>(CHANNEL_CAPACITY);
+    // The only client is never disconnected for reading slowly; the server
+    // waits for it instead.
+    let disconnect = lock(&router).add_connection(connection_id, tx, true);
+
+    // The connection stays registered after EOF so responses to requests that
+    // are still in flight are written out. The writer finishes once the
+    // router is dropped, which happens after the processor shuts down.
+    let writer_handle = tokio::spawn(write_lines(tokio::io::stdout(), rx));
+    read_lines(
+        tokio::io::stdin(),
+        connection_id,
+        &disconnect,
+        &router,
+        &incoming_tx,
+    )
+    .await;
+    debug!("stdin reader finished (EOF)");
+    drop(router);
+    drop(incoming_tx);
+    let _ = writer_handle.await;
+}
+
+/// Accepts clients on the configured sockets until interrupted with Ctrl-C.
+pub(crate) async fn serve_daemon(
+    args: TransportArgs,
+    codex_home: &Path,
+    router: SharedRouter,
+    incoming_tx: mpsc::Sender<IncomingEvent>,
+) -> IoResult<()> {
+    let socket_path = args
+        .unix_socket
+        .map(|path| path.unwrap_or_else(|| codex_home.join(DEFAULT_SOCKET_FILE)));
+
+    #[cfg(unix)]
+    if let Some(socket_path) = &socket_path {
+        let listener = bind_unix_socket(socket_path)?;
+        info!("app server listening on {}", socket_path.display());
+        tokio::spawn(accept_unix(listener, router.clone(), incoming_tx.clone()));
+    }
+    #[cfg(not(unix))]
+    if socket_path.is_some() {
+        return Err(std::io::Error::new(
+            ErrorKind::Unsupported,
+            "--unix-socket is only supported on Unix",
+        ));
+    }
+
+    if let Some(addr) = args.websocket {
+        if !addr.ip().is_loopback() {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("refusing to accept WebSocket clients on non-loopback address {addr}"),
+            ));
+        }
+        let token = load_or_create_token(codex_home)?;
+        let listener = TcpListener::bind(addr).await?;
+        info!("app server accepting WebSocket clients on ws://{addr}");
+        tokio::spawn(accept_websocket(
+            listener,
+            Arc::new(token),
+            router.clone(),
+            incoming_tx.clone(),
+        ));
+    }
+
+    let result = tokio::signal::ctrl_c().await;
+    info!("app server shutting down");
+    if let Some(socket_path) = &socket_path {
+        let _ = std::fs::remove_file(socket_path);
+    }
+    result
+}
+
+#[cfg(unix)]
+fn bind_unix_socket(path: &Path) -> IoResult<tokio::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if path.exists() {
+        // A socket nobody answers on is left over from a server that did not
+        // shut down cleanly; a live one means another server owns the path.
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(std::io::Error::new(
+                ErrorKind::AddrInUse,
+                format!("an app server is already listening on {}", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)?;
+    // The socket is unauthenticated, so only the current user may connect.
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+#[cfg(unix)]
+async fn accept_unix(
+    listener: tokio::net::UnixListener,
+    router: SharedRouter,
+    incoming_tx: mpsc::Sender<IncomingEvent>,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let (reader, writer) = stream.into_split();
+                tokio::spawn(serve_line_connection(
+                    reader,
+                    writer,
+                    router.clone(),
+                    incoming_tx.clone(),
+                ));
+            }
+            Err(err) => warn!("failed to accept Unix socket client: {err}"),
+        }
+    }
+}
+
+async fn accept_websocket(
+    listener: TcpListener,
+    token: Arc<String>,
+    router: SharedRouter,
+    incoming_tx: mpsc::Sender<IncomingEvent>,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(serve_websocket_connection(
+                    stream,
+                    token.clone(),
+                    router.clone(),
+                    incoming_tx.clone(),
+                ));
+            }
+            Err(err) => warn!("failed to accept WebSocket client: {err}"),
+        }
+    }
+}
+
+/// Serves one newline-delimited JSON-RPC client until it disconnects.
+#[cfg(unix)]
+async fn serve_line_connection<R, W>(
+    reader: R,
+    writer: W,
+    router: SharedRouter,
+    incoming_tx: mpsc::Sender<IncomingEvent>,
+) where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let connection_id = next_connection_id();
+    let (tx, rx) = mpsc::channel::<String>(CHANNEL_CAPACITY);
+    let disconnect = lock(&router).add_connection(connection_id, tx, false);
+    debug!("client {connection_id} connected");
+
+    tokio::spawn(write_lines(writer, rx));
+    read_lines(reader, connection_id, &disconnect, &router, &incoming_tx).await;
+    close_connection(connection_id, &router, &incoming_tx).await;
+}
+
+async fn serve_websocket_connection(
+    stream: TcpStream,
+    token: Arc<String>,
+    router: SharedRouter,
+    incoming_tx: mpsc::Sender<IncomingEvent>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    match websocket::accept(&mut reader, &mut writer, &token).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(err) => {
+            warn!("WebSocket handshake failed: {err}");
+            return;
+        }
+    }
+
+    let connection_id = next_connection_id();
+    let (tx, mut rx) = mpsc::channel::<String>(CHANNEL_CAPACITY);
+    let (control_tx, mut control_rx) = mpsc::channel::<Vec<u8>>(CHANNEL_CAPACITY);
+    let disconnect = lock(&router).add_connection(connection_id, tx, false);
+    debug!("WebSocket client {connection_id} connected");
+
+    tokio::spawn(async move {
+        loop {
+            let frame = tokio::select! {
+                Some(line) = rx.recv() => websocket::text_frame(&line),
+                Some(frame) = control_rx.recv() => frame,
+                else => break,
+            };
+            if let Err(err) = writer.write_all(&frame).await {
+                debug!("failed to write to WebSocket client: {err}");
+                break;
+            }
+        }
+    });
+
+    let mut messages = websocket::MessageReader::new(reader);
+    loop {
+        let message = tokio::select! {
+            message = messages.read_message() => message,
+            () = disconnect.notified() => break,
+        };
+        match message {
+            Ok(Some(websocket::Message::Text(text))) => {
+                if !forward_line(&text, connection_id, &router, &incoming_tx).await {
+                    break;
+                }
+            }
+            Ok(Some(websocket::Message::Ping(payload))) => {
+                let _ = control_tx.send(websocket::pong_frame(&payload)).await;
+            }
+            Ok(Some(websocket::Message::Close)) | Ok(None) => {
+                let _ = control_tx.send(websocket::close_frame()).await;
+                break;
+            }
+            Err(err) => {
+                warn!("closing WebSocket client {connection_id}: {err}");
+                break;
+            }
+        }
+    }
+    close_connection(connection_id, &router, &incoming_tx).await;
+}
+
+/// Forwards lines from `reader` until it hits EOF or `disconnect` fires.
+async fn read_lines<R: AsyncRead + Unpin>(
+    reader: R,
+    connection_id: ConnectionId,
+    disconnect: &Notify,
+    router: &SharedRouter,
+    incoming_tx: &mpsc::Sender<IncomingEvent>,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line.unwrap_or_default(),
+            () = disconnect.notified() => None,
+        };
+        let Some(line) = line else {
+            break;
+        };
+        if !forward_line(&line, connection_id, router, incoming_tx).await {
+            break;
+        }
+    }
+}
+
+/// Parses one JSON-RPC message and hands it to the processor. Returns `false`
+/// once the processor is gone.
+async fn forward_line(
+    line: &str,
+    connection_id: ConnectionId,
+    router: &SharedRouter,
+    incoming_tx: &mpsc::Sender<IncomingEvent>,
+) -> bool {
+    let message = match serde_json::from_str::<JSONRPCMessage>(line) {
+        Ok(message) => message,
+        Err(e) => {
+            error!("Failed to deserialize JSONRPCMessage: {e}");
+            return true;
+        }
+    };
+    let message = lock(router).incoming(connection_id, message);
+    incoming_tx
+        .send(IncomingEvent::Message {
+            connection_id,
+            message,
+        })
+        .await
+        .is_ok()
+}
+
+async fn write_lines<W: AsyncWrite + Unpin>(mut writer: W, mut rx: mpsc::Receiver<String>) {
+    while let Some(mut line) = rx.recv().await {
+        line.push('\n');
+        if let Err(e) = writer.write_all(line.as_bytes()).await {
+            error!("Failed to write to client: {e}");
+            break;
+        }
+    }
+}
+
+/// Queues each delivery on its connection. A connection whose queue is full
+/// is disconnected rather than allowed to hold up everyone else, unless it
+/// asked to be waited for.
+pub(crate) async fn send_deliveries(router: &SharedRouter, deliveries: Vec<Delivery>) {
+    for delivery in deliveries {
+        match delivery.sender.try_send(delivery.line) {
+            // A closed channel means the client disconnected.
+            Ok(()) | Err(TrySendError::Closed(_)) => {}
+            Err(TrySendError::Full(line)) if delivery.wait_when_full => {
+                let _ = delivery.sender.send(line).await;
+            }
+            Err(TrySendError::Full(_)) => lock(router).disconnect_lagging(delivery.connection_id),
+        }
+    }
+}
+
+async fn close_connection(
+    connection_id: ConnectionId,
+    router: &SharedRouter,
+    incoming_tx: &mpsc::Sender<IncomingEvent>,
+) {
+    debug!("client {connection_id} disconnected");
+    let closed = lock(router).remove_connection(connection_id);
+    send_deliveries(router, closed.resend).await;
+    let _ = incoming_tx
+        .send(IncomingEvent::Closed {
+            connection_id,
+            abandoned_requests: closed.abandoned_requests,
+        })
+        .await;
+}
+
+/// Returns the token WebSocket clients must present, generating one and
+/// writing it to `$CODEX_HOME/app-server-token` when the environment does not
+/// provide it.
+fn load_or_create_token(codex_home: &Path) -> IoResult<String> {
+    if let Ok(token) = std::env::var(APP_SERVER_TOKEN_ENV_VAR)
+        && !token.is_empty()
+    {
+        return Ok(token);
+    }
+
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let path = codex_home.join(TOKEN_FILE);
+    // Recreate the file so a stale copy with looser permissions is not reused.
+    let _ = std::fs::remove_file(&path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    std::io::Write::write_all(&mut options.open(&path)?, token.as_bytes())?;
+    info!("wrote app server token to {}", path.display());
+    Ok(token)
+}
@@ -0,0 +1,323 @@
+//! Minimal server side of RFC 6455, enough to carry JSON-RPC text messages
+//! between a local client and the app server.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use sha1::Digest;
+use sha1::Sha1;
+use std::io;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+
+/// Upper bound on a single (possibly fragmented) message from a client.
+const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Upper bound on the HTTP upgrade request, headers included.
+const MAX_HANDSHAKE_BYTES: u64 = 16 * 1024;
+
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Message {
+    Text(String),
+    Ping(Vec<u8>),
+    Close,
+}
+
+/// Reads the HTTP upgrade request and answers it. Returns `Ok(false)` when the
+/// request was rejected (and a response already written), for example because
+/// it did not carry `token` as a bearer token or `token` query parameter.
+pub(crate) async fn accept<R, W>(reader: &mut R, writer: &mut W, token: &str) -> io::Result<bool>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut limited = reader.take(MAX_HANDSHAKE_BYTES);
+    let mut request_line = String::new();
+    limited.read_line(&mut request_line).await?;
+    let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+
+    let mut key = None;
+    let mut bearer = None;
+    let mut is_upgrade = false;
+    loop {
+        let mut line = String::new();
+        if limited.read_line(&mut line).await? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "incomplete websocket handshake",
+            ));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "upgrade" => is_upgrade = value.eq_ignore_ascii_case("websocket"),
+            "sec-websocket-key" => key = Some(value.to_string()),
+            "authorization" => {
+                bearer = value
+                    .strip_prefix("Bearer ")
+                    .map(|token| token.trim().to_string());
+            }
+            _ => {}
+        }
+    }
+
+    // Tokens are generated as hex, so the query value needs no percent-decoding.
+    let query_token = target
+        .split_once('?')
+        .and_then(|(_, query)| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("token="))
+        })
+        .map(str::to_string);
+    let authorized = [bearer, query_token]
+        .iter()
+        .flatten()
+        .any(|candidate| constant_time_eq(candidate.as_bytes(), token.as_bytes()));
+    if !authorized {
+        writer
+            .write_all(
+                b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            )
+            .await?;
+        return Ok(false);
+    }
+
+    let Some(key) = key.filter(|_| is_upgrade) else {
+        writer
+            .write_all(
+                b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            )
+            .await?;
+        return Ok(false);
+    };
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key)
+    );
+    writer.write_all(response.as_bytes()).await?;
+    Ok(true)
+}
+
+/// Reads client messages from the upgraded stream, reassembling fragmented
+/// messages around interleaved control frames.
+pub(crate) struct MessageReader<R> {
+    reader: R,
+    partial: Option<Vec<u8>>,
+}
+
+impl<R: AsyncRead + Unpin> MessageReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            partial: None,
+        }
+    }
+
+    /// Reads frames until a complete message or control frame arrives.
+    /// Returns `Ok(None)` on a clean EOF between messages.
+    pub(crate) async fn read_message(&mut self) -> io::Result<Option<Message>> {
+        loop {
+            let mut header = [0u8; 2];
+            match self.reader.read_exact(&mut header).await {
+                Ok(_) => {}
+                Err(err)
+                    if err.kind() == io::ErrorKind::UnexpectedEof && self.partial.is_none() =>
+                {
+                    return Ok(None);
+                }
+                Err(err) => return Err(err),
+            }
+            let fin = header[0] & 0x80 != 0;
+            let opcode = header[0] & 0x0F;
+            if header[1] & 0x80 == 0 {
+                return Err(protocol_error("client frames must be masked"));
+            }
+            let len = match header[1] & 0x7F {
+                126 => u64::from(self.reader.read_u16().await?),
+                127 => self.reader.read_u64().await?,
+                len => u64::from(len),
+            };
+            let is_control = opcode & 0x8 != 0;
+            if is_control && (len > 125 || !fin) {
+                return Err(protocol_error("invalid control frame"));
+            }
+            let buffered = self.partial.as_ref().map_or(0, Vec::len);
+            let len = usize::try_from(len)
+                .ok()
+                .filter(|len| buffered.saturating_add(*len) <= MAX_MESSAGE_BYTES)
+                .ok_or_else(|| protocol_error("message too large"))?;
+
+            let mut mask = [0u8; 4];
+            self.reader.read_exact(&mut mask).await?;
+            let mut payload = vec![0u8; len];
+            self.reader.read_exact(&mut payload).await?;
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[i % 4];
+            }
+
+            match opcode {
+                OPCODE_PING => return Ok(Some(Message::Ping(payload))),
+                OPCODE_PONG => continue,
+                OPCODE_CLOSE => return Ok(Some(Message::Close)),
+                OPCODE_TEXT | OPCODE_BINARY if self.partial.is_none() => {
+                    self.partial = Some(payload);
+                }
+                OPCODE_CONTINUATION => match self.partial.as_mut() {
+                    Some(partial) => partial.extend_from_slice(&payload),
+                    None => return Err(protocol_error("unexpected continuation frame")),
+                },
+                _ => return Err(protocol_error("unexpected opcode")),
+            }
+            if fin {
+                let message = self.partial.take().unwrap_or_default();
+                return String::from_utf8(message)
+                    .map(|text| Some(Message::Text(text)))
+                    .map_err(|_| protocol_error("message is not valid UTF-8"));
+            }
+        }
+    }
+}
+
+pub(crate) fn text_frame(text: &str) -> Vec<u8> {
+    frame(OPCODE_TEXT, text.as_bytes())
+}
+
+pub(crate) fn pong_frame(payload: &[u8]) -> Vec<u8> {
+    frame(OPCODE_PONG, payload)
+}
+
+pub(crate) fn close_frame() -> Vec<u8> {
+    frame(OPCODE_CLOSE, &[])
+}
+
+/// Encodes a single unmasked, final frame as sent by a server.
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(HANDSHAKE_GUID.as_bytes());
+    BASE64_STANDARD.encode(hasher.finalize())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn protocol_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::io::BufReader;
+
+    use super::*;
+
+    fn masked(opcode_byte: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [1u8, 2, 3, 4];
+        let mut frame = vec![opcode_byte, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    #[test]
+    fn accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[tokio::test]
+    async fn handshake_requires_token() {
+        let request = "GET /?token=secret HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n";
+        let mut response = Vec::new();
+        let accepted = accept(
+            &mut BufReader::new(request.as_bytes()),
+            &mut response,
+            "secret",
+        )
+        .await
+        .expect("handshake");
+        assert!(accepted);
+        assert!(
+            String::from_utf8_lossy(&response).contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="),
+            "unexpected response: {}",
+            String::from_utf8_lossy(&response)
+        );
+
+        let mut response = Vec::new();
+        let accepted = accept(
+            &mut BufReader::new(request.as_bytes()),
+            &mut response,
+            "other",
+        )
+        .await
+        .expect("handshake");
+        assert!(!accepted);
+        assert!(response.starts_with(b"HTTP/1.1 401"));
+    }
+
+    #[tokio::test]
+    async fn reassembles_fragmented_text_around_pings() {
+        let mut bytes = masked(OPCODE_TEXT, b"{\"id\":");
+        bytes.extend(masked(0x80 | OPCODE_PING, b"hi"));
+        bytes.extend(masked(0x80 | OPCODE_CONTINUATION, b"1}"));
+        let mut reader = MessageReader::new(bytes.as_slice());
+
+        assert_eq!(
+            reader.read_message().await.expect("ping"),
+            Some(Message::Ping(b"hi".to_vec()))
+        );
+        assert_eq!(
+            reader.read_message().await.expect("text"),
+            Some(Message::Text("{\"id\":1}".to_string()))
+        );
+        assert_eq!(reader.read_message().await.expect("eof"), None);
+    }
+
+    #[tokio::test]
+    async fn rejects_unmasked_frames() {
+        let bytes = text_frame("hello");
+        let mut reader = MessageReader::new(bytes.as_slice());
+        assert!(reader.read_message().await.is_err());
+    }
+}
@@ -3,12 +3,13 @@ use std::path::Path;
 use std::process::Stdio;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
-use tokio::process::ChildStdin;
-use tokio::process::ChildStdout;
 
 use anyhow::Context;
 use codex_app_server_protocol::AddConversationListenerParams;
@@ -44,14 +45,21 @@ use codex_app_server_protocol::SendUserTurnParams;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::SetDefaultModelParams;
 use codex_app_server_protocol::ThreadArchiveParams;
+use codex_app_server_protocol::ThreadExportParams;
 use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadLoadedListParams;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchParams;
 use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadSubscribeParams;
+use codex_app_server_protocol::ThreadUnarchiveParams;
+use codex_app_server_protocol::ThreadUnsubscribeParams;
+use codex_app_server_protocol::ThreadUpdateParams;
 use codex_app_server_protocol::TurnInterruptParams;
 use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnSteerParams;
 use tokio::process::Command;
 
 pub struct McpProcess {
@@ -59,10 +67,12 @@ pub struct McpProcess {
     /// Retain this child process until the client is dropped. The Tokio runtime
     /// will make a "best effort" to reap the process after it exits, but it is
     /// not a guarantee. See the `kill_on_drop` documentation for details.
+    /// `None` when connected to a server that was started separately, e.g.
+    /// via [`spawn_app_server_daemon`].
     #[allow(dead_code)]
-    process: Child,
-    stdin: ChildStdin,
-    stdout: BufReader<ChildStdout>,
+    process: Option<Child>,
+    stdin: Box<dyn AsyncWrite + Send + Unpin>,
+    stdout: BufReader<Box<dyn AsyncRead + Send + Unpin>>,
     pending_messages: VecDeque<JSONRPCMessage>,
 }
 
@@ -115,8 +125,6 @@ impl McpProcess {
             .stdout
             .take()
             .ok_or_else(|| anyhow::format_err!("mcp should have stdout fd"))?;
-        let stdout = BufReader::new(stdout);
-
         // Forward child's stderr to our stderr so failures are visible even
         // when stdout/stderr are captured by the test harness.
         if let Some(stderr) = process.stderr.take() {
@@ -129,9 +137,25 @@ impl McpProcess {
         }
         Ok(Self {
             next_request_id: AtomicI64::new(0),
-            process,
-            stdin,
-            stdout,
+            process: Some(process),
+            stdin: Box::new(stdin),
+            stdout: BufReader::new(Box::new(stdout)),
+            pending_messages: VecDeque::new(),
+        })
+    }
+
+    /// Connects to an app server listening on a Unix domain socket.
+    #[cfg(unix)]
+    pub async fn connect_unix_socket(socket_path: &Path) -> anyhow::Result<Self> {
+        let stream = tokio::net::UnixStream::connect(socket_path)
+            .await
+            .with_context(|| format!("should connect to {}", socket_path.display()))?;
+        let (reader, writer) = stream.into_split();
+        Ok(Self {
+            next_request_id: AtomicI64::new(0),
+            process: None,
+            stdin: Box::new(writer),
+            stdout: BufReader::new(Box::new(reader)),
             pending_messages: VecDeque::new(),
         })
     }
@@ -360,6 +384,33 @@ impl McpProcess {
         self.send_request("thread/archive", params).await
     }
 
+    /// Send a `thread/unarchive` JSON-RPC request.
+    pub async fn send_thread_unarchive_request(
+        &mut self,
+        params: ThreadUnarchiveParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/unarchive", params).await
+    }
+
+    /// Send a `thread/update` JSON-RPC request.
+    pub async fn send_thread_update_request(
+        &mut self,
+        params: ThreadUpdateParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/update", params).await
+    }
+
+    /// Send a `thread/export` JSON-RPC request.
+    pub async fn send_thread_export_request(
+        &mut self,
+        params: ThreadExportParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/export", params).await
+    }
+
     /// Send a `thread/rollback` JSON-RPC request.
     pub async fn send_thread_rollback_request(
         &mut self,
@@ -378,6 +429,15 @@ impl McpProcess {
         self.send_request("thread/list", params).await
     }
 
+    /// Send a `thread/search` JSON-RPC request.
+    pub async fn send_thread_search_request(
+        &mut self,
+        params: ThreadSearchParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/search", params).await
+    }
+
     /// Send a `thread/loaded/list` JSON-RPC request.
     pub async fn send_thread_loaded_list_request(
         &mut self,
@@ -387,6 +447,24 @@ impl McpProcess {
         self.send_request("thread/loaded/list", params).await
     }
 
+    /// Send a `thread/subscribe` JSON-RPC request.
+    pub async fn send_thread_subscribe_request(
+        &mut self,
+        params: ThreadSubscribeParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/subscribe", params).await
+    }
+
+    /// Send a `thread/unsubscribe` JSON-RPC request.
+    pub async fn send_thread_unsubscribe_request(
+        &mut self,
+        params: ThreadUnsubscribeParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/unsubscribe", params).await
+    }
+
     /// Send a `model/list` JSON-RPC request.
     pub async fn send_list_models_request(
         &mut self,
@@ -446,6 +524,15 @@ impl McpProcess {
         self.send_request("turn/interrupt", params).await
     }
 
+    /// Send a `turn/steer` JSON-RPC request (v2).
+    pub async fn send_turn_steer_request(
+        &mut self,
+        params: TurnSteerParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("turn/steer", params).await
+    }
+
     /// Send a `review/start` JSON-RPC request (v2).
     pub async fn send_review_start_request(
         &mut self,
@@ -718,3 +805,47 @@ impl McpProcess {
         }
     }
 }
+
+/// Starts `codex-app-server --unix-socket <socket_path>` and waits until the
+/// socket accepts connections.
+#[cfg(unix)]
+pub async fn spawn_app_server_daemon(
+    codex_home: &Path,
+    socket_path: &Path,
+) -> anyhow::Result<Child> {
+    let program = codex_utils_cargo_bin::cargo_bin("codex-app-server")
+        .context("should find binary for codex-app-server")?;
+    let mut process = Command::new(program)
+        .arg("--unix-socket")
+        .arg(socket_path)
+        .env("CODEX_HOME", codex_home)
+        .env("RUST_LOG", "debug")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("codex-app-server daemon should start")?;
+    if let Some(stderr) = process.stderr.take() {
+        let mut stderr_reader = BufReader::new(stderr).lines();
+        tokio::spawn(async move {
+            while let Ok(Some(line)) = stderr_reader.next_line().await {
+                eprintln!("[daemon stderr] {line}");
+            }
+        });
+    }
+
+    for _ in 0..100 {
+        if tokio::net::UnixStream::connect(socket_path).await.is_ok() {
+            return Ok(process);
+        }
+        if let Some(status) = process.try_wait()? {
+            anyhow::bail!("codex-app-server daemon exited early: {status}");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    anyhow::bail!(
+        "codex-app-server daemon did not listen on {}",
+        socket_path.display()
+    )
+}
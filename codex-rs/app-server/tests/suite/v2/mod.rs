@@ -6,11 +6,17 @@ mod output_schema;
 mod rate_limits;
 mod review;
 mod thread_archive;
+mod thread_export;
 mod thread_fork;
 mod thread_list;
 mod thread_loaded_list;
 mod thread_resume;
 mod thread_rollback;
+mod thread_search;
 mod thread_start;
+mod thread_subscribe;
+mod thread_unarchive;
+mod thread_update;
 mod turn_interrupt;
 mod turn_start;
+mod turn_steer;
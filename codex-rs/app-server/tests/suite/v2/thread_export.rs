@@ -0,0 +1,125 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadExportFormat;
+use codex_app_server_protocol::ThreadExportParams;
+use codex_app_server_protocol::ThreadExportResponse;
+use codex_protocol::ThreadId;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+async fn export_thread(mcp: &mut McpProcess, params: ThreadExportParams) -> Result<String> {
+    let request_id = mcp.send_thread_export_request(params).await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadExportResponse { content } = to_response::<ThreadExportResponse>(resp)?;
+    Ok(content)
+}
+
+#[tokio::test]
+async fn thread_export_renders_stored_rollout() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+    let thread_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Deploy with OPENAI_API_KEY=abcdef123456",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let markdown = export_thread(
+        &mut mcp,
+        ThreadExportParams {
+            thread_id: thread_id.clone(),
+            format: ThreadExportFormat::Markdown,
+            redact: false,
+            max_output_lines: None,
+        },
+    )
+    .await?;
+    assert!(
+        markdown.starts_with(&format!("# Codex session {thread_id}\n")),
+        "unexpected markdown: {markdown}"
+    );
+    assert!(markdown.contains("## User\n\nDeploy with OPENAI_API_KEY=abcdef123456\n"));
+
+    let json = export_thread(
+        &mut mcp,
+        ThreadExportParams {
+            thread_id: thread_id.clone(),
+            format: ThreadExportFormat::Json,
+            redact: true,
+            max_output_lines: Some(10),
+        },
+    )
+    .await?;
+    let transcript: serde_json::Value = serde_json::from_str(&json)?;
+    assert_eq!(transcript["thread_id"], thread_id.as_str());
+    assert_eq!(
+        transcript["entries"],
+        serde_json::json!([{
+            "type": "user_message",
+            "text": "Deploy with OPENAI_API_KEY=[REDACTED]",
+        }])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn thread_export_rejects_unknown_thread() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_id = ThreadId::new().to_string();
+    let request_id = mcp
+        .send_thread_export_request(ThreadExportParams {
+            thread_id: thread_id.clone(),
+            format: ThreadExportFormat::Html,
+            redact: false,
+            max_output_lines: None,
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(
+        error.error.message,
+        format!("no rollout found for thread id {thread_id}")
+    );
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &Path) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#,
+    )
+}
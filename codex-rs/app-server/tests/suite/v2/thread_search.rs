@@ -0,0 +1,145 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_protocol::protocol::GitInfo as CoreGitInfo;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+async fn search_threads(mcp: &mut McpProcess, params: ThreadSearchParams) -> Result<Vec<String>> {
+    let request_id = mcp.send_thread_search_request(params).await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadSearchResponse { data, .. } = to_response::<ThreadSearchResponse>(resp)?;
+    Ok(data.into_iter().map(|thread| thread.id).collect())
+}
+
+fn git_branch(branch: &str) -> Option<CoreGitInfo> {
+    Some(CoreGitInfo {
+        commit_hash: None,
+        branch: Some(branch.to_string()),
+        repository_url: None,
+    })
+}
+
+#[tokio::test]
+async fn thread_search_filters_by_text_branch_and_date() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_minimal_config(codex_home.path())?;
+
+    let newest = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-03T12-00-00",
+        "2025-01-03T12:00:00Z",
+        "Fix the flaky websocket test",
+        Some("mock_provider"),
+        git_branch("main"),
+    )?;
+    let middle = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Write release notes",
+        Some("mock_provider"),
+        git_branch("release"),
+    )?;
+    let oldest = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-01T12-00-00",
+        "2025-01-01T12:00:00Z",
+        "Bump the websocket crate",
+        Some("mock_provider"),
+        git_branch("main"),
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let all_providers = Some(Vec::new());
+    assert_eq!(
+        search_threads(
+            &mut mcp,
+            ThreadSearchParams {
+                query: Some("WebSocket".to_string()),
+                model_providers: all_providers.clone(),
+                ..Default::default()
+            },
+        )
+        .await?,
+        vec![newest.clone(), oldest.clone()]
+    );
+    assert_eq!(
+        search_threads(
+            &mut mcp,
+            ThreadSearchParams {
+                query: Some("websocket flaky".to_string()),
+                model_providers: all_providers.clone(),
+                ..Default::default()
+            },
+        )
+        .await?,
+        vec![newest.clone()]
+    );
+    assert_eq!(
+        search_threads(
+            &mut mcp,
+            ThreadSearchParams {
+                git_branch: Some("release".to_string()),
+                model_providers: all_providers.clone(),
+                ..Default::default()
+            },
+        )
+        .await?,
+        vec![middle.clone()]
+    );
+    // 2025-01-02T00:00:00Z
+    assert_eq!(
+        search_threads(
+            &mut mcp,
+            ThreadSearchParams {
+                created_after: Some(1_735_776_000),
+                git_branch: Some("main".to_string()),
+                model_providers: all_providers.clone(),
+                ..Default::default()
+            },
+        )
+        .await?,
+        vec![newest]
+    );
+    assert_eq!(
+        search_threads(
+            &mut mcp,
+            ThreadSearchParams {
+                created_before: Some(1_735_776_000),
+                model_providers: all_providers,
+                ..Default::default()
+            },
+        )
+        .await?,
+        vec![oldest]
+    );
+
+    Ok(())
+}
+
+fn create_minimal_config(codex_home: &Path) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        r#"
+model = "mock-model"
+approval_policy = "never"
+"#,
+    )
+}
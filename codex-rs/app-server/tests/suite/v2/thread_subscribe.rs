@@ -0,0 +1,146 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_responses_server_repeating_assistant;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::ThreadSubscribeParams;
+use codex_app_server_protocol::ThreadSubscribeResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[cfg(unix)]
+#[tokio::test]
+async fn second_client_receives_notifications_for_subscribed_thread() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let socket_path = codex_home.path().join("app-server.sock");
+    let _daemon =
+        app_test_support::spawn_app_server_daemon(codex_home.path(), &socket_path).await?;
+
+    let mut owner = McpProcess::connect_unix_socket(&socket_path).await?;
+    timeout(DEFAULT_READ_TIMEOUT, owner.initialize()).await??;
+    let mut viewer = McpProcess::connect_unix_socket(&socket_path).await?;
+    timeout(DEFAULT_READ_TIMEOUT, viewer.initialize()).await??;
+
+    let start_id = owner
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        owner.read_stream_until_response_message(RequestId::Integer(start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(start_resp)?;
+
+    let subscribe_id = viewer
+        .send_thread_subscribe_request(ThreadSubscribeParams {
+            thread_id: thread.id.clone(),
+        })
+        .await?;
+    let subscribe_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        viewer.read_stream_until_response_message(RequestId::Integer(subscribe_id)),
+    )
+    .await??;
+    let ThreadSubscribeResponse { thread: subscribed } =
+        to_response::<ThreadSubscribeResponse>(subscribe_resp)?;
+    assert_eq!(subscribed.id, thread.id);
+
+    let turn_id = owner
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread.id.clone(),
+            input: vec![V2UserInput::Text {
+                text: "Hello".to_string(),
+            }],
+            ..Default::default()
+        })
+        .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        owner.read_stream_until_response_message(RequestId::Integer(turn_id)),
+    )
+    .await??;
+
+    for client in [&mut owner, &mut viewer] {
+        let completed = timeout(
+            DEFAULT_READ_TIMEOUT,
+            client.read_stream_until_notification_message("turn/completed"),
+        )
+        .await??;
+        assert_eq!(
+            completed
+                .params
+                .as_ref()
+                .and_then(|params| params.get("threadId"))
+                .and_then(serde_json::Value::as_str),
+            Some(thread.id.as_str())
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn thread_subscribe_rejects_unloaded_thread() -> Result<()> {
+    let server = create_mock_responses_server_repeating_assistant("Done").await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let subscribe_id = mcp
+        .send_thread_subscribe_request(ThreadSubscribeParams {
+            thread_id: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(subscribe_id)),
+    )
+    .await??;
+    assert!(
+        error.error.message.contains("thread not found"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadArchiveParams;
+use codex_app_server_protocol::ThreadArchiveResponse;
+use codex_app_server_protocol::ThreadUnarchiveParams;
+use codex_app_server_protocol::ThreadUnarchiveResponse;
+use codex_core::ARCHIVED_SESSIONS_SUBDIR;
+use codex_core::find_thread_path_by_id_str;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn thread_unarchive_restores_rollout_into_sessions_directory() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+    let thread_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Hello",
+        Some("mock_provider"),
+        None,
+    )?;
+    let rollout_path = find_thread_path_by_id_str(codex_home.path(), &thread_id)
+        .await?
+        .expect("expected rollout path for thread id to exist");
+    // Truncated tool output saved for `read_tool_output` lives next to the rollout.
+    let saved_output = rollout_path
+        .with_extension("tool_output")
+        .join("call_1.log");
+    std::fs::create_dir_all(saved_output.parent().expect("saved output dir"))?;
+    std::fs::write(&saved_output, "full output")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let archive_id = mcp
+        .send_thread_archive_request(ThreadArchiveParams {
+            thread_id: thread_id.clone(),
+        })
+        .await?;
+    let archive_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(archive_id)),
+    )
+    .await??;
+    let _: ThreadArchiveResponse = to_response::<ThreadArchiveResponse>(archive_resp)?;
+    let archived_rollout_path = codex_home
+        .path()
+        .join(ARCHIVED_SESSIONS_SUBDIR)
+        .join(rollout_path.file_name().expect("rollout file name"));
+    assert!(archived_rollout_path.exists());
+    let archived_output = archived_rollout_path
+        .with_extension("tool_output")
+        .join("call_1.log");
+    assert!(!saved_output.exists());
+    assert_eq!(std::fs::read_to_string(&archived_output)?, "full output");
+
+    let unarchive_id = mcp
+        .send_thread_unarchive_request(ThreadUnarchiveParams {
+            thread_id: thread_id.clone(),
+        })
+        .await?;
+    let unarchive_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(unarchive_id)),
+    )
+    .await??;
+    let ThreadUnarchiveResponse { thread } =
+        to_response::<ThreadUnarchiveResponse>(unarchive_resp)?;
+    assert_eq!(thread.id, thread_id);
+    assert_eq!(thread.preview, "Hello");
+    assert!(
+        rollout_path.exists(),
+        "expected rollout path {} to be restored",
+        rollout_path.display()
+    );
+    assert!(!archived_rollout_path.exists());
+    assert_eq!(std::fs::read_to_string(&saved_output)?, "full output");
+    assert!(!archived_output.exists());
+
+    // Nothing is left to unarchive.
+    let again_id = mcp
+        .send_thread_unarchive_request(ThreadUnarchiveParams { thread_id })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(again_id)),
+    )
+    .await??;
+    assert!(
+        error.error.message.contains("no archived rollout found"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &Path) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        r#"model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#,
+    )
+}
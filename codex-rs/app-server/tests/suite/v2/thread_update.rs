@@ -0,0 +1,170 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadListParams;
+use codex_app_server_protocol::ThreadListResponse;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::ThreadUpdateParams;
+use codex_app_server_protocol::ThreadUpdateResponse;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+async fn update_thread(
+    mcp: &mut McpProcess,
+    params: ThreadUpdateParams,
+) -> Result<ThreadUpdateResponse> {
+    let request_id = mcp.send_thread_update_request(params).await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response::<ThreadUpdateResponse>(resp)
+}
+
+#[tokio::test]
+async fn thread_update_persists_title_and_tags() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+    let thread_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Hello",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let ThreadUpdateResponse { thread } = update_thread(
+        &mut mcp,
+        ThreadUpdateParams {
+            thread_id: thread_id.clone(),
+            title: Some("Greeting experiments".to_string()),
+            tags: Some(vec![
+                " infra".to_string(),
+                "infra".to_string(),
+                "bug".to_string(),
+            ]),
+        },
+    )
+    .await?;
+    assert_eq!(thread.id, thread_id);
+    assert_eq!(thread.title.as_deref(), Some("Greeting experiments"));
+    assert_eq!(thread.tags, vec!["infra".to_string(), "bug".to_string()]);
+    assert_eq!(thread.preview, "Hello");
+
+    // Omitted fields are left alone; an empty title clears it.
+    let ThreadUpdateResponse { thread } = update_thread(
+        &mut mcp,
+        ThreadUpdateParams {
+            thread_id: thread_id.clone(),
+            title: Some(String::new()),
+            tags: None,
+        },
+    )
+    .await?;
+    assert_eq!(thread.title, None);
+    assert_eq!(thread.tags, vec!["infra".to_string(), "bug".to_string()]);
+
+    let list_id = mcp
+        .send_thread_list_request(ThreadListParams {
+            cursor: None,
+            limit: Some(10),
+            model_providers: Some(Vec::new()),
+        })
+        .await?;
+    let list_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let ThreadListResponse { data, .. } = to_response::<ThreadListResponse>(list_resp)?;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].tags, vec!["infra".to_string(), "bug".to_string()]);
+
+    let search_id = mcp
+        .send_thread_search_request(ThreadSearchParams {
+            tags: Some(vec!["bug".to_string()]),
+            model_providers: Some(Vec::new()),
+            ..Default::default()
+        })
+        .await?;
+    let search_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(search_id)),
+    )
+    .await??;
+    let ThreadSearchResponse { data, .. } = to_response::<ThreadSearchResponse>(search_resp)?;
+    assert_eq!(
+        data.into_iter().map(|thread| thread.id).collect::<Vec<_>>(),
+        vec![thread_id]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn thread_update_on_loaded_thread_keeps_recording() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let start_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(start_resp)?;
+
+    let ThreadUpdateResponse { thread: updated } = update_thread(
+        &mut mcp,
+        ThreadUpdateParams {
+            thread_id: thread.id.clone(),
+            title: Some("Scratch".to_string()),
+            tags: None,
+        },
+    )
+    .await?;
+    assert_eq!(updated.title.as_deref(), Some("Scratch"));
+
+    let rollout = std::fs::read_to_string(&updated.path)?;
+    let first_line = rollout.lines().next().expect("session meta line");
+    let meta: serde_json::Value = serde_json::from_str(first_line)?;
+    assert_eq!(meta["payload"]["title"], "Scratch");
+    assert_eq!(meta["payload"]["id"], thread.id.as_str());
+
+    Ok(())
+}
+
+fn create_config_toml(codex_home: &Path) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#,
+    )
+}
@@ -4,6 +4,10 @@ use std::path::PathBuf;
 
 use codex_core::CODEX_APPLY_PATCH_ARG1;
 #[cfg(unix)]
+use codex_core::resource_limits::CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1;
+#[cfg(unix)]
+use codex_core::resource_limits::exec_with_resource_limits;
+#[cfg(unix)]
 use std::os::unix::fs::symlink;
 use tempfile::TempDir;
 
@@ -47,6 +51,13 @@ pub fn arg0_dispatch() -> Option<TempDir> {
         std::process::exit(exit_code);
     }
 
+    #[cfg(unix)]
+    if argv1 == CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1 {
+        let err = exec_with_resource_limits(args);
+        eprintln!("Error: {CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1}: {err}");
+        std::process::exit(1);
+    }
+
     // This modifies the environment, which is not thread-safe, so do this
     // before creating any threads/the Tokio runtime.
     load_dotenv();
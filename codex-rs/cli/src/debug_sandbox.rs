@@ -8,7 +8,9 @@ use std::path::PathBuf;
 use codex_common::CliConfigOverrides;
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
+use codex_core::config::types::LinuxSandboxBackend;
 use codex_core::exec_env::create_env;
+use codex_core::landlock::LinuxSandboxOptions;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
 #[cfg(target_os = "macos")]
 use codex_core::seatbelt::spawn_command_under_seatbelt;
@@ -59,6 +61,8 @@ pub async fn run_command_under_landlock(
 ) -> anyhow::Result<()> {
     let LandlockCommand {
         full_auto,
+        backend,
+        overlay_dir,
         config_overrides,
         command,
     } = command;
@@ -67,7 +71,10 @@ pub async fn run_command_under_landlock(
         command,
         config_overrides,
         codex_linux_sandbox_exe,
-        SandboxType::Landlock,
+        SandboxType::Linux {
+            backend: backend.map(Into::into),
+            overlay_dir,
+        },
         false,
     )
     .await
@@ -96,7 +103,10 @@ pub async fn run_command_under_windows(
 enum SandboxType {
     #[cfg(target_os = "macos")]
     Seatbelt,
-    Landlock,
+    Linux {
+        backend: Option<LinuxSandboxBackend>,
+        overlay_dir: Option<PathBuf>,
+    },
     Windows,
 }
 
@@ -223,17 +233,45 @@ async fn run_command_under_sandbox(
             )
             .await?
         }
-        SandboxType::Landlock => {
+        SandboxType::Linux {
+            backend,
+            overlay_dir,
+        } => {
             #[expect(clippy::expect_used)]
             let codex_linux_sandbox_exe = config
                 .codex_linux_sandbox_exe
+                .clone()
                 .expect("codex-linux-sandbox executable not found");
+            let backend = backend.unwrap_or(config.linux_sandbox_backend);
+            let overlay_dir = overlay_dir.or_else(|| {
+                config
+                    .linux_sandbox_overlay_dir
+                    .as_ref()
+                    .map(|dir| dir.to_path_buf())
+            });
+            if backend == LinuxSandboxBackend::Namespaces
+                && let Some(overlay_dir) = &overlay_dir
+            {
+                eprintln!(
+                    "Changes to {} will be written to {}",
+                    cwd.display(),
+                    overlay_dir.join("upper").display()
+                );
+            }
             spawn_command_under_linux_sandbox(
                 codex_linux_sandbox_exe,
                 command,
                 cwd,
                 config.sandbox_policy.get(),
                 sandbox_policy_cwd.as_path(),
+                LinuxSandboxOptions {
+                    backend,
+                    network_proxy_port: None,
+                    deny_read: &config.sandbox_deny_read,
+                    overlay_dir: overlay_dir.as_deref(),
+                    resource_limits: config.sandbox_resource_limits,
+                    cgroup: None,
+                },
                 stdio_policy,
                 env,
             )
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::ValueEnum;
+use codex_common::CliConfigOverrides;
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::export::DEFAULT_MAX_OUTPUT_LINES;
+use codex_core::export::ExportFormat;
+use codex_core::export::ExportOptions;
+use codex_core::export::export_rollout;
+use codex_core::find_thread_path_by_id_str;
+
+/// Export a recorded session as a Markdown, HTML or JSON transcript.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session id (UUID) to export. If omitted, use --last to export the most
+    /// recent recorded session.
+    #[arg(value_name = "SESSION_ID", required_unless_present = "last")]
+    pub session_id: Option<String>,
+
+    /// Export the most recent recorded session.
+    #[arg(long = "last", default_value_t = false, conflicts_with = "session_id")]
+    pub last: bool,
+
+    /// Transcript format.
+    #[arg(long, value_enum, default_value_t = ExportFormatArg::Md)]
+    pub format: ExportFormatArg,
+
+    /// Mask likely secrets and replace the home directory with `~`.
+    #[arg(long, default_value_t = false)]
+    pub redact: bool,
+
+    /// Lines kept from each tool call's output; 0 keeps everything.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_OUTPUT_LINES)]
+    pub max_output_lines: usize,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormatArg {
+    Md,
+    Html,
+    Json,
+}
+
+impl From<ExportFormatArg> for ExportFormat {
+    fn from(value: ExportFormatArg) -> Self {
+        match value {
+            ExportFormatArg::Md => ExportFormat::Markdown,
+            ExportFormatArg::Html => ExportFormat::Html,
+            ExportFormatArg::Json => ExportFormat::Json,
+        }
+    }
+}
+
+impl ExportCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let rollout_path = match &self.session_id {
+            Some(id) => find_thread_path_by_id_str(&config.codex_home, id)
+                .await
+                .with_context(|| format!("failed to look up session {id}"))?
+                .with_context(|| format!("no recorded session found with id {id}"))?,
+            None => {
+                let provider_filter = vec![config.model_provider_id.clone()];
+                let page = RolloutRecorder::list_threads(
+                    &config.codex_home,
+                    1,
+                    None,
+                    &[],
+                    Some(provider_filter.as_slice()),
+                    &config.model_provider_id,
+                )
+                .await
+                .context("failed to list recorded sessions")?;
+                match page.items.into_iter().next() {
+                    Some(item) => item.path,
+                    None => bail!("no recorded sessions found"),
+                }
+            }
+        };
+
+        let options = ExportOptions {
+            format: self.format.into(),
+            redact: self.redact,
+            max_output_lines: self.max_output_lines,
+        };
+        let content = export_rollout(&rollout_path, &options)
+            .await
+            .with_context(|| format!("failed to export {}", rollout_path.display()))?;
+
+        match self.output {
+            Some(path) => tokio::fs::write(&path, content)
+                .await
+                .with_context(|| format!("failed to write {}", path.display()))?,
+            None => print!("{content}"),
+        }
+        Ok(())
+    }
+}
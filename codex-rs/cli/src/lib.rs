@@ -3,7 +3,10 @@ mod exit_status;
 pub mod login;
 
 use clap::Parser;
+use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
+use codex_core::config::types::LinuxSandboxBackend;
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 pub struct SeatbeltCommand {
@@ -29,6 +32,14 @@ pub struct LandlockCommand {
     #[arg(long = "full-auto", default_value_t = false)]
     pub full_auto: bool,
 
+    /// Sandbox backend to use instead of `sandbox_linux.backend` from config.toml
+    #[arg(long = "backend", value_enum)]
+    pub backend: Option<LinuxSandboxBackendCliArg>,
+
+    /// Mount the working directory as an overlay and write its changes to DIR/upper (namespaces backend only)
+    #[arg(long = "overlay-dir", value_name = "DIR")]
+    pub overlay_dir: Option<PathBuf>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -50,3 +61,20 @@ pub struct WindowsCommand {
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
 }
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum LinuxSandboxBackendCliArg {
+    Landlock,
+    #[value(alias = "ns")]
+    Namespaces,
+}
+
+impl From<LinuxSandboxBackendCliArg> for LinuxSandboxBackend {
+    fn from(value: LinuxSandboxBackendCliArg) -> Self {
+        match value {
+            LinuxSandboxBackendCliArg::Landlock => LinuxSandboxBackend::Landlock,
+            LinuxSandboxBackendCliArg::Namespaces => LinuxSandboxBackend::Namespaces,
+        }
+    }
+}
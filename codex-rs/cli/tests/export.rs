@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "0194f5a6-1a2b-7c3d-8e4f-5a6b7c8d9e0f";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<()> {
+    let dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&dir)?;
+    let timestamp = "2025-01-02T12:00:00Z";
+    let lines = [
+        json!({
+            "timestamp": timestamp,
+            "type": "session_meta",
+            "payload": {
+                "id": SESSION_ID,
+                "timestamp": timestamp,
+                "cwd": "/",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": "openai",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {
+                "type": "user_message",
+                "message": "Use OPENAI_API_KEY=abcdef123456",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"echo\",\"hi\"]}",
+                "call_id": "call-1",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": "hi",
+            },
+        }),
+        json!({
+            "timestamp": timestamp,
+            "type": "event_msg",
+            "payload": {"type": "agent_message", "message": "Printed hi."},
+        }),
+    ];
+    let content = lines
+        .iter()
+        .map(JsonValue::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(
+        dir.join(format!("rollout-2025-01-02T12-00-00-{SESSION_ID}.jsonl")),
+        content + "\n",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn export_renders_markdown_for_session_id() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["export", SESSION_ID])
+        .assert()
+        .success()
+        .stdout(contains(format!("# Codex session {SESSION_ID}")))
+        .stdout(contains("## User\n\nUse OPENAI_API_KEY=abcdef123456"))
+        .stdout(contains("### Tool call: `shell`\n\n```\necho hi\n```"))
+        .stdout(contains("## Codex\n\nPrinted hi."));
+
+    Ok(())
+}
+
+#[test]
+fn export_last_writes_redacted_json() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+    let output = codex_home.path().join("transcript.json");
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args([
+        "export", "--last", "--format", "json", "--redact", "--output",
+    ])
+    .arg(&output)
+    .assert()
+    .success();
+
+    let transcript: JsonValue = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    assert_eq!(transcript["thread_id"], SESSION_ID);
+    assert_eq!(
+        transcript["entries"][0],
+        json!({"type": "user_message", "text": "Use OPENAI_API_KEY=[REDACTED]"})
+    );
+    assert_eq!(transcript["entries"][1]["output"], "hi");
+
+    Ok(())
+}
+
+#[test]
+fn export_reports_unknown_session() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    cmd.args(["export", SESSION_ID])
+        .assert()
+        .failure()
+        .stderr(contains("no recorded session found"));
+
+    Ok(())
+}
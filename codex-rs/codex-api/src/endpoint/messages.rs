@@ -0,0 +1,98 @@
+use crate::auth::AuthProvider;
+use crate::common::Prompt as ApiPrompt;
+use crate::common::ResponseStream;
+use crate::endpoint::streaming::StreamingClient;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::MessagesRequest;
+use crate::requests::MessagesRequestBuilder;
+use crate::sse::messages::spawn_messages_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestCompression;
+use codex_client::RequestTelemetry;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use serde_json::Value;
+use std::sync::Arc;
+
+pub struct MessagesClient<T: HttpTransport, A: AuthProvider> {
+    streaming: StreamingClient<T, A>,
+}
+
+#[derive(Default)]
+pub struct MessagesOptions {
+    pub max_tokens: Option<u32>,
+    pub thinking_budget_tokens: Option<u32>,
+    pub conversation_id: Option<String>,
+    pub session_source: Option<SessionSource>,
+    pub extra_headers: HeaderMap,
+}
+
+impl<T: HttpTransport, A: AuthProvider> MessagesClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            streaming: StreamingClient::new(transport, provider, auth),
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            streaming: self.streaming.with_telemetry(request, sse),
+        }
+    }
+
+    pub async fn stream_request(
+        &self,
+        request: MessagesRequest,
+    ) -> Result<ResponseStream, ApiError> {
+        self.stream(request.body, request.headers).await
+    }
+
+    pub async fn stream_prompt(
+        &self,
+        model: &str,
+        prompt: &ApiPrompt,
+        options: MessagesOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let MessagesOptions {
+            max_tokens,
+            thinking_budget_tokens,
+            conversation_id,
+            session_source,
+            extra_headers,
+        } = options;
+
+        let mut request =
+            MessagesRequestBuilder::new(model, &prompt.instructions, &prompt.input, &prompt.tools)
+                .parallel_tool_calls(prompt.parallel_tool_calls)
+                .max_tokens(max_tokens)
+                .thinking_budget_tokens(thinking_budget_tokens)
+                .conversation_id(conversation_id)
+                .session_source(session_source)
+                .build(self.streaming.provider())?;
+        request.headers.extend(extra_headers);
+
+        self.stream_request(request).await
+    }
+
+    pub async fn stream(
+        &self,
+        body: Value,
+        extra_headers: HeaderMap,
+    ) -> Result<ResponseStream, ApiError> {
+        self.streaming
+            .stream(
+                "messages",
+                body,
+                extra_headers,
+                RequestCompression::None,
+                spawn_messages_stream,
+            )
+            .await
+    }
+}
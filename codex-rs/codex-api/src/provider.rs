@@ -13,6 +13,7 @@ pub enum WireApi {
     Responses,
     Chat,
     Compact,
+    Messages,
 }
 
 /// High-level retry configuration for a provider.
@@ -0,0 +1,459 @@
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionSource;
+use http::HeaderMap;
+use serde_json::Value;
+use serde_json::json;
+
+/// Version sent in the `anthropic-version` header.
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Output token cap sent when the caller does not set one. The Messages API
+/// requires `max_tokens` on every request.
+pub const DEFAULT_MAX_TOKENS: u32 = 32_000;
+
+/// Assembled request body plus headers for Messages streaming calls.
+pub struct MessagesRequest {
+    pub body: Value,
+    pub headers: HeaderMap,
+}
+
+pub struct MessagesRequestBuilder<'a> {
+    model: &'a str,
+    instructions: &'a str,
+    input: &'a [ResponseItem],
+    tools: &'a [Value],
+    parallel_tool_calls: bool,
+    max_tokens: u32,
+    thinking_budget_tokens: Option<u32>,
+    conversation_id: Option<String>,
+    session_source: Option<SessionSource>,
+}
+
+impl<'a> MessagesRequestBuilder<'a> {
+    pub fn new(
+        model: &'a str,
+        instructions: &'a str,
+        input: &'a [ResponseItem],
+        tools: &'a [Value],
+    ) -> Self {
+        Self {
+            model,
+            instructions,
+            input,
+            tools,
+            parallel_tool_calls: true,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            thinking_budget_tokens: None,
+            conversation_id: None,
+            session_source: None,
+        }
+    }
+
+    pub fn parallel_tool_calls(mut self, enabled: bool) -> Self {
+        self.parallel_tool_calls = enabled;
+        self
+    }
+
+    pub fn max_tokens(mut self, max_tokens: Option<u32>) -> Self {
+        self.max_tokens = max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        self
+    }
+
+    /// Enables extended thinking with the given token budget.
+    pub fn thinking_budget_tokens(mut self, budget: Option<u32>) -> Self {
+        self.thinking_budget_tokens = budget;
+        self
+    }
+
+    pub fn conversation_id(mut self, id: Option<String>) -> Self {
+        self.conversation_id = id;
+        self
+    }
+
+    pub fn session_source(mut self, source: Option<SessionSource>) -> Self {
+        self.session_source = source;
+        self
+    }
+
+    pub fn build(self, _provider: &Provider) -> Result<MessagesRequest, ApiError> {
+        let mut messages = Vec::<Value>::new();
+        for item in self.input {
+            let (role, block) = match item {
+                ResponseItem::Message { role, content, .. } => {
+                    let role = if role == "assistant" {
+                        "assistant"
+                    } else {
+                        "user"
+                    };
+                    for block in content.iter().filter_map(|c| content_block(role, c)) {
+                        push_block(&mut messages, role, block);
+                    }
+                    continue;
+                }
+                ResponseItem::Reasoning {
+                    summary,
+                    encrypted_content,
+                    ..
+                } => match thinking_block(summary, encrypted_content.as_deref()) {
+                    Some(block) => ("assistant", block),
+                    None => continue,
+                },
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                } => (
+                    "assistant",
+                    json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": tool_input(arguments),
+                    }),
+                ),
+                ResponseItem::CustomToolCall {
+                    call_id,
+                    name,
+                    input,
+                    ..
+                } => (
+                    "assistant",
+                    json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        "input": {"input": input},
+                    }),
+                ),
+                ResponseItem::LocalShellCall {
+                    id,
+                    call_id,
+                    action,
+                    ..
+                } => (
+                    "assistant",
+                    json!({
+                        "type": "tool_use",
+                        "id": call_id.clone().or_else(|| id.clone()).unwrap_or_default(),
+                        "name": "local_shell",
+                        "input": action,
+                    }),
+                ),
+                ResponseItem::FunctionCallOutput { call_id, output } => {
+                    let content = match &output.content_items {
+                        Some(items) => Value::Array(
+                            items
+                                .iter()
+                                .map(|item| match item {
+                                    FunctionCallOutputContentItem::InputText { text } => {
+                                        json!({"type": "text", "text": text})
+                                    }
+                                    FunctionCallOutputContentItem::InputImage { image_url } => {
+                                        image_block(image_url)
+                                    }
+                                })
+                                .collect(),
+                        ),
+                        None => json!(output.content),
+                    };
+                    let mut block = json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": content,
+                    });
+                    if output.success == Some(false)
+                        && let Some(obj) = block.as_object_mut()
+                    {
+                        obj.insert("is_error".to_string(), Value::Bool(true));
+                    }
+                    ("user", block)
+                }
+                ResponseItem::CustomToolCallOutput { call_id, output } => (
+                    "user",
+                    json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": output,
+                    }),
+                ),
+                ResponseItem::WebSearchCall { .. }
+                | ResponseItem::GhostSnapshot { .. }
+                | ResponseItem::Compaction { .. }
+                | ResponseItem::Other => continue,
+            };
+            push_block(&mut messages, role, block);
+        }
+
+        let mut payload = json!({
+            "model": self.model,
+            "system": self.instructions,
+            "messages": messages,
+            "max_tokens": self.max_tokens,
+            "stream": true,
+        });
+        if let Some(obj) = payload.as_object_mut() {
+            if !self.tools.is_empty() {
+                obj.insert("tools".to_string(), json!(self.tools));
+                obj.insert(
+                    "tool_choice".to_string(),
+                    json!({
+                        "type": "auto",
+                        "disable_parallel_tool_use": !self.parallel_tool_calls,
+                    }),
+                );
+            }
+            if let Some(budget) = self.thinking_budget_tokens {
+                // The budget must stay below `max_tokens`.
+                let max_tokens = self.max_tokens.max(budget.saturating_add(4_096));
+                obj.insert("max_tokens".to_string(), json!(max_tokens));
+                obj.insert(
+                    "thinking".to_string(),
+                    json!({"type": "enabled", "budget_tokens": budget}),
+                );
+            }
+        }
+
+        let mut headers = build_conversation_headers(self.conversation_id);
+        insert_header(&mut headers, "anthropic-version", ANTHROPIC_VERSION);
+        if let Some(subagent) = subagent_header(&self.session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+
+        Ok(MessagesRequest {
+            body: payload,
+            headers,
+        })
+    }
+}
+
+/// Appends `block` to the last message when it has the same role, so that
+/// user and assistant turns alternate as the Messages API requires.
+fn push_block(messages: &mut Vec<Value>, role: &str, block: Value) {
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some(role)
+        && let Some(content) = last.get_mut("content").and_then(Value::as_array_mut)
+    {
+        content.push(block);
+        return;
+    }
+    messages.push(json!({"role": role, "content": [block]}));
+}
+
+fn content_block(role: &str, content: &ContentItem) -> Option<Value> {
+    match content {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            if text.is_empty() {
+                None
+            } else {
+                Some(json!({"type": "text", "text": text}))
+            }
+        }
+        // Assistant turns cannot carry images.
+        ContentItem::InputImage { .. } if role == "assistant" => None,
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        });
+    }
+    json!({
+        "type": "image",
+        "source": {"type": "url", "url": image_url},
+    })
+}
+
+/// Thinking blocks round-trip through `ResponseItem::Reasoning`: the thinking
+/// text lives in `summary` and the signature in `encrypted_content`. A
+/// reasoning item with no summary but with `encrypted_content` is a redacted
+/// thinking block. Reasoning produced by other providers carries no signature
+/// and is dropped, since the API rejects unsigned thinking.
+fn thinking_block(
+    summary: &[ReasoningItemReasoningSummary],
+    encrypted_content: Option<&str>,
+) -> Option<Value> {
+    let signature = encrypted_content?;
+    let text = summary
+        .iter()
+        .map(|part| match part {
+            ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+        })
+        .collect::<String>();
+    if text.is_empty() {
+        Some(json!({"type": "redacted_thinking", "data": signature}))
+    } else {
+        Some(json!({"type": "thinking", "thinking": text, "signature": signature}))
+    }
+}
+
+/// Tool-use input must be a JSON object; arguments that are not are wrapped.
+fn tool_input(arguments: &str) -> Value {
+    match serde_json::from_str::<Value>(arguments) {
+        Ok(value @ Value::Object(_)) => value,
+        Ok(other) => json!({"input": other}),
+        Err(_) if arguments.trim().is_empty() => json!({}),
+        Err(_) => json!({"input": arguments}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::RetryConfig;
+    use crate::provider::WireApi;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use http::HeaderValue;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn provider() -> Provider {
+        Provider {
+            name: "anthropic".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            query_params: None,
+            wire: WireApi::Messages,
+            headers: HeaderMap::new(),
+            retry: RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                retry_429: false,
+                retry_5xx: true,
+                retry_transport: true,
+            },
+            stream_idle_timeout: Duration::from_secs(1),
+        }
+    }
+
+    fn user(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn maps_tool_calls_thinking_and_results_into_alternating_turns() {
+        let input = vec![
+            user("list files"),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                    text: "Use ls.".to_string(),
+                }],
+                content: None,
+                encrypted_content: Some("sig-1".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "a.txt".to_string(),
+                    success: Some(false),
+                    ..Default::default()
+                },
+            },
+            user("thanks"),
+        ];
+        let tools = vec![json!({"name": "shell", "input_schema": {"type": "object"}})];
+
+        let req = MessagesRequestBuilder::new("claude-test", "be helpful", &input, &tools)
+            .parallel_tool_calls(false)
+            .thinking_budget_tokens(Some(2_048))
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(
+            req.body,
+            json!({
+                "model": "claude-test",
+                "system": "be helpful",
+                "max_tokens": DEFAULT_MAX_TOKENS,
+                "stream": true,
+                "tools": tools,
+                "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+                "thinking": {"type": "enabled", "budget_tokens": 2_048},
+                "messages": [
+                    {"role": "user", "content": [{"type": "text", "text": "list files"}]},
+                    {"role": "assistant", "content": [
+                        {"type": "thinking", "thinking": "Use ls.", "signature": "sig-1"},
+                        {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["ls"]}},
+                    ]},
+                    {"role": "user", "content": [
+                        {"type": "tool_result", "tool_use_id": "toolu_1", "content": "a.txt", "is_error": true},
+                        {"type": "text", "text": "thanks"},
+                    ]},
+                ],
+            })
+        );
+        assert_eq!(
+            req.headers.get("anthropic-version"),
+            Some(&HeaderValue::from_static(ANTHROPIC_VERSION))
+        );
+    }
+
+    #[test]
+    fn drops_unsigned_reasoning_and_converts_data_url_images() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                }],
+            },
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "A square.".to_string(),
+                }],
+            },
+        ];
+
+        let req = MessagesRequestBuilder::new("claude-test", "", &input, &[])
+            .build(&provider())
+            .expect("request");
+
+        assert_eq!(
+            req.body["messages"],
+            json!([
+                {"role": "user", "content": [{
+                    "type": "image",
+                    "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"},
+                }]},
+                {"role": "assistant", "content": [{"type": "text", "text": "A square."}]},
+            ])
+        );
+        assert_eq!(req.body.get("tools"), None);
+        assert_eq!(req.body.get("thinking"), None);
+    }
+}
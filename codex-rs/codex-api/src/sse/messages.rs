@@ -0,0 +1,542 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::telemetry::SseTelemetry;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::Stream;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_messages_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        process_messages_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: Value,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: Value,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        delta: MessageDelta,
+        #[serde(default)]
+        usage: Option<Usage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: MessagesError,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessageDelta {
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+struct Usage {
+    input_tokens: Option<i64>,
+    cache_creation_input_tokens: Option<i64>,
+    cache_read_input_tokens: Option<i64>,
+    output_tokens: Option<i64>,
+}
+
+impl Usage {
+    /// Later usage reports only carry the fields that changed.
+    fn merge(&mut self, other: Usage) {
+        self.input_tokens = other.input_tokens.or(self.input_tokens);
+        self.cache_creation_input_tokens = other
+            .cache_creation_input_tokens
+            .or(self.cache_creation_input_tokens);
+        self.cache_read_input_tokens = other
+            .cache_read_input_tokens
+            .or(self.cache_read_input_tokens);
+        self.output_tokens = other.output_tokens.or(self.output_tokens);
+    }
+}
+
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        let cached = usage.cache_read_input_tokens.unwrap_or(0);
+        // `input_tokens` excludes tokens read from or written to the cache.
+        let input = usage.input_tokens.unwrap_or(0)
+            + cached
+            + usage.cache_creation_input_tokens.unwrap_or(0);
+        let output = usage.output_tokens.unwrap_or(0);
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesError {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl From<MessagesError> for ApiError {
+    fn from(error: MessagesError) -> Self {
+        match error.kind.as_str() {
+            "overloaded_error" | "api_error" | "rate_limit_error" => ApiError::Retryable {
+                message: error.message,
+                delay: None,
+            },
+            "invalid_request_error" if error.message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            _ => ApiError::Stream(format!("{}: {}", error.kind, error.message)),
+        }
+    }
+}
+
+/// A content block that has started but not yet stopped.
+enum Block {
+    Text(String),
+    Thinking {
+        text: String,
+        signature: String,
+    },
+    RedactedThinking(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+}
+
+impl Block {
+    fn into_item(self) -> ResponseItem {
+        match self {
+            Block::Text(text) => ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text }],
+            },
+            Block::Thinking { text, signature } => ResponseItem::Reasoning {
+                id: String::new(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText { text }],
+                content: None,
+                encrypted_content: Some(signature),
+            },
+            Block::RedactedThinking(data) => ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(data),
+            },
+            Block::ToolUse { id, name, input } => ResponseItem::FunctionCall {
+                id: None,
+                name,
+                arguments: if input.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    input
+                },
+                call_id: id,
+            },
+        }
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Processes Server-Sent Events from the Anthropic Messages streaming API.
+///
+/// Content blocks are buffered from `content_block_start` until
+/// `content_block_stop` and then emitted as a single `ResponseItem`: text
+/// becomes an assistant message, thinking becomes reasoning (text in the
+/// summary, signature in `encrypted_content`) and `tool_use` becomes a
+/// function call whose arguments are the concatenated `input_json_delta`s.
+pub async fn process_messages_sse<S>(
+    stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) where
+    S: Stream<Item = Result<bytes::Bytes, codex_client::TransportError>> + Unpin,
+{
+    let mut stream = stream.eventsource();
+    let mut blocks: HashMap<usize, Block> = HashMap::new();
+    let mut response_id = String::new();
+    let mut usage: Option<Usage> = None;
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", sse.data);
+
+        let data = sse.data.trim();
+        if data.is_empty() {
+            continue;
+        }
+        let event: MessagesEvent = match serde_json::from_str(data) {
+            Ok(event) => event,
+            Err(err) => {
+                debug!("Failed to parse Messages SSE event: {err}, data: {data}");
+                continue;
+            }
+        };
+
+        let event = match event {
+            MessagesEvent::MessageStart { message } => {
+                response_id = message.id;
+                usage = message.usage;
+                Some(ResponseEvent::Created)
+            }
+            MessagesEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let kind = content_block.get("type").and_then(Value::as_str);
+                let (block, added) = match kind {
+                    Some("text") => {
+                        let block = Block::Text(str_field(&content_block, "text"));
+                        let added = ResponseItem::Message {
+                            id: None,
+                            role: "assistant".to_string(),
+                            content: Vec::new(),
+                        };
+                        (block, Some(added))
+                    }
+                    Some("thinking") => {
+                        let block = Block::Thinking {
+                            text: str_field(&content_block, "thinking"),
+                            signature: str_field(&content_block, "signature"),
+                        };
+                        let added = ResponseItem::Reasoning {
+                            id: String::new(),
+                            summary: Vec::new(),
+                            content: None,
+                            encrypted_content: None,
+                        };
+                        (block, Some(added))
+                    }
+                    Some("redacted_thinking") => (
+                        Block::RedactedThinking(str_field(&content_block, "data")),
+                        None,
+                    ),
+                    Some("tool_use") => {
+                        // Non-streaming gateways may send the full input up front.
+                        let input = match content_block.get("input") {
+                            Some(Value::Object(map)) if !map.is_empty() => {
+                                Value::Object(map.clone()).to_string()
+                            }
+                            _ => String::new(),
+                        };
+                        let block = Block::ToolUse {
+                            id: str_field(&content_block, "id"),
+                            name: str_field(&content_block, "name"),
+                            input,
+                        };
+                        (block, None)
+                    }
+                    other => {
+                        debug!("ignoring unsupported Messages content block: {other:?}");
+                        continue;
+                    }
+                };
+                blocks.insert(index, block);
+                added.map(ResponseEvent::OutputItemAdded)
+            }
+            MessagesEvent::ContentBlockDelta { index, delta } => {
+                let Some(block) = blocks.get_mut(&index) else {
+                    continue;
+                };
+                let kind = delta.get("type").and_then(Value::as_str);
+                match (block, kind) {
+                    (Block::Text(text), Some("text_delta")) => {
+                        let delta = str_field(&delta, "text");
+                        text.push_str(&delta);
+                        Some(ResponseEvent::OutputTextDelta(delta))
+                    }
+                    (Block::Thinking { text, .. }, Some("thinking_delta")) => {
+                        let delta = str_field(&delta, "thinking");
+                        text.push_str(&delta);
+                        Some(ResponseEvent::ReasoningSummaryDelta {
+                            delta,
+                            summary_index: 0,
+                        })
+                    }
+                    (Block::Thinking { signature, .. }, Some("signature_delta")) => {
+                        signature.push_str(&str_field(&delta, "signature"));
+                        None
+                    }
+                    (Block::ToolUse { input, .. }, Some("input_json_delta")) => {
+                        input.push_str(&str_field(&delta, "partial_json"));
+                        None
+                    }
+                    (_, kind) => {
+                        debug!("ignoring unexpected Messages delta {kind:?} for block {index}");
+                        None
+                    }
+                }
+            }
+            MessagesEvent::ContentBlockStop { index } => blocks
+                .remove(&index)
+                .map(|block| ResponseEvent::OutputItemDone(block.into_item())),
+            MessagesEvent::MessageDelta {
+                delta,
+                usage: delta_usage,
+            } => {
+                if let Some(delta_usage) = delta_usage {
+                    usage.get_or_insert_with(Usage::default).merge(delta_usage);
+                }
+                if delta.stop_reason.as_deref() == Some("model_context_window_exceeded") {
+                    let _ = tx_event.send(Err(ApiError::ContextWindowExceeded)).await;
+                    return;
+                }
+                None
+            }
+            MessagesEvent::MessageStop => {
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id: std::mem::take(&mut response_id),
+                        token_usage: usage.take().map(Into::into),
+                    }))
+                    .await;
+                return;
+            }
+            MessagesEvent::Ping => None,
+            MessagesEvent::Error { error } => {
+                let _ = tx_event.send(Err(error.into())).await;
+                return;
+            }
+        };
+
+        if let Some(event) = event
+            && tx_event.send(Ok(event)).await.is_err()
+        {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    fn build_body(events: &[Value]) -> String {
+        let mut body = String::new();
+        for e in events {
+            let kind = e["type"].as_str().unwrap_or_default();
+            body.push_str(&format!("event: {kind}\ndata: {e}\n\n"));
+        }
+        body
+    }
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = ReaderStream::new(std::io::Cursor::new(body.to_string()))
+            .map_err(|err| codex_client::TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(16);
+        tokio::spawn(process_messages_sse(
+            reader,
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut out = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            out.push(ev);
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn emits_thinking_text_and_tool_use_items() {
+        let body = build_body(&[
+            json!({"type": "message_start", "message": {
+                "id": "msg_1",
+                "usage": {"input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 1},
+            }}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Check the dir."}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "ping"}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Listing"}}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "[\"ls\"]}"}}),
+            json!({"type": "content_block_stop", "index": 2}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 25}}),
+            json!({"type": "message_stop"}),
+        ]);
+
+        let events = collect_events(&body)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("stream error");
+
+        assert_matches!(&events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { .. })
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningSummaryDelta { delta, summary_index: 0 } if delta == "Check the dir."
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning { summary, encrypted_content: Some(signature), .. })
+                if signature == "sig"
+                    && summary == &vec![ReasoningItemReasoningSummary::SummaryText { text: "Check the dir.".to_string() }]
+        );
+        assert_matches!(
+            &events[4],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { .. })
+        );
+        assert_matches!(&events[5], ResponseEvent::OutputTextDelta(delta) if delta == "Listing");
+        assert_matches!(
+            &events[6],
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                if role == "assistant" && content == &vec![ContentItem::OutputText { text: "Listing".to_string() }]
+        );
+        assert_matches!(
+            &events[7],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { name, arguments, call_id, .. })
+                if name == "shell" && arguments == "{\"command\":[\"ls\"]}" && call_id == "toolu_1"
+        );
+        match &events[8] {
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+            } => {
+                assert_eq!(response_id, "msg_1");
+                assert_eq!(
+                    token_usage,
+                    &Some(TokenUsage {
+                        input_tokens: 100,
+                        cached_input_tokens: 90,
+                        output_tokens: 25,
+                        reasoning_output_tokens: 0,
+                        total_tokens: 125,
+                    })
+                );
+            }
+            other => panic!("expected completed event, got {other:?}"),
+        }
+        assert_eq!(events.len(), 9);
+    }
+
+    #[tokio::test]
+    async fn maps_error_events() {
+        let overloaded = build_body(&[
+            json!({"type": "message_start", "message": {"id": "msg_1"}}),
+            json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        ]);
+        let events = collect_events(&overloaded).await;
+        assert_matches!(
+            &events[..],
+            [Ok(ResponseEvent::Created), Err(ApiError::Retryable { message, .. })] if message == "Overloaded"
+        );
+
+        let too_long = build_body(&[json!({"type": "error", "error": {
+            "type": "invalid_request_error",
+            "message": "prompt is too long: 210000 tokens > 200000 maximum",
+        }})]);
+        let events = collect_events(&too_long).await;
+        assert_matches!(&events[..], [Err(ApiError::ContextWindowExceeded)]);
+    }
+
+    #[tokio::test]
+    async fn errors_when_stream_ends_before_message_stop() {
+        let body = build_body(&[json!({"type": "message_start", "message": {"id": "msg_1"}})]);
+        let events = collect_events(&body).await;
+        assert_matches!(
+            &events[..],
+            [Ok(ResponseEvent::Created), Err(ApiError::Stream(message))]
+                if message == "stream closed before message_stop"
+        );
+    }
+}
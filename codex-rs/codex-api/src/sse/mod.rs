@@ -1,4 +1,5 @@
 pub mod chat;
+pub mod messages;
 pub mod responses;
 
 pub use responses::process_sse;
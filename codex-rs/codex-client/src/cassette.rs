@@ -0,0 +1,634 @@
+//! Record/replay transports backed by a cassette file.
+//!
+//! A cassette is a JSONL file with one [`CassetteEntry`] per HTTP exchange.
+//! [`RecordingTransport`] writes every response (including SSE bodies and
+//! error statuses) served by an inner transport; [`ReplayTransport`] serves
+//! them back in order without touching the network. Request headers are never
+//! written, so credentials do not end up in cassettes.
+//!
+//! Replay matches requests on method, URL path and request body. Fields that
+//! differ on every run, such as the per-conversation `prompt_cache_key`, are
+//! left out of the body comparison. A request whose body was not recorded
+//! fails unless mismatches are explicitly allowed.
+
+use crate::error::TransportError;
+use crate::request::Request;
+use crate::request::Response;
+use crate::transport::ByteStream;
+use crate::transport::HttpTransport;
+use crate::transport::StreamResponse;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+use futures::StreamExt;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use tracing::warn;
+
+/// One recorded HTTP exchange.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CassetteEntry {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Whether the response was consumed as a byte stream (SSE).
+    #[serde(default)]
+    pub stream: bool,
+    /// Response body; for streams, everything received before the stream
+    /// ended or failed.
+    #[serde(default)]
+    pub body: String,
+}
+
+impl CassetteEntry {
+    fn new(req: &Request, status: StatusCode, headers: &HeaderMap, stream: bool) -> Self {
+        Self {
+            method: req.method.to_string(),
+            url: req.url.clone(),
+            request_body: req.body.clone(),
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .to_str()
+                        .ok()
+                        .map(|value| (name.to_string(), value.to_string()))
+                })
+                .collect(),
+            stream,
+            body: String::new(),
+        }
+    }
+
+    fn from_error(req: &Request, err: &TransportError, stream: bool) -> Option<Self> {
+        let TransportError::Http {
+            status,
+            headers,
+            body,
+            ..
+        } = err
+        else {
+            return None;
+        };
+        let mut entry = Self::new(req, *status, &headers.clone().unwrap_or_default(), stream);
+        entry.body = body.clone().unwrap_or_default();
+        Some(entry)
+    }
+
+    fn header_map(&self) -> HeaderMap {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect()
+    }
+
+    fn status_code(&self) -> Result<StatusCode, TransportError> {
+        StatusCode::from_u16(self.status).map_err(|err| {
+            TransportError::Build(format!("invalid status in cassette entry: {err}"))
+        })
+    }
+
+    fn http_error(&self, status: StatusCode) -> TransportError {
+        TransportError::Http {
+            status,
+            url: Some(self.url.clone()),
+            headers: Some(self.header_map()),
+            body: Some(self.body.clone()),
+        }
+    }
+
+    fn matches_endpoint(&self, req: &Request) -> bool {
+        self.method == req.method.as_str() && url_path(&self.url) == url_path(&req.url)
+    }
+
+    fn matches_body(&self, req: &Request) -> bool {
+        normalized_body(self.request_body.as_ref()) == normalized_body(req.body.as_ref())
+    }
+}
+
+/// Request body fields that change between otherwise identical sessions.
+const VOLATILE_BODY_FIELDS: &[&str] = &["prompt_cache_key"];
+
+/// Request body as compared during replay, with volatile fields removed.
+fn normalized_body(body: Option<&Value>) -> Value {
+    let mut body = body.cloned().unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut body {
+        for field in VOLATILE_BODY_FIELDS {
+            map.remove(*field);
+        }
+    }
+    body
+}
+
+/// Path component used to match requests against recorded entries; the host
+/// and query string are ignored so cassettes survive base URL changes.
+fn url_path(url: &str) -> String {
+    url.parse::<http::Uri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|_| url.to_string())
+}
+
+#[derive(Debug)]
+struct CassetteWriter {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl CassetteWriter {
+    fn append(&self, entry: &CassetteEntry) {
+        let mut line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(err) => {
+                warn!("failed to serialize cassette entry: {err}");
+                return;
+            }
+        };
+        line.push('\n');
+        let Ok(mut file) = self.file.lock() else {
+            warn!("cassette writer lock poisoned");
+            return;
+        };
+        if let Err(err) = file.write_all(line.as_bytes()) {
+            warn!("failed to write cassette {}: {err}", self.path.display());
+        }
+    }
+}
+
+/// Transport that forwards to `inner` and appends every exchange to a
+/// cassette file.
+#[derive(Debug, Clone)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    writer: Arc<CassetteWriter>,
+}
+
+impl<T: HttpTransport> RecordingTransport<T> {
+    /// Creates the cassette at `path`, replacing an existing one, so a
+    /// recording only ever holds a single session.
+    pub fn new(inner: T, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(&path)?;
+        Ok(Self {
+            inner,
+            writer: Arc::new(CassetteWriter {
+                path,
+                file: Mutex::new(file),
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl<T: HttpTransport> HttpTransport for RecordingTransport<T> {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let result = self.inner.execute(req.clone()).await;
+        let entry = match &result {
+            Ok(response) => {
+                let mut entry = CassetteEntry::new(&req, response.status, &response.headers, false);
+                entry.body = String::from_utf8_lossy(&response.body).into_owned();
+                Some(entry)
+            }
+            Err(err) => CassetteEntry::from_error(&req, err, false),
+        };
+        if let Some(entry) = entry {
+            self.writer.append(&entry);
+        }
+        result
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        match self.inner.stream(req.clone()).await {
+            Ok(response) => {
+                let entry = CassetteEntry::new(&req, response.status, &response.headers, true);
+                let bytes: ByteStream = Box::pin(RecordingStream {
+                    inner: response.bytes,
+                    received: Vec::new(),
+                    entry: Some(entry),
+                    writer: Arc::clone(&self.writer),
+                });
+                Ok(StreamResponse {
+                    status: response.status,
+                    headers: response.headers,
+                    bytes,
+                })
+            }
+            Err(err) => {
+                if let Some(entry) = CassetteEntry::from_error(&req, &err, true) {
+                    self.writer.append(&entry);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Byte stream that buffers what it yields and writes the cassette entry once
+/// the stream is exhausted or dropped.
+struct RecordingStream {
+    inner: ByteStream,
+    received: Vec<u8>,
+    entry: Option<CassetteEntry>,
+    writer: Arc<CassetteWriter>,
+}
+
+impl RecordingStream {
+    fn finish(&mut self) {
+        if let Some(mut entry) = self.entry.take() {
+            entry.body = String::from_utf8_lossy(&self.received).into_owned();
+            self.writer.append(&entry);
+        }
+    }
+}
+
+impl Stream for RecordingStream {
+    type Item = Result<Bytes, TransportError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.inner.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(chunk))) => self.received.extend_from_slice(chunk),
+            Poll::Ready(Some(Err(_))) | Poll::Ready(None) => self.finish(),
+            Poll::Pending => {}
+        }
+        poll
+    }
+}
+
+impl Drop for RecordingStream {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Transport that serves responses from a cassette instead of the network.
+///
+/// Each request consumes the first unused entry with the same method, URL
+/// path and normalized body, so interleaved endpoints replay independently.
+/// When no entry has a matching body the session has diverged from the
+/// recording and the request fails, unless
+/// [`ReplayTransport::allow_body_mismatch`] lets it take the first unused
+/// entry for the endpoint instead. Clones share the cursor.
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    path: PathBuf,
+    entries: Arc<Mutex<Vec<Option<CassetteEntry>>>>,
+    allow_body_mismatch: bool,
+}
+
+impl ReplayTransport {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = std::fs::read_to_string(&path)?;
+        let entries = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str::<CassetteEntry>(line).map(Some))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid cassette {}: {err}", path.display()),
+                )
+            })?;
+        Ok(Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+            allow_body_mismatch: false,
+        })
+    }
+
+    /// Serves the first unused entry for an endpoint when no recorded body
+    /// matches the request, logging the mismatch instead of failing.
+    pub fn allow_body_mismatch(mut self, allow: bool) -> Self {
+        self.allow_body_mismatch = allow;
+        self
+    }
+
+    /// Number of recorded exchanges that have not been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.iter().flatten().count())
+            .unwrap_or_default()
+    }
+
+    fn take(&self, req: &Request) -> Result<CassetteEntry, TransportError> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| TransportError::Network("cassette lock poisoned".to_string()))?;
+        let position = |matches: &dyn Fn(&CassetteEntry) -> bool| {
+            entries
+                .iter()
+                .position(|slot| slot.as_ref().is_some_and(matches))
+        };
+        let index = match position(&|entry| entry.matches_endpoint(req) && entry.matches_body(req))
+        {
+            Some(index) => index,
+            None => {
+                let Some(index) = position(&|entry| entry.matches_endpoint(req)) else {
+                    return Err(TransportError::Network(format!(
+                        "no recorded response for {} {} in cassette {}",
+                        req.method,
+                        url_path(&req.url),
+                        self.path.display()
+                    )));
+                };
+                if !self.allow_body_mismatch {
+                    return Err(TransportError::Network(format!(
+                        "request body for {} {} matches no unused entry in cassette {}; the session diverged from the recording",
+                        req.method,
+                        url_path(&req.url),
+                        self.path.display()
+                    )));
+                }
+                warn!(
+                    "request body for {} {} differs from the one recorded in cassette {}; replaying it anyway",
+                    req.method,
+                    url_path(&req.url),
+                    self.path.display()
+                );
+                index
+            }
+        };
+        entries
+            .get_mut(index)
+            .and_then(Option::take)
+            .ok_or_else(|| TransportError::Network("cassette entry already replayed".to_string()))
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReplayTransport {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let entry = self.take(&req)?;
+        let status = entry.status_code()?;
+        if !status.is_success() {
+            return Err(entry.http_error(status));
+        }
+        Ok(Response {
+            status,
+            headers: entry.header_map(),
+            body: Bytes::from(entry.body),
+        })
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let entry = self.take(&req)?;
+        let status = entry.status_code()?;
+        if !status.is_success() {
+            return Err(entry.http_error(status));
+        }
+        let headers = entry.header_map();
+        // Yield one SSE event per chunk so consumers see the same framing they
+        // would over the network.
+        let chunks: Vec<Result<Bytes, TransportError>> = entry
+            .body
+            .split_inclusive("\n\n")
+            .map(|chunk| Ok(Bytes::from(chunk.to_string())))
+            .collect();
+        Ok(StreamResponse {
+            status,
+            headers,
+            bytes: Box::pin(futures::stream::iter(chunks)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use pretty_assertions::assert_eq;
+
+    const SSE_BODY: &str =
+        "event: response.created\ndata: {}\n\nevent: response.completed\ndata: {}\n\n";
+
+    struct FixedTransport;
+
+    #[async_trait]
+    impl HttpTransport for FixedTransport {
+        async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+            if req.url.ends_with("/missing") {
+                return Err(TransportError::Http {
+                    status: StatusCode::NOT_FOUND,
+                    url: Some(req.url),
+                    headers: None,
+                    body: Some("not found".to_string()),
+                });
+            }
+            Ok(Response {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: Bytes::from_static(b"{\"models\":[]}"),
+            })
+        }
+
+        async fn stream(&self, _req: Request) -> Result<StreamResponse, TransportError> {
+            let chunks = SSE_BODY
+                .as_bytes()
+                .chunks(7)
+                .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>();
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                http::header::CONTENT_TYPE,
+                HeaderValue::from_static("text/event-stream"),
+            );
+            Ok(StreamResponse {
+                status: StatusCode::OK,
+                headers,
+                bytes: Box::pin(futures::stream::iter(chunks)),
+            })
+        }
+    }
+
+    async fn collect(bytes: ByteStream) -> String {
+        let chunks: Vec<Bytes> = bytes
+            .map(|chunk| chunk.expect("chunk"))
+            .collect::<Vec<_>>()
+            .await;
+        chunks
+            .iter()
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect()
+    }
+
+    fn recorded(request_body: Value, body: &str) -> String {
+        let entry = CassetteEntry {
+            method: "POST".to_string(),
+            url: "https://api.example.com/v1/responses".to_string(),
+            request_body: Some(request_body),
+            status: 200,
+            headers: BTreeMap::new(),
+            stream: false,
+            body: body.to_string(),
+        };
+        serde_json::to_string(&entry).expect("entry")
+    }
+
+    async fn replay_body(replay: &ReplayTransport, request_body: Value) -> String {
+        let request = Request::new(
+            Method::POST,
+            "http://localhost:1234/v1/responses".to_string(),
+        )
+        .with_json(&request_body);
+        let response = replay.execute(request).await.expect("replayed response");
+        String::from_utf8_lossy(&response.body).into_owned()
+    }
+
+    #[tokio::test]
+    async fn replay_matches_request_bodies() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        let lines = [
+            recorded(
+                serde_json::json!({"input": "first", "prompt_cache_key": "conv-1"}),
+                "first",
+            ),
+            recorded(serde_json::json!({"input": "second"}), "second"),
+        ];
+        std::fs::write(&path, lines.join("\n")).expect("write cassette");
+
+        let replay = ReplayTransport::open(&path).expect("replay");
+        assert_eq!(
+            replay_body(&replay, serde_json::json!({"input": "second"})).await,
+            "second"
+        );
+        // The cache key is tied to the conversation and not compared.
+        assert_eq!(
+            replay_body(
+                &replay,
+                serde_json::json!({"input": "first", "prompt_cache_key": "conv-2"})
+            )
+            .await,
+            "first"
+        );
+
+        // A request whose body was never recorded fails by default...
+        let replay = ReplayTransport::open(&path).expect("replay");
+        let diverged = Request::new(
+            Method::POST,
+            "http://localhost:1234/v1/responses".to_string(),
+        )
+        .with_json(&serde_json::json!({"input": "diverged"}));
+        assert!(replay.execute(diverged).await.is_err());
+        assert_eq!(replay.remaining(), 2);
+
+        // ...and only gets the next entry for its endpoint when allowed.
+        let replay = ReplayTransport::open(&path)
+            .expect("replay")
+            .allow_body_mismatch(true);
+        assert_eq!(
+            replay_body(&replay, serde_json::json!({"input": "diverged"})).await,
+            "first"
+        );
+        assert_eq!(replay.remaining(), 1);
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_exchanges() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+
+        let recorder = RecordingTransport::new(FixedTransport, &path).expect("recorder");
+        let mut request = Request::new(
+            Method::POST,
+            "https://api.example.com/v1/responses".to_string(),
+        );
+        request.headers.insert(
+            http::header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        let streamed = recorder.stream(request).await.expect("stream");
+        assert_eq!(collect(streamed.bytes).await, SSE_BODY);
+        let err = recorder
+            .execute(Request::new(
+                Method::GET,
+                "https://api.example.com/v1/missing".to_string(),
+            ))
+            .await
+            .expect_err("404");
+        assert!(matches!(err, TransportError::Http { .. }));
+
+        let contents = std::fs::read_to_string(&path).expect("cassette");
+        assert!(!contents.contains("secret"));
+
+        let replay = ReplayTransport::open(&path).expect("replay");
+        assert_eq!(replay.remaining(), 2);
+
+        let err = replay
+            .execute(Request::new(
+                Method::GET,
+                "http://localhost:1234/v1/missing".to_string(),
+            ))
+            .await
+            .expect_err("recorded 404");
+        let TransportError::Http { status, body, .. } = err else {
+            panic!("expected http error");
+        };
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.as_deref(), Some("not found"));
+
+        let streamed = replay
+            .stream(Request::new(
+                Method::POST,
+                "http://localhost:1234/v1/responses".to_string(),
+            ))
+            .await
+            .expect("replayed stream");
+        assert_eq!(
+            streamed.headers.get(http::header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("text/event-stream"))
+        );
+        assert_eq!(collect(streamed.bytes).await, SSE_BODY);
+        assert_eq!(replay.remaining(), 0);
+
+        let err = replay
+            .stream(Request::new(
+                Method::POST,
+                "http://localhost:1234/v1/responses".to_string(),
+            ))
+            .await
+            .err()
+            .expect("cassette exhausted");
+        assert!(
+            err.to_string()
+                .contains("no recorded response for POST /v1/responses")
+        );
+
+        // Recording again starts a new cassette.
+        RecordingTransport::new(FixedTransport, &path).expect("recorder");
+        assert_eq!(std::fs::read_to_string(&path).expect("cassette"), "");
+    }
+}
@@ -1,3 +1,4 @@
+mod cassette;
 mod default_client;
 mod error;
 mod request;
@@ -6,6 +7,9 @@ mod sse;
 mod telemetry;
 mod transport;
 
+pub use crate::cassette::CassetteEntry;
+pub use crate::cassette::RecordingTransport;
+pub use crate::cassette::ReplayTransport;
 pub use crate::default_client::CodexHttpClient;
 pub use crate::default_client::CodexRequestBuilder;
 pub use crate::error::StreamError;
@@ -1,14 +1,84 @@
 use crate::CodexThread;
 use crate::agent::AgentStatus;
+use crate::agent::agent_status_from_event;
+use crate::agent::status::is_final;
+use crate::codex::Session;
+use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::thread_manager::ThreadManagerState;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::SubAgentActivityEvent;
+use codex_protocol::protocol::TokenUsage;
 use codex_protocol::user_input::UserInput;
+use futures::future::join_all;
 use std::sync::Arc;
 use std::sync::Weak;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Parent session that a spawned agent reports to. The agent's activity is forwarded to the
+/// parent as `SubAgentActivity` events and its approval requests are raised through the parent.
+#[derive(Clone)]
+pub(crate) struct AgentParent {
+    pub(crate) session: Weak<Session>,
+    pub(crate) turn: Arc<TurnContext>,
+    /// Name of the `[agents]` profile the agent was spawned with, if any.
+    pub(crate) agent: Option<String>,
+}
+
+/// Lifecycle snapshot of a spawned agent, as observed from its event stream.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct AgentRecord {
+    pub(crate) status: AgentStatus,
+    /// Final assistant message of the most recently completed turn. Unlike
+    /// `AgentStatus::Completed`, this survives the agent being interrupted or shut down.
+    pub(crate) last_agent_message: Option<String>,
+    /// Cumulative token usage reported by the agent.
+    pub(crate) token_usage: Option<TokenUsage>,
+    /// Set by [`AgentControl::send_prompt`] when the agent was idle, until the turn for the
+    /// prompt starts. Until then `status` still describes the previous turn.
+    pub(crate) awaiting_turn: bool,
+}
+
+impl AgentRecord {
+    /// Fold a single event into the record. Returns `true` when the record changed.
+    pub(crate) fn apply_event(&mut self, msg: &EventMsg) -> bool {
+        match msg {
+            EventMsg::TokenCount(ev) => {
+                let Some(info) = ev.info.as_ref() else {
+                    return false;
+                };
+                self.token_usage = Some(info.total_token_usage.clone());
+                true
+            }
+            msg => {
+                let Some(status) = agent_status_from_event(msg) else {
+                    return false;
+                };
+                if let AgentStatus::Completed(Some(message)) = &status {
+                    self.last_agent_message = Some(message.clone());
+                }
+                // A turn that completes before the awaited one started is the previous one, so
+                // it does not end the wait.
+                if self.awaiting_turn && matches!(status, AgentStatus::Completed(_)) {
+                    return true;
+                }
+                self.awaiting_turn = false;
+                self.status = status;
+                true
+            }
+        }
+    }
+
+    /// Whether the agent is done with its current work (completed, errored or gone).
+    pub(crate) fn is_final(&self) -> bool {
+        !self.awaiting_turn && is_final(&self.status)
+    }
+}
 
 /// Control-plane handle for multi-agent operations.
 /// `AgentControl` is held by each session (via `SessionServices`). It provides capability to
@@ -27,22 +97,34 @@ impl AgentControl {
         Self { manager }
     }
 
-    #[allow(dead_code)] // Used by upcoming multi-agent tooling.
     /// Spawn a new agent thread and submit the initial prompt.
     ///
     /// If `headless` is true, a background drain task is spawned to prevent unbounded event growth
-    /// of the channel queue when there is no client actively reading the thread events.
+    /// of the channel queue when there is no client actively reading the thread events. The drain
+    /// also records the agent's lifecycle so it can be joined with [`AgentControl::wait_agents`],
+    /// and forwards the agent's activity to `parent` when one is given.
     pub(crate) async fn spawn_agent(
         &self,
         config: crate::config::Config,
         prompt: String,
         headless: bool,
+        parent: Option<AgentParent>,
     ) -> CodexResult<ThreadId> {
         let state = self.upgrade()?;
         let new_thread = state.spawn_new_thread(config, self.clone()).await?;
 
         if headless {
-            spawn_headless_drain(Arc::clone(&new_thread.thread));
+            let (record, _) = watch::channel(AgentRecord::default());
+            state
+                .register_agent_record(new_thread.thread_id, record.clone())
+                .await;
+            spawn_headless_drain(
+                new_thread.thread_id,
+                Arc::clone(&new_thread.thread),
+                record,
+                Arc::downgrade(&state),
+                parent,
+            );
         }
 
         self.send_prompt(new_thread.thread_id, prompt).await?;
@@ -50,15 +132,27 @@ impl AgentControl {
         Ok(new_thread.thread_id)
     }
 
-    #[allow(dead_code)] // Used by upcoming multi-agent tooling.
     /// Send a `user` prompt to an existing agent thread.
+    ///
+    /// When the agent is idle, its record is marked as awaiting the turn for this prompt so that
+    /// [`AgentControl::wait_agents`] does not return the previous turn's result. A prompt sent
+    /// while a turn is running joins that turn instead.
     pub(crate) async fn send_prompt(
         &self,
         agent_id: ThreadId,
         prompt: String,
     ) -> CodexResult<String> {
         let state = self.upgrade()?;
+        let mut was_idle = false;
         state
+            .update_agent_record(agent_id, |record| {
+                was_idle = record.status == AgentStatus::PendingInit || record.is_final();
+                if was_idle {
+                    record.awaiting_turn = true;
+                }
+            })
+            .await;
+        let result = state
             .send_op(
                 agent_id,
                 Op::UserInput {
@@ -66,10 +160,15 @@ impl AgentControl {
                     final_output_json_schema: None,
                 },
             )
-            .await
+            .await;
+        if result.is_err() && was_idle {
+            state
+                .update_agent_record(agent_id, |record| record.awaiting_turn = false)
+                .await;
+        }
+        result
     }
 
-    #[allow(dead_code)] // Used by upcoming multi-agent tooling.
     /// Fetch the last known status for `agent_id`, returning `NotFound` when unavailable.
     pub(crate) async fn get_status(&self, agent_id: ThreadId) -> AgentStatus {
         let Ok(state) = self.upgrade() else {
@@ -82,6 +181,62 @@ impl AgentControl {
         thread.agent_status().await
     }
 
+    /// Wait up to `timeout` for every agent in `agent_ids` to finish its current work, then
+    /// return the latest record of each agent in the same order. Agents that are still running
+    /// when the timeout elapses are reported with their current status.
+    pub(crate) async fn wait_agents(
+        &self,
+        agent_ids: &[ThreadId],
+        timeout: Duration,
+    ) -> CodexResult<Vec<(ThreadId, AgentRecord)>> {
+        let state = self.upgrade()?;
+        let mut receivers = Vec::with_capacity(agent_ids.len());
+        for agent_id in agent_ids {
+            receivers.push((*agent_id, state.agent_record(*agent_id).await));
+        }
+
+        let waits = receivers
+            .iter_mut()
+            .filter_map(|(_, receiver)| receiver.as_mut())
+            .map(|receiver| async move {
+                // An error means the drain exited; the last record is still readable.
+                let _ = receiver.wait_for(AgentRecord::is_final).await;
+            });
+        let _ = tokio::time::timeout(timeout, join_all(waits)).await;
+
+        let mut records = Vec::with_capacity(receivers.len());
+        for (agent_id, receiver) in receivers {
+            let record = match receiver {
+                Some(receiver) => receiver.borrow().clone(),
+                None => AgentRecord {
+                    status: self.get_status(agent_id).await,
+                    ..Default::default()
+                },
+            };
+            records.push((agent_id, record));
+        }
+        Ok(records)
+    }
+
+    /// Shut down `agent_id`, aborting any running turn, and return the last record observed
+    /// before it was closed.
+    pub(crate) async fn close_agent(&self, agent_id: ThreadId) -> CodexResult<AgentRecord> {
+        let state = self.upgrade()?;
+        let thread = state.get_thread(agent_id).await?;
+        let record = match state.agent_record(agent_id).await {
+            Some(receiver) => receiver.borrow().clone(),
+            None => AgentRecord {
+                status: thread.agent_status().await,
+                ..Default::default()
+            },
+        };
+
+        // `Op::Shutdown` interrupts the running turn before tearing the session down.
+        thread.submit(Op::Shutdown).await?;
+        state.remove_thread(&agent_id).await;
+        Ok(record)
+    }
+
     fn upgrade(&self) -> CodexResult<Arc<ThreadManagerState>> {
         self.manager
             .upgrade()
@@ -91,31 +246,114 @@ impl AgentControl {
 
 /// When an agent is spawned "headless" (no UI/view attached), there may be no consumer polling
 /// `CodexThread::next_event()`. The underlying event channel is unbounded, so the producer can
-/// accumulate events indefinitely. This drain task prevents that memory growth by polling and
-/// discarding events until shutdown.
-fn spawn_headless_drain(thread: Arc<CodexThread>) {
+/// accumulate events indefinitely. This drain task prevents that memory growth by polling
+/// events until shutdown, folding each one into the agent's lifecycle record and forwarding
+/// it to the parent session. The record is dropped from `manager` once the thread shuts down.
+fn spawn_headless_drain(
+    agent_id: ThreadId,
+    thread: Arc<CodexThread>,
+    record: watch::Sender<AgentRecord>,
+    manager: Weak<ThreadManagerState>,
+    parent: Option<AgentParent>,
+) {
     tokio::spawn(async move {
         loop {
             match thread.next_event().await {
                 Ok(event) => {
-                    if matches!(event.msg, EventMsg::ShutdownComplete) {
+                    record.send_if_modified(|record| record.apply_event(&event.msg));
+                    let shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+                    if let Some(parent) = parent.as_ref() {
+                        forward_to_parent(parent, agent_id, &thread, event).await;
+                    }
+                    if shutdown {
                         break;
                     }
                 }
                 Err(err) => {
                     tracing::warn!("failed to receive event from agent: {err:?}");
+                    record.send_modify(|record| {
+                        record.status = AgentStatus::Shutdown;
+                        record.awaiting_turn = false;
+                    });
                     break;
                 }
             }
         }
+        if let Some(manager) = manager.upgrade() {
+            manager.remove_agent_record(&agent_id).await;
+        }
     });
 }
 
+/// Forward a sub-agent event to its parent. Approval requests are raised in the parent session
+/// and the decision is sent back to the sub-agent; streaming deltas are dropped.
+async fn forward_to_parent(
+    parent: &AgentParent,
+    agent_id: ThreadId,
+    thread: &Arc<CodexThread>,
+    event: Event,
+) {
+    let Some(session) = parent.session.upgrade() else {
+        return;
+    };
+    let Event { id, msg } = event;
+    match msg {
+        EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_) => {
+            let is_exec = matches!(msg, EventMsg::ExecApprovalRequest(_));
+            let turn = Arc::clone(&parent.turn);
+            let thread = Arc::clone(thread);
+            tokio::spawn(async move {
+                let decision = session.request_agent_approval(agent_id, &turn, msg).await;
+                let op = if is_exec {
+                    Op::ExecApproval { id, decision }
+                } else {
+                    Op::PatchApproval { id, decision }
+                };
+                if let Err(err) = thread.submit(op).await {
+                    tracing::warn!("failed to forward approval to agent {agent_id}: {err}");
+                }
+            });
+        }
+        msg if should_forward_to_parent(&msg) => {
+            let activity = EventMsg::SubAgentActivity(SubAgentActivityEvent {
+                agent_id,
+                turn_id: id,
+                agent: parent.agent.clone(),
+                msg: Box::new(msg),
+            });
+            session
+                .send_event_raw(Event {
+                    id: parent.turn.sub_id.clone(),
+                    msg: activity,
+                })
+                .await;
+        }
+        _ => {}
+    }
+}
+
+fn should_forward_to_parent(msg: &EventMsg) -> bool {
+    !matches!(
+        msg,
+        EventMsg::SessionConfigured(_)
+            | EventMsg::TokenCount(_)
+            | EventMsg::RawResponseItem(_)
+            | EventMsg::AgentMessageDelta(_)
+            | EventMsg::AgentReasoningDelta(_)
+            | EventMsg::AgentReasoningRawContentDelta(_)
+            | EventMsg::AgentMessageContentDelta(_)
+            | EventMsg::ReasoningContentDelta(_)
+            | EventMsg::ReasoningRawContentDelta(_)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::agent::agent_status_from_event;
     use codex_protocol::protocol::ErrorEvent;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsageInfo;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::TurnAbortedEvent;
     use codex_protocol::protocol::TurnCompleteEvent;
@@ -142,6 +380,107 @@ mod tests {
         assert_eq!(got, AgentStatus::NotFound);
     }
 
+    #[tokio::test]
+    async fn wait_agents_errors_when_manager_dropped() {
+        let control = AgentControl::default();
+        let err = control
+            .wait_agents(&[ThreadId::new()], Duration::from_millis(10))
+            .await
+            .expect_err("wait_agents should fail without a manager");
+        assert_eq!(
+            err.to_string(),
+            "unsupported operation: thread manager dropped"
+        );
+    }
+
+    #[tokio::test]
+    async fn close_agent_errors_when_manager_dropped() {
+        let control = AgentControl::default();
+        let err = control
+            .close_agent(ThreadId::new())
+            .await
+            .expect_err("close_agent should fail without a manager");
+        assert_eq!(
+            err.to_string(),
+            "unsupported operation: thread manager dropped"
+        );
+    }
+
+    #[test]
+    fn record_keeps_last_message_after_shutdown() {
+        let mut record = AgentRecord::default();
+        assert!(
+            record.apply_event(&EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message: Some("done".to_string()),
+            }))
+        );
+        assert!(record.apply_event(&EventMsg::ShutdownComplete));
+
+        let expected = AgentRecord {
+            status: AgentStatus::Shutdown,
+            last_agent_message: Some("done".to_string()),
+            token_usage: None,
+            awaiting_turn: false,
+        };
+        assert_eq!(record, expected);
+        assert!(record.is_final());
+    }
+
+    #[test]
+    fn record_tracks_token_usage() {
+        let usage = TokenUsage {
+            input_tokens: 10,
+            output_tokens: 5,
+            total_tokens: 15,
+            ..Default::default()
+        };
+        let mut record = AgentRecord::default();
+        assert!(!record.apply_event(&EventMsg::TokenCount(TokenCountEvent {
+            info: None,
+            rate_limits: None,
+        })));
+        assert!(record.apply_event(&EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage.clone(),
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        })));
+
+        assert_eq!(record.token_usage, Some(usage));
+        assert!(!record.is_final());
+    }
+
+    #[test]
+    fn record_awaiting_a_turn_ignores_the_previous_completion() {
+        let mut record = AgentRecord {
+            status: AgentStatus::Completed(Some("first".to_string())),
+            last_agent_message: Some("first".to_string()),
+            awaiting_turn: true,
+            ..Default::default()
+        };
+        assert!(!record.is_final());
+
+        // The previous turn's completion is drained after the next prompt was sent.
+        record.apply_event(&EventMsg::TurnComplete(TurnCompleteEvent {
+            last_agent_message: Some("first".to_string()),
+        }));
+        assert!(!record.is_final());
+
+        record.apply_event(&EventMsg::TurnStarted(TurnStartedEvent {
+            model_context_window: None,
+        }));
+        assert_eq!(record.status, AgentStatus::Running);
+        assert!(!record.awaiting_turn);
+
+        record.apply_event(&EventMsg::TurnComplete(TurnCompleteEvent {
+            last_agent_message: Some("second".to_string()),
+        }));
+        assert!(record.is_final());
+        assert_eq!(record.last_agent_message, Some("second".to_string()));
+    }
+
     #[tokio::test]
     async fn on_event_updates_status_from_task_started() {
         let status = agent_status_from_event(&EventMsg::TurnStarted(TurnStartedEvent {
@@ -3,4 +3,6 @@ pub(crate) mod status;
 
 pub(crate) use codex_protocol::protocol::AgentStatus;
 pub(crate) use control::AgentControl;
+pub(crate) use control::AgentParent;
+pub(crate) use control::AgentRecord;
 pub(crate) use status::agent_status_from_event;
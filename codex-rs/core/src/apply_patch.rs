@@ -64,6 +64,7 @@ pub(crate) async fn apply_patch(
                 exec_approval_requirement: ExecApprovalRequirement::NeedsApproval {
                     reason: None,
                     proposed_execpolicy_amendment: None,
+                    explanation: None,
                 },
             })
         }
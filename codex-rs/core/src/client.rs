@@ -1,4 +1,6 @@
 use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
@@ -7,9 +9,10 @@ use codex_api::AggregateStreamExt;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
+use codex_api::MessagesClient as ApiMessagesClient;
+use codex_api::MessagesOptions as ApiMessagesOptions;
 use codex_api::Prompt as ApiPrompt;
 use codex_api::RequestTelemetry;
-use codex_api::ReqwestTransport;
 use codex_api::ResponseStream as ApiResponseStream;
 use codex_api::ResponsesClient as ApiResponsesClient;
 use codex_api::ResponsesOptions as ApiResponsesOptions;
@@ -46,15 +49,18 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::default_client::build_http_transport;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::features::FEATURES;
 use crate::features::Feature;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
+use crate::model_provider_info::ProviderFallback;
 use crate::model_provider_info::WireApi;
+use crate::models_manager::manager::ModelsManager;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
+use crate::tools::spec::create_tools_json_for_messages_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
 #[derive(Debug, Clone)]
@@ -68,6 +74,9 @@ pub struct ModelClient {
     effort: Option<ReasoningEffortConfig>,
     summary: ReasoningSummaryConfig,
     session_source: SessionSource,
+    /// 0 while requests go to `provider`; otherwise the 1-based index of the
+    /// active entry in `config.model_provider_fallbacks`.
+    fallback_index: Arc<AtomicUsize>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -93,6 +102,7 @@ impl ModelClient {
             effort,
             summary,
             session_source,
+            fallback_index: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -108,17 +118,39 @@ impl ModelClient {
         Arc::clone(&self.config)
     }
 
+    /// Returns the provider requests are currently sent to: the configured
+    /// provider, or the active fallback after [`ModelClient::fail_over`].
     pub fn provider(&self) -> &ModelProviderInfo {
-        &self.provider
+        match self.active_fallback() {
+            Some(fallback) => &fallback.provider,
+            None => &self.provider,
+        }
+    }
+
+    /// Switches this client to the next configured fallback provider, returning
+    /// it, or `None` once every fallback has been tried. The switch is shared by
+    /// clones of this client and lasts for the rest of the turn.
+    pub fn fail_over(&self) -> Option<ProviderFallback> {
+        let next = self.fallback_index.load(Ordering::SeqCst) + 1;
+        let fallback = self.config.model_provider_fallbacks.get(next - 1)?.clone();
+        self.fallback_index.store(next, Ordering::SeqCst);
+        Some(fallback)
+    }
+
+    fn active_fallback(&self) -> Option<&ProviderFallback> {
+        match self.fallback_index.load(Ordering::SeqCst) {
+            0 => None,
+            index => self.config.model_provider_fallbacks.get(index - 1),
+        }
     }
 
-    /// Streams a single model turn using either the Responses or Chat
-    /// Completions wire API, depending on the configured provider.
+    /// Streams a single model turn using the Responses, Chat Completions or
+    /// Messages wire API, depending on the configured provider.
     ///
     /// For Chat providers, the underlying stream is optionally aggregated
     /// based on the `show_raw_agent_reasoning` flag in the config.
     pub async fn stream(&self, prompt: &Prompt) -> Result<ResponseStream> {
-        match self.provider.wire_api {
+        match self.provider().wire_api {
             WireApi::Responses => self.stream_responses_api(prompt).await,
             WireApi::Chat => {
                 let api_stream = self.stream_chat_completions(prompt).await?;
@@ -135,6 +167,10 @@ impl ModelClient {
                     ))
                 }
             }
+            WireApi::Messages => {
+                let api_stream = self.stream_messages_api(prompt).await?;
+                Ok(map_response_stream(api_stream, self.otel_manager.clone()))
+            }
         }
     }
 
@@ -166,10 +202,10 @@ impl ModelClient {
                 None => None,
             };
             let api_provider = self
-                .provider
+                .provider()
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let api_auth = auth_provider_from_auth(auth.clone(), self.provider())?;
+            let transport = build_http_transport(self.config.http_cassette.as_ref())?;
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -196,6 +232,67 @@ impl ModelClient {
         }
     }
 
+    /// Streams a turn via the Anthropic Messages API.
+    ///
+    /// Extended thinking is only requested when a reasoning effort is
+    /// configured; `output_schema` is not supported.
+    async fn stream_messages_api(&self, prompt: &Prompt) -> Result<ApiResponseStream> {
+        if prompt.output_schema.is_some() {
+            return Err(CodexErr::UnsupportedOperation(
+                "output_schema is not supported for Messages API".to_string(),
+            ));
+        }
+
+        let auth_manager = self.auth_manager.clone();
+        let model_info = self.get_model_info();
+        let instructions = prompt.get_full_instructions(&model_info).into_owned();
+        let tools_json = create_tools_json_for_messages_api(&prompt.tools)?;
+        let api_prompt = build_api_prompt(prompt, instructions, tools_json);
+        let conversation_id = self.conversation_id.to_string();
+        let session_source = self.session_source.clone();
+        let thinking_budget_tokens = self.effort.and_then(thinking_budget_for_effort);
+
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let auth = match auth_manager.as_ref() {
+                Some(manager) => manager.auth().await,
+                None => None,
+            };
+            let api_provider = self
+                .provider()
+                .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+            let api_auth = auth_provider_from_auth(auth.clone(), self.provider())?;
+            let transport = build_http_transport(self.config.http_cassette.as_ref())?;
+            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let client = ApiMessagesClient::new(transport, api_provider, api_auth)
+                .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+
+            let options = ApiMessagesOptions {
+                max_tokens: None,
+                thinking_budget_tokens,
+                conversation_id: Some(conversation_id.clone()),
+                session_source: Some(session_source.clone()),
+                extra_headers: ApiHeaderMap::new(),
+            };
+            let stream_result = client
+                .stream_prompt(&self.get_model(), &api_prompt, options)
+                .await;
+
+            match stream_result {
+                Ok(stream) => return Ok(stream),
+                Err(ApiError::Transport(TransportError::Http { status, .. }))
+                    if status == StatusCode::UNAUTHORIZED =>
+                {
+                    handle_unauthorized(status, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the OpenAI Responses API.
     ///
     /// Handles SSE fixtures, reasoning summaries, verbosity, and the
@@ -203,8 +300,9 @@ impl ModelClient {
     async fn stream_responses_api(&self, prompt: &Prompt) -> Result<ResponseStream> {
         if let Some(path) = &*CODEX_RS_SSE_FIXTURE {
             warn!(path, "Streaming from fixture");
-            let stream = codex_api::stream_from_fixture(path, self.provider.stream_idle_timeout())
-                .map_err(map_api_error)?;
+            let stream =
+                codex_api::stream_from_fixture(path, self.provider().stream_idle_timeout())
+                    .map_err(map_api_error)?;
             return Ok(map_response_stream(stream, self.otel_manager.clone()));
         }
 
@@ -259,10 +357,10 @@ impl ModelClient {
                 None => None,
             };
             let api_provider = self
-                .provider
+                .provider()
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-            let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let api_auth = auth_provider_from_auth(auth.clone(), self.provider())?;
+            let transport = build_http_transport(self.config.http_cassette.as_ref())?;
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let compression = if self
                 .config
@@ -271,7 +369,7 @@ impl ModelClient {
                 && auth
                     .as_ref()
                     .is_some_and(|auth| auth.mode == AuthMode::ChatGPT)
-                && self.provider.is_openai()
+                && self.provider().is_openai()
             {
                 Compression::Zstd
             } else {
@@ -313,7 +411,15 @@ impl ModelClient {
     }
 
     pub fn get_provider(&self) -> ModelProviderInfo {
-        self.provider.clone()
+        self.provider().clone()
+    }
+
+    /// Returns the key of the provider requests are currently sent to.
+    pub fn get_provider_id(&self) -> String {
+        match self.active_fallback() {
+            Some(fallback) => fallback.provider_id.clone(),
+            None => self.config.model_provider_id.clone(),
+        }
     }
 
     pub fn get_otel_manager(&self) -> OtelManager {
@@ -324,13 +430,26 @@ impl ModelClient {
         self.session_source.clone()
     }
 
-    /// Returns the currently configured model slug.
+    /// Returns the model slug requests are currently sent with, which a
+    /// fallback provider may override.
     pub fn get_model(&self) -> String {
-        self.model_info.slug.clone()
+        self.active_fallback()
+            .and_then(|fallback| fallback.model.clone())
+            .unwrap_or_else(|| self.model_info.slug.clone())
     }
 
+    /// Returns the metadata of the model from [`ModelClient::get_model`], so
+    /// a fallback model is prompted with its own instructions and settings.
     pub fn get_model_info(&self) -> ModelInfo {
-        self.model_info.clone()
+        match self
+            .active_fallback()
+            .and_then(|fallback| fallback.model.as_deref())
+        {
+            Some(model) if model != self.model_info.slug => {
+                ModelsManager::construct_model_info_offline(model, &self.config)
+            }
+            _ => self.model_info.clone(),
+        }
     }
 
     /// Returns the current reasoning effort setting.
@@ -361,10 +480,10 @@ impl ModelClient {
             None => None,
         };
         let api_provider = self
-            .provider
+            .provider()
             .to_api_provider(auth.as_ref().map(|a| a.mode))?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let api_auth = auth_provider_from_auth(auth.clone(), self.provider())?;
+        let transport = build_http_transport(self.config.http_cassette.as_ref())?;
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
             .with_telemetry(Some(request_telemetry));
@@ -428,6 +547,18 @@ fn build_api_prompt(prompt: &Prompt, instructions: String, tools_json: Vec<Value
     }
 }
 
+/// Extended-thinking budget requested from Messages providers for each
+/// reasoning effort; `None` disables thinking.
+fn thinking_budget_for_effort(effort: ReasoningEffortConfig) -> Option<u32> {
+    match effort {
+        ReasoningEffortConfig::None | ReasoningEffortConfig::Minimal => None,
+        ReasoningEffortConfig::Low => Some(2_048),
+        ReasoningEffortConfig::Medium => Some(8_192),
+        ReasoningEffortConfig::High => Some(16_384),
+        ReasoningEffortConfig::XHigh => Some(24_576),
+    }
+}
+
 fn beta_feature_headers(config: &Config) -> ApiHeaderMap {
     let enabled = FEATURES
         .iter()
@@ -21,8 +21,12 @@ use crate::exec_policy::ExecPolicyManager;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::hooks::HookContext;
+use crate::hooks::HookEnvironment;
 use crate::hooks::HooksManager;
+use crate::hooks::build_additional_context_item;
+use crate::hooks::build_notification_hook_payload;
 use crate::hooks::build_session_start_hook_payload;
+use crate::hooks::build_stop_hook_payload;
 use crate::models_manager::manager::ModelsManager;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
@@ -31,12 +35,14 @@ use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
 use crate::terminal;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::tokenizer_for_model;
 use crate::user_notification::UserNotifier;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
 use codex_protocol::ThreadId;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyExplanation;
 use codex_protocol::items::TurnItem;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::protocol::FileChange;
@@ -87,6 +93,8 @@ use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::types::LinuxSandboxBackend;
+use crate::config::types::ResourceLimits;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::environment_context::EnvironmentContext;
@@ -99,6 +107,8 @@ use crate::feedback_tags;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::network_proxy::DomainAllowlist;
+use crate::network_proxy::NetworkProxy;
 use crate::project_doc::get_user_instructions;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
@@ -110,6 +120,9 @@ use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::HookActivityEvent;
+use crate::protocol::HookActivityHook;
+use crate::protocol::HookActivityStatus;
 use crate::protocol::Op;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
@@ -125,6 +138,7 @@ use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::TurnSteeredEvent;
 use crate::protocol::WarningEvent;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
@@ -165,8 +179,10 @@ use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::ReadinessFlag;
+use codex_utils_tokenizer::Tokenizer;
 
 /// The high-level interface to the Codex system.
 /// It operates as a queue pair where you send submissions and receive events.
@@ -176,6 +192,8 @@ pub struct Codex {
     pub(crate) rx_event: Receiver<Event>,
     // Last known status of the agent.
     pub(crate) agent_status: Arc<RwLock<AgentStatus>>,
+    // Recorder persisting this session, if any; used to edit thread metadata.
+    pub(crate) rollout: Option<RolloutRecorder>,
 }
 
 /// Wrapper returned by [`Codex::spawn`] containing the spawned [`Codex`],
@@ -299,6 +317,7 @@ impl Codex {
             map_session_init_error(&e, &config.codex_home)
         })?;
         let thread_id = session.conversation_id;
+        let rollout = session.services.rollout.lock().await.clone();
 
         // This task will run until Op::Shutdown is received.
         tokio::spawn(submission_loop(session, config, rx_sub));
@@ -307,6 +326,7 @@ impl Codex {
             tx_sub,
             rx_event,
             agent_status,
+            rollout,
         };
 
         #[allow(deprecated)]
@@ -389,8 +409,18 @@ pub(crate) struct TurnContext {
     pub(crate) ghost_snapshot: GhostSnapshotConfig,
     pub(crate) final_output_json_schema: Option<Value>,
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
+    /// Paths that sandboxed commands may not access.
+    pub(crate) sandbox_deny_read: Vec<AbsolutePathBuf>,
+    /// Backend `codex-linux-sandbox` uses for sandboxed commands.
+    pub(crate) linux_sandbox_backend: LinuxSandboxBackend,
+    /// Workspace overlay directory for the Linux namespaces sandbox.
+    pub(crate) linux_sandbox_overlay_dir: Option<AbsolutePathBuf>,
+    /// CPU, memory, process and file size limits for spawned commands.
+    pub(crate) sandbox_resource_limits: ResourceLimits,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
+    /// Vocabulary of the turn's model, used to measure token budgets.
+    pub(crate) tokenizer: Option<Tokenizer>,
 }
 
 impl TurnContext {
@@ -405,6 +435,20 @@ impl TurnContext {
             .as_deref()
             .unwrap_or(compact::SUMMARIZATION_PROMPT)
     }
+
+    /// Upper directory of the overlay that collects sandboxed commands'
+    /// changes to the workspace, when the Linux namespaces sandbox mounts one.
+    pub(crate) fn workspace_overlay_upper_dir(&self) -> Option<PathBuf> {
+        if !cfg!(target_os = "linux")
+            || self.linux_sandbox_backend != LinuxSandboxBackend::Namespaces
+            || !matches!(self.sandbox_policy, SandboxPolicy::WorkspaceWrite { .. })
+        {
+            return None;
+        }
+        self.linux_sandbox_overlay_dir
+            .as_ref()
+            .map(|overlay_dir| overlay_dir.as_path().join("upper"))
+    }
 }
 
 #[derive(Clone)]
@@ -541,7 +585,8 @@ impl Session {
         let tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_info: &model_info,
             features: &per_turn_config.features,
-        });
+        })
+        .with_agent_profiles(&per_turn_config.agents);
 
         TurnContext {
             sub_id,
@@ -558,8 +603,13 @@ impl Session {
             ghost_snapshot: per_turn_config.ghost_snapshot.clone(),
             final_output_json_schema: None,
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
+            sandbox_deny_read: per_turn_config.sandbox_deny_read.clone(),
+            linux_sandbox_backend: per_turn_config.linux_sandbox_backend,
+            linux_sandbox_overlay_dir: per_turn_config.linux_sandbox_overlay_dir.clone(),
+            sandbox_resource_limits: per_turn_config.sandbox_resource_limits,
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: model_info.truncation_policy.into(),
+            tokenizer: tokenizer_for_model(&model_info),
         }
     }
 
@@ -697,12 +747,39 @@ impl Session {
         }
         let state = SessionState::new(session_configuration.clone());
 
+        let allowed_domains = DomainAllowlist::new(&config.sandbox_allowed_domains);
+        let network_proxy = if allowed_domains.is_empty() {
+            None
+        } else {
+            match NetworkProxy::start(allowed_domains, tx_event.clone()).await {
+                Ok(proxy) => Some(proxy),
+                Err(err) => {
+                    warn!("failed to start sandbox network proxy: {err}");
+                    post_session_configured_events.push(Event {
+                        id: INITIAL_SUBMIT_ID.to_owned(),
+                        msg: EventMsg::Warning(WarningEvent {
+                            message: format!(
+                                "Failed to start the sandbox network proxy, so \
+                                 `sandbox_workspace_write.allowed_domains` is ignored: {err}"
+                            ),
+                        }),
+                    });
+                    None
+                }
+            }
+        };
+
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecProcessManager::default(),
             notifier: UserNotifier::new(config.notify.clone()),
-            hooks: config.hooks.clone().map(HooksManager::new),
+            hooks: config.hooks.clone().map(|hooks| {
+                HooksManager::new(hooks).with_environment(HookEnvironment {
+                    project_dir: Some(session_configuration.cwd.clone()),
+                    session_id: Some(conversation_id.to_string()),
+                })
+            }),
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: Arc::new(default_shell),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -713,6 +790,7 @@ impl Session {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy,
         };
 
         let sess = Arc::new(Session {
@@ -726,6 +804,7 @@ impl Session {
             next_internal_sub_id: AtomicU64::new(0),
         });
 
+        let mut session_start_context = Vec::new();
         if let Some(hooks) = sess.services.hooks.as_ref() {
             let session_start_source = match &initial_history {
                 InitialHistory::New => "startup",
@@ -738,7 +817,10 @@ impl Session {
                 hook_event_name: "SessionStart".to_string(),
             };
             let payload = build_session_start_hook_payload(&context, session_start_source);
-            let _ = hooks.run_session_start(&payload).await;
+            match hooks.run_session_start(&payload).await {
+                Ok(additional_context) => session_start_context = additional_context,
+                Err(err) => warn!("SessionStart hook failed: {err}"),
+            }
         }
 
         // Dispatch the SessionConfiguredEvent first and then report any errors.
@@ -771,6 +853,7 @@ impl Session {
             sandbox_policy: session_configuration.sandbox_policy.get().clone(),
             codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
             sandbox_cwd: session_configuration.cwd.clone(),
+            deny_read: config.sandbox_deny_read.clone(),
         };
         sess.services
             .mcp_connection_manager
@@ -788,6 +871,11 @@ impl Session {
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
+        if let Some(item) = build_additional_context_item("SessionStart", &session_start_context) {
+            let turn_context = sess.new_default_turn().await;
+            sess.record_conversation_items(&turn_context, &[item.into()])
+                .await;
+        }
 
         Ok(sess)
     }
@@ -966,6 +1054,7 @@ impl Session {
                 sandbox_policy: per_turn_config.sandbox_policy.get().clone(),
                 codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
                 sandbox_cwd: per_turn_config.cwd.clone(),
+                deny_read: per_turn_config.sandbox_deny_read.clone(),
             };
             if let Err(e) = self
                 .services
@@ -997,6 +1086,9 @@ impl Session {
         if let Some(final_schema) = final_output_json_schema {
             turn_context.final_output_json_schema = final_schema;
         }
+        if let Some(hooks) = self.services.hooks.as_ref() {
+            hooks.set_project_dir(turn_context.cwd.clone());
+        }
         Arc::new(turn_context)
     }
 
@@ -1053,6 +1145,17 @@ impl Session {
         }
     }
 
+    /// Delivers an event to clients without recording it in the rollout.
+    pub(crate) async fn send_event_unrecorded(&self, turn_context: &TurnContext, msg: EventMsg) {
+        let event = Event {
+            id: turn_context.sub_id.clone(),
+            msg,
+        };
+        if let Err(e) = self.tx_event.send(event).await {
+            error!("failed to send event: {e}");
+        }
+    }
+
     pub(crate) async fn send_event_raw(&self, event: Event) {
         // Record the last known agent status.
         if let Some(status) = agent_status_from_event(&event.msg) {
@@ -1157,6 +1260,7 @@ impl Session {
         cwd: PathBuf,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        explanation: Option<ExecPolicyExplanation>,
     ) -> ReviewDecision {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
@@ -1177,6 +1281,8 @@ impl Session {
         }
 
         let parsed_cmd = parse_command(&command);
+        let notification_call_id = call_id.clone();
+        let exec_approval_message = exec_approval_message(&command, reason.as_deref());
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
@@ -1184,9 +1290,17 @@ impl Session {
             cwd,
             reason,
             proposed_execpolicy_amendment,
+            explanation,
             parsed_cmd,
         });
         self.send_event(turn_context, event).await;
+        self.run_notification_hooks(
+            turn_context,
+            "exec_approval",
+            &exec_approval_message,
+            &notification_call_id,
+        )
+        .await;
         rx_approve.await.unwrap_or_default()
     }
 
@@ -1216,6 +1330,8 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        let notification_call_id = call_id.clone();
+        let patch_approval_message = patch_approval_message(changes.len());
         let event = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
@@ -1224,10 +1340,130 @@ impl Session {
             grant_root,
         });
         self.send_event(turn_context, event).await;
+        self.run_notification_hooks(
+            turn_context,
+            "apply_patch_approval",
+            &patch_approval_message,
+            &notification_call_id,
+        )
+        .await;
         rx_approve
     }
 
+    /// Raise an approval request from sub-agent `agent_id` in this session and await the
+    /// decision.
+    ///
+    /// Unlike turn approvals, these are keyed by agent and call id and kept on the session,
+    /// so concurrent requests from several agents do not collide and requests still reach
+    /// the user after the turn that spawned the agent has ended. The request is attached to
+    /// the turn running when it arrives, or to `spawn_turn` when none is.
+    pub(crate) async fn request_agent_approval(
+        &self,
+        agent_id: ThreadId,
+        spawn_turn: &Arc<TurnContext>,
+        mut request: EventMsg,
+    ) -> ReviewDecision {
+        let turn_context = self
+            .active_turn_context()
+            .await
+            .unwrap_or_else(|| Arc::clone(spawn_turn));
+        let (call_id, notification_type, message) = match &mut request {
+            EventMsg::ExecApprovalRequest(event) => {
+                event.turn_id = turn_context.sub_id.clone();
+                let message = exec_approval_message(&event.command, event.reason.as_deref());
+                (event.call_id.clone(), "exec_approval", message)
+            }
+            EventMsg::ApplyPatchApprovalRequest(event) => {
+                event.turn_id = turn_context.sub_id.clone();
+                let message = patch_approval_message(event.changes.len());
+                (event.call_id.clone(), "apply_patch_approval", message)
+            }
+            _ => return ReviewDecision::Denied,
+        };
+
+        let key = format!("{agent_id}:{call_id}");
+        let (tx_approve, rx_approve) = oneshot::channel();
+        let prev_entry = self
+            .state
+            .lock()
+            .await
+            .insert_agent_approval(key.clone(), tx_approve);
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending approval for {key}");
+        }
+
+        self.send_event_raw(Event {
+            id: key,
+            msg: request,
+        })
+        .await;
+        self.run_notification_hooks(&turn_context, notification_type, &message, &call_id)
+            .await;
+        rx_approve.await.unwrap_or_default()
+    }
+
+    /// Resolve a pending sub-agent approval raised by [`Session::request_agent_approval`].
+    /// Returns `false` when `id` does not name one.
+    async fn resolve_agent_approval(&self, id: &str, decision: ReviewDecision) -> bool {
+        let entry = self.state.lock().await.remove_agent_approval(id);
+        match entry {
+            Some(tx_approve) => {
+                tx_approve.send(decision).ok();
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn active_turn_context(&self) -> Option<Arc<TurnContext>> {
+        let active = self.active_turn.lock().await;
+        active
+            .as_ref()
+            .and_then(|at| at.tasks.values().next())
+            .map(|task| Arc::clone(&task.turn_context))
+    }
+
+    /// Builds the common hook payload fields for this session.
+    pub(crate) async fn hook_context(&self, hook_event_name: &str) -> HookContext {
+        let transcript_path = self
+            .rollout_path()
+            .await
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        HookContext {
+            session_id: self.conversation_id().to_string(),
+            transcript_path,
+            hook_event_name: hook_event_name.to_string(),
+        }
+    }
+
+    async fn run_notification_hooks(
+        &self,
+        turn_context: &TurnContext,
+        notification_type: &str,
+        message: &str,
+        call_id: &str,
+    ) {
+        let Some(hooks) = self.services.hooks.as_ref() else {
+            return;
+        };
+        let context = self.hook_context("Notification").await;
+        let payload = build_notification_hook_payload(
+            &context,
+            notification_type,
+            message,
+            call_id,
+            &turn_context.cwd,
+        );
+        if let Err(err) = hooks.run_notification(&payload).await {
+            warn!("Notification hook failed: {err}");
+        }
+    }
+
     pub async fn notify_approval(&self, sub_id: &str, decision: ReviewDecision) {
+        if self.resolve_agent_approval(sub_id, decision.clone()).await {
+            return;
+        }
         let entry = {
             let mut active = self.active_turn.lock().await;
             match active.as_mut() {
@@ -1286,6 +1522,7 @@ impl Session {
                     history.record_items(
                         std::iter::once(response_item),
                         turn_context.truncation_policy,
+                        turn_context.tokenizer,
                     );
                 }
                 RolloutItem::Compacted(compacted) => {
@@ -1297,6 +1534,7 @@ impl Session {
                             self.build_initial_context(turn_context),
                             &user_messages,
                             &compacted.message,
+                            turn_context.tokenizer,
                         );
                         history.replace(rebuilt);
                     }
@@ -1317,7 +1555,11 @@ impl Session {
         turn_context: &TurnContext,
     ) {
         let mut state = self.state.lock().await;
-        state.record_items(items.iter(), turn_context.truncation_policy);
+        state.record_items(
+            items.iter(),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
     }
 
     pub(crate) async fn record_model_warning(&self, message: impl Into<String>, ctx: &TurnContext) {
@@ -1570,6 +1812,38 @@ impl Session {
         }
     }
 
+    /// Queues `input` as a steer for the running turn and returns that turn's
+    /// id. Fails when no turn is running, when `expected_turn_id` does not
+    /// name the running turn, or when the running turn is already finishing.
+    pub(crate) async fn steer_turn(
+        &self,
+        input: Vec<UserInput>,
+        expected_turn_id: Option<&str>,
+    ) -> Result<String, String> {
+        let mut active = self.active_turn.lock().await;
+        let Some(at) = active.as_mut() else {
+            return Err("no turn is currently running".to_string());
+        };
+        let Some(turn_id) = at.tasks.keys().next().cloned() else {
+            return Err("no turn is currently running".to_string());
+        };
+        if let Some(expected) = expected_turn_id
+            && expected != turn_id
+        {
+            return Err(format!(
+                "turn {expected} is not running (running turn is {turn_id})"
+            ));
+        }
+        let mut ts = at.turn_state.lock().await;
+        if ts.is_finishing() {
+            return Err(format!(
+                "turn {turn_id} is finishing; send the input as a new turn"
+            ));
+        }
+        ts.push_steer(input);
+        Ok(turn_id)
+    }
+
     /// Returns the input if there was no task running to inject into
     pub async fn inject_response_items(
         &self,
@@ -1588,14 +1862,26 @@ impl Session {
         }
     }
 
-    pub async fn get_pending_input(&self) -> Vec<ResponseInputItem> {
+    /// Takes the pending input together with the steers it contains, so a
+    /// steer is only confirmed once its items have been handed to the model.
+    async fn take_pending_input_and_steers(&self) -> (Vec<ResponseInputItem>, Vec<Vec<UserInput>>) {
         let mut active = self.active_turn.lock().await;
         match active.as_mut() {
             Some(at) => {
                 let mut ts = at.turn_state.lock().await;
-                ts.take_pending_input()
+                (ts.take_pending_input(), ts.take_pending_steers())
             }
-            None => Vec::with_capacity(0),
+            None => (Vec::with_capacity(0), Vec::with_capacity(0)),
+        }
+    }
+
+    /// Stops the running turn from accepting steers unless some are still
+    /// waiting to be sent to the model. Returns whether the turn may end.
+    async fn finish_turn_unless_steered(&self) -> bool {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => at.turn_state.lock().await.finish_unless_steered(),
+            None => true,
         }
     }
 
@@ -1757,6 +2043,12 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ThreadRollback { num_turns } => {
                 handlers::thread_rollback(&sess, sub.id.clone(), num_turns).await;
             }
+            Op::SteerTurn {
+                items,
+                expected_turn_id,
+            } => {
+                handlers::steer_turn(&sess, sub.id.clone(), items, expected_turn_id).await;
+            }
             Op::RunUserShellCommand { command } => {
                 handlers::run_user_shell_command(
                     &sess,
@@ -1796,6 +2088,8 @@ mod handlers {
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
     use crate::hooks::HookContext;
+    use crate::hooks::build_additional_context_item;
+    use crate::hooks::build_session_end_hook_payload;
     use crate::hooks::build_user_prompt_submit_hook_payload;
 
     use crate::mcp::auth::compute_auth_statuses;
@@ -1818,6 +2112,8 @@ mod handlers {
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::ThreadRolledBackEvent;
     use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use codex_protocol::protocol::TurnStartedEvent;
     use codex_protocol::protocol::WarningEvent;
 
     use crate::context_manager::is_user_turn_boundary;
@@ -1840,6 +2136,24 @@ mod handlers {
         parts.join("\n")
     }
 
+    /// Replaces the text of a prompt with a hook-provided rewrite. The rewrite
+    /// takes the place of the first text input and the other text inputs are
+    /// dropped, so non-text inputs such as images keep their position.
+    pub(super) fn rewrite_prompt_text(items: Vec<UserInput>, prompt: String) -> Vec<UserInput> {
+        let mut prompt = Some(prompt);
+        let mut rewritten: Vec<UserInput> = items
+            .into_iter()
+            .filter_map(|item| match item {
+                UserInput::Text { .. } => prompt.take().map(|text| UserInput::Text { text }),
+                other => Some(other),
+            })
+            .collect();
+        if let Some(text) = prompt {
+            rewritten.insert(0, UserInput::Text { text });
+        }
+        rewritten
+    }
+
     pub async fn interrupt(sess: &Arc<Session>) {
         sess.interrupt_task().await;
     }
@@ -1867,7 +2181,7 @@ mod handlers {
         op: Op,
         previous_context: &mut Option<Arc<TurnContext>>,
     ) {
-        let (items, updates) = match op {
+        let (mut items, updates) = match op {
             Op::UserTurn {
                 cwd,
                 approval_policy,
@@ -1911,6 +2225,7 @@ mod handlers {
             .get_otel_manager()
             .user_prompt(&items);
 
+        let mut context_item = None;
         if let Some(hooks) = sess.services.hooks.as_ref() {
             let prompt = prompt_text(&items);
             let transcript_path = sess
@@ -1925,7 +2240,56 @@ mod handlers {
             };
             let payload =
                 build_user_prompt_submit_hook_payload(&context, &prompt, &current_context.cwd);
-            let _ = hooks.run_user_prompt_submit(&payload).await;
+            match hooks.run_user_prompt_submit(&payload).await {
+                Ok(outcome) => {
+                    if let Some(blocked) = outcome.blocked {
+                        let reason = blocked
+                            .decision
+                            .reason
+                            .unwrap_or_else(|| "blocked by hook".to_string());
+                        // A prompt for a running turn would only have joined
+                        // it; otherwise report the turn it would have started
+                        // as completed so clients stop waiting for it.
+                        let starts_turn = sess.active_turn.lock().await.is_none();
+                        if starts_turn {
+                            sess.send_event(
+                                &current_context,
+                                EventMsg::TurnStarted(TurnStartedEvent {
+                                    model_context_window: current_context
+                                        .client
+                                        .get_model_context_window(),
+                                }),
+                            )
+                            .await;
+                        }
+                        sess.send_event(
+                            &current_context,
+                            EventMsg::Warning(WarningEvent {
+                                message: format!("prompt blocked by hook: {reason}"),
+                            }),
+                        )
+                        .await;
+                        if starts_turn {
+                            sess.send_event(
+                                &current_context,
+                                EventMsg::TurnComplete(TurnCompleteEvent {
+                                    last_agent_message: None,
+                                }),
+                            )
+                            .await;
+                        }
+                        return;
+                    }
+                    if let Some(prompt) = outcome.prompt {
+                        items = rewrite_prompt_text(items, prompt);
+                    }
+                    context_item = build_additional_context_item(
+                        "UserPromptSubmit",
+                        &outcome.additional_context,
+                    );
+                }
+                Err(err) => warn!("UserPromptSubmit hook failed: {err}"),
+            }
         }
 
         // Attempt to inject input into current task
@@ -1936,10 +2300,16 @@ mod handlers {
                 sess.record_conversation_items(&current_context, std::slice::from_ref(&env_item))
                     .await;
             }
+            if let Some(context_item) = context_item {
+                sess.record_conversation_items(&current_context, &[context_item.into()])
+                    .await;
+            }
 
             sess.spawn_task(Arc::clone(&current_context), items, RegularTask)
                 .await;
             *previous_context = Some(current_context);
+        } else if let Some(context_item) = context_item {
+            let _ = sess.inject_response_items(vec![context_item]).await;
         }
     }
 
@@ -2006,6 +2376,8 @@ mod handlers {
         }
         match decision {
             ReviewDecision::Abort => {
+                sess.resolve_agent_approval(&id, ReviewDecision::Abort)
+                    .await;
                 sess.interrupt_task().await;
             }
             other => sess.notify_approval(&id, other).await,
@@ -2015,6 +2387,8 @@ mod handlers {
     pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
         match decision {
             ReviewDecision::Abort => {
+                sess.resolve_agent_approval(&id, ReviewDecision::Abort)
+                    .await;
                 sess.interrupt_task().await;
             }
             other => sess.notify_approval(&id, other).await,
@@ -2155,6 +2529,30 @@ mod handlers {
         .await;
     }
 
+    pub async fn steer_turn(
+        sess: &Arc<Session>,
+        sub_id: String,
+        items: Vec<UserInput>,
+        expected_turn_id: Option<String>,
+    ) {
+        let message = if items.is_empty() {
+            "steer input must not be empty".to_string()
+        } else {
+            match sess.steer_turn(items, expected_turn_id.as_deref()).await {
+                Ok(_) => return,
+                Err(message) => message,
+            }
+        };
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::TurnSteerFailed),
+            }),
+        })
+        .await;
+    }
+
     pub async fn thread_rollback(sess: &Arc<Session>, sub_id: String, num_turns: u32) {
         if num_turns == 0 {
             sess.send_event_raw(Event {
@@ -2205,6 +2603,7 @@ mod handlers {
             .terminate_all_processes()
             .await;
         info!("Shutting down Codex instance");
+        let session_end_context = sess.hook_context("SessionEnd").await;
         let history = sess.clone_history().await;
         let turn_count = history
             .raw_items()
@@ -2237,6 +2636,14 @@ mod handlers {
             sess.send_event_raw(event).await;
         }
 
+        // Run after the rollout is flushed so hooks can upload the full transcript.
+        if let Some(hooks) = sess.services.hooks.as_ref() {
+            let payload = build_session_end_hook_payload(&session_end_context, "shutdown");
+            if let Err(err) = hooks.run_session_end(&payload).await {
+                warn!("SessionEnd hook failed: {err}");
+            }
+        }
+
         let event = Event {
             id: sub_id,
             msg: EventMsg::ShutdownComplete,
@@ -2346,8 +2753,13 @@ async fn spawn_review_thread(
         cwd: parent_turn_context.cwd.clone(),
         final_output_json_schema: None,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
+        sandbox_deny_read: parent_turn_context.sandbox_deny_read.clone(),
+        linux_sandbox_backend: parent_turn_context.linux_sandbox_backend,
+        linux_sandbox_overlay_dir: parent_turn_context.linux_sandbox_overlay_dir.clone(),
+        sandbox_resource_limits: parent_turn_context.sandbox_resource_limits,
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         truncation_policy: model_info.truncation_policy.into(),
+        tokenizer: tokenizer_for_model(&model_info),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -2366,6 +2778,20 @@ async fn spawn_review_thread(
         .await;
 }
 
+fn exec_approval_message(command: &[String], reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!(
+            "Codex needs your approval to run `{}`: {reason}",
+            command.join(" ")
+        ),
+        None => format!("Codex needs your approval to run `{}`", command.join(" ")),
+    }
+}
+
+fn patch_approval_message(file_count: usize) -> String {
+    format!("Codex needs your approval to apply changes to {file_count} file(s)")
+}
+
 fn skills_to_info(skills: &[SkillMetadata]) -> Vec<ProtocolSkillMetadata> {
     skills
         .iter()
@@ -2454,17 +2880,23 @@ pub(crate) async fn run_turn(
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
     let mut last_agent_message: Option<String> = None;
+    let mut stop_hook_continuations = 0;
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
-    let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+    // With a workspace overlay, sandboxed commands' changes only show up in
+    // the overlay, so the tracker reads them from there.
+    let turn_diff_tracker = match turn_context.workspace_overlay_upper_dir() {
+        Some(upper_dir) => TurnDiffTracker::with_overlay(turn_context.cwd.clone(), upper_dir),
+        None => TurnDiffTracker::new(),
+    };
+    let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(turn_diff_tracker));
 
     loop {
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
-        let pending_input = sess
-            .get_pending_input()
-            .await
+        let (pending_input, steers) = sess.take_pending_input_and_steers().await;
+        let pending_input = pending_input
             .into_iter()
             .map(ResponseItem::from)
             .collect::<Vec<ResponseItem>>();
@@ -2475,6 +2907,13 @@ pub(crate) async fn run_turn(
                 .await;
             sess.clone_history().await.for_prompt()
         };
+        for items in steers {
+            sess.send_event(
+                &turn_context,
+                EventMsg::TurnSteered(TurnSteeredEvent { items }),
+            )
+            .await;
+        }
 
         let turn_input_messages = turn_input
             .iter()
@@ -2509,6 +2948,33 @@ pub(crate) async fn run_turn(
 
                 if !needs_follow_up {
                     last_agent_message = turn_last_agent_message;
+                    if stop_hook_continuations < MAX_STOP_HOOK_CONTINUATIONS
+                        && let Some(reason) = run_stop_hooks(
+                            &sess,
+                            &turn_context,
+                            last_agent_message.as_deref(),
+                            stop_hook_continuations > 0,
+                        )
+                        .await
+                    {
+                        stop_hook_continuations += 1;
+                        // The turn may already have been detached from the
+                        // session; record the reason directly so the next
+                        // request still carries it.
+                        if let Err(items) = sess
+                            .inject_input(vec![UserInput::Text { text: reason }])
+                            .await
+                        {
+                            let item = ResponseItem::from(ResponseInputItem::from(items));
+                            sess.record_conversation_items(&turn_context, &[item]).await;
+                        }
+                        continue;
+                    }
+                    // Steers that arrived during the last request would
+                    // otherwise be dropped when the turn ends.
+                    if !sess.finish_turn_unless_steered().await {
+                        continue;
+                    }
                     sess.notifier()
                         .notify(&UserNotification::AgentTurnComplete {
                             thread_id: sess.conversation_id.to_string(),
@@ -2542,9 +3008,71 @@ pub(crate) async fn run_turn(
         }
     }
 
+    record_final_turn_diff(&sess, &turn_diff_tracker).await;
     last_agent_message
 }
 
+/// Records the diff of everything the turn changed in the rollout.
+async fn record_final_turn_diff(sess: &Session, turn_diff_tracker: &SharedTurnDiffTracker) {
+    let unified_diff = turn_diff_tracker.lock().await.get_unified_diff();
+    if let Ok(Some(unified_diff)) = unified_diff {
+        let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
+        sess.persist_rollout_items(&[RolloutItem::EventMsg(msg)])
+            .await;
+    }
+}
+
+/// Upper bound on how many times Stop hooks may keep a single turn running.
+const MAX_STOP_HOOK_CONTINUATIONS: usize = 5;
+
+/// Runs the `Stop` (or `SubagentStop`) hooks when the agent is about to finish
+/// a turn. Returns the block reason when a hook wants the agent to continue.
+async fn run_stop_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    last_agent_message: Option<&str>,
+    stop_hook_active: bool,
+) -> Option<String> {
+    let hooks = sess.services.hooks.as_ref()?;
+    let hook_event_name = match turn_context.client.get_session_source() {
+        SessionSource::SubAgent(_) => "SubagentStop",
+        _ => "Stop",
+    };
+    let context = sess.hook_context(hook_event_name).await;
+    let payload = build_stop_hook_payload(&context, stop_hook_active, last_agent_message);
+    let blocked = match hooks.run_stop(&payload).await {
+        Ok(blocked) => blocked?,
+        Err(err) => {
+            warn!("{hook_event_name} hook failed: {err}");
+            return None;
+        }
+    };
+
+    let reason = blocked
+        .decision
+        .reason
+        .unwrap_or_else(|| "stop blocked by hook".to_string());
+    sess.send_event(
+        turn_context,
+        EventMsg::HookActivity(HookActivityEvent {
+            status: HookActivityStatus::Continued,
+            tool: None,
+            hooks: vec![HookActivityHook {
+                name: blocked
+                    .hook
+                    .command
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| "unknown".to_string()),
+                decision: "block".to_string(),
+            }],
+            reason: Some(reason.clone()),
+        }),
+    )
+    .await;
+    Some(reason)
+}
+
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
     if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
@@ -2621,18 +3149,14 @@ async fn run_model_turn(
                 if let Some(rate_limits) = rate_limits {
                     sess.update_rate_limits(&turn_context, rate_limits).await;
                 }
-                return Err(CodexErr::UsageLimitReached(e));
+                CodexErr::UsageLimitReached(e)
             }
             Err(err) => err,
         };
 
-        if !err.is_retryable() {
-            return Err(err);
-        }
-
         // Use the configured provider-specific stream retry budget.
         let max_retries = turn_context.client.get_provider().stream_max_retries();
-        if retries < max_retries {
+        if err.is_retryable() && retries < max_retries {
             retries += 1;
             let delay = match &err {
                 CodexErr::Stream(_, requested_delay) => {
@@ -2653,6 +3177,23 @@ async fn run_model_turn(
             .await;
 
             tokio::time::sleep(delay).await;
+        } else if err.is_provider_outage()
+            && let Some(fallback) = turn_context.client.fail_over()
+        {
+            let model = turn_context.client.get_model();
+            warn!(
+                "provider unavailable ({err}) - failing over to `{}` with model {model}",
+                fallback.provider_id
+            );
+            sess.notify_background_event(
+                &turn_context,
+                format!(
+                    "Model provider unavailable ({err}); switching to `{}` ({model}).",
+                    fallback.provider_id
+                ),
+            )
+            .await;
+            retries = 0;
         } else {
             return Err(err);
         }
@@ -2705,6 +3246,7 @@ async fn try_run_turn(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider: Some(turn_context.client.get_provider_id()),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
         base_instructions: turn_context.base_instructions.clone(),
@@ -2815,9 +3357,14 @@ async fn try_run_turn(
             }
             ResponseEvent::ModelsEtag(etag) => {
                 // Update internal state with latest models etag
+                let config = turn_context.client.config();
                 sess.services
                     .models_manager
-                    .refresh_if_new_etag(etag, sess.features.enabled(Feature::RemoteModels))
+                    .refresh_if_new_etag(
+                        etag,
+                        sess.features.enabled(Feature::RemoteModels),
+                        config.http_cassette.as_ref(),
+                    )
                     .await;
             }
             ResponseEvent::Completed {
@@ -2909,7 +3456,9 @@ async fn try_run_turn(
         };
         if let Ok(Some(unified_diff)) = unified_diff {
             let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
-            sess.clone().send_event(&turn_context, msg).await;
+            // Each snapshot supersedes the last; `record_final_turn_diff`
+            // records the one left when the turn ends.
+            sess.send_event_unrecorded(&turn_context, msg).await;
         }
     }
 
@@ -3108,6 +3657,70 @@ mod tests {
         assert_eq!(expected, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn concurrent_agent_approvals_resolve_independently() {
+        let (sess, tc, rx) = make_session_and_context_with_rx().await;
+        let request = || {
+            EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: "call-1".to_string(),
+                turn_id: String::new(),
+                command: vec!["echo".to_string(), "hi".to_string()],
+                cwd: tc.cwd.clone(),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                explanation: None,
+                parsed_cmd: Vec::new(),
+            })
+        };
+
+        // Two agents ask to approve the same call id while no parent turn is running.
+        let first_agent = ThreadId::new();
+        let second_agent = ThreadId::new();
+        let spawn_request = |agent_id: ThreadId| {
+            let sess = Arc::clone(&sess);
+            let tc = Arc::clone(&tc);
+            let request = request();
+            tokio::spawn(async move { sess.request_agent_approval(agent_id, &tc, request).await })
+        };
+        let first = spawn_request(first_agent);
+        let second = spawn_request(second_agent);
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("approval request event")
+                .expect("event channel open");
+            let EventMsg::ExecApprovalRequest(request) = event.msg else {
+                panic!("expected exec approval request, got {:?}", event.msg);
+            };
+            assert_eq!(request.turn_id, tc.sub_id);
+            ids.push(event.id);
+        }
+        ids.sort();
+        let mut expected = vec![
+            format!("{first_agent}:call-1"),
+            format!("{second_agent}:call-1"),
+        ];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        handlers::exec_approval(
+            &sess,
+            format!("{second_agent}:call-1"),
+            ReviewDecision::Denied,
+        )
+        .await;
+        handlers::exec_approval(
+            &sess,
+            format!("{first_agent}:call-1"),
+            ReviewDecision::Approved,
+        )
+        .await;
+        assert_eq!(first.await.expect("join"), ReviewDecision::Approved);
+        assert_eq!(second.await.expect("join"), ReviewDecision::Denied);
+    }
+
     #[tokio::test]
     async fn thread_rollback_drops_last_turn_from_history() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -3191,6 +3804,90 @@ mod tests {
         assert_eq!(initial_context, history.raw_items());
     }
 
+    #[tokio::test]
+    async fn steer_turn_fails_without_running_turn() {
+        let (sess, _tc, rx) = make_session_and_context_with_rx().await;
+
+        handlers::steer_turn(
+            &sess,
+            "sub-1".to_string(),
+            vec![UserInput::Text {
+                text: "keep going".to_string(),
+            }],
+            None,
+        )
+        .await;
+
+        let (id, error_event) = loop {
+            let evt = tokio::time::timeout(StdDuration::from_secs(2), rx.recv())
+                .await
+                .expect("timeout waiting for event")
+                .expect("event");
+            if let EventMsg::Error(payload) = evt.msg {
+                break (evt.id, payload);
+            }
+        };
+        assert_eq!(id, "sub-1");
+        assert_eq!(
+            error_event.codex_error_info,
+            Some(CodexErrorInfo::TurnSteerFailed)
+        );
+    }
+
+    #[test]
+    fn prompt_rewrites_keep_images_in_place() {
+        let image = |url: &str| UserInput::Image {
+            image_url: url.to_string(),
+        };
+        let text = |text: &str| UserInput::Text {
+            text: text.to_string(),
+        };
+
+        assert_eq!(
+            handlers::rewrite_prompt_text(
+                vec![image("a"), text("one"), image("b"), text("two")],
+                "rewritten".to_string(),
+            ),
+            vec![image("a"), text("rewritten"), image("b")]
+        );
+        assert_eq!(
+            handlers::rewrite_prompt_text(vec![image("a")], "added".to_string()),
+            vec![text("added"), image("a")]
+        );
+    }
+
+    #[tokio::test]
+    async fn steers_are_rejected_once_the_turn_is_finishing() {
+        let (sess, tc, _rx) = make_session_and_context_with_rx().await;
+        sess.spawn_task(
+            Arc::clone(&tc),
+            Vec::new(),
+            NeverEndingTask {
+                kind: TaskKind::Regular,
+                listen_to_cancellation_token: true,
+            },
+        )
+        .await;
+        let steer = || {
+            vec![UserInput::Text {
+                text: "keep going".to_string(),
+            }]
+        };
+
+        assert!(sess.steer_turn(steer(), None).await.is_ok());
+        assert!(!sess.finish_turn_unless_steered().await);
+        let (_, steers) = sess.take_pending_input_and_steers().await;
+        assert_eq!(steers, vec![steer()]);
+
+        assert!(sess.finish_turn_unless_steered().await);
+        assert!(sess.steer_turn(steer(), None).await.is_err());
+        let (pending_input, steers) = sess.take_pending_input_and_steers().await;
+        assert!(pending_input.is_empty());
+        assert!(steers.is_empty());
+
+        sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
+    }
+
     #[tokio::test]
     async fn thread_rollback_fails_when_turn_in_progress() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -3400,10 +4097,15 @@ mod tests {
             aggregated_output: StreamOutput::new("Command output".to_string()),
             duration: StdDuration::from_secs(1),
             timed_out: true,
+            exceeded_limit: None,
         };
         let (_, turn_context) = make_session_and_context().await;
 
-        let out = format_exec_output_str(&exec, turn_context.truncation_policy);
+        let out = format_exec_output_str(
+            &exec,
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
 
         assert_eq!(
             out,
@@ -3601,6 +4303,7 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy: None,
         };
 
         let turn_context = Session::make_turn_context(
@@ -3696,6 +4399,7 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -3948,7 +4652,11 @@ mod tests {
         for item in &initial_context {
             rollout_items.push(RolloutItem::ResponseItem(item.clone()));
         }
-        live_history.record_items(initial_context.iter(), turn_context.truncation_policy);
+        live_history.record_items(
+            initial_context.iter(),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
 
         let user1 = ResponseItem::Message {
             id: None,
@@ -3957,7 +4665,11 @@ mod tests {
                 text: "first user".to_string(),
             }],
         };
-        live_history.record_items(std::iter::once(&user1), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&user1),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(user1.clone()));
 
         let assistant1 = ResponseItem::Message {
@@ -3967,7 +4679,11 @@ mod tests {
                 text: "assistant reply one".to_string(),
             }],
         };
-        live_history.record_items(std::iter::once(&assistant1), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&assistant1),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(assistant1.clone()));
 
         let summary1 = "summary one";
@@ -3977,6 +4693,7 @@ mod tests {
             session.build_initial_context(turn_context),
             &user_messages1,
             summary1,
+            turn_context.tokenizer,
         );
         live_history.replace(rebuilt1);
         rollout_items.push(RolloutItem::Compacted(CompactedItem {
@@ -3991,7 +4708,11 @@ mod tests {
                 text: "second user".to_string(),
             }],
         };
-        live_history.record_items(std::iter::once(&user2), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&user2),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(user2.clone()));
 
         let assistant2 = ResponseItem::Message {
@@ -4001,7 +4722,11 @@ mod tests {
                 text: "assistant reply two".to_string(),
             }],
         };
-        live_history.record_items(std::iter::once(&assistant2), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&assistant2),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(assistant2.clone()));
 
         let summary2 = "summary two";
@@ -4011,6 +4736,7 @@ mod tests {
             session.build_initial_context(turn_context),
             &user_messages2,
             summary2,
+            turn_context.tokenizer,
         );
         live_history.replace(rebuilt2);
         rollout_items.push(RolloutItem::Compacted(CompactedItem {
@@ -4025,7 +4751,11 @@ mod tests {
                 text: "third user".to_string(),
             }],
         };
-        live_history.record_items(std::iter::once(&user3), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&user3),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(user3.clone()));
 
         let assistant3 = ResponseItem::Message {
@@ -4035,7 +4765,11 @@ mod tests {
                 text: "assistant reply three".to_string(),
             }],
         };
-        live_history.record_items(std::iter::once(&assistant3), turn_context.truncation_policy);
+        live_history.record_items(
+            std::iter::once(&assistant3),
+            turn_context.truncation_policy,
+            turn_context.tokenizer,
+        );
         rollout_items.push(RolloutItem::ResponseItem(assistant3.clone()));
 
         (rollout_items, live_history.for_prompt())
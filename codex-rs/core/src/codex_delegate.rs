@@ -88,6 +88,7 @@ pub(crate) async fn run_codex_thread_interactive(
         tx_sub: tx_ops,
         rx_event: rx_sub,
         agent_status: Arc::clone(&codex.agent_status),
+        rollout: codex.rollout.clone(),
     })
 }
 
@@ -130,6 +131,7 @@ pub(crate) async fn run_codex_thread_one_shot(
     let (tx_bridge, rx_bridge) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
     let ops_tx = io.tx_sub.clone();
     let agent_status = Arc::clone(&io.agent_status);
+    let rollout = io.rollout.clone();
     let io_for_bridge = io;
     tokio::spawn(async move {
         while let Ok(event) = io_for_bridge.next_event().await {
@@ -162,6 +164,7 @@ pub(crate) async fn run_codex_thread_one_shot(
         rx_event: rx_bridge,
         tx_sub: tx_closed,
         agent_status,
+        rollout,
     })
 }
 
@@ -294,6 +297,7 @@ async fn handle_exec_approval(
         event.cwd,
         event.reason,
         event.proposed_execpolicy_amendment,
+        event.explanation,
     );
     let decision = await_approval_with_cancel(
         approval_fut,
@@ -377,6 +381,7 @@ mod tests {
             tx_sub,
             rx_event: rx_events,
             agent_status: Default::default(),
+            rollout: None,
         });
 
         let (session, ctx, _rx_evt) = crate::codex::make_session_and_context_with_rx().await;
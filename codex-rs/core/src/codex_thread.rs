@@ -4,6 +4,9 @@ use crate::error::Result as CodexResult;
 use crate::protocol::Event;
 use crate::protocol::Op;
 use crate::protocol::Submission;
+use crate::rollout::SessionMetaUpdate;
+use crate::rollout::update_session_meta;
+use codex_protocol::protocol::SessionMetaLine;
 use std::path::PathBuf;
 
 pub struct CodexThread {
@@ -41,4 +44,16 @@ impl CodexThread {
     pub fn rollout_path(&self) -> PathBuf {
         self.rollout_path.clone()
     }
+
+    /// Update the title and tags persisted in this thread's rollout. Goes
+    /// through the session's recorder so concurrent writes are not lost.
+    pub async fn update_session_meta(
+        &self,
+        update: SessionMetaUpdate,
+    ) -> std::io::Result<SessionMetaLine> {
+        match &self.codex.rollout {
+            Some(recorder) => recorder.update_session_meta(update).await,
+            None => update_session_meta(&self.rollout_path, update).await,
+        }
+    }
 }
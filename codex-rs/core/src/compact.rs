@@ -9,6 +9,7 @@ use crate::codex::get_last_assistant_message_from_turn;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
+use crate::hooks::build_pre_compact_hook_payload;
 use crate::protocol::CompactedItem;
 use crate::protocol::ContextCompactedEvent;
 use crate::protocol::EventMsg;
@@ -16,7 +17,7 @@ use crate::protocol::TurnContextItem;
 use crate::protocol::TurnStartedEvent;
 use crate::protocol::WarningEvent;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
+use crate::truncate::token_count;
 use crate::truncate::truncate_text;
 use crate::util::backoff;
 use codex_protocol::items::TurnItem;
@@ -25,8 +26,10 @@ use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::user_input::UserInput;
+use codex_utils_tokenizer::Tokenizer;
 use futures::prelude::*;
 use tracing::error;
+use tracing::warn;
 
 pub const SUMMARIZATION_PROMPT: &str = include_str!("../templates/compact/prompt.md");
 pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_prefix.md");
@@ -46,6 +49,7 @@ pub(crate) async fn run_inline_auto_compact_task(
     let prompt = turn_context.compact_prompt().to_string();
     let input = vec![UserInput::Text { text: prompt }];
 
+    run_pre_compact_hooks(&sess, "auto").await;
     run_compact_task_inner(sess, turn_context, input).await;
 }
 
@@ -58,9 +62,24 @@ pub(crate) async fn run_compact_task(
         model_context_window: turn_context.client.get_model_context_window(),
     });
     sess.send_event(&turn_context, start_event).await;
+    run_pre_compact_hooks(&sess, "manual").await;
     run_compact_task_inner(sess.clone(), turn_context, input).await;
 }
 
+/// Runs the `PreCompact` hooks with the rollout flushed so hooks can archive
+/// the full pre-compaction transcript.
+pub(crate) async fn run_pre_compact_hooks(sess: &Session, trigger: &str) {
+    let Some(hooks) = sess.services.hooks.as_ref() else {
+        return;
+    };
+    sess.flush_rollout().await;
+    let context = sess.hook_context("PreCompact").await;
+    let payload = build_pre_compact_hook_payload(&context, trigger);
+    if let Err(err) = hooks.run_pre_compact(&payload).await {
+        warn!("PreCompact hook failed: {err}");
+    }
+}
+
 async fn run_compact_task_inner(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
@@ -72,6 +91,7 @@ async fn run_compact_task_inner(
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
+        turn_context.tokenizer,
     );
 
     let mut truncated_count = 0usize;
@@ -84,6 +104,7 @@ async fn run_compact_task_inner(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider: Some(turn_context.client.get_provider_id()),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
         base_instructions: turn_context.base_instructions.clone(),
@@ -164,7 +185,12 @@ async fn run_compact_task_inner(
     let user_messages = collect_user_messages(history_items);
 
     let initial_context = sess.build_initial_context(turn_context.as_ref());
-    let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
+    let mut new_history = build_compacted_history(
+        initial_context,
+        &user_messages,
+        &summary_text,
+        turn_context.tokenizer,
+    );
     let ghost_snapshots: Vec<ResponseItem> = history_items
         .iter()
         .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
@@ -232,12 +258,14 @@ pub(crate) fn build_compacted_history(
     initial_context: Vec<ResponseItem>,
     user_messages: &[String],
     summary_text: &str,
+    tokenizer: Option<Tokenizer>,
 ) -> Vec<ResponseItem> {
     build_compacted_history_with_limit(
         initial_context,
         user_messages,
         summary_text,
         COMPACT_USER_MESSAGE_MAX_TOKENS,
+        tokenizer,
     )
 }
 
@@ -246,6 +274,7 @@ fn build_compacted_history_with_limit(
     user_messages: &[String],
     summary_text: &str,
     max_tokens: usize,
+    tokenizer: Option<Tokenizer>,
 ) -> Vec<ResponseItem> {
     let mut selected_messages: Vec<String> = Vec::new();
     if max_tokens > 0 {
@@ -254,12 +283,13 @@ fn build_compacted_history_with_limit(
             if remaining == 0 {
                 break;
             }
-            let tokens = approx_token_count(message);
+            let tokens = token_count(message, tokenizer);
             if tokens <= remaining {
                 selected_messages.push(message.clone());
                 remaining = remaining.saturating_sub(tokens);
             } else {
-                let truncated = truncate_text(message, TruncationPolicy::Tokens(remaining));
+                let truncated =
+                    truncate_text(message, TruncationPolicy::Tokens(remaining), tokenizer);
                 selected_messages.push(truncated);
                 break;
             }
@@ -429,6 +459,7 @@ mod tests {
             std::slice::from_ref(&big),
             "SUMMARY",
             max_tokens,
+            crate::truncate::default_tokenizer(),
         );
         assert_eq!(history.len(), 2);
 
@@ -466,7 +497,12 @@ mod tests {
         let user_messages = vec!["first user message".to_string()];
         let summary_text = "summary text";
 
-        let history = build_compacted_history(initial_context, &user_messages, summary_text);
+        let history = build_compacted_history(
+            initial_context,
+            &user_messages,
+            summary_text,
+            crate::truncate::default_tokenizer(),
+        );
         assert!(
             !history.is_empty(),
             "expected compacted history to include summary"
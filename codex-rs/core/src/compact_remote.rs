@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::Prompt;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::compact::run_pre_compact_hooks;
 use crate::error::Result as CodexResult;
 use crate::protocol::CompactedItem;
 use crate::protocol::ContextCompactedEvent;
@@ -15,6 +16,7 @@ pub(crate) async fn run_inline_remote_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
 ) {
+    run_pre_compact_hooks(&sess, "auto").await;
     run_remote_compact_task_inner(&sess, &turn_context).await;
 }
 
@@ -24,6 +26,7 @@ pub(crate) async fn run_remote_compact_task(sess: Arc<Session>, turn_context: Ar
     });
     sess.send_event(&turn_context, start_event).await;
 
+    run_pre_compact_hooks(&sess, "manual").await;
     run_remote_compact_task_inner(&sess, &turn_context).await;
 }
 
@@ -1,12 +1,18 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::AgentProfile;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HttpCassette;
+use crate::config::types::LinuxSandboxBackend;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ProviderFallbackToml;
+use crate::config::types::ResourceLimits;
+use crate::config::types::SandboxLinux;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ScrollInputMode;
 use crate::config::types::ShellEnvironmentPolicy;
@@ -26,6 +32,7 @@ use crate::hooks::HooksConfig;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+use crate::model_provider_info::ProviderFallback;
 use crate::model_provider_info::built_in_model_providers;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
@@ -118,6 +125,14 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Providers to fail over to, in order, when `model_provider` is
+    /// unavailable.
+    pub model_provider_fallbacks: Vec<ProviderFallback>,
+
+    /// Cassette that model API requests are recorded to or replayed from.
+    /// `CODEX_RS_HTTP_RECORD` / `CODEX_RS_HTTP_REPLAY` take precedence.
+    pub http_cassette: Option<HttpCassette>,
+
     /// Approval policy for executing commands.
     pub approval_policy: Constrained<AskForApproval>,
 
@@ -179,6 +194,9 @@ pub struct Config {
     /// Optional hook configuration for tool/lifecycle events.
     pub hooks: Option<HooksConfig>,
 
+    /// Named sub-agent profiles selectable by `spawn_agent`.
+    pub agents: HashMap<String, AgentProfile>,
+
     /// TUI notifications preference. When set, the TUI will send OSC 9 notifications on approvals
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -303,6 +321,29 @@ pub struct Config {
     /// When this program is invoked, arg0 will be set to `codex-linux-sandbox`.
     pub codex_linux_sandbox_exe: Option<PathBuf>,
 
+    /// Hosts that sandboxed commands may reach through the Codex egress proxy
+    /// when the sandbox policy otherwise blocks network access. Read from
+    /// `sandbox_workspace_write.allowed_domains`.
+    pub sandbox_allowed_domains: Vec<String>,
+
+    /// Paths that sandboxed commands may not access: the credential locations
+    /// from [`default_sandbox_deny_read`] plus
+    /// `sandbox_workspace_write.deny_read`.
+    pub sandbox_deny_read: Vec<AbsolutePathBuf>,
+
+    /// Backend `codex-linux-sandbox` uses for
+    /// [`crate::exec::SandboxType::LinuxSeccomp`]. Read from
+    /// `sandbox_linux.backend`.
+    pub linux_sandbox_backend: LinuxSandboxBackend,
+
+    /// Directory holding the workspace overlay for the namespaces backend.
+    /// Read from `sandbox_linux.overlay_dir`.
+    pub linux_sandbox_overlay_dir: Option<AbsolutePathBuf>,
+
+    /// Limits applied to every command Codex spawns for the model. Read from
+    /// `sandbox_resource_limits`.
+    pub sandbox_resource_limits: ResourceLimits,
+
     /// Value to use for `reasoning.effort` when making a request using the
     /// Responses API.
     pub model_reasoning_effort: Option<ReasoningEffort>,
@@ -696,6 +737,12 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Ordered providers to fail over to when `model_provider` is unavailable.
+    pub fallback: Option<Vec<ProviderFallbackToml>>,
+
+    /// Record model API traffic to, or replay it from, a cassette file.
+    pub http_cassette: Option<HttpCassette>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -714,6 +761,12 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Linux sandbox backend selection.
+    pub sandbox_linux: Option<SandboxLinux>,
+
+    /// CPU, memory, process and file size limits for sandboxed commands.
+    pub sandbox_resource_limits: Option<ResourceLimits>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
@@ -722,6 +775,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub hooks: Option<HooksConfig>,
 
+    /// Named sub-agent profiles, e.g. `[agents.reviewer]`.
+    #[serde(default)]
+    pub agents: HashMap<String, AgentProfile>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -972,6 +1029,8 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    allowed_domains: _,
+                    deny_read: _,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
@@ -1164,6 +1223,8 @@ impl Config {
             crate::safety::set_windows_elevated_sandbox_enabled(elevated_enabled);
         }
 
+        let sandbox_linux = cfg.sandbox_linux.clone().unwrap_or_default();
+
         let resolved_cwd = {
             use std::env;
 
@@ -1194,6 +1255,19 @@ impl Config {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
         } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        let sandbox_allowed_domains = cfg
+            .sandbox_workspace_write
+            .as_ref()
+            .map(|sandbox_workspace_write| sandbox_workspace_write.allowed_domains.clone())
+            .unwrap_or_default();
+        let mut sandbox_deny_read = default_sandbox_deny_read(&codex_home);
+        if let Some(sandbox_workspace_write) = cfg.sandbox_workspace_write.as_ref() {
+            for path in &sandbox_workspace_write.deny_read {
+                if !sandbox_deny_read.contains(path) {
+                    sandbox_deny_read.push(path.clone());
+                }
+            }
+        }
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
@@ -1242,6 +1316,29 @@ impl Config {
                 )
             })?
             .clone();
+        let model_provider_fallbacks = config_profile
+            .fallback
+            .or(cfg.fallback)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| {
+                let provider_id = entry.provider().to_string();
+                let provider = model_providers
+                    .get(&provider_id)
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("Fallback model provider `{provider_id}` not found"),
+                        )
+                    })?
+                    .clone();
+                Ok(ProviderFallback {
+                    provider_id,
+                    provider,
+                    model: entry.model().map(str::to_string),
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
@@ -1351,6 +1448,8 @@ impl Config {
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            model_provider_fallbacks,
+            http_cassette: cfg.http_cassette,
             cwd: resolved_cwd,
             approval_policy: constrained_approval_policy,
             sandbox_policy: constrained_sandbox_policy,
@@ -1359,6 +1458,7 @@ impl Config {
             shell_environment_policy,
             notify: cfg.notify,
             hooks: cfg.hooks,
+            agents: cfg.agents,
             user_instructions,
             base_instructions,
             developer_instructions,
@@ -1391,6 +1491,11 @@ impl Config {
             history,
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
+            sandbox_allowed_domains,
+            sandbox_deny_read,
+            linux_sandbox_backend: sandbox_linux.backend,
+            linux_sandbox_overlay_dir: sandbox_linux.overlay_dir,
+            sandbox_resource_limits: cfg.sandbox_resource_limits.unwrap_or_default(),
 
             hide_agent_reasoning: cfg.hide_agent_reasoning.unwrap_or(false),
             show_raw_agent_reasoning: cfg
@@ -1581,6 +1686,37 @@ pub fn find_codex_home() -> std::io::Result<PathBuf> {
     Ok(p)
 }
 
+/// Credential locations that sandboxed commands may never read: Codex's own
+/// auth files under `codex_home` and common tool credentials in the user's home
+/// directory.
+pub fn default_sandbox_deny_read(codex_home: &Path) -> Vec<AbsolutePathBuf> {
+    let mut paths = vec![
+        codex_home.join("auth.json"),
+        codex_home.join(".credentials.json"),
+    ];
+    if let Some(home) = home_dir() {
+        paths.extend(
+            [
+                ".ssh",
+                ".gnupg",
+                ".aws",
+                ".azure",
+                ".config/gcloud",
+                ".kube",
+                ".docker/config.json",
+                ".netrc",
+                ".git-credentials",
+            ]
+            .into_iter()
+            .map(|path| home.join(path)),
+        );
+    }
+    paths
+        .into_iter()
+        .filter_map(|path| AbsolutePathBuf::from_absolute_path(path).ok())
+        .collect()
+}
+
 /// Returns the path to the folder where Codex logs are stored. Does not verify
 /// that the directory exists.
 pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
@@ -1663,11 +1799,40 @@ persistence = "none"
                 matcher: Some("startup|resume|clear".to_string()),
                 command: vec!["tdd-guard".to_string()],
                 timeout_ms: None,
+                ..Default::default()
             }]
         );
         Ok(())
     }
 
+    #[test]
+    fn agent_profiles_are_applied_to_runtime_config() -> std::io::Result<()> {
+        let agents_config = r#"
+[agents.reviewer]
+model = "gpt-5.1-codex"
+sandbox_mode = "read-only"
+developer_instructions = "Only review."
+"#;
+        let parsed = toml::from_str::<ConfigToml>(agents_config)
+            .expect("TOML deserialization should succeed");
+        let codex_home = tempdir().expect("create temp dir");
+        let config = Config::load_from_base_config_with_overrides(
+            parsed,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(
+            config.agents.get("reviewer"),
+            Some(&AgentProfile {
+                model: Some("gpt-5.1-codex".to_string()),
+                sandbox_mode: Some(SandboxMode::ReadOnly),
+                developer_instructions: Some("Only review.".to_string()),
+                ..Default::default()
+            })
+        );
+        Ok(())
+    }
+
     #[test]
     fn tui_config_missing_notifications_field_defaults_to_enabled() {
         let cfg = r#"
@@ -2031,6 +2196,63 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_fallback_resolves_providers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "work"
+fallback = ["ollama"]
+
+[model_providers.azure-east]
+name = "Azure East"
+base_url = "https://east.example.com/openai"
+
+[profiles.work]
+fallback = ["azure-east", { provider = "openai", model = "gpt-5.1" }]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        let fallbacks: Vec<(&str, Option<&str>)> = config
+            .model_provider_fallbacks
+            .iter()
+            .map(|fallback| (fallback.provider_id.as_str(), fallback.model.as_deref()))
+            .collect();
+        assert_eq!(
+            fallbacks,
+            vec![("azure-east", None), ("openai", Some("gpt-5.1"))]
+        );
+        assert_eq!(
+            config.model_provider_fallbacks[0].provider.name,
+            "Azure East"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_fallback_provider_is_an_error() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let cfg = toml::from_str::<ConfigToml>(r#"fallback = ["missing"]"#)
+            .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("unknown fallback provider should fail");
+
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
+
     #[test]
     fn cli_override_takes_precedence_over_profile_sandbox_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -3272,6 +3494,8 @@ model_verbosity = "high"
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                model_provider_fallbacks: Vec::new(),
+                http_cassette: None,
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3280,6 +3504,7 @@ model_verbosity = "high"
                 user_instructions: None,
                 notify: None,
                 hooks: None,
+                agents: HashMap::new(),
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: HashMap::new(),
@@ -3293,6 +3518,11 @@ model_verbosity = "high"
                 history: History::default(),
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
+                sandbox_allowed_domains: Vec::new(),
+                sandbox_deny_read: default_sandbox_deny_read(&fixture.codex_home()),
+                linux_sandbox_backend: LinuxSandboxBackend::Landlock,
+                linux_sandbox_overlay_dir: None,
+                sandbox_resource_limits: ResourceLimits::default(),
                 hide_agent_reasoning: false,
                 show_raw_agent_reasoning: false,
                 model_reasoning_effort: Some(ReasoningEffort::High),
@@ -3359,6 +3589,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            model_provider_fallbacks: Vec::new(),
+            http_cassette: None,
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3367,6 +3599,7 @@ model_verbosity = "high"
             user_instructions: None,
             notify: None,
             hooks: None,
+            agents: HashMap::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
@@ -3380,6 +3613,11 @@ model_verbosity = "high"
             history: History::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
+            sandbox_allowed_domains: Vec::new(),
+            sandbox_deny_read: default_sandbox_deny_read(&fixture.codex_home()),
+            linux_sandbox_backend: LinuxSandboxBackend::Landlock,
+            linux_sandbox_overlay_dir: None,
+            sandbox_resource_limits: ResourceLimits::default(),
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
@@ -3461,6 +3699,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_provider_fallbacks: Vec::new(),
+            http_cassette: None,
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3469,6 +3709,7 @@ model_verbosity = "high"
             user_instructions: None,
             notify: None,
             hooks: None,
+            agents: HashMap::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
@@ -3482,6 +3723,11 @@ model_verbosity = "high"
             history: History::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
+            sandbox_allowed_domains: Vec::new(),
+            sandbox_deny_read: default_sandbox_deny_read(&fixture.codex_home()),
+            linux_sandbox_backend: LinuxSandboxBackend::Landlock,
+            linux_sandbox_overlay_dir: None,
+            sandbox_resource_limits: ResourceLimits::default(),
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: None,
@@ -3549,6 +3795,8 @@ model_verbosity = "high"
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_provider_fallbacks: Vec::new(),
+            http_cassette: None,
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3557,6 +3805,7 @@ model_verbosity = "high"
             user_instructions: None,
             notify: None,
             hooks: None,
+            agents: HashMap::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
@@ -3570,6 +3819,11 @@ model_verbosity = "high"
             history: History::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
+            sandbox_allowed_domains: Vec::new(),
+            sandbox_deny_read: default_sandbox_deny_read(&fixture.codex_home()),
+            linux_sandbox_backend: LinuxSandboxBackend::Landlock,
+            linux_sandbox_overlay_dir: None,
+            sandbox_resource_limits: ResourceLimits::default(),
             hide_agent_reasoning: false,
             show_raw_agent_reasoning: false,
             model_reasoning_effort: Some(ReasoningEffort::High),
@@ -16,6 +16,9 @@ pub struct ConfigProfile {
     /// The key in the `model_providers` map identifying the
     /// [`ModelProviderInfo`] to use.
     pub model_provider: Option<String>,
+    /// Providers to fail over to, in order, when `model_provider` is
+    /// unavailable. Overrides the top-level `fallback` list.
+    pub fallback: Option<Vec<crate::config::types::ProviderFallbackToml>>,
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
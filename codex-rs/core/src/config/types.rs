@@ -3,7 +3,10 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use crate::features::FeaturesToml;
 pub use codex_protocol::config_types::AltScreenMode;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -577,6 +580,63 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Hosts that sandboxed commands may reach through the Codex egress proxy while
+    /// `network_access` is `false`. A leading `*.` matches any subdomain.
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Files and directories that sandboxed commands may not read (or write),
+    /// on top of the built-in list of credential locations.
+    #[serde(default)]
+    pub deny_read: Vec<AbsolutePathBuf>,
+}
+
+/// Linux-specific sandbox settings, read from `[sandbox_linux]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SandboxLinux {
+    /// Mechanism used to confine sandboxed commands.
+    #[serde(default)]
+    pub backend: LinuxSandboxBackend,
+    /// With the `namespaces` backend, mount the workspace as an overlay whose
+    /// changes are written to `<overlay_dir>/upper` instead of the workspace
+    /// itself, so they can be reviewed before being copied back.
+    #[serde(default)]
+    pub overlay_dir: Option<AbsolutePathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinuxSandboxBackend {
+    /// Landlock file-system rules plus a seccomp network filter.
+    #[default]
+    Landlock,
+    /// Unprivileged user, mount, PID (and, without network access, network)
+    /// namespaces with a read-only view of the system.
+    Namespaces,
+}
+
+/// Per-command resource limits, read from `[sandbox_resource_limits]`.
+/// Unset fields are unlimited.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ResourceLimits {
+    /// Maximum memory of the command, in megabytes. With a cgroup this
+    /// covers everything the command starts; otherwise it limits the private
+    /// writable memory of each process.
+    pub max_memory_mb: Option<u64>,
+    /// Maximum CPU time per process, in seconds.
+    pub max_cpu_seconds: Option<u64>,
+    /// Maximum number of processes the command may run at once. Without a
+    /// cgroup this falls back to the per-user `RLIMIT_NPROC`, which every
+    /// process of the user, including Codex itself, counts against.
+    #[serde(alias = "max_user_processes")]
+    pub max_processes: Option<u64>,
+    /// Maximum size of any file the command writes, in megabytes.
+    pub max_file_size_mb: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -590,6 +650,76 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
     }
 }
 
+/// Cassette used to record model API traffic or replay it offline, read from
+/// `[http_cassette]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HttpCassette {
+    pub mode: HttpCassetteMode,
+    /// JSONL file that recorded exchanges are written to or replayed from.
+    pub path: AbsolutePathBuf,
+    /// When replaying, serve the next recorded response for an endpoint even
+    /// if no recorded request body matches, instead of failing the request.
+    #[serde(default)]
+    pub allow_body_mismatch: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpCassetteMode {
+    /// Send requests to the provider and record every exchange to the
+    /// cassette, replacing what an earlier session recorded there.
+    Record,
+    /// Serve responses from the cassette without touching the network.
+    Replay,
+}
+
+/// Entry in a `fallback` list: either a provider id, which keeps the current
+/// model, or a table naming the model to use with that provider.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ProviderFallbackToml {
+    Provider(String),
+    Detailed {
+        provider: String,
+        model: Option<String>,
+    },
+}
+
+impl ProviderFallbackToml {
+    pub fn provider(&self) -> &str {
+        match self {
+            Self::Provider(provider) | Self::Detailed { provider, .. } => provider,
+        }
+    }
+
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            Self::Provider(_) => None,
+            Self::Detailed { model, .. } => model.as_deref(),
+        }
+    }
+}
+
+/// Named sub-agent profile (`[agents.<name>]`) that `spawn_agent` can select.
+///
+/// Unset fields inherit from the spawning session. A profile can only narrow the
+/// parent's permissions: it may pick a stricter `sandbox_mode` and disable features,
+/// but never loosen either.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AgentProfile {
+    /// Short description of the agent's role, shown to the model in the `spawn_agent` tool.
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// Developer instructions for the sub-agent. Replaces the parent's when set.
+    pub developer_instructions: Option<String>,
+    pub sandbox_mode: Option<SandboxMode>,
+    /// Feature toggles applied on top of the parent's features (e.g. `shell_tool = false`).
+    #[serde(default)]
+    pub features: Option<FeaturesToml>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum ShellEnvironmentPolicyInherit {
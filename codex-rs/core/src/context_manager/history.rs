@@ -1,8 +1,8 @@
 use crate::codex::TurnContext;
 use crate::context_manager::normalize;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::approx_tokens_from_byte_count;
+use crate::truncate::token_count;
 use crate::truncate::truncate_function_output_items_with_policy;
 use crate::truncate::truncate_text;
 use crate::user_instructions::SkillInstructions;
@@ -14,7 +14,20 @@ use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
+use codex_utils_tokenizer::Tokenizer;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+/// Room, on top of the 20% serialization margin, for the exit code/timing
+/// header and truncation marker wrapped around tool output that was already
+/// truncated to the turn's token budget, so it is not truncated twice.
+const SERIALIZED_OUTPUT_HEADROOM_TOKENS: usize = 64;
 
 /// Transcript of thread history
 #[derive(Debug, Clone, Default)]
@@ -22,6 +35,10 @@ pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
     token_info: Option<TokenUsageInfo>,
+    /// Token counts from the last estimate, keyed by [`token_count_key`].
+    /// Clones share it, so estimates taken on history snapshots only
+    /// tokenize items added since the previous one.
+    token_counts: Arc<Mutex<HashMap<u64, i64>>>,
 }
 
 impl ContextManager {
@@ -29,6 +46,7 @@ impl ContextManager {
         Self {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
+            token_counts: Arc::default(),
         }
     }
 
@@ -50,8 +68,12 @@ impl ContextManager {
     }
 
     /// `items` is ordered from oldest to newest.
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
-    where
+    pub(crate) fn record_items<I>(
+        &mut self,
+        items: I,
+        policy: TruncationPolicy,
+        tokenizer: Option<Tokenizer>,
+    ) where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
@@ -62,7 +84,7 @@ impl ContextManager {
                 continue;
             }
 
-            let processed = self.process_item(item_ref, policy);
+            let processed = self.process_item(item_ref, policy, tokenizer);
             self.items.push(processed);
         }
     }
@@ -81,13 +103,37 @@ impl ContextManager {
         &self.items
     }
 
-    // Estimate token usage using byte-based heuristics from the truncation helpers.
-    // This is a coarse lower bound, not a tokenizer-accurate count.
+    // Estimate token usage by tokenizing instructions and serialized items with
+    // the model's vocabulary. Encrypted reasoning is still sized from its byte
+    // length, and JSON framing makes this an approximation of what the API bills.
     pub(crate) fn estimate_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
         let model_info = turn_context.client.get_model_info();
-        let base_instructions = model_info.base_instructions.as_str();
-        let base_tokens = i64::try_from(approx_token_count(base_instructions)).unwrap_or(i64::MAX);
+        Some(self.estimate_token_count_with(&model_info.base_instructions, turn_context.tokenizer))
+    }
+
+    fn estimate_token_count_with(
+        &self,
+        base_instructions: &str,
+        tokenizer: Option<Tokenizer>,
+    ) -> i64 {
+        // Only texts from this estimate are kept, so the cache stays the size
+        // of the history.
+        let mut cached = self
+            .token_counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut counted = HashMap::with_capacity(self.items.len() + 1);
+        let mut count = |text: &str| {
+            let key = token_count_key(tokenizer, text);
+            let tokens = cached
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| i64::try_from(token_count(text, tokenizer)).unwrap_or(i64::MAX));
+            counted.insert(key, tokens);
+            tokens
+        };
 
+        let base_tokens = count(base_instructions);
         let items_tokens = self.items.iter().fold(0i64, |acc, item| {
             acc + match item {
                 ResponseItem::GhostSnapshot { .. } => 0,
@@ -98,14 +144,12 @@ impl ContextManager {
                 | ResponseItem::Compaction {
                     encrypted_content: content,
                 } => estimate_reasoning_length(content.len()) as i64,
-                item => {
-                    let serialized = serde_json::to_string(item).unwrap_or_default();
-                    i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
-                }
+                item => count(&serde_json::to_string(item).unwrap_or_default()),
             }
         });
 
-        Some(base_tokens.saturating_add(items_tokens))
+        *cached = counted;
+        base_tokens.saturating_add(items_tokens)
     }
 
     pub(crate) fn remove_first_item(&mut self) {
@@ -249,16 +293,30 @@ impl ContextManager {
         normalize::remove_orphan_outputs(&mut self.items);
     }
 
-    fn process_item(&self, item: &ResponseItem, policy: TruncationPolicy) -> ResponseItem {
-        let policy_with_serialization_budget = policy.mul(1.2);
+    fn process_item(
+        &self,
+        item: &ResponseItem,
+        policy: TruncationPolicy,
+        tokenizer: Option<Tokenizer>,
+    ) -> ResponseItem {
+        let policy_with_serialization_budget = match policy.mul(1.2) {
+            TruncationPolicy::Tokens(tokens) => {
+                TruncationPolicy::Tokens(tokens.saturating_add(SERIALIZED_OUTPUT_HEADROOM_TOKENS))
+            }
+            bytes @ TruncationPolicy::Bytes(_) => bytes,
+        };
         match item {
             ResponseItem::FunctionCallOutput { call_id, output } => {
-                let truncated =
-                    truncate_text(output.content.as_str(), policy_with_serialization_budget);
+                let truncated = truncate_text(
+                    output.content.as_str(),
+                    policy_with_serialization_budget,
+                    tokenizer,
+                );
                 let truncated_items = output.content_items.as_ref().map(|items| {
                     truncate_function_output_items_with_policy(
                         items,
                         policy_with_serialization_budget,
+                        tokenizer,
                     )
                 });
                 ResponseItem::FunctionCallOutput {
@@ -271,7 +329,7 @@ impl ContextManager {
                 }
             }
             ResponseItem::CustomToolCallOutput { call_id, output } => {
-                let truncated = truncate_text(output, policy_with_serialization_budget);
+                let truncated = truncate_text(output, policy_with_serialization_budget, tokenizer);
                 ResponseItem::CustomToolCallOutput {
                     call_id: call_id.clone(),
                     output: truncated,
@@ -290,6 +348,16 @@ impl ContextManager {
     }
 }
 
+/// Cache key for the token count of `text` under `tokenizer`'s vocabulary.
+fn token_count_key(tokenizer: Option<Tokenizer>, text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    tokenizer
+        .map(|tokenizer| tokenizer.kind())
+        .hash(&mut hasher);
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// API messages include every non-system item (user/assistant messages, reasoning,
 /// tool calls, tool outputs, shell calls, and web-search calls).
 fn is_api_message(message: &ResponseItem) -> bool {
@@ -29,7 +29,11 @@ fn create_history_with_items(items: Vec<ResponseItem>) -> ContextManager {
     let mut h = ContextManager::new();
     // Use a generous but fixed token budget; tests only rely on truncation
     // behavior, not on a specific model's token limit.
-    h.record_items(items.iter(), TruncationPolicy::Tokens(10_000));
+    h.record_items(
+        items.iter(),
+        TruncationPolicy::Tokens(10_000),
+        truncate::default_tokenizer(),
+    );
     h
 }
 
@@ -78,7 +82,11 @@ fn reasoning_with_encrypted_content(len: usize) -> ResponseItem {
 }
 
 fn truncate_exec_output(content: &str) -> String {
-    truncate::truncate_text(content, TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS))
+    truncate::truncate_text(
+        content,
+        TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS),
+        truncate::default_tokenizer(),
+    )
 }
 
 #[test]
@@ -94,12 +102,16 @@ fn filters_non_api_messages() {
         }],
     };
     let reasoning = reasoning_msg("thinking...");
-    h.record_items([&system, &reasoning, &ResponseItem::Other], policy);
+    h.record_items(
+        [&system, &reasoning, &ResponseItem::Other],
+        policy,
+        truncate::default_tokenizer(),
+    );
 
     // User and assistant should be retained.
     let u = user_msg("hi");
     let a = assistant_msg("hello");
-    h.record_items([&u, &a], policy);
+    h.record_items([&u, &a], policy, truncate::default_tokenizer());
 
     let items = h.raw_items();
     assert_eq!(
@@ -155,6 +167,36 @@ fn non_last_reasoning_tokens_ignore_entries_after_last_user() {
     assert_eq!(history.get_non_last_reasoning_items_tokens(), 32);
 }
 
+#[test]
+fn token_estimates_reuse_counts_of_unchanged_items() {
+    let tokenizer = truncate::default_tokenizer();
+    let mut history = create_history_with_items(vec![user_msg("hello"), assistant_msg("hi")]);
+    let estimate = history.estimate_token_count_with("base", tokenizer);
+    // One entry for the base instructions and one per item.
+    assert_eq!(history.token_counts.lock().unwrap().len(), 3);
+
+    // A snapshot shares the cache, so a seeded count is picked up instead of
+    // tokenizing the item again.
+    let serialized = serde_json::to_string(&history.items[0]).unwrap();
+    let key = token_count_key(tokenizer, &serialized);
+    let snapshot = history.clone();
+    history
+        .token_counts
+        .lock()
+        .unwrap()
+        .entry(key)
+        .and_modify(|tokens| *tokens += 100);
+    assert_eq!(
+        snapshot.estimate_token_count_with("base", tokenizer),
+        estimate + 100
+    );
+
+    // Counts of items no longer in the history are dropped.
+    history.remove_first_item();
+    history.estimate_token_count_with("base", tokenizer);
+    assert_eq!(history.token_counts.lock().unwrap().len(), 2);
+}
+
 #[test]
 fn get_history_for_prompt_drops_ghost_commits() {
     let items = vec![ResponseItem::GhostSnapshot {
@@ -424,7 +466,7 @@ fn record_items_truncates_function_call_output_content() {
         },
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, truncate::default_tokenizer());
 
     assert_eq!(history.items.len(), 1);
     match &history.items[0] {
@@ -456,7 +498,7 @@ fn record_items_truncates_custom_tool_call_output_content() {
         output: long_output.clone(),
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, truncate::default_tokenizer());
 
     assert_eq!(history.items.len(), 1);
     match &history.items[0] {
@@ -489,7 +531,7 @@ fn record_items_respects_custom_token_limit() {
         },
     };
 
-    history.record_items([&item], policy);
+    history.record_items([&item], policy, truncate::default_tokenizer());
 
     let stored = match &history.items[0] {
         ResponseItem::FunctionCallOutput { output, .. } => output,
@@ -536,16 +578,17 @@ fn format_exec_output_truncates_large_error() {
 
     let truncated = truncate_exec_output(&large_error);
 
-    assert_truncated_message_matches(&truncated, line, 36250);
+    assert_truncated_message_matches(&truncated, line, 25000);
     assert_ne!(truncated, large_error);
 }
 
 #[test]
 fn format_exec_output_marks_byte_truncation_without_omitted_lines() {
-    let long_line = "a".repeat(EXEC_FORMAT_MAX_BYTES + 10000);
+    // Runs of `a` encode at eight bytes per token, so this is 3,750 tokens.
+    let long_line = "a".repeat(EXEC_FORMAT_MAX_BYTES * 3);
     let truncated = truncate_exec_output(&long_line);
     assert_ne!(truncated, long_line);
-    assert_truncated_message_matches(&truncated, "a", 2500);
+    assert_truncated_message_matches(&truncated, "a", 1250);
     assert!(
         !truncated.contains("omitted"),
         "line omission marker should not appear when no lines were dropped: {truncated}"
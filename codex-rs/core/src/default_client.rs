@@ -1,7 +1,19 @@
+use crate::config::types::HttpCassette;
+use crate::config::types::HttpCassetteMode;
+use crate::flags::CODEX_RS_HTTP_RECORD;
+use crate::flags::CODEX_RS_HTTP_REPLAY;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
+use codex_api::RecordingTransport;
+use codex_api::ReplayTransport;
+use codex_api::ReqwestTransport;
 use codex_client::CodexHttpClient;
 pub use codex_client::CodexRequestBuilder;
+use codex_client::HttpTransport;
 use reqwest::header::HeaderValue;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::RwLock;
@@ -159,6 +171,67 @@ pub fn create_client() -> CodexHttpClient {
     CodexHttpClient::new(inner)
 }
 
+/// Replay transports keyed by cassette path. Model clients are rebuilt for
+/// every turn, so the replay cursor has to outlive them.
+static REPLAY_TRANSPORTS: LazyLock<Mutex<HashMap<PathBuf, ReplayTransport>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Recording transports keyed by cassette path, so the cassette is only
+/// truncated when the process first records to it.
+static RECORDING_TRANSPORTS: LazyLock<
+    Mutex<HashMap<PathBuf, RecordingTransport<ReqwestTransport>>>,
+> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Builds the transport for model API requests: the network, optionally
+/// recorded to a cassette, or a cassette replay. `CODEX_RS_HTTP_REPLAY` and
+/// `CODEX_RS_HTTP_RECORD` take precedence over the configured cassette.
+pub(crate) fn build_http_transport(
+    cassette: Option<&HttpCassette>,
+) -> std::io::Result<Arc<dyn HttpTransport>> {
+    let cassette = match (*CODEX_RS_HTTP_REPLAY, *CODEX_RS_HTTP_RECORD) {
+        (Some(path), _) => Some((HttpCassetteMode::Replay, PathBuf::from(path), false)),
+        (None, Some(path)) => Some((HttpCassetteMode::Record, PathBuf::from(path), false)),
+        (None, None) => cassette.map(|cassette| {
+            (
+                cassette.mode,
+                cassette.path.to_path_buf(),
+                cassette.allow_body_mismatch,
+            )
+        }),
+    };
+    Ok(match cassette {
+        None => Arc::new(ReqwestTransport::new(build_reqwest_client())),
+        Some((HttpCassetteMode::Record, path, _)) => Arc::new(recording_transport(&path)?),
+        Some((HttpCassetteMode::Replay, path, allow_body_mismatch)) => {
+            Arc::new(replay_transport(&path, allow_body_mismatch)?)
+        }
+    })
+}
+
+fn recording_transport(path: &Path) -> std::io::Result<RecordingTransport<ReqwestTransport>> {
+    let mut transports = RECORDING_TRANSPORTS
+        .lock()
+        .map_err(|_| std::io::Error::other("recording transport lock poisoned"))?;
+    if let Some(transport) = transports.get(path) {
+        return Ok(transport.clone());
+    }
+    let transport = RecordingTransport::new(ReqwestTransport::new(build_reqwest_client()), path)?;
+    transports.insert(path.to_path_buf(), transport.clone());
+    Ok(transport)
+}
+
+fn replay_transport(path: &Path, allow_body_mismatch: bool) -> std::io::Result<ReplayTransport> {
+    let mut transports = REPLAY_TRANSPORTS
+        .lock()
+        .map_err(|_| std::io::Error::other("replay transport lock poisoned"))?;
+    if let Some(transport) = transports.get(path) {
+        return Ok(transport.clone());
+    }
+    let transport = ReplayTransport::open(path)?.allow_body_mismatch(allow_body_mismatch);
+    transports.insert(path.to_path_buf(), transport.clone());
+    Ok(transport)
+}
+
 pub fn build_reqwest_client() -> reqwest::Client {
     use reqwest::header::HeaderMap;
 
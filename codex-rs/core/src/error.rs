@@ -47,6 +47,11 @@ pub enum SandboxErr {
     #[error("command timed out")]
     Timeout { output: Box<ExecToolCallOutput> },
 
+    /// Command was stopped by one of the configured resource limits; the
+    /// limit is recorded in `output.exceeded_limit`.
+    #[error("command exceeded a resource limit")]
+    ResourceLimit { output: Box<ExecToolCallOutput> },
+
     /// Command was killed by a signal
     #[error("command was killed by a signal")]
     Signal(i32),
@@ -216,6 +221,24 @@ impl CodexErr {
             CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => false,
         }
     }
+
+    /// Whether the error means the provider itself is unavailable, so the
+    /// request may succeed against a fallback provider. Transient errors
+    /// count only once their retry budget is spent.
+    pub fn is_provider_outage(&self) -> bool {
+        match self {
+            CodexErr::UsageLimitReached(_)
+            | CodexErr::QuotaExceeded
+            | CodexErr::RetryLimit(_)
+            | CodexErr::InternalServerError
+            | CodexErr::Stream(..)
+            | CodexErr::Timeout
+            | CodexErr::ResponseStreamFailed(_)
+            | CodexErr::ConnectionFailed(_) => true,
+            CodexErr::UnexpectedStatus(err) => err.status.is_server_error(),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -556,12 +579,17 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 output.duration.as_millis()
             )
         }
+        CodexErr::Sandbox(SandboxErr::ResourceLimit { output }) => match output.exceeded_limit {
+            Some(limit) => format!("error: command exceeded its {limit}"),
+            None => "error: command exceeded a resource limit".to_string(),
+        },
         _ => e.to_string(),
     };
 
     truncate_text(
         &message,
         TruncationPolicy::Bytes(ERROR_MESSAGE_UI_MAX_BYTES),
+        None,
     )
 }
 
@@ -636,6 +664,7 @@ mod tests {
             aggregated_output: StreamOutput::new("aggregate detail".to_string()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            exceeded_limit: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -652,6 +681,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            exceeded_limit: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -668,6 +698,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(8),
             timed_out: false,
+            exceeded_limit: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -711,6 +742,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(5),
             timed_out: false,
+            exceeded_limit: None,
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -6,16 +6,19 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 use async_channel::Sender;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::types::ResourceLimits;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
@@ -25,8 +28,12 @@ use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::CommandCgroup;
+use crate::resource_limits::ExceededResourceLimit;
+use crate::resource_limits::exceeded_limit;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
+use crate::sandboxing::SandboxExtras;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
 use crate::spawn::StdioPolicy;
@@ -113,7 +120,8 @@ pub enum SandboxType {
     /// Only available on macOS.
     MacosSeatbelt,
 
-    /// Only available on Linux.
+    /// Only available on Linux. Runs `codex-linux-sandbox` with the backend
+    /// from [`crate::sandboxing::SandboxExtras`].
     LinuxSeccomp,
 
     /// Only available on Windows.
@@ -132,6 +140,7 @@ pub async fn process_exec_tool_call(
     sandbox_policy: &SandboxPolicy,
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
+    deny_read: &[AbsolutePathBuf],
     stdout_stream: Option<StdoutStream>,
 ) -> Result<ExecToolCallOutput> {
     let sandbox_type = match &sandbox_policy {
@@ -177,6 +186,10 @@ pub async fn process_exec_tool_call(
             sandbox_type,
             sandbox_cwd,
             codex_linux_sandbox_exe.as_ref(),
+            SandboxExtras {
+                deny_read,
+                ..Default::default()
+            },
         )
         .map_err(CodexErr::from)?;
 
@@ -198,6 +211,8 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        resource_limits,
+        cgroup,
     } = env;
 
     let params = ExecParams {
@@ -211,9 +226,23 @@ pub(crate) async fn execute_exec_env(
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(
+        params,
+        sandbox,
+        sandbox_policy,
+        resource_limits,
+        cgroup.clone(),
+        stdout_stream,
+    )
+    .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    finalize_exec_result(
+        raw_output_result,
+        sandbox,
+        &resource_limits,
+        cgroup.as_deref(),
+        duration,
+    )
 }
 
 #[cfg(target_os = "windows")]
@@ -318,21 +347,23 @@ async fn exec_windows_sandbox(
 fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
+    resource_limits: &ResourceLimits,
+    cgroup: Option<&CommandCgroup>,
     duration: Duration,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            #[allow(unused_mut)]
+            let mut signal = None;
 
             #[cfg(target_family = "unix")]
             {
-                if let Some(signal) = raw_output.exit_status.signal() {
-                    if signal == TIMEOUT_CODE {
-                        timed_out = true;
-                    } else {
-                        return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
-                    }
+                signal = raw_output.exit_status.signal();
+                if signal == Some(TIMEOUT_CODE) {
+                    timed_out = true;
+                    signal = None;
                 }
             }
 
@@ -351,6 +382,7 @@ fn finalize_exec_result(
                 aggregated_output,
                 duration,
                 timed_out,
+                exceeded_limit: None,
             };
 
             if timed_out {
@@ -359,6 +391,21 @@ fn finalize_exec_result(
                 }));
             }
 
+            if let Some(limit) =
+                exceeded_limit(resource_limits, cgroup, signal, exec_output.exit_code)
+            {
+                return Err(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                    output: Box::new(ExecToolCallOutput {
+                        exceeded_limit: Some(limit),
+                        ..exec_output
+                    }),
+                }));
+            }
+
+            if let Some(signal) = signal {
+                return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
+            }
+
             if is_likely_sandbox_denied(sandbox_type, &exec_output) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
@@ -501,6 +548,8 @@ pub struct ExecToolCallOutput {
     pub aggregated_output: StreamOutput<String>,
     pub duration: Duration,
     pub timed_out: bool,
+    /// Set when one of the configured resource limits stopped the command.
+    pub exceeded_limit: Option<ExceededResourceLimit>,
 }
 
 impl Default for ExecToolCallOutput {
@@ -512,6 +561,7 @@ impl Default for ExecToolCallOutput {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::ZERO,
             timed_out: false,
+            exceeded_limit: None,
         }
     }
 }
@@ -521,6 +571,8 @@ async fn exec(
     params: ExecParams,
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
+    resource_limits: ResourceLimits,
+    cgroup: Option<Arc<CommandCgroup>>,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
@@ -548,6 +600,15 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
+    // `codex-linux-sandbox` joins the cgroup and applies the limits itself,
+    // around its own setup.
+    let (spawn_resource_limits, spawn_cgroup) = match sandbox {
+        SandboxType::LinuxSeccomp => (None, None),
+        _ => (
+            Some(resource_limits).filter(|limits| !limits.is_empty()),
+            cgroup,
+        ),
+    };
     let child = spawn_child_async(
         PathBuf::from(program),
         args.into(),
@@ -556,6 +617,8 @@ async fn exec(
         sandbox_policy,
         StdioPolicy::RedirectForShellTool,
         env,
+        spawn_resource_limits,
+        spawn_cgroup,
     )
     .await?;
     consume_truncated_output(child, expiration, stdout_stream).await
@@ -800,6 +863,7 @@ mod tests {
             aggregated_output: StreamOutput::new(aggregated.to_string()),
             duration: Duration::from_millis(1),
             timed_out: false,
+            exceeded_limit: None,
         }
     }
 
@@ -883,7 +947,15 @@ mod tests {
             arg0: None,
         };
 
-        let output = exec(params, SandboxType::None, &SandboxPolicy::ReadOnly, None).await?;
+        let output = exec(
+            params,
+            SandboxType::None,
+            &SandboxPolicy::ReadOnly,
+            ResourceLimits::default(),
+            None,
+            None,
+        )
+        .await?;
         assert!(output.timed_out);
 
         let stdout = output.stdout.from_utf8_lossy().text;
@@ -911,6 +983,42 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_size_limit_is_reported() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let env = ExecEnv {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                // `exec` so the signal is not turned into an exit code by the
+                // shell.
+                "exec head -c 2097152 /dev/zero > out.bin".to_string(),
+            ],
+            cwd: dir.path().to_path_buf(),
+            env: std::env::vars().collect(),
+            expiration: 10_000.into(),
+            sandbox: SandboxType::None,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: None,
+            resource_limits: ResourceLimits {
+                max_file_size_mb: Some(1),
+                ..Default::default()
+            },
+            cgroup: None,
+        };
+
+        match execute_exec_env(env, &SandboxPolicy::DangerFullAccess, None).await {
+            Err(CodexErr::Sandbox(SandboxErr::ResourceLimit { output })) => assert_eq!(
+                output.exceeded_limit,
+                Some(ExceededResourceLimit::FileSize { max_mb: 1 })
+            ),
+            other => panic!("expected a resource limit error, got {other:?}"),
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
@@ -936,6 +1044,7 @@ mod tests {
             &SandboxPolicy::DangerFullAccess,
             cwd.as_path(),
             &None,
+            &[],
             None,
         )
         .await;
@@ -5,18 +5,25 @@ use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 
+use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
 use crate::command_safety::is_dangerous_command::requires_initial_appoval;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
+use codex_app_server_protocol::ConfigLayerSource;
 use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyDecision;
+use codex_protocol::approvals::ExecPolicyExplanation;
+use codex_protocol::approvals::ExecPolicyLayer;
+use codex_protocol::approvals::ExecPolicyRuleMatch;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
 use thiserror::Error;
@@ -36,6 +43,28 @@ const RULES_DIR_NAME: &str = "rules";
 const RULE_EXTENSION: &str = "rules";
 const DEFAULT_POLICY_FILE: &str = "default.rules";
 
+/// Builds the context `workspace_path()` matchers evaluate a command run in `cwd` against.
+///
+/// The workspace is what the sandbox lets the command write to: the writable roots of a
+/// workspace-write policy, nothing under a read-only policy, and the session's `sandbox_cwd`
+/// when the policy grants full disk access.
+pub fn workspace_match_context(
+    cwd: PathBuf,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_cwd: &Path,
+) -> MatchContext {
+    let workspace_roots = if sandbox_policy.has_full_disk_write_access() {
+        vec![sandbox_cwd.to_path_buf()]
+    } else {
+        sandbox_policy
+            .get_writable_roots_with_cwd(sandbox_cwd)
+            .into_iter()
+            .map(|writable_root| writable_root.root.into_path_buf())
+            .collect()
+    };
+    MatchContext::new(cwd, workspace_roots)
+}
+
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
     match rule_match {
         RuleMatch::PrefixRuleMatch { .. } => true,
@@ -84,12 +113,16 @@ pub enum ExecPolicyUpdateError {
 
 pub(crate) struct ExecPolicyManager {
     policy: ArcSwap<Policy>,
+    /// Rules directory of each config layer, used to report which layer a matched rule came
+    /// from.
+    rules_dirs: Vec<(PathBuf, ExecPolicyLayer)>,
 }
 
 impl ExecPolicyManager {
     pub(crate) fn new(policy: Arc<Policy>) -> Self {
         Self {
             policy: ArcSwap::from(policy),
+            rules_dirs: Vec::new(),
         }
     }
 
@@ -98,7 +131,10 @@ impl ExecPolicyManager {
         config_stack: &ConfigLayerStack,
     ) -> Result<Self, ExecPolicyError> {
         let policy = load_exec_policy_for_features(features, config_stack).await?;
-        Ok(Self::new(Arc::new(policy)))
+        Ok(Self {
+            policy: ArcSwap::from(Arc::new(policy)),
+            rules_dirs: rules_dirs_by_layer(config_stack),
+        })
     }
 
     pub(crate) fn current(&self) -> Arc<Policy> {
@@ -112,6 +148,7 @@ impl ExecPolicyManager {
         approval_policy: AskForApproval,
         sandbox_policy: &SandboxPolicy,
         sandbox_permissions: SandboxPermissions,
+        match_context: &MatchContext,
     ) -> ExecApprovalRequirement {
         let exec_policy = self.current();
         let commands =
@@ -123,11 +160,15 @@ impl ExecPolicyManager {
                 Decision::Allow
             }
         };
-        let evaluation = exec_policy.check_multiple(commands.iter(), &heuristics_fallback);
+        let evaluation = exec_policy.check_multiple_with_context(
+            commands.iter(),
+            match_context,
+            &heuristics_fallback,
+        );
 
         match evaluation.decision {
             Decision::Forbidden => ExecApprovalRequirement::Forbidden {
-                reason: derive_forbidden_reason(command, &evaluation),
+                reason: self.derive_forbidden_reason(command, &evaluation),
             },
             Decision::Prompt => {
                 if matches!(approval_policy, AskForApproval::Never) {
@@ -144,6 +185,9 @@ impl ExecPolicyManager {
                         } else {
                             None
                         },
+                        explanation: Some(
+                            self.derive_explanation(&evaluation, sandbox_permissions),
+                        ),
                     }
                 }
             }
@@ -185,6 +229,149 @@ impl ExecPolicyManager {
         self.policy.store(Arc::new(updated_policy));
         Ok(())
     }
+
+    /// Describe the execpolicy rules that matched, or, when none of them prompted, the
+    /// heuristic that asked for approval instead.
+    fn derive_explanation(
+        &self,
+        evaluation: &Evaluation,
+        sandbox_permissions: SandboxPermissions,
+    ) -> ExecPolicyExplanation {
+        let matched_rules = evaluation
+            .matched_rules
+            .iter()
+            .filter_map(|rule_match| match rule_match {
+                RuleMatch::PrefixRuleMatch {
+                    matched_prefix,
+                    decision,
+                    justification,
+                    source,
+                } => Some(ExecPolicyRuleMatch {
+                    matched_prefix: matched_prefix.clone(),
+                    decision: explanation_decision(*decision),
+                    justification: justification.clone(),
+                    source: source.as_ref().map(PathBuf::from),
+                    layer: source.as_deref().and_then(|source| self.layer_for(source)),
+                }),
+                RuleMatch::HeuristicsRuleMatch { .. } => None,
+            })
+            .collect::<Vec<_>>();
+
+        let policy_prompted = matched_rules
+            .iter()
+            .any(|rule_match| rule_match.decision != ExecPolicyDecision::Allow);
+        let heuristic = if policy_prompted {
+            None
+        } else {
+            evaluation
+                .matched_rules
+                .iter()
+                .find_map(|rule_match| match rule_match {
+                    RuleMatch::HeuristicsRuleMatch {
+                        command,
+                        decision: Decision::Prompt,
+                    } => Some(describe_heuristic(command, sandbox_permissions)),
+                    _ => None,
+                })
+        };
+
+        ExecPolicyExplanation {
+            matched_rules,
+            heuristic,
+        }
+    }
+
+    /// Derive a string explaining why the command was forbidden. If `justification`
+    /// is set by the user, this can contain instructions with recommended
+    /// alternatives, for example.
+    fn derive_forbidden_reason(&self, command_args: &[String], evaluation: &Evaluation) -> String {
+        let command = render_shlex_command(command_args);
+
+        let most_specific_forbidden = evaluation
+            .matched_rules
+            .iter()
+            .filter_map(|rule_match| match rule_match {
+                RuleMatch::PrefixRuleMatch {
+                    matched_prefix,
+                    decision: Decision::Forbidden,
+                    justification,
+                    source,
+                } => Some((matched_prefix, justification.as_deref(), source.as_deref())),
+                _ => None,
+            })
+            .max_by_key(|(matched_prefix, _, _)| matched_prefix.len());
+
+        let Some((matched_prefix, justification, source)) = most_specific_forbidden else {
+            return format!("`{command}` rejected: blocked by policy");
+        };
+        let reason = match justification {
+            Some(justification) => format!("`{command}` rejected: {justification}"),
+            None => {
+                let prefix = render_shlex_command(matched_prefix);
+                format!("`{command}` rejected: policy forbids commands starting with `{prefix}`")
+            }
+        };
+        match source.and_then(|source| Some((source, self.layer_for(source)?))) {
+            Some((source, layer)) => {
+                format!("{reason} (rule from {} policy {source})", layer_name(layer))
+            }
+            None => reason,
+        }
+    }
+
+    fn layer_for(&self, source: &str) -> Option<ExecPolicyLayer> {
+        let rules_dir = Path::new(source).parent()?;
+        self.rules_dirs
+            .iter()
+            .rev()
+            .find(|(dir, _)| dir == rules_dir)
+            .map(|(_, layer)| *layer)
+    }
+}
+
+fn rules_dirs_by_layer(config_stack: &ConfigLayerStack) -> Vec<(PathBuf, ExecPolicyLayer)> {
+    config_stack
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst)
+        .into_iter()
+        .filter_map(|layer| {
+            let policy_layer = match &layer.name {
+                ConfigLayerSource::System { .. } => ExecPolicyLayer::System,
+                ConfigLayerSource::User { .. } => ExecPolicyLayer::User,
+                ConfigLayerSource::Project { .. } => ExecPolicyLayer::Project,
+                _ => return None,
+            };
+            let rules_dir = layer.config_folder()?.join(RULES_DIR_NAME).ok()?;
+            Some((rules_dir.to_path_buf(), policy_layer))
+        })
+        .collect()
+}
+
+fn explanation_decision(decision: Decision) -> ExecPolicyDecision {
+    match decision {
+        Decision::Allow => ExecPolicyDecision::Allow,
+        Decision::Prompt => ExecPolicyDecision::Prompt,
+        Decision::Forbidden => ExecPolicyDecision::Forbidden,
+    }
+}
+
+fn layer_name(layer: ExecPolicyLayer) -> &'static str {
+    match layer {
+        ExecPolicyLayer::System => "system",
+        ExecPolicyLayer::User => "user",
+        ExecPolicyLayer::Project => "project",
+    }
+}
+
+/// Explain why the built-in safety heuristics asked for approval of `command`.
+fn describe_heuristic(command: &[String], sandbox_permissions: SandboxPermissions) -> String {
+    let command_display = render_shlex_command(command);
+    if command_might_be_dangerous(command) {
+        format!("`{command_display}` looks dangerous")
+    } else if sandbox_permissions.requires_escalated_permissions() {
+        format!("`{command_display}` asked to run outside the sandbox")
+    } else {
+        format!("`{command_display}` is not on the list of known-safe commands")
+    }
 }
 
 impl Default for ExecPolicyManager {
@@ -204,7 +391,10 @@ async fn load_exec_policy_for_features(
     }
 }
 
-pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy, ExecPolicyError> {
+/// Paths of the `*.rules` files in every config layer, lowest precedence first.
+pub async fn collect_exec_policy_files(
+    config_stack: &ConfigLayerStack,
+) -> Result<Vec<PathBuf>, ExecPolicyError> {
     // Iterate the layers in increasing order of precedence, adding the *.rules
     // from each layer, so that higher-precedence layers can override
     // rules defined in lower-precedence ones.
@@ -217,6 +407,11 @@ pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy,
             policy_paths.extend(layer_policy_paths);
         }
     }
+    Ok(policy_paths)
+}
+
+pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy, ExecPolicyError> {
+    let policy_paths = collect_exec_policy_files(config_stack).await?;
 
     let mut parser = PolicyParser::new();
     for policy_path in &policy_paths {
@@ -331,38 +526,6 @@ fn render_shlex_command(args: &[String]) -> String {
     shlex_try_join(args.iter().map(String::as_str)).unwrap_or_else(|_| args.join(" "))
 }
 
-/// Derive a string explaining why the command was forbidden. If `justification`
-/// is set by the user, this can contain instructions with recommended
-/// alternatives, for example.
-fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) -> String {
-    let command = render_shlex_command(command_args);
-
-    let most_specific_forbidden = evaluation
-        .matched_rules
-        .iter()
-        .filter_map(|rule_match| match rule_match {
-            RuleMatch::PrefixRuleMatch {
-                matched_prefix,
-                decision: Decision::Forbidden,
-                justification,
-                ..
-            } => Some((matched_prefix, justification.as_deref())),
-            _ => None,
-        })
-        .max_by_key(|(matched_prefix, _)| matched_prefix.len());
-
-    match most_specific_forbidden {
-        Some((_matched_prefix, Some(justification))) => {
-            format!("`{command}` rejected: {justification}")
-        }
-        Some((matched_prefix, None)) => {
-            let prefix = render_shlex_command(matched_prefix);
-            format!("`{command}` rejected: policy forbids commands starting with `{prefix}`")
-        }
-        None => format!("`{command}` rejected: blocked by policy"),
-    }
-}
-
 async fn collect_policy_files(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, ExecPolicyError> {
     let dir = dir.as_ref();
     let mut read_dir = match fs::read_dir(dir).await {
@@ -450,6 +613,26 @@ mod tests {
         .expect("ConfigLayerStack")
     }
 
+    fn policy_rule_match(prefix: &[&str], decision: ExecPolicyDecision) -> ExecPolicyRuleMatch {
+        ExecPolicyRuleMatch {
+            matched_prefix: prefix.iter().map(ToString::to_string).collect(),
+            decision,
+            justification: None,
+            source: Some(PathBuf::from("test.rules")),
+            layer: None,
+        }
+    }
+
+    fn heuristic_explanation(
+        matched_rules: Vec<ExecPolicyRuleMatch>,
+        heuristic: &str,
+    ) -> Option<ExecPolicyExplanation> {
+        Some(ExecPolicyExplanation {
+            matched_rules,
+            heuristic: Some(heuristic.to_string()),
+        })
+    }
+
     #[tokio::test]
     async fn returns_empty_policy_when_feature_disabled() {
         let mut features = Features::with_defaults();
@@ -471,7 +654,9 @@ mod tests {
                     decision: Decision::Allow
                 }],
             },
-            policy.check_multiple(commands.iter(), &|_| Decision::Allow)
+            policy.check_multiple_with_context(commands.iter(), &MatchContext::default(), &|_| {
+                Decision::Allow
+            })
         );
         assert!(!temp_dir.path().join(RULES_DIR_NAME).exists());
     }
@@ -511,9 +696,12 @@ mod tests {
                     matched_prefix: vec!["rm".to_string()],
                     decision: Decision::Forbidden,
                     justification: None,
+                    source: Some(policy_dir.join("deny.rules").to_string_lossy().to_string()),
                 }],
             },
-            policy.check_multiple(command.iter(), &|_| Decision::Allow)
+            policy.check_multiple_with_context(command.iter(), &MatchContext::default(), &|_| {
+                Decision::Allow
+            })
         );
     }
 
@@ -539,7 +727,9 @@ mod tests {
                     decision: Decision::Allow
                 }],
             },
-            policy.check_multiple(command.iter(), &|_| Decision::Allow)
+            policy.check_multiple_with_context(command.iter(), &MatchContext::default(), &|_| {
+                Decision::Allow
+            })
         );
     }
 
@@ -594,9 +784,19 @@ mod tests {
                     matched_prefix: vec!["rm".to_string()],
                     decision: Decision::Forbidden,
                     justification: None,
+                    source: Some(
+                        user_policy_dir
+                            .join("user.rules")
+                            .to_string_lossy()
+                            .to_string()
+                    ),
                 }],
             },
-            policy.check_multiple([vec!["rm".to_string()]].iter(), &|_| Decision::Allow)
+            policy.check_multiple_with_context(
+                [vec!["rm".to_string()]].iter(),
+                &MatchContext::default(),
+                &|_| Decision::Allow
+            )
         );
         assert_eq!(
             Evaluation {
@@ -605,9 +805,19 @@ mod tests {
                     matched_prefix: vec!["ls".to_string()],
                     decision: Decision::Prompt,
                     justification: None,
+                    source: Some(
+                        project_policy_dir
+                            .join("project.rules")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
                 }],
             },
-            policy.check_multiple([vec!["ls".to_string()]].iter(), &|_| Decision::Allow)
+            policy.check_multiple_with_context(
+                [vec!["ls".to_string()]].iter(),
+                &MatchContext::default(),
+                &|_| Decision::Allow
+            )
         );
         Ok(())
     }
@@ -637,6 +847,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -648,6 +859,67 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         );
     }
 
+    #[tokio::test]
+    async fn exec_approval_requirement_explains_rule_layer_and_source() {
+        let temp_dir = tempdir().expect("create temp dir");
+        let config_stack = config_stack_for_dot_codex_folder(temp_dir.path());
+        let policy_dir = temp_dir.path().join(RULES_DIR_NAME);
+        fs::create_dir_all(&policy_dir).expect("create policy dir");
+        fs::write(
+            policy_dir.join("git.rules"),
+            r#"
+prefix_rule(pattern=["git", "push"], decision="prompt", justification="pushes need review")
+prefix_rule(pattern=["git", "reset"], decision="forbidden")
+"#,
+        )
+        .expect("write policy file");
+        let source = policy_dir.join("git.rules");
+
+        let manager = ExecPolicyManager::load(&Features::with_defaults(), &config_stack)
+            .await
+            .expect("load policy");
+        let requirement = |command: &[&str]| {
+            let command: Vec<String> = command.iter().map(ToString::to_string).collect();
+            let manager = &manager;
+            async move {
+                manager
+                    .create_exec_approval_requirement_for_command(
+                        &Features::with_defaults(),
+                        &command,
+                        AskForApproval::OnRequest,
+                        &SandboxPolicy::ReadOnly,
+                        SandboxPermissions::UseDefault,
+                        &MatchContext::default(),
+                    )
+                    .await
+            }
+        };
+
+        assert_eq!(
+            requirement(&["git", "push"]).await.explanation(),
+            Some(&ExecPolicyExplanation {
+                matched_rules: vec![ExecPolicyRuleMatch {
+                    matched_prefix: vec!["git".to_string(), "push".to_string()],
+                    decision: ExecPolicyDecision::Prompt,
+                    justification: Some("pushes need review".to_string()),
+                    source: Some(source.clone()),
+                    layer: Some(ExecPolicyLayer::Project),
+                }],
+                heuristic: None,
+            })
+        );
+        assert_eq!(
+            requirement(&["git", "reset"]).await,
+            ExecApprovalRequirement::Forbidden {
+                reason: format!(
+                    "`git reset` rejected: policy forbids commands starting with `git reset` \
+                     (rule from project policy {})",
+                    source.display()
+                ),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn justification_is_included_in_forbidden_exec_approval_requirement() {
         let policy_src = r#"
@@ -675,6 +947,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -704,6 +977,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -712,6 +986,10 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: Some("`rm` requires approval by policy".to_string()),
                 proposed_execpolicy_amendment: None,
+                explanation: Some(ExecPolicyExplanation {
+                    matched_rules: vec![policy_rule_match(&["rm"], ExecPolicyDecision::Prompt)],
+                    heuristic: None,
+                }),
             }
         );
     }
@@ -734,6 +1012,7 @@ prefix_rule(
                 AskForApproval::Never,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -757,6 +1036,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -764,7 +1044,11 @@ prefix_rule(
             requirement,
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
-                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command))
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                explanation: heuristic_explanation(
+                    Vec::new(),
+                    "`cargo build` is not on the list of known-safe commands",
+                ),
             }
         );
     }
@@ -791,13 +1075,18 @@ prefix_rule(
                     AskForApproval::UnlessTrusted,
                     &SandboxPolicy::DangerFullAccess,
                     SandboxPermissions::UseDefault,
+                    &MatchContext::default(),
                 )
                 .await,
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "orange".to_string()
-                ]))
+                ])),
+                explanation: heuristic_explanation(
+                    vec![policy_rule_match(&["apple"], ExecPolicyDecision::Allow)],
+                    "`orange` is not on the list of known-safe commands",
+                ),
             }
         );
     }
@@ -814,8 +1103,9 @@ prefix_rule(
             .expect("update policy");
         let updated_policy = manager.current();
 
-        let evaluation = updated_policy.check(
+        let evaluation = updated_policy.check_with_context(
             &["echo".to_string(), "hello".to_string(), "world".to_string()],
+            &MatchContext::default(),
             &|_| Decision::Allow,
         );
         assert!(matches!(
@@ -865,6 +1155,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -872,7 +1163,11 @@ prefix_rule(
             requirement,
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
-                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command))
+                proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+                explanation: heuristic_explanation(
+                    Vec::new(),
+                    "`cargo build` is not on the list of known-safe commands",
+                ),
             }
         );
     }
@@ -892,6 +1187,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -900,6 +1196,10 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: None,
+                explanation: heuristic_explanation(
+                    Vec::new(),
+                    "`cargo build` is not on the list of known-safe commands",
+                ),
             }
         );
     }
@@ -922,6 +1222,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -930,6 +1231,10 @@ prefix_rule(
             ExecApprovalRequirement::NeedsApproval {
                 reason: Some("`rm` requires approval by policy".to_string()),
                 proposed_execpolicy_amendment: None,
+                explanation: Some(ExecPolicyExplanation {
+                    matched_rules: vec![policy_rule_match(&["rm"], ExecPolicyDecision::Prompt)],
+                    heuristic: None,
+                }),
             }
         );
     }
@@ -949,6 +1254,7 @@ prefix_rule(
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -960,6 +1266,10 @@ prefix_rule(
                     "cargo".to_string(),
                     "build".to_string()
                 ])),
+                explanation: heuristic_explanation(
+                    Vec::new(),
+                    "`cargo build` is not on the list of known-safe commands",
+                ),
             }
         );
     }
@@ -987,6 +1297,7 @@ prefix_rule(
                     AskForApproval::UnlessTrusted,
                     &SandboxPolicy::ReadOnly,
                     SandboxPermissions::UseDefault,
+                    &MatchContext::default(),
                 )
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -994,6 +1305,10 @@ prefix_rule(
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "apple".to_string()
                 ])),
+                explanation: heuristic_explanation(
+                    vec![policy_rule_match(&["cat"], ExecPolicyDecision::Allow)],
+                    "`apple` is not on the list of known-safe commands",
+                ),
             }
         );
     }
@@ -1010,6 +1325,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -1040,6 +1356,7 @@ prefix_rule(
                 AskForApproval::OnRequest,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &MatchContext::default(),
             )
             .await;
 
@@ -1051,4 +1368,29 @@ prefix_rule(
             }
         );
     }
+
+    #[test]
+    fn workspace_roots_come_from_the_sandbox_policy() {
+        let cwd = PathBuf::from("/repo");
+        let extra = AbsolutePathBuf::from_absolute_path("/cache").expect("absolute path");
+        let workspace_write = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![extra],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let context = workspace_match_context(cwd.join("src"), &workspace_write, &cwd);
+        assert!(context.is_workspace_path("main.rs"));
+        assert!(context.is_workspace_path("/cache/build"));
+        assert!(!context.is_workspace_path("/etc/passwd"));
+
+        let read_only = workspace_match_context(cwd.clone(), &SandboxPolicy::ReadOnly, &cwd);
+        assert!(!read_only.is_workspace_path("main.rs"));
+
+        let full_access =
+            workspace_match_context(cwd.clone(), &SandboxPolicy::DangerFullAccess, &cwd);
+        assert!(full_access.is_workspace_path("main.rs"));
+        assert!(!full_access.is_workspace_path("/etc/passwd"));
+    }
 }
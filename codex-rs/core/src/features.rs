@@ -96,6 +96,8 @@ pub enum Feature {
     EnableRequestCompression,
     /// Enable collab tools.
     Collab,
+    /// Save truncated exec output to disk and expose `read_tool_output`.
+    ToolOutputSpill,
 }
 
 impl Feature {
@@ -414,6 +416,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::ToolOutputSpill,
+        key: "tool_output_spill",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::Tui2,
         key: "tui2",
@@ -3,4 +3,8 @@ use env_flags::env_flags;
 env_flags! {
     /// Fixture path for offline tests (see client.rs).
     pub CODEX_RS_SSE_FIXTURE: Option<&str> = None;
+    /// Cassette file that model API exchanges are appended to.
+    pub CODEX_RS_HTTP_RECORD: Option<&str> = None;
+    /// Cassette file that model API responses are replayed from.
+    pub CODEX_RS_HTTP_REPLAY: Option<&str> = None;
 }
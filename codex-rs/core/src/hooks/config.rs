@@ -1,5 +1,7 @@
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct HooksConfig {
@@ -11,12 +13,44 @@ pub struct HooksConfig {
     pub session_start: Vec<HookCommandConfig>,
     #[serde(default)]
     pub user_prompt_submit: Vec<HookCommandConfig>,
+    #[serde(default)]
+    pub stop: Vec<HookCommandConfig>,
+    #[serde(default)]
+    pub subagent_stop: Vec<HookCommandConfig>,
+    #[serde(default)]
+    pub pre_compact: Vec<HookCommandConfig>,
+    #[serde(default)]
+    pub notification: Vec<HookCommandConfig>,
+    #[serde(default)]
+    pub session_end: Vec<HookCommandConfig>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct HookCommandConfig {
     pub matcher: Option<String>,
     pub command: Vec<String>,
     #[serde(default)]
     pub timeout_ms: Option<u64>,
+    /// What to do when the hook times out, exits non-zero (other than 2), or
+    /// prints an invalid response.
+    #[serde(default)]
+    pub on_error: HookErrorPolicy,
+    /// Working directory for the hook; defaults to the session's project dir.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    /// Extra environment variables for the hook process.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HookErrorPolicy {
+    /// Fail the event: tool calls return the hook error to the model.
+    #[default]
+    Block,
+    /// Log a warning and continue as if the hook returned `{}`.
+    Warn,
+    /// Continue silently as if the hook returned `{}`.
+    Ignore,
 }
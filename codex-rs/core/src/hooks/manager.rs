@@ -1,61 +1,145 @@
+use futures::future::join_all;
 use regex::Regex;
+use serde::Serialize;
 use serde_json::to_value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 use std::time::Duration;
+use tracing::warn;
 
+use crate::hooks::HookCommandConfig;
+use crate::hooks::HookDecision;
 use crate::hooks::HookDecisionKind;
 use crate::hooks::HookDecisionWithContext;
 use crate::hooks::HookError;
+use crate::hooks::HookErrorPolicy;
+use crate::hooks::HookProcessOptions;
 use crate::hooks::HookRunner;
 use crate::hooks::HooksConfig;
+use crate::hooks::NotificationHookPayload;
+use crate::hooks::PreCompactHookPayload;
+use crate::hooks::SessionEndHookPayload;
 use crate::hooks::SessionStartHookPayload;
+use crate::hooks::StopHookPayload;
 use crate::hooks::ToolHookPayload;
 use crate::hooks::UserPromptSubmitHookPayload;
 
 const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Result of running the `PreToolUse` hooks for a single tool call.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PreToolUseOutcome {
+    /// First hook (in config order) that blocked the call, if any.
+    pub blocked: Option<HookDecisionWithContext>,
+    /// Payloads with every returned `tool_input` applied, or `None` when no
+    /// hook rewrote the call.
+    pub rewritten_payloads: Option<Vec<ToolHookPayload>>,
+    /// Hooks whose replacement `tool_input` changed the call, in config order.
+    pub rewritten_by: Vec<HookCommandConfig>,
+    /// `additional_context` returned by hooks that did not block the call.
+    pub additional_context: Vec<String>,
+}
+
+/// Result of running the `UserPromptSubmit` hooks for a single prompt.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UserPromptSubmitOutcome {
+    /// First hook (in config order) that blocked the prompt, if any.
+    pub blocked: Option<HookDecisionWithContext>,
+    /// Prompt text after every rewrite, or `None` when no hook rewrote it.
+    pub prompt: Option<String>,
+    /// `additional_context` returned by the hooks, in config order.
+    pub additional_context: Vec<String>,
+}
+
+/// Session details exposed to every hook process.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookEnvironment {
+    /// Exported as `CODEX_PROJECT_DIR` and used as the default hook cwd.
+    /// Follows the cwd of the latest turn.
+    pub project_dir: Option<PathBuf>,
+    /// Exported as `CODEX_SESSION_ID`.
+    pub session_id: Option<String>,
+}
+
 pub struct HooksManager {
     config: HooksConfig,
     runner: HookRunner,
+    environment: RwLock<HookEnvironment>,
+    /// Matchers compiled once up front; invalid patterns map to `None` and
+    /// never match.
+    matchers: HashMap<String, Option<Regex>>,
 }
 
 impl HooksManager {
     pub fn new(config: HooksConfig) -> Self {
+        let matchers = compile_matchers(&config);
         Self {
             config,
             runner: HookRunner::new(DEFAULT_HOOK_TIMEOUT),
+            environment: RwLock::new(HookEnvironment::default()),
+            matchers,
         }
     }
 
+    pub fn with_environment(self, environment: HookEnvironment) -> Self {
+        Self {
+            environment: RwLock::new(environment),
+            ..self
+        }
+    }
+
+    /// Points `CODEX_PROJECT_DIR` and the default hook cwd at `project_dir`.
+    pub fn set_project_dir(&self, project_dir: PathBuf) {
+        self.environment
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .project_dir = Some(project_dir);
+    }
+
     pub async fn run_pre_tool_use(
         &self,
         payloads: &[ToolHookPayload],
-    ) -> Result<Option<HookDecisionWithContext>, HookError> {
+    ) -> Result<PreToolUseOutcome, HookError> {
+        let mut outcome = PreToolUseOutcome::default();
         if self.config.pre_tool_use.is_empty() || payloads.is_empty() {
-            return Ok(None);
+            return Ok(outcome);
         }
 
-        for payload in payloads {
-            for hook in &self.config.pre_tool_use {
-                if !matcher_matches(hook.matcher.as_deref(), &payload.tool_name) {
+        // Hooks may rewrite the input, so they run one at a time in config
+        // order, each seeing the input left by the previous one.
+        let mut current = payloads.to_vec();
+        for payload in &mut current {
+            for hook in self.matching_hooks(&self.config.pre_tool_use, &payload.tool_name) {
+                let Some(decision) = self.run_hook(hook, &*payload).await? else {
                     continue;
-                }
-
-                let input = to_value(payload).map_err(HookError::SerializePayload)?;
-                let timeout_override = hook.timeout_ms.map(Duration::from_millis);
-                let decision = self
-                    .runner
-                    .run(hook.command.clone(), input, timeout_override)
-                    .await?;
+                };
                 if matches!(decision.decision, Some(HookDecisionKind::Block)) {
-                    return Ok(Some(HookDecisionWithContext {
+                    outcome.blocked = Some(HookDecisionWithContext {
                         decision,
                         hook: hook.clone(),
-                    }));
+                    });
+                    return Ok(outcome);
+                }
+                outcome
+                    .additional_context
+                    .extend(decision.additional_context);
+                if let Some(tool_input) = decision.tool_input
+                    && tool_input != payload.tool_input
+                {
+                    payload.tool_input = tool_input;
+                    if !outcome.rewritten_by.contains(hook) {
+                        outcome.rewritten_by.push(hook.clone());
+                    }
                 }
             }
         }
 
-        Ok(None)
+        if !outcome.rewritten_by.is_empty() {
+            outcome.rewritten_payloads = Some(current);
+        }
+        Ok(outcome)
     }
 
     pub async fn run_post_tool_use(&self, payloads: &[ToolHookPayload]) -> Result<(), HookError> {
@@ -64,79 +148,248 @@ impl HooksManager {
         }
 
         for payload in payloads {
-            for hook in &self.config.post_tool_use {
-                if !matcher_matches(hook.matcher.as_deref(), &payload.tool_name) {
-                    continue;
-                }
-
-                let input = to_value(payload).map_err(HookError::SerializePayload)?;
-                let timeout_override = hook.timeout_ms.map(Duration::from_millis);
-                let _ = self
-                    .runner
-                    .run(hook.command.clone(), input, timeout_override)
-                    .await?;
-            }
+            self.run_matching(&self.config.post_tool_use, &payload.tool_name, payload)
+                .await?;
         }
 
         Ok(())
     }
 
+    /// Runs the `SessionStart` hooks and returns any `additional_context` they
+    /// produced.
     pub async fn run_session_start(
         &self,
         payload: &SessionStartHookPayload,
-    ) -> Result<(), HookError> {
-        if self.config.session_start.is_empty() {
-            return Ok(());
-        }
+    ) -> Result<Vec<String>, HookError> {
+        let decisions = self
+            .run_matching(&self.config.session_start, &payload.source, payload)
+            .await?;
+        Ok(decisions
+            .into_iter()
+            .filter_map(|(_, decision)| decision.additional_context)
+            .collect())
+    }
 
-        for hook in &self.config.session_start {
-            if !matcher_matches(hook.matcher.as_deref(), &payload.source) {
+    pub async fn run_user_prompt_submit(
+        &self,
+        payload: &UserPromptSubmitHookPayload,
+    ) -> Result<UserPromptSubmitOutcome, HookError> {
+        let mut outcome = UserPromptSubmitOutcome::default();
+        // Like `PreToolUse`, hooks run one at a time so each sees the prompt
+        // as rewritten by the previous one.
+        let mut current = payload.clone();
+        for hook in self.matching_hooks(&self.config.user_prompt_submit, &payload.prompt) {
+            let Some(decision) = self.run_hook(hook, &current).await? else {
                 continue;
+            };
+            if matches!(decision.decision, Some(HookDecisionKind::Block)) {
+                outcome.blocked = Some(HookDecisionWithContext {
+                    decision,
+                    hook: hook.clone(),
+                });
+                return Ok(outcome);
+            }
+            outcome
+                .additional_context
+                .extend(decision.additional_context);
+            if let Some(prompt) = decision.prompt {
+                current.prompt = prompt.clone();
+                outcome.prompt = Some(prompt);
             }
-
-            let input = to_value(payload).map_err(HookError::SerializePayload)?;
-            let timeout_override = hook.timeout_ms.map(Duration::from_millis);
-            let _ = self
-                .runner
-                .run(hook.command.clone(), input, timeout_override)
-                .await?;
         }
 
-        Ok(())
+        Ok(outcome)
     }
 
-    pub async fn run_user_prompt_submit(
+    /// Runs the `Stop` hooks (or `SubagentStop` hooks for sub-agent sessions)
+    /// and returns the first decision that blocks the agent from stopping.
+    /// Matchers are tested against the event name, never against what the
+    /// model wrote.
+    pub async fn run_stop(
         &self,
-        payload: &UserPromptSubmitHookPayload,
+        payload: &StopHookPayload,
+    ) -> Result<Option<HookDecisionWithContext>, HookError> {
+        let hooks = if payload.hook_event_name == "SubagentStop" {
+            &self.config.subagent_stop
+        } else {
+            &self.config.stop
+        };
+        let decisions = self
+            .run_matching(hooks, &payload.hook_event_name, payload)
+            .await?;
+        Ok(first_block(&decisions))
+    }
+
+    pub async fn run_pre_compact(&self, payload: &PreCompactHookPayload) -> Result<(), HookError> {
+        self.run_matching(&self.config.pre_compact, &payload.trigger, payload)
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn run_notification(
+        &self,
+        payload: &NotificationHookPayload,
     ) -> Result<(), HookError> {
-        if self.config.user_prompt_submit.is_empty() {
-            return Ok(());
-        }
+        self.run_matching(
+            &self.config.notification,
+            &payload.notification_type,
+            payload,
+        )
+        .await
+        .map(|_| ())
+    }
 
-        for hook in &self.config.user_prompt_submit {
-            if !matcher_matches(hook.matcher.as_deref(), &payload.prompt) {
-                continue;
+    pub async fn run_session_end(&self, payload: &SessionEndHookPayload) -> Result<(), HookError> {
+        self.run_matching(&self.config.session_end, &payload.reason, payload)
+            .await
+            .map(|_| ())
+    }
+
+    /// Runs every hook whose matcher accepts `match_target` concurrently and
+    /// returns their decisions in config order. Only for events whose hooks
+    /// cannot rewrite the payload.
+    async fn run_matching<'a, T: Serialize>(
+        &self,
+        hooks: &'a [HookCommandConfig],
+        match_target: &str,
+        payload: &T,
+    ) -> Result<Vec<(&'a HookCommandConfig, HookDecision)>, HookError> {
+        let matching = self.matching_hooks(hooks, match_target);
+        let results = join_all(matching.iter().map(|hook| self.run_hook(hook, payload))).await;
+
+        let mut decisions = Vec::with_capacity(results.len());
+        for (hook, result) in matching.into_iter().zip(results) {
+            if let Some(decision) = result? {
+                decisions.push((hook, decision));
             }
+        }
+        Ok(decisions)
+    }
 
-            let input = to_value(payload).map_err(HookError::SerializePayload)?;
-            let timeout_override = hook.timeout_ms.map(Duration::from_millis);
-            let _ = self
-                .runner
-                .run(hook.command.clone(), input, timeout_override)
-                .await?;
+    fn matching_hooks<'a>(
+        &self,
+        hooks: &'a [HookCommandConfig],
+        match_target: &str,
+    ) -> Vec<&'a HookCommandConfig> {
+        hooks
+            .iter()
+            .filter(|hook| self.matcher_matches(hook.matcher.as_deref(), match_target))
+            .collect()
+    }
+
+    /// Runs a single hook. Failures are handled according to its `on_error`
+    /// policy: `block` fails the whole event, while `warn` and `ignore` drop
+    /// the hook's decision.
+    async fn run_hook<T: Serialize>(
+        &self,
+        hook: &HookCommandConfig,
+        payload: &T,
+    ) -> Result<Option<HookDecision>, HookError> {
+        let input = to_value(payload).map_err(HookError::SerializePayload)?;
+        let options = self.process_options(hook);
+        let result = self
+            .runner
+            .run_with_options(
+                hook.command.clone(),
+                input,
+                hook.timeout_ms.map(Duration::from_millis),
+                &options,
+            )
+            .await;
+        match result {
+            Ok(decision) => Ok(Some(decision)),
+            Err(err) => match hook.on_error {
+                HookErrorPolicy::Block => Err(err),
+                HookErrorPolicy::Warn => {
+                    warn!("hook {:?} failed: {err}", hook.command);
+                    Ok(None)
+                }
+                HookErrorPolicy::Ignore => Ok(None),
+            },
         }
+    }
 
-        Ok(())
+    fn process_options(&self, hook: &HookCommandConfig) -> HookProcessOptions {
+        let environment = self
+            .environment
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut env = HashMap::new();
+        if let Some(project_dir) = environment.project_dir.as_ref() {
+            env.insert(
+                "CODEX_PROJECT_DIR".to_string(),
+                project_dir.display().to_string(),
+            );
+        }
+        if let Some(session_id) = environment.session_id.as_ref() {
+            env.insert("CODEX_SESSION_ID".to_string(), session_id.clone());
+        }
+        env.extend(hook.env.clone());
+        HookProcessOptions {
+            cwd: hook.cwd.clone().or_else(|| environment.project_dir.clone()),
+            env,
+        }
+    }
+
+    fn matcher_matches(&self, matcher: Option<&str>, target: &str) -> bool {
+        match matcher {
+            None => true,
+            Some(pattern) => self
+                .matchers
+                .get(pattern)
+                .and_then(Option::as_ref)
+                .is_some_and(|re| re.is_match(target)),
+        }
     }
 }
 
-fn matcher_matches(matcher: Option<&str>, tool_name: &str) -> bool {
-    match matcher {
-        None => true,
-        Some(pattern) => Regex::new(pattern)
-            .map(|re| re.is_match(tool_name))
-            .unwrap_or(false),
+fn compile_matchers(config: &HooksConfig) -> HashMap<String, Option<Regex>> {
+    let HooksConfig {
+        pre_tool_use,
+        post_tool_use,
+        session_start,
+        user_prompt_submit,
+        stop,
+        subagent_stop,
+        pre_compact,
+        notification,
+        session_end,
+    } = config;
+    let mut matchers = HashMap::new();
+    for hook in pre_tool_use
+        .iter()
+        .chain(post_tool_use)
+        .chain(session_start)
+        .chain(user_prompt_submit)
+        .chain(stop)
+        .chain(subagent_stop)
+        .chain(pre_compact)
+        .chain(notification)
+        .chain(session_end)
+    {
+        let Some(pattern) = hook.matcher.as_ref() else {
+            continue;
+        };
+        matchers.entry(pattern.clone()).or_insert_with(|| {
+            Regex::new(pattern)
+                .inspect_err(|err| warn!("invalid hook matcher {pattern:?}: {err}"))
+                .ok()
+        });
     }
+    matchers
+}
+
+fn first_block(
+    decisions: &[(&HookCommandConfig, HookDecision)],
+) -> Option<HookDecisionWithContext> {
+    decisions.iter().find_map(|(hook, decision)| {
+        matches!(decision.decision, Some(HookDecisionKind::Block)).then(|| {
+            HookDecisionWithContext {
+                decision: decision.clone(),
+                hook: (*hook).clone(),
+            }
+        })
+    })
 }
 
 #[cfg(test)]
@@ -150,8 +403,6 @@ mod tests {
     use serde_json::json;
     use tempfile::TempDir;
 
-    use crate::hooks::HookCommandConfig;
-
     use super::*;
 
     #[test]
@@ -181,6 +432,7 @@ echo '{"decision":"allow"}'
                 matcher: Some("Write".to_string()),
                 command: vec![script_str],
                 timeout_ms: Some(50),
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -193,10 +445,219 @@ echo '{"decision":"allow"}'
                 "file_path": "/tmp/file.txt",
                 "content": "data",
             }),
+            tool_response: None,
         }];
 
         let result = manager.run_pre_tool_use(&payloads).await;
         assert!(matches!(result, Err(HookError::Timeout)));
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg(not(target_os = "windows"))]
+    async fn on_error_warn_skips_failed_hook() -> Result<()> {
+        let hook_dir = TempDir::new()?;
+        let script = hook_dir.path().join("hook.sh");
+        std::fs::write(&script, "#!/bin/bash\nexit 1\n")?;
+        std::fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+
+        let manager = HooksManager::new(HooksConfig {
+            stop: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![script.to_string_lossy().to_string()],
+                on_error: HookErrorPolicy::Warn,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let payload = StopHookPayload {
+            session_id: "sess-1".to_string(),
+            transcript_path: "/tmp/rollout.jsonl".to_string(),
+            hook_event_name: "Stop".to_string(),
+            stop_hook_active: false,
+            last_assistant_message: None,
+        };
+
+        assert_eq!(manager.run_stop(&payload).await?, None);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg(not(target_os = "windows"))]
+    async fn stop_matchers_ignore_the_assistant_message() -> Result<()> {
+        let hook_dir = TempDir::new()?;
+        let script = hook_dir.path().join("hook.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/bash\ncat >/dev/null\necho '{\"decision\":\"block\",\"reason\":\"again\"}'\n",
+        )?;
+        std::fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+        let hook = |matcher: &str| HookCommandConfig {
+            matcher: Some(matcher.to_string()),
+            command: vec![script.to_string_lossy().to_string()],
+            ..Default::default()
+        };
+        let payload = StopHookPayload {
+            session_id: "sess-1".to_string(),
+            transcript_path: "/tmp/rollout.jsonl".to_string(),
+            hook_event_name: "Stop".to_string(),
+            stop_hook_active: false,
+            last_assistant_message: Some("all tests pass".to_string()),
+        };
+
+        let by_message = HooksManager::new(HooksConfig {
+            stop: vec![hook("tests pass")],
+            ..Default::default()
+        });
+        assert_eq!(by_message.run_stop(&payload).await?, None);
+
+        let by_event = HooksManager::new(HooksConfig {
+            stop: vec![hook("^Stop$")],
+            ..Default::default()
+        });
+        assert!(by_event.run_stop(&payload).await?.is_some());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg(not(target_os = "windows"))]
+    async fn hooks_run_in_project_dir_with_session_env() -> Result<()> {
+        let hook_dir = TempDir::new()?;
+        let project_dir = TempDir::new()?;
+        let script = hook_dir.path().join("hook.sh");
+        std::fs::write(
+            &script,
+            r#"#!/bin/bash
+cat >/dev/null
+echo "{\"additional_context\":\"$(pwd)|$CODEX_PROJECT_DIR|$CODEX_SESSION_ID|$EXTRA\"}"
+"#,
+        )?;
+        std::fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+
+        let project_path = project_dir.path().canonicalize()?;
+        let manager = HooksManager::new(HooksConfig {
+            session_start: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![script.to_string_lossy().to_string()],
+                env: HashMap::from([("EXTRA".to_string(), "1".to_string())]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .with_environment(HookEnvironment {
+            project_dir: Some(project_path.clone()),
+            session_id: Some("sess-1".to_string()),
+        });
+        let payload = SessionStartHookPayload {
+            session_id: "sess-1".to_string(),
+            transcript_path: "/tmp/rollout.jsonl".to_string(),
+            hook_event_name: "SessionStart".to_string(),
+            source: "startup".to_string(),
+        };
+
+        let context = manager.run_session_start(&payload).await?;
+        let project = project_path.display();
+        assert_eq!(context, vec![format!("{project}|{project}|sess-1|1")]);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg(not(target_os = "windows"))]
+    async fn pre_tool_use_rewrites_compose_in_config_order() -> Result<()> {
+        let hook_dir = TempDir::new()?;
+        // Each hook appends its suffix to the command it receives.
+        let rewriter = |name: &str, suffix: &str| -> Result<HookCommandConfig> {
+            let script = hook_dir.path().join(name);
+            std::fs::write(
+                &script,
+                format!(
+                    r#"#!/bin/bash
+command=$(sed -E 's/.*"command":"([^"]*)".*/\1/')
+echo "{{\"tool_input\":{{\"command\":\"$command{suffix}\"}}}}"
+"#
+                ),
+            )?;
+            std::fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+            Ok(HookCommandConfig {
+                matcher: Some("Bash".to_string()),
+                command: vec![script.to_string_lossy().to_string()],
+                ..Default::default()
+            })
+        };
+        let first = rewriter("first.sh", " --first")?;
+        let second = rewriter("second.sh", " --second")?;
+
+        let manager = HooksManager::new(HooksConfig {
+            pre_tool_use: vec![first.clone(), second.clone()],
+            ..Default::default()
+        });
+        let payloads = vec![ToolHookPayload {
+            session_id: "sess-1".to_string(),
+            transcript_path: "/tmp/rollout.jsonl".to_string(),
+            hook_event_name: "PreToolUse".to_string(),
+            tool_name: "Bash".to_string(),
+            tool_input: json!({ "command": "ls" }),
+            tool_response: None,
+        }];
+
+        let outcome = manager.run_pre_tool_use(&payloads).await?;
+        let rewritten = outcome.rewritten_payloads.expect("rewritten payloads");
+        assert_eq!(
+            rewritten[0].tool_input,
+            json!({ "command": "ls --first --second" })
+        );
+        assert_eq!(outcome.rewritten_by, vec![first, second]);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    #[cfg(not(target_os = "windows"))]
+    async fn project_dir_follows_the_latest_turn() -> Result<()> {
+        let first_dir = TempDir::new()?;
+        let second_dir = TempDir::new()?;
+        let manager = HooksManager::new(HooksConfig {
+            session_start: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![
+                    "bash".to_string(),
+                    "-c".to_string(),
+                    r#"cat >/dev/null; echo "{\"additional_context\":\"$CODEX_PROJECT_DIR\"}""#
+                        .to_string(),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .with_environment(HookEnvironment {
+            project_dir: Some(first_dir.path().to_path_buf()),
+            session_id: None,
+        });
+        let payload = SessionStartHookPayload {
+            session_id: "sess-1".to_string(),
+            transcript_path: "/tmp/rollout.jsonl".to_string(),
+            hook_event_name: "SessionStart".to_string(),
+            source: "startup".to_string(),
+        };
+
+        manager.set_project_dir(second_dir.path().to_path_buf());
+
+        let context = manager.run_session_start(&payload).await?;
+        assert_eq!(context, vec![second_dir.path().display().to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_matcher_never_matches() {
+        let manager = HooksManager::new(HooksConfig {
+            pre_tool_use: vec![HookCommandConfig {
+                matcher: Some("(".to_string()),
+                command: vec!["true".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        assert!(!manager.matcher_matches(Some("("), "Write"));
+        assert!(manager.matcher_matches(None, "Write"));
+    }
 }
@@ -1,5 +1,10 @@
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseInputItem;
 use serde::Deserialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::io::AsyncWriteExt;
@@ -11,14 +16,29 @@ mod manager;
 mod payload;
 
 pub use config::HookCommandConfig;
+pub use config::HookErrorPolicy;
 pub use config::HooksConfig;
+pub use manager::HookEnvironment;
 pub use manager::HooksManager;
+pub use manager::PreToolUseOutcome;
+pub use manager::UserPromptSubmitOutcome;
 pub use payload::HookContext;
+pub use payload::NotificationHookPayload;
+pub use payload::PreCompactHookPayload;
+pub use payload::SessionEndHookPayload;
 pub use payload::SessionStartHookPayload;
+pub use payload::StopHookPayload;
 pub use payload::ToolHookPayload;
 pub use payload::UserPromptSubmitHookPayload;
 pub use payload::build_apply_patch_hook_payloads;
+pub use payload::build_apply_patch_post_tool_use_hook_payloads;
+pub use payload::build_notification_hook_payload;
+pub use payload::build_post_tool_use_hook_payloads;
+pub use payload::build_pre_compact_hook_payload;
+pub use payload::build_session_end_hook_payload;
 pub use payload::build_session_start_hook_payload;
+pub use payload::build_stop_hook_payload;
+pub use payload::build_tool_response;
 pub use payload::build_user_prompt_submit_hook_payload;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -26,6 +46,15 @@ pub struct HookDecision {
     #[serde(default)]
     pub decision: Option<HookDecisionKind>,
     pub reason: Option<String>,
+    /// Replacement `tool_input` for `PreToolUse` hooks that rewrite the call.
+    #[serde(default)]
+    pub tool_input: Option<Value>,
+    /// Replacement prompt for `UserPromptSubmit` hooks that rewrite the input.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Extra text appended to the model context as a developer message.
+    #[serde(default)]
+    pub additional_context: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -41,6 +70,26 @@ pub enum HookDecisionKind {
     Allow,
 }
 
+/// Wraps the `additional_context` returned by hooks for `hook_event_name` in a
+/// developer message so it can be appended to the conversation history.
+pub fn build_additional_context_item(
+    hook_event_name: &str,
+    additional_context: &[String],
+) -> Option<ResponseInputItem> {
+    if additional_context.is_empty() {
+        return None;
+    }
+
+    let text = format!(
+        "<hook_context hook_event_name=\"{hook_event_name}\">\n{}\n</hook_context>",
+        additional_context.join("\n")
+    );
+    Some(ResponseInputItem::Message {
+        role: "developer".to_string(),
+        content: vec![ContentItem::InputText { text }],
+    })
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum HookError {
     #[error("hook command is empty")]
@@ -59,6 +108,16 @@ pub enum HookError {
     InvalidUtf8(#[from] std::string::FromUtf8Error),
 }
 
+/// Exit code a hook uses to block an event, with stderr as the reason.
+pub const HOOK_BLOCK_EXIT_CODE: i32 = 2;
+
+/// Process settings applied when spawning a hook command.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookProcessOptions {
+    pub cwd: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+}
+
 pub struct HookRunner {
     timeout: Duration,
 }
@@ -73,18 +132,45 @@ impl HookRunner {
         command: Vec<String>,
         payload: Value,
         timeout_override: Option<Duration>,
+    ) -> Result<HookDecision, HookError> {
+        self.run_with_options(
+            command,
+            payload,
+            timeout_override,
+            &HookProcessOptions::default(),
+        )
+        .await
+    }
+
+    pub async fn run_with_options(
+        &self,
+        command: Vec<String>,
+        payload: Value,
+        timeout_override: Option<Duration>,
+        options: &HookProcessOptions,
     ) -> Result<HookDecision, HookError> {
         let (program, args) = command.split_first().ok_or(HookError::EmptyCommand)?;
-        let mut child = Command::new(program)
-            .args(args)
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .envs(&options.env)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .kill_on_drop(true);
+        if let Some(cwd) = options.cwd.as_ref() {
+            cmd.current_dir(cwd);
+        }
+        let mut child = cmd.spawn()?;
 
         if let Some(mut stdin) = child.stdin.take() {
             let input = serde_json::to_vec(&payload).map_err(HookError::SerializePayload)?;
-            stdin.write_all(&input).await?;
+            // Hooks that decide without reading stdin may exit before we finish
+            // writing; their exit status is what matters.
+            if let Err(err) = stdin.write_all(&input).await
+                && err.kind() != ErrorKind::BrokenPipe
+            {
+                return Err(err.into());
+            }
         }
 
         let timeout_duration = timeout_override.unwrap_or(self.timeout);
@@ -92,6 +178,17 @@ impl HookRunner {
             .await
             .map_err(|_| HookError::Timeout)??;
 
+        if output.status.code() == Some(HOOK_BLOCK_EXIT_CODE) {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Ok(HookDecision {
+                decision: Some(HookDecisionKind::Block),
+                reason: (!stderr.is_empty()).then_some(stderr),
+                tool_input: None,
+                prompt: None,
+                additional_context: None,
+            });
+        }
+
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
             return Err(HookError::CommandFailed(stderr));
@@ -1,10 +1,12 @@
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_protocol::models::ResponseInputItem;
 use serde::Serialize;
 use serde_json::Value;
 use serde_json::json;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct HookContext {
@@ -20,6 +22,9 @@ pub struct ToolHookPayload {
     pub hook_event_name: String,
     pub tool_name: String,
     pub tool_input: Value,
+    /// Outcome of the tool call; only populated for `PostToolUse` payloads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_response: Option<Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -39,6 +44,46 @@ pub struct UserPromptSubmitHookPayload {
     pub cwd: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StopHookPayload {
+    pub session_id: String,
+    pub transcript_path: String,
+    pub hook_event_name: String,
+    /// True when the turn is already continuing because of an earlier Stop
+    /// hook block, so hooks can avoid looping forever.
+    pub stop_hook_active: bool,
+    pub last_assistant_message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PreCompactHookPayload {
+    pub session_id: String,
+    pub transcript_path: String,
+    pub hook_event_name: String,
+    /// `manual` for `/compact`, `auto` when the context window filled up.
+    pub trigger: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NotificationHookPayload {
+    pub session_id: String,
+    pub transcript_path: String,
+    pub hook_event_name: String,
+    /// `exec_approval` or `apply_patch_approval`.
+    pub notification_type: String,
+    pub message: String,
+    pub call_id: String,
+    pub cwd: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionEndHookPayload {
+    pub session_id: String,
+    pub transcript_path: String,
+    pub hook_event_name: String,
+    pub reason: String,
+}
+
 pub fn build_apply_patch_hook_payloads(
     context: &HookContext,
     action: &ApplyPatchAction,
@@ -56,6 +101,7 @@ pub fn build_apply_patch_hook_payloads(
                         "file_path": path.to_string_lossy(),
                         "content": content,
                     }),
+                    tool_response: None,
                 });
             }
             ApplyPatchFileChange::Update {
@@ -75,6 +121,7 @@ pub fn build_apply_patch_hook_payloads(
                         "old_string": old_content,
                         "new_string": new_content,
                     }),
+                    tool_response: None,
                 });
             }
             ApplyPatchFileChange::Delete { .. } => {}
@@ -83,6 +130,136 @@ pub fn build_apply_patch_hook_payloads(
     payloads
 }
 
+/// Derives `PostToolUse` payloads from the `PreToolUse` payloads of the same
+/// call, attaching the tool's response so hooks can react to what happened.
+pub fn build_post_tool_use_hook_payloads(
+    pre_payloads: &[ToolHookPayload],
+    response: &ResponseInputItem,
+    duration: Duration,
+) -> Vec<ToolHookPayload> {
+    let tool_response = build_tool_response(response, duration);
+    pre_payloads
+        .iter()
+        .map(|payload| ToolHookPayload {
+            hook_event_name: "PostToolUse".to_string(),
+            tool_response: Some(tool_response.clone()),
+            ..payload.clone()
+        })
+        .collect()
+}
+
+/// Derives `PostToolUse` payloads for the per-file `Write`/`Edit` payloads of
+/// an apply_patch call. Each file reports whether it now holds the content
+/// the call wrote, since a failed patch may have applied only some files.
+pub fn build_apply_patch_post_tool_use_hook_payloads(
+    pre_payloads: &[ToolHookPayload],
+    response: &ResponseInputItem,
+    duration: Duration,
+) -> Vec<ToolHookPayload> {
+    let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    pre_payloads
+        .iter()
+        .map(|payload| {
+            let file_path = payload
+                .tool_input
+                .get("file_path")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let expected = payload
+                .tool_input
+                .get("content")
+                .or_else(|| payload.tool_input.get("new_string"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let applied = fs::read_to_string(file_path)
+                .is_ok_and(|content| content.contains(expected.trim_end_matches('\n')));
+            let mut tool_response = json!({
+                "file_path": file_path,
+                "success": applied,
+                "duration_ms": duration_ms,
+            });
+            if !applied
+                && let (Some(output), Value::Object(map)) =
+                    (response_output(response), &mut tool_response)
+            {
+                map.insert("error".to_string(), json!(output));
+            }
+            ToolHookPayload {
+                hook_event_name: "PostToolUse".to_string(),
+                tool_response: Some(tool_response),
+                ..payload.clone()
+            }
+        })
+        .collect()
+}
+
+fn response_output(response: &ResponseInputItem) -> Option<&str> {
+    match response {
+        ResponseInputItem::FunctionCallOutput { output, .. } => Some(&output.content),
+        ResponseInputItem::CustomToolCallOutput { output, .. } => Some(output),
+        ResponseInputItem::McpToolCallOutput { .. } | ResponseInputItem::Message { .. } => None,
+    }
+}
+
+pub fn build_tool_response(response: &ResponseInputItem, duration: Duration) -> Value {
+    let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    match response {
+        ResponseInputItem::FunctionCallOutput { output, .. } => json!({
+            "output": output.content,
+            "success": output.success.unwrap_or(true),
+            "exit_code": parse_exit_code(&output.content),
+            "duration_ms": duration_ms,
+        }),
+        ResponseInputItem::CustomToolCallOutput { output, .. } => json!({
+            "output": output,
+            "success": true,
+            "exit_code": parse_exit_code(output),
+            "duration_ms": duration_ms,
+        }),
+        ResponseInputItem::McpToolCallOutput { result, .. } => match result {
+            Ok(result) => json!({
+                "result": result,
+                "success": !result.is_error.unwrap_or(false),
+                "duration_ms": duration_ms,
+            }),
+            Err(err) => json!({
+                "error": err,
+                "success": false,
+                "duration_ms": duration_ms,
+            }),
+        },
+        ResponseInputItem::Message { content, .. } => json!({
+            "output": content,
+            "success": true,
+            "duration_ms": duration_ms,
+        }),
+    }
+}
+
+/// Recovers the process exit code from the formatted output of the shell
+/// tools, which is either the freeform `Exit code: N` header, the unified exec
+/// `Process exited with code N` header, or the structured JSON metadata. Only
+/// the header lines before `Output:` are read, so the command's own output
+/// cannot pose as the exit code.
+fn parse_exit_code(output: &str) -> Option<i32> {
+    if let Ok(Value::Object(map)) = serde_json::from_str::<Value>(output) {
+        return map
+            .get("metadata")
+            .and_then(|metadata| metadata.get("exit_code"))
+            .and_then(Value::as_i64)
+            .and_then(|code| i32::try_from(code).ok());
+    }
+
+    output
+        .lines()
+        .take_while(|line| *line != "Output:")
+        .find_map(|line| {
+            line.strip_prefix("Exit code: ")
+                .or_else(|| line.strip_prefix("Process exited with code "))
+                .and_then(|code| code.trim().parse().ok())
+        })
+}
+
 pub fn build_session_start_hook_payload(
     context: &HookContext,
     source: &str,
@@ -108,3 +285,59 @@ pub fn build_user_prompt_submit_hook_payload(
         cwd: cwd.display().to_string(),
     }
 }
+
+pub fn build_stop_hook_payload(
+    context: &HookContext,
+    stop_hook_active: bool,
+    last_assistant_message: Option<&str>,
+) -> StopHookPayload {
+    StopHookPayload {
+        session_id: context.session_id.clone(),
+        transcript_path: context.transcript_path.clone(),
+        hook_event_name: context.hook_event_name.clone(),
+        stop_hook_active,
+        last_assistant_message: last_assistant_message.map(str::to_string),
+    }
+}
+
+pub fn build_pre_compact_hook_payload(
+    context: &HookContext,
+    trigger: &str,
+) -> PreCompactHookPayload {
+    PreCompactHookPayload {
+        session_id: context.session_id.clone(),
+        transcript_path: context.transcript_path.clone(),
+        hook_event_name: context.hook_event_name.clone(),
+        trigger: trigger.to_string(),
+    }
+}
+
+pub fn build_notification_hook_payload(
+    context: &HookContext,
+    notification_type: &str,
+    message: &str,
+    call_id: &str,
+    cwd: &Path,
+) -> NotificationHookPayload {
+    NotificationHookPayload {
+        session_id: context.session_id.clone(),
+        transcript_path: context.transcript_path.clone(),
+        hook_event_name: context.hook_event_name.clone(),
+        notification_type: notification_type.to_string(),
+        message: message.to_string(),
+        call_id: call_id.to_string(),
+        cwd: cwd.display().to_string(),
+    }
+}
+
+pub fn build_session_end_hook_payload(
+    context: &HookContext,
+    reason: &str,
+) -> SessionEndHookPayload {
+    SessionEndHookPayload {
+        session_id: context.session_id.clone(),
+        transcript_path: context.transcript_path.clone(),
+        hook_event_name: context.hook_event_name.clone(),
+        reason: reason.to_string(),
+    }
+}
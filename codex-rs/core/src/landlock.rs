@@ -1,31 +1,56 @@
+use crate::config::types::LinuxSandboxBackend;
+use crate::config::types::ResourceLimits;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::linux_sandbox_args;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
-/// Spawn a shell tool command under the Linux Landlock+seccomp sandbox helper
+/// Settings for `codex-linux-sandbox` that are not part of [`SandboxPolicy`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LinuxSandboxOptions<'a> {
+    pub backend: LinuxSandboxBackend,
+    /// Loopback port of the egress proxy that stays reachable while the
+    /// policy blocks network access.
+    pub network_proxy_port: Option<u16>,
+    /// Paths the command may not access at all.
+    pub deny_read: &'a [AbsolutePathBuf],
+    /// Overlay directory for the workspace (namespaces backend only).
+    pub overlay_dir: Option<&'a Path>,
+    /// Limits the helper applies before exec'ing the command.
+    pub resource_limits: ResourceLimits,
+    /// cgroup the helper joins first, which enforces the limits that are not
+    /// in `resource_limits`.
+    pub cgroup: Option<&'a Path>,
+}
+
+/// Spawn a shell tool command under the Linux sandbox helper
 /// (codex-linux-sandbox).
 ///
 /// Unlike macOS Seatbelt where we directly embed the policy text, the Linux
 /// helper accepts a list of `--sandbox-permission`/`-s` flags mirroring the
 /// public CLI. We convert the internal [`SandboxPolicy`] representation into
 /// the equivalent CLI options.
+#[allow(clippy::too_many_arguments)]
 pub async fn spawn_command_under_linux_sandbox<P>(
     codex_linux_sandbox_exe: P,
     command: Vec<String>,
     command_cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    options: LinuxSandboxOptions<'_>,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
 ) -> std::io::Result<Child>
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args =
+        create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd, options);
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
         codex_linux_sandbox_exe.as_ref().to_path_buf(),
@@ -35,15 +60,23 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        None,
+        None,
     )
     .await
 }
 
 /// Converts the sandbox policy into the CLI invocation for `codex-linux-sandbox`.
+///
+/// When `network_proxy_port` is set, the helper relays that loopback port
+/// into the command's private network namespace so it can reach the egress
+/// proxy and nothing else. Each
+/// `deny_read` path is passed as `--deny-read` and made inaccessible.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    options: LinuxSandboxOptions<'_>,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -60,10 +93,27 @@ pub(crate) fn create_linux_sandbox_command_args(
         sandbox_policy_cwd,
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
-        // Separator so that command arguments starting with `-` are not parsed as
-        // options of the helper itself.
-        "--".to_string(),
     ];
+    if options.backend == LinuxSandboxBackend::Namespaces {
+        linux_cmd.push("--backend".to_string());
+        linux_cmd.push("namespaces".to_string());
+        if let Some(overlay_dir) = options.overlay_dir {
+            linux_cmd.push("--overlay-dir".to_string());
+            linux_cmd.push(overlay_dir.to_string_lossy().to_string());
+        }
+    }
+    if let Some(port) = options.network_proxy_port {
+        linux_cmd.push("--network-proxy-port".to_string());
+        linux_cmd.push(port.to_string());
+    }
+    for path in options.deny_read {
+        linux_cmd.push("--deny-read".to_string());
+        linux_cmd.push(path.to_string_lossy().to_string());
+    }
+    linux_cmd.extend(linux_sandbox_args(&options.resource_limits, options.cgroup));
+    // Separator so that command arguments starting with `-` are not parsed as
+    // options of the helper itself.
+    linux_cmd.push("--".to_string());
 
     // Append the original tool command.
     linux_cmd.extend(command);
@@ -45,6 +45,9 @@ pub enum WireApi {
     /// Regular Chat Completions compatible with `/v1/chat/completions`.
     #[default]
     Chat,
+
+    /// The Anthropic Messages API exposed at `/v1/messages`.
+    Messages,
 }
 
 /// Serializable representation of a provider definition.
@@ -157,6 +160,7 @@ impl ModelProviderInfo {
             wire: match self.wire_api {
                 WireApi::Responses => ApiWireApi::Responses,
                 WireApi::Chat => ApiWireApi::Chat,
+                WireApi::Messages => ApiWireApi::Messages,
             },
             headers,
             retry,
@@ -255,6 +259,17 @@ impl ModelProviderInfo {
     }
 }
 
+/// A provider that requests fail over to when the configured provider is
+/// unavailable, resolved from a `fallback` entry in `config.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderFallback {
+    /// Key into the `model_providers` map.
+    pub provider_id: String,
+    pub provider: ModelProviderInfo,
+    /// Model to request from this provider; `None` keeps the current model.
+    pub model: Option<String>,
+}
+
 pub const DEFAULT_LMSTUDIO_PORT: u16 = 1234;
 pub const DEFAULT_OLLAMA_PORT: u16 = 11434;
 
@@ -1,6 +1,5 @@
 use chrono::Utc;
 use codex_api::ModelsClient;
-use codex_api::ReqwestTransport;
 use codex_app_server_protocol::AuthMode;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelPreset;
@@ -21,7 +20,8 @@ use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::config::types::HttpCassette;
+use crate::default_client::build_http_transport;
 use crate::error::CodexErr;
 use crate::error::Result as CoreResult;
 use crate::features::Feature;
@@ -90,13 +90,19 @@ impl ModelsManager {
         if self.try_load_cache().await {
             return Ok(());
         }
-        self.refresh_available_models_no_cache(config.features.enabled(Feature::RemoteModels))
-            .await
+        self.refresh_available_models_no_cache(
+            config.features.enabled(Feature::RemoteModels),
+            config.http_cassette.as_ref(),
+        )
+        .await
     }
 
+    /// Fetch the latest remote models through the same (possibly recorded or
+    /// replayed) transport as model requests.
     pub(crate) async fn refresh_available_models_no_cache(
         &self,
         remote_models_feature: bool,
+        http_cassette: Option<&HttpCassette>,
     ) -> CoreResult<()> {
         if !remote_models_feature || self.auth_manager.get_auth_mode() == Some(AuthMode::ApiKey) {
             return Ok(());
@@ -104,7 +110,7 @@ impl ModelsManager {
         let auth = self.auth_manager.auth().await;
         let api_provider = self.provider.to_api_provider(Some(AuthMode::ChatGPT))?;
         let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = build_http_transport(http_cassette)?;
         let client = ModelsClient::new(transport, api_provider, api_auth);
 
         let client_version = format_client_version_to_whole();
@@ -172,13 +178,18 @@ impl ModelsManager {
         }
         OPENAI_DEFAULT_API_MODEL.to_string()
     }
-    pub async fn refresh_if_new_etag(&self, etag: String, remote_models_feature: bool) {
+    pub async fn refresh_if_new_etag(
+        &self,
+        etag: String,
+        remote_models_feature: bool,
+        http_cassette: Option<&HttpCassette>,
+    ) {
         let current_etag = self.get_etag().await;
         if current_etag.clone().is_some() && current_etag.as_deref() == Some(etag.as_str()) {
             return;
         }
         if let Err(err) = self
-            .refresh_available_models_no_cache(remote_models_feature)
+            .refresh_available_models_no_cache(remote_models_feature, http_cassette)
             .await
         {
             error!("failed to refresh available models: {err}");
@@ -0,0 +1,397 @@
+//! Egress proxy that lets sandboxed commands reach an allowlist of hosts while
+//! the sandbox otherwise blocks network access.
+//!
+//! Sandboxed commands are pointed at the proxy through the usual
+//! `HTTP_PROXY`/`HTTPS_PROXY` variables, and the platform sandbox only permits
+//! outbound TCP connections to the proxy's loopback port. The proxy handles
+//! `CONNECT host:port` tunnels (used for HTTPS) and absolute-form plain HTTP
+//! requests, and refuses every host that is not on the allowlist, reporting it
+//! to the session as an [`EventMsg::NetworkAccessDenied`] event.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_channel::Sender;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio_util::task::AbortOnDropHandle;
+use tracing::debug;
+use tracing::info;
+use url::Url;
+
+use crate::codex::INITIAL_SUBMIT_ID;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::NetworkAccessDeniedEvent;
+
+/// Largest request head (request line plus headers) the proxy will buffer.
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+const NO_PROXY_ENV_VARS: [&str; 2] = ["NO_PROXY", "no_proxy"];
+const NO_PROXY_HOSTS: &str = "localhost,127.0.0.1,::1";
+
+/// Hosts that the proxy lets through. Entries match a host exactly
+/// (case-insensitively); an entry of the form `*.example.com` matches any
+/// subdomain of `example.com` but not `example.com` itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DomainAllowlist {
+    domains: Vec<String>,
+}
+
+impl DomainAllowlist {
+    pub(crate) fn new(domains: &[String]) -> Self {
+        Self {
+            domains: domains
+                .iter()
+                .map(|domain| normalize_host(domain))
+                .filter(|domain| !domain.is_empty())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    pub(crate) fn allows(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+        self.domains
+            .iter()
+            .any(|domain| match domain.strip_prefix("*.") {
+                Some(parent) => host
+                    .strip_suffix(parent)
+                    .is_some_and(|subdomain| subdomain.len() > 1 && subdomain.ends_with('.')),
+                None => *domain == host,
+            })
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// A running egress proxy bound to a loopback port. The proxy stops when this
+/// value is dropped.
+pub(crate) struct NetworkProxy {
+    addr: SocketAddr,
+    _task: AbortOnDropHandle<()>,
+}
+
+impl NetworkProxy {
+    pub(crate) async fn start(
+        allowlist: DomainAllowlist,
+        tx_event: Sender<Event>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+        let allowlist = Arc::new(allowlist);
+        let task = tokio::spawn(async move {
+            loop {
+                let (stream, _) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(err) => {
+                        debug!("network proxy failed to accept connection: {err}");
+                        continue;
+                    }
+                };
+                let allowlist = Arc::clone(&allowlist);
+                let tx_event = tx_event.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = handle_connection(stream, &allowlist, &tx_event).await {
+                        debug!("network proxy connection failed: {err}");
+                    }
+                });
+            }
+        });
+        info!("network proxy listening on {addr}");
+        Ok(Self {
+            addr,
+            _task: AbortOnDropHandle::new(task),
+        })
+    }
+
+    pub(crate) fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// Point HTTP clients in `env` at the proxy.
+    pub(crate) fn apply_env(&self, env: &mut HashMap<String, String>) {
+        let proxy_url = format!("http://{}", self.addr);
+        for key in PROXY_ENV_VARS {
+            env.insert(key.to_string(), proxy_url.clone());
+        }
+        for key in NO_PROXY_ENV_VARS {
+            env.insert(key.to_string(), NO_PROXY_HOSTS.to_string());
+        }
+    }
+}
+
+/// Where a proxied request wants to go and what to send once connected.
+#[derive(Debug, PartialEq, Eq)]
+enum ProxyTarget {
+    /// `CONNECT host:port`: reply 200 and tunnel bytes in both directions.
+    Tunnel { host: String, port: u16 },
+    /// Plain HTTP: forward the rewritten request head upstream.
+    Forward {
+        host: String,
+        port: u16,
+        request_head: Vec<u8>,
+    },
+}
+
+impl ProxyTarget {
+    fn host_and_port(&self) -> (&str, u16) {
+        match self {
+            Self::Tunnel { host, port } | Self::Forward { host, port, .. } => (host, *port),
+        }
+    }
+}
+
+async fn handle_connection(
+    mut client: TcpStream,
+    allowlist: &DomainAllowlist,
+    tx_event: &Sender<Event>,
+) -> io::Result<()> {
+    let (head, body_prefix) = read_request_head(&mut client).await?;
+    let target = match parse_request_head(&head) {
+        Ok(target) => target,
+        Err(message) => {
+            return write_response(&mut client, "400 Bad Request", message).await;
+        }
+    };
+
+    let (host, port) = target.host_and_port();
+    if !allowlist.allows(host) {
+        info!("network proxy denied connection to {host}:{port}");
+        let event = Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::NetworkAccessDenied(NetworkAccessDeniedEvent {
+                host: host.to_string(),
+                port,
+            }),
+        };
+        if let Err(err) = tx_event.send(event).await {
+            debug!("failed to report denied network access: {err}");
+        }
+        let message = format!("codex sandbox: network access to {host} is not allowed\n");
+        return write_response(&mut client, "403 Forbidden", &message).await;
+    }
+
+    let mut upstream = match TcpStream::connect((host, port)).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let message = format!("codex sandbox: failed to connect to {host}:{port}: {err}\n");
+            return write_response(&mut client, "502 Bad Gateway", &message).await;
+        }
+    };
+
+    match target {
+        ProxyTarget::Tunnel { .. } => {
+            client
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+        }
+        ProxyTarget::Forward { request_head, .. } => {
+            upstream.write_all(&request_head).await?;
+        }
+    }
+    upstream.write_all(&body_prefix).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Read up to the blank line that ends the request head. Returns the head
+/// (including the terminating `\r\n\r\n`) and any bytes read past it.
+async fn read_request_head(client: &mut TcpStream) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0_u8; 1024];
+    loop {
+        if let Some(end) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            let body_prefix = buf.split_off(end + 4);
+            return Ok((buf, body_prefix));
+        }
+        if buf.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed before the request head was complete",
+            ));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn parse_request_head(head: &[u8]) -> Result<ProxyTarget, &'static str> {
+    let head = std::str::from_utf8(head).map_err(|_| "request head is not valid UTF-8\n")?;
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(target), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("malformed request line\n");
+    };
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_authority(target).ok_or("CONNECT target must be host:port\n")?;
+        return Ok(ProxyTarget::Tunnel { host, port });
+    }
+
+    let url =
+        Url::parse(target).map_err(|_| "only absolute-form http:// requests are proxied\n")?;
+    if url.scheme() != "http" {
+        return Err("only absolute-form http:// requests are proxied\n");
+    }
+    let host = url.host_str().ok_or("request target has no host\n")?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+
+    let mut request_head = format!("{method} {path} {version}\r\n");
+    for header in lines.filter(|line| !line.is_empty()) {
+        let name = header.split(':').next().unwrap_or_default();
+        if name.to_ascii_lowercase().starts_with("proxy-") {
+            continue;
+        }
+        request_head.push_str(header);
+        request_head.push_str("\r\n");
+    }
+    request_head.push_str("\r\n");
+
+    Ok(ProxyTarget::Forward {
+        host: normalize_host(host),
+        port,
+        request_head: request_head.into_bytes(),
+    })
+}
+
+fn split_authority(authority: &str) -> Option<(String, u16)> {
+    let (host, port) = authority.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let host = normalize_host(host);
+    (!host.is_empty()).then_some((host, port))
+}
+
+async fn write_response(client: &mut TcpStream, status: &str, body: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn allowlist(domains: &[&str]) -> DomainAllowlist {
+        DomainAllowlist::new(&domains.iter().map(ToString::to_string).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn allowlist_matches_exact_hosts_and_wildcard_subdomains() {
+        let allowlist = allowlist(&["crates.io", "*.githubusercontent.com", "GitHub.com."]);
+        assert!(allowlist.allows("crates.io"));
+        assert!(allowlist.allows("CRATES.IO"));
+        assert!(allowlist.allows("github.com"));
+        assert!(allowlist.allows("raw.githubusercontent.com"));
+        assert!(!allowlist.allows("githubusercontent.com"));
+        assert!(!allowlist.allows("static.crates.io"));
+        assert!(!allowlist.allows("evilcrates.io"));
+        assert!(!allowlist.allows("xgithubusercontent.com"));
+    }
+
+    #[test]
+    fn parses_connect_and_absolute_form_requests() {
+        assert_eq!(
+            Ok(ProxyTarget::Tunnel {
+                host: "static.crates.io".to_string(),
+                port: 443,
+            }),
+            parse_request_head(b"CONNECT static.crates.io:443 HTTP/1.1\r\nHost: x\r\n\r\n")
+        );
+        assert_eq!(
+            Ok(ProxyTarget::Forward {
+                host: "example.com".to_string(),
+                port: 8080,
+                request_head: b"GET /a?b=c HTTP/1.1\r\nHost: example.com\r\n\r\n".to_vec(),
+            }),
+            parse_request_head(
+                b"GET http://example.com:8080/a?b=c HTTP/1.1\r\nHost: example.com\r\nProxy-Connection: keep-alive\r\n\r\n"
+            )
+        );
+        assert!(parse_request_head(b"GET /relative HTTP/1.1\r\n\r\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn tunnels_allowed_hosts_and_reports_denied_ones() -> anyhow::Result<()> {
+        let upstream = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let upstream_port = upstream.local_addr()?.port();
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = upstream.accept().await {
+                let mut buf = [0_u8; 4];
+                if stream.read_exact(&mut buf).await.is_ok() {
+                    let _ = stream.write_all(&buf).await;
+                }
+            }
+        });
+
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let proxy = NetworkProxy::start(allowlist(&["127.0.0.1"]), tx_event).await?;
+
+        let mut client = TcpStream::connect(("127.0.0.1", proxy.port())).await?;
+        client
+            .write_all(format!("CONNECT 127.0.0.1:{upstream_port} HTTP/1.1\r\n\r\nping").as_bytes())
+            .await?;
+        let mut response = vec![0_u8; "HTTP/1.1 200 Connection Established\r\n\r\nping".len()];
+        client.read_exact(&mut response).await?;
+        assert_eq!(
+            "HTTP/1.1 200 Connection Established\r\n\r\nping",
+            String::from_utf8(response)?
+        );
+
+        let mut denied = TcpStream::connect(("127.0.0.1", proxy.port())).await?;
+        denied
+            .write_all(b"CONNECT registry.npmjs.org:443 HTTP/1.1\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        denied.read_to_string(&mut response).await?;
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"));
+
+        let event = rx_event.recv().await?;
+        let EventMsg::NetworkAccessDenied(NetworkAccessDeniedEvent { host, port }) = event.msg
+        else {
+            panic!("expected NetworkAccessDenied, got {:?}", event.msg);
+        };
+        assert_eq!(("registry.npmjs.org".to_string(), 443), (host, port));
+        Ok(())
+    }
+}
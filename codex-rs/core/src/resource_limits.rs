@@ -0,0 +1,670 @@
+//! Enforcement of `[sandbox_resource_limits]`.
+//!
+//! On Linux, the memory and process limits are enforced by a cgroup v2
+//! [`CommandCgroup`] created for each command when Codex can delegate the
+//! `memory` and `pids` controllers, so they cover the command and everything
+//! it starts, and nothing else. Other limits, and these two when no cgroup is
+//! available, are applied as rlimits in the spawned process right before it
+//! execs.
+//!
+//! Commands run under `codex-linux-sandbox` join the cgroup and get their
+//! rlimits from the helper instead. PTYs for `unified_exec` cannot run code
+//! between `fork` and `exec`, so outside the Linux sandbox they are started
+//! through [`CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1`].
+//!
+//! [`exceeded_limit`] reports the CPU time and file size limits from the
+//! signal they end a command with (`SIGXCPU`, `SIGXFSZ`), and the cgroup
+//! limits from the cgroup's event counters. Without a cgroup, the memory and
+//! process limits only make allocations fail with `ENOMEM` and forks with
+//! `EAGAIN` inside the command, which reports them itself.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use tracing::debug;
+
+use crate::config::types::ResourceLimits;
+use crate::exec::SandboxType;
+use crate::sandboxing::ExecEnv;
+
+/// Running the Codex executable with this as its first argument applies the
+/// limits given by the following flags and then execs the command after `--`.
+pub const CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1: &str = "--codex-run-with-resource-limits";
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// The limit that stopped a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExceededResourceLimit {
+    Memory { max_mb: u64 },
+    CpuTime { max_seconds: u64 },
+    Processes { max: u64 },
+    FileSize { max_mb: u64 },
+}
+
+impl fmt::Display for ExceededResourceLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Memory { max_mb } => write!(f, "memory limit of {max_mb} MB"),
+            Self::CpuTime { max_seconds } => write!(f, "CPU time limit of {max_seconds} s"),
+            Self::Processes { max } => write!(f, "limit of {max} processes"),
+            Self::FileSize { max_mb } => write!(f, "file size limit of {max_mb} MB"),
+        }
+    }
+}
+
+const MAX_MEMORY_MB_FLAG: &str = "--max-memory-mb";
+const MAX_CPU_SECONDS_FLAG: &str = "--max-cpu-seconds";
+const MAX_PROCESSES_FLAG: &str = "--max-processes";
+const MAX_FILE_SIZE_MB_FLAG: &str = "--max-file-size-mb";
+const CGROUP_FLAG: &str = "--cgroup";
+
+/// Arguments that make `codex-linux-sandbox` apply `limits` and join
+/// `cgroup`. The [`CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1`] helper takes the
+/// same flags.
+pub(crate) fn linux_sandbox_args(limits: &ResourceLimits, cgroup: Option<&Path>) -> Vec<String> {
+    let ResourceLimits {
+        max_memory_mb,
+        max_cpu_seconds,
+        max_processes,
+        max_file_size_mb,
+    } = limits;
+    let mut args: Vec<String> = [
+        (MAX_MEMORY_MB_FLAG, max_memory_mb),
+        (MAX_CPU_SECONDS_FLAG, max_cpu_seconds),
+        (MAX_PROCESSES_FLAG, max_processes),
+        (MAX_FILE_SIZE_MB_FLAG, max_file_size_mb),
+    ]
+    .into_iter()
+    .filter_map(|(flag, value)| value.map(|value| [flag.to_string(), value.to_string()]))
+    .flatten()
+    .collect();
+    if let Some(cgroup) = cgroup {
+        args.push(CGROUP_FLAG.to_string());
+        args.push(cgroup.to_string_lossy().to_string());
+    }
+    args
+}
+
+/// Makes a PTY command apply the resource limits of `env` before it starts,
+/// by running it through `codex_exe` with
+/// [`CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1`].
+///
+/// Leaves commands run under `codex-linux-sandbox` alone, since the helper
+/// applies the limits and joins the cgroup itself.
+pub(crate) fn wrap_pty_command_with_limits(env: &mut ExecEnv, codex_exe: &Path) {
+    if cfg!(not(unix))
+        || (env.resource_limits.is_empty() && env.cgroup.is_none())
+        || env.sandbox == SandboxType::LinuxSeccomp
+    {
+        return;
+    }
+    let mut command = vec![
+        codex_exe.to_string_lossy().to_string(),
+        CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1.to_string(),
+    ];
+    command.extend(linux_sandbox_args(
+        &env.resource_limits,
+        env.cgroup.as_deref().map(CommandCgroup::path),
+    ));
+    command.push("--".to_string());
+    command.append(&mut env.command);
+    env.command = command;
+    env.arg0 = None;
+}
+
+/// What the arguments that follow [`CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1`] ask
+/// for.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ResourceLimitArgs {
+    pub limits: ResourceLimits,
+    /// Directory of the [`CommandCgroup`] to join.
+    pub cgroup: Option<PathBuf>,
+    pub command: Vec<OsString>,
+}
+
+/// Parses the arguments that follow [`CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1`].
+pub fn parse_resource_limit_args(
+    args: impl IntoIterator<Item = OsString>,
+) -> std::io::Result<ResourceLimitArgs> {
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+    let mut limits = ResourceLimits::default();
+    let mut cgroup = None;
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        if flag == "--" {
+            let command: Vec<OsString> = args.collect();
+            if command.is_empty() {
+                return Err(invalid("no command given after `--`".to_string()));
+            }
+            return Ok(ResourceLimitArgs {
+                limits,
+                cgroup,
+                command,
+            });
+        }
+        let flag = flag.to_string_lossy().to_string();
+        if flag == CGROUP_FLAG {
+            let dir = args
+                .next()
+                .ok_or_else(|| invalid(format!("{flag} requires a directory")))?;
+            cgroup = Some(PathBuf::from(dir));
+            continue;
+        }
+        let value = args
+            .next()
+            .and_then(|value| value.to_str().and_then(|value| value.parse::<u64>().ok()))
+            .ok_or_else(|| invalid(format!("{flag} requires a number")))?;
+        let field = match flag.as_str() {
+            MAX_MEMORY_MB_FLAG => &mut limits.max_memory_mb,
+            MAX_CPU_SECONDS_FLAG => &mut limits.max_cpu_seconds,
+            MAX_PROCESSES_FLAG => &mut limits.max_processes,
+            MAX_FILE_SIZE_MB_FLAG => &mut limits.max_file_size_mb,
+            _ => return Err(invalid(format!("unknown resource limit flag {flag}"))),
+        };
+        *field = Some(value);
+    }
+    Err(invalid("missing `--` before the command".to_string()))
+}
+
+/// Joins the cgroup and applies the limits given in `args`, then execs the
+/// command that follows them. Only returns if that fails.
+#[cfg(unix)]
+pub fn exec_with_resource_limits(args: impl IntoIterator<Item = OsString>) -> std::io::Error {
+    use std::os::unix::process::CommandExt;
+
+    let ResourceLimitArgs {
+        limits,
+        cgroup,
+        command,
+    } = match parse_resource_limit_args(args) {
+        Ok(parsed) => parsed,
+        Err(err) => return err,
+    };
+    if let Some(cgroup) = cgroup
+        && let Err(err) = join_cgroup(&cgroup)
+    {
+        return err;
+    }
+    if let Err(err) = apply_rlimits(&limits) {
+        return err;
+    }
+    let Some((program, args)) = command.split_first() else {
+        return std::io::Error::from(std::io::ErrorKind::InvalidInput);
+    };
+    std::process::Command::new(program).args(args).exec()
+}
+
+/// Applies `limits` to the current process.
+///
+/// Only calls `setrlimit(2)`, so it is safe to use between `fork` and `exec`.
+#[cfg(unix)]
+pub fn apply_rlimits(limits: &ResourceLimits) -> std::io::Result<()> {
+    if let Some(max_mb) = limits.max_memory_mb {
+        // RLIMIT_DATA counts private writable mappings on Linux, which is
+        // close to what a process can make resident. Other platforms only
+        // enforce the address space limit.
+        #[cfg(target_os = "linux")]
+        let resource = libc::RLIMIT_DATA;
+        #[cfg(not(target_os = "linux"))]
+        let resource = libc::RLIMIT_AS;
+        set_rlimit(resource, max_mb.saturating_mul(BYTES_PER_MB), None)?;
+    }
+    if let Some(max_seconds) = limits.max_cpu_seconds {
+        // The soft limit delivers SIGXCPU, which `exceeded_limit` recognizes;
+        // the hard limit one second later kills commands that ignore it.
+        set_rlimit(
+            libc::RLIMIT_CPU,
+            max_seconds,
+            Some(max_seconds.saturating_add(1)),
+        )?;
+    }
+    if let Some(max) = limits.max_processes {
+        // Only used when no cgroup is available: RLIMIT_NPROC counts every
+        // process of the user, not just the command's.
+        set_rlimit(libc::RLIMIT_NPROC, max, None)?;
+    }
+    if let Some(max_mb) = limits.max_file_size_mb {
+        set_rlimit(
+            libc::RLIMIT_FSIZE,
+            max_mb.saturating_mul(BYTES_PER_MB),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+/// glibc declares `setrlimit` with its own enum type for the resource.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type Resource = libc::c_int;
+
+#[cfg(unix)]
+fn set_rlimit(resource: Resource, soft: u64, hard: Option<u64>) -> std::io::Result<()> {
+    let rlimit = libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard.unwrap_or(soft) as libc::rlim_t,
+    };
+    if unsafe { libc::setrlimit(resource, &rlimit) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Moves the current process into the cgroup at `dir`.
+pub fn join_cgroup(dir: &Path) -> std::io::Result<()> {
+    // Writing 0 to `cgroup.procs` moves the writing process.
+    fs::write(dir.join("cgroup.procs"), "0")
+}
+
+/// A cgroup v2 group that enforces the memory and process limits of a single
+/// command.
+///
+/// Dropping it kills whatever is still running in it and removes it.
+#[derive(Debug)]
+pub struct CommandCgroup {
+    dir: PathBuf,
+    procs: File,
+    max_memory_mb: Option<u64>,
+    max_processes: Option<u64>,
+}
+
+impl CommandCgroup {
+    /// Creates a cgroup for the memory and process limits in `limits`.
+    ///
+    /// Returns `None` when neither is set or cgroups cannot be used here, in
+    /// which case the limits are applied as rlimits.
+    pub(crate) fn create(limits: &ResourceLimits) -> Option<Self> {
+        if cfg!(not(target_os = "linux"))
+            || (limits.max_memory_mb.is_none() && limits.max_processes.is_none())
+        {
+            return None;
+        }
+        let parent = command_cgroup_parent()?;
+        match Self::create_in(parent, limits) {
+            Ok(cgroup) => Some(cgroup),
+            Err(err) => {
+                debug!("not using a cgroup for resource limits: {err}");
+                None
+            }
+        }
+    }
+
+    fn create_in(parent: &Path, limits: &ResourceLimits) -> std::io::Result<Self> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let dir = parent.join(format!(
+            "codex-command-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir(&dir)?;
+        let configure = || -> std::io::Result<File> {
+            if let Some(max_mb) = limits.max_memory_mb {
+                fs::write(
+                    dir.join("memory.max"),
+                    max_mb.saturating_mul(BYTES_PER_MB).to_string(),
+                )?;
+                // Keep the command from swapping instead of hitting the limit.
+                // `memory.swap.max` is missing when swap accounting is off.
+                let _ = fs::write(dir.join("memory.swap.max"), "0");
+            }
+            if let Some(max) = limits.max_processes {
+                fs::write(dir.join("pids.max"), max.to_string())?;
+            }
+            File::options().write(true).open(dir.join("cgroup.procs"))
+        };
+        match configure() {
+            Ok(procs) => Ok(Self {
+                dir,
+                procs,
+                max_memory_mb: limits.max_memory_mb,
+                max_processes: limits.max_processes,
+            }),
+            Err(err) => {
+                let _ = fs::remove_dir(&dir);
+                Err(err)
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Moves the current process into this cgroup.
+    ///
+    /// Only calls `write(2)` on an already open file, so it is safe to use
+    /// between `fork` and `exec`.
+    pub(crate) fn join(&self) -> std::io::Result<()> {
+        (&self.procs).write_all(b"0")
+    }
+
+    /// The limit enforced by this cgroup that the command ran into, if any.
+    fn exceeded(&self) -> Option<ExceededResourceLimit> {
+        if let Some(max_mb) = self.max_memory_mb
+            && self.event_count("memory.events", "oom_kill") > 0
+        {
+            return Some(ExceededResourceLimit::Memory { max_mb });
+        }
+        if let Some(max) = self.max_processes
+            && self.event_count("pids.events", "max") > 0
+        {
+            return Some(ExceededResourceLimit::Processes { max });
+        }
+        None
+    }
+
+    fn event_count(&self, file: &str, event: &str) -> u64 {
+        fs::read_to_string(self.dir.join(file))
+            .ok()
+            .and_then(|events| {
+                events.lines().find_map(|line| {
+                    line.strip_prefix(event)?
+                        .strip_prefix(' ')?
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl Drop for CommandCgroup {
+    fn drop(&mut self) {
+        let _ = fs::write(self.dir.join("cgroup.kill"), "1");
+        // The killed processes leave the cgroup asynchronously, and it can
+        // only be removed once it is empty.
+        for _ in 0..20 {
+            match fs::remove_dir(&self.dir) {
+                Err(err) if err.raw_os_error() == Some(libc::EBUSY) => {
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                }
+                Err(err) => {
+                    debug!("failed to remove cgroup {}: {err}", self.dir.display());
+                    return;
+                }
+                Ok(()) => return,
+            }
+        }
+        debug!("failed to remove cgroup {}: still busy", self.dir.display());
+    }
+}
+
+/// The cgroup that command cgroups are created in, set up on first use.
+fn command_cgroup_parent() -> Option<&'static Path> {
+    static PARENT: OnceLock<Option<PathBuf>> = OnceLock::new();
+    PARENT
+        .get_or_init(|| match prepare_command_cgroup_parent() {
+            Ok(parent) => Some(parent),
+            Err(err) => {
+                debug!("cgroups are not available for resource limits: {err}");
+                None
+            }
+        })
+        .as_deref()
+}
+
+/// Makes the cgroup Codex runs in hand the `memory` and `pids` controllers to
+/// child cgroups.
+fn prepare_command_cgroup_parent() -> std::io::Result<PathBuf> {
+    let unsupported =
+        |message: &str| std::io::Error::new(std::io::ErrorKind::Unsupported, message.to_string());
+    let own = fs::read_to_string("/proc/self/cgroup")?
+        .lines()
+        .find_map(|line| line.strip_prefix("0::").map(str::to_string))
+        .ok_or_else(|| unsupported("not running in a cgroup v2 hierarchy"))?;
+    let own = Path::new("/sys/fs/cgroup").join(own.trim_start_matches('/'));
+    let controllers = fs::read_to_string(own.join("cgroup.controllers"))?;
+    let controllers: Vec<&str> = controllers.split_whitespace().collect();
+    if !controllers.contains(&"memory") || !controllers.contains(&"pids") {
+        return Err(unsupported(
+            "the memory and pids controllers are not delegated",
+        ));
+    }
+    let enable = || fs::write(own.join("cgroup.subtree_control"), "+memory +pids");
+    if enable().is_err() {
+        // A non-root cgroup can only hand controllers to its children while
+        // it has no processes of its own, so move Codex and everything it has
+        // started so far into a leaf first.
+        let leaf = own.join("codex");
+        if let Err(err) = fs::create_dir(&leaf)
+            && err.kind() != std::io::ErrorKind::AlreadyExists
+        {
+            return Err(err);
+        }
+        for pid in fs::read_to_string(own.join("cgroup.procs"))?.lines() {
+            match fs::write(leaf.join("cgroup.procs"), pid) {
+                // The process exited in the meantime.
+                Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {}
+                result => result?,
+            }
+        }
+        enable()?;
+    }
+    Ok(own)
+}
+
+/// Splits `limits` into the cgroup that enforces its memory and process
+/// limits, if one can be created, and the limits left to apply as rlimits.
+pub(crate) fn prepare_resource_limits(
+    limits: ResourceLimits,
+) -> (Option<CommandCgroup>, ResourceLimits) {
+    match CommandCgroup::create(&limits) {
+        Some(cgroup) => (
+            Some(cgroup),
+            ResourceLimits {
+                max_memory_mb: None,
+                max_processes: None,
+                ..limits
+            },
+        ),
+        None => (None, limits),
+    }
+}
+
+/// Reports the limit that stopped the command, if any.
+///
+/// `limits` are the limits applied as rlimits, which are only recognized by
+/// the signal that terminated the command. Shells also report a child killed
+/// by a signal as exit code `128 + signal`, but so can any command, so that is
+/// not taken as a limit. The limits enforced by `cgroup` are recognized by
+/// its event counters.
+pub(crate) fn exceeded_limit(
+    limits: &ResourceLimits,
+    cgroup: Option<&CommandCgroup>,
+    signal: Option<i32>,
+    exit_code: i32,
+) -> Option<ExceededResourceLimit> {
+    if (limits.is_empty() && cgroup.is_none()) || (signal.is_none() && exit_code == 0) {
+        return None;
+    }
+    if let Some(limit) = cgroup.and_then(CommandCgroup::exceeded) {
+        return Some(limit);
+    }
+
+    #[cfg(unix)]
+    {
+        if let Some(max_seconds) = limits.max_cpu_seconds
+            && signal == Some(libc::SIGXCPU)
+        {
+            return Some(ExceededResourceLimit::CpuTime { max_seconds });
+        }
+        if let Some(max_mb) = limits.max_file_size_mb
+            && signal == Some(libc::SIGXFSZ)
+        {
+            return Some(ExceededResourceLimit::FileSize { max_mb });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::ExecExpiration;
+    use crate::sandboxing::SandboxPermissions;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn linux_sandbox_args_only_include_set_limits() {
+        let limits = ResourceLimits {
+            max_cpu_seconds: Some(60),
+            max_file_size_mb: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            linux_sandbox_args(&limits, Some(Path::new("/sys/fs/cgroup/codex-command-1-0"))),
+            vec![
+                "--max-cpu-seconds".to_string(),
+                "60".to_string(),
+                "--max-file-size-mb".to_string(),
+                "10".to_string(),
+                "--cgroup".to_string(),
+                "/sys/fs/cgroup/codex-command-1-0".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_limit_args_round_trip() -> std::io::Result<()> {
+        let limits = ResourceLimits {
+            max_memory_mb: Some(512),
+            max_processes: Some(4096),
+            ..Default::default()
+        };
+        let cgroup = PathBuf::from("/sys/fs/cgroup/codex-command-1-0");
+        let mut args: Vec<OsString> = linux_sandbox_args(&limits, Some(&cgroup))
+            .into_iter()
+            .map(OsString::from)
+            .collect();
+        args.extend(["--", "echo", "--max-cpu-seconds"].map(OsString::from));
+        assert_eq!(
+            parse_resource_limit_args(args)?,
+            ResourceLimitArgs {
+                limits,
+                cgroup: Some(cgroup),
+                command: vec![OsString::from("echo"), OsString::from("--max-cpu-seconds")],
+            }
+        );
+        assert!(
+            parse_resource_limit_args([OsString::from("--max-user-processes"), "1".into()])
+                .is_err()
+        );
+        assert!(parse_resource_limit_args([OsString::from("--")]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn pty_commands_are_wrapped_unless_the_linux_sandbox_applies_limits() {
+        let env = |sandbox| ExecEnv {
+            command: vec!["bash".to_string(), "-i".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            env: Default::default(),
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: None,
+            resource_limits: ResourceLimits {
+                max_cpu_seconds: Some(60),
+                ..Default::default()
+            },
+            cgroup: None,
+        };
+        let codex = Path::new("/usr/bin/codex");
+
+        let mut plain = env(SandboxType::None);
+        wrap_pty_command_with_limits(&mut plain, codex);
+        if cfg!(unix) {
+            assert_eq!(
+                plain.command,
+                vec![
+                    "/usr/bin/codex",
+                    CODEX_RUN_WITH_RESOURCE_LIMITS_ARG1,
+                    "--max-cpu-seconds",
+                    "60",
+                    "--",
+                    "bash",
+                    "-i",
+                ]
+            );
+        }
+
+        let mut sandboxed = env(SandboxType::LinuxSeccomp);
+        wrap_pty_command_with_limits(&mut sandboxed, codex);
+        assert_eq!(sandboxed.command, vec!["bash", "-i"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cpu_limit_is_only_detected_from_the_signal_of_a_configured_limit() {
+        let limits = ResourceLimits {
+            max_cpu_seconds: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded_limit(&limits, None, Some(libc::SIGXCPU), -1),
+            Some(ExceededResourceLimit::CpuTime { max_seconds: 5 })
+        );
+        // Any command can exit with the code a shell reports for the signal.
+        assert_eq!(
+            exceeded_limit(&limits, None, None, 128 + libc::SIGXCPU),
+            None
+        );
+        assert_eq!(exceeded_limit(&limits, None, Some(libc::SIGXFSZ), -1), None);
+        assert_eq!(
+            exceeded_limit(&ResourceLimits::default(), None, Some(libc::SIGXCPU), -1),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cgroup_limits_are_detected_from_its_events() -> std::io::Result<()> {
+        // Stands in for the cgroup: only the event files are read.
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("cgroup.procs"), "")?;
+        fs::write(
+            dir.path().join("memory.events"),
+            "low 0\nhigh 0\nmax 12\noom 0\noom_kill 0\n",
+        )?;
+        fs::write(dir.path().join("pids.events"), "max 3\n")?;
+        let cgroup = CommandCgroup {
+            dir: dir.path().to_path_buf(),
+            procs: File::options()
+                .write(true)
+                .open(dir.path().join("cgroup.procs"))?,
+            max_memory_mb: Some(512),
+            max_processes: Some(64),
+        };
+        let rlimits = ResourceLimits::default();
+
+        assert_eq!(
+            exceeded_limit(&rlimits, Some(&cgroup), None, 1),
+            Some(ExceededResourceLimit::Processes { max: 64 })
+        );
+        // A command that got past the refused forks did not run into the limit.
+        assert_eq!(exceeded_limit(&rlimits, Some(&cgroup), None, 0), None);
+
+        fs::write(
+            dir.path().join("memory.events"),
+            "low 0\nhigh 0\nmax 12\noom 1\noom_kill 1\n",
+        )?;
+        assert_eq!(
+            exceeded_limit(&rlimits, Some(&cgroup), Some(libc::SIGKILL), -1),
+            Some(ExceededResourceLimit::Memory { max_mb: 512 })
+        );
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use crate::protocol::EventMsg;
 use codex_file_search as file_search;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
 
 /// Returned page of thread (thread) summaries.
@@ -53,9 +54,28 @@ pub type ConversationItem = ThreadItem;
 #[deprecated(note = "use ThreadsPage")]
 pub type ConversationsPage = ThreadsPage;
 
+/// Criteria for `RolloutRecorder::search_threads`. Unset fields match every thread.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThreadSearchFilter {
+    /// Whitespace-separated terms that must all appear, case-insensitively,
+    /// in the thread title or in one of its user or agent messages.
+    pub query: Option<String>,
+    /// Only threads started in this working directory.
+    pub cwd: Option<PathBuf>,
+    /// Only threads started on this git branch.
+    pub git_branch: Option<String>,
+    /// Only threads created at or after this Unix timestamp (in seconds).
+    pub created_after: Option<i64>,
+    /// Only threads created before this Unix timestamp (in seconds).
+    pub created_before: Option<i64>,
+    /// Only threads carrying every one of these tags.
+    pub tags: Vec<String>,
+}
+
 #[derive(Default)]
 struct HeadTailSummary {
     head: Vec<serde_json::Value>,
+    meta: Option<SessionMetaLine>,
     saw_session_meta: bool,
     saw_user_event: bool,
     source: Option<SessionSource>,
@@ -116,6 +136,50 @@ pub(crate) async fn get_threads(
     allowed_sources: &[SessionSource],
     model_providers: Option<&[String]>,
     default_provider: &str,
+) -> io::Result<ThreadsPage> {
+    get_matching_threads(
+        codex_home,
+        page_size,
+        cursor,
+        allowed_sources,
+        model_providers,
+        default_provider,
+        None,
+    )
+    .await
+}
+
+/// Like [`get_threads`], but only returns threads matching `search`. Message
+/// text is only read for threads that pass the cheaper metadata filters.
+pub(crate) async fn search_threads(
+    codex_home: &Path,
+    page_size: usize,
+    cursor: Option<&Cursor>,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    default_provider: &str,
+    search: &ThreadSearchFilter,
+) -> io::Result<ThreadsPage> {
+    get_matching_threads(
+        codex_home,
+        page_size,
+        cursor,
+        allowed_sources,
+        model_providers,
+        default_provider,
+        Some(search),
+    )
+    .await
+}
+
+async fn get_matching_threads(
+    codex_home: &Path,
+    page_size: usize,
+    cursor: Option<&Cursor>,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    default_provider: &str,
+    search: Option<&ThreadSearchFilter>,
 ) -> io::Result<ThreadsPage> {
     let mut root = codex_home.to_path_buf();
     root.push(SESSIONS_SUBDIR);
@@ -140,6 +204,7 @@ pub(crate) async fn get_threads(
         anchor,
         allowed_sources,
         provider_matcher.as_ref(),
+        search,
     )
     .await?;
     Ok(result)
@@ -155,6 +220,7 @@ async fn traverse_directories_for_paths(
     anchor: Option<Cursor>,
     allowed_sources: &[SessionSource],
     provider_matcher: Option<&ProviderMatcher<'_>>,
+    search: Option<&ThreadSearchFilter>,
 ) -> io::Result<ThreadsPage> {
     let mut items: Vec<ThreadItem> = Vec::with_capacity(page_size);
     let mut scanned_files = 0usize;
@@ -164,6 +230,13 @@ async fn traverse_directories_for_paths(
         None => (OffsetDateTime::UNIX_EPOCH, Uuid::nil()),
     };
     let mut more_matches_available = false;
+    // Filenames carry local time, so allow a day of slack before concluding
+    // that every remaining (older) file was created too early.
+    let scan_floor = search
+        .and_then(|search| search.created_after)
+        .and_then(|after| OffsetDateTime::from_unix_timestamp(after).ok())
+        .map(|after| after - time::Duration::DAY);
+    let query_terms = search.map(ThreadSearchFilter::query_terms);
 
     let year_dirs = collect_dirs_desc(&root, |s| s.parse::<u16>().ok()).await?;
 
@@ -193,6 +266,9 @@ async fn traverse_directories_for_paths(
                 // Stable ordering within the same second: (timestamp desc, uuid desc)
                 day_files.sort_by_key(|(ts, sid, _name_str, _path)| (Reverse(*ts), Reverse(*sid)));
                 for (ts, sid, _name_str, path) in day_files.into_iter() {
+                    if scan_floor.is_some_and(|floor| ts < floor) {
+                        break 'outer;
+                    }
                     scanned_files += 1;
                     if scanned_files >= MAX_SCAN_FILES && items.len() >= page_size {
                         more_matches_available = true;
@@ -225,6 +301,25 @@ async fn traverse_directories_for_paths(
                     {
                         continue;
                     }
+                    if let Some(search) = search
+                        && !search.matches_meta(&summary, ts)
+                    {
+                        continue;
+                    }
+                    if let Some(terms) = query_terms.as_deref()
+                        && !terms.is_empty()
+                    {
+                        let title = summary
+                            .meta
+                            .as_ref()
+                            .and_then(|meta_line| meta_line.meta.title.as_deref());
+                        if !rollout_contains_terms(&path, title, terms)
+                            .await
+                            .unwrap_or(false)
+                        {
+                            continue;
+                        }
+                    }
                     // Apply filters: must have session meta and at least one user message event
                     if summary.saw_session_meta && summary.saw_user_event {
                         let HeadTailSummary {
@@ -383,6 +478,97 @@ impl<'a> ProviderMatcher<'a> {
     }
 }
 
+impl ThreadSearchFilter {
+    fn query_terms(&self) -> Vec<String> {
+        self.query
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Applies the filters that only need the session meta line. `file_ts` is
+    /// the creation time encoded in the rollout filename, used when the meta
+    /// line carries no usable timestamp.
+    fn matches_meta(&self, summary: &HeadTailSummary, file_ts: OffsetDateTime) -> bool {
+        let Some(meta_line) = summary.meta.as_ref() else {
+            return false;
+        };
+        if let Some(cwd) = &self.cwd
+            && &meta_line.meta.cwd != cwd
+        {
+            return false;
+        }
+        if let Some(branch) = &self.git_branch
+            && meta_line.git.as_ref().and_then(|git| git.branch.as_ref()) != Some(branch)
+        {
+            return false;
+        }
+        if self.created_after.is_some() || self.created_before.is_some() {
+            let created_at = summary
+                .created_at
+                .as_deref()
+                .and_then(|ts| OffsetDateTime::parse(ts, &Rfc3339).ok())
+                .unwrap_or(file_ts)
+                .unix_timestamp();
+            if self.created_after.is_some_and(|after| created_at < after)
+                || self
+                    .created_before
+                    .is_some_and(|before| created_at >= before)
+            {
+                return false;
+            }
+        }
+        self.tags
+            .iter()
+            .all(|tag| meta_line.meta.tags.contains(tag))
+    }
+}
+
+/// Returns whether every (lowercase) term appears in `title` or in a user or
+/// agent message recorded in the rollout at `path`.
+async fn rollout_contains_terms(
+    path: &Path,
+    title: Option<&str>,
+    terms: &[String],
+) -> io::Result<bool> {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut remaining: Vec<&str> = terms.iter().map(String::as_str).collect();
+    let mut all_seen = |text: &str| {
+        let text = text.to_lowercase();
+        remaining.retain(|term| !text.contains(term));
+        remaining.is_empty()
+    };
+    if let Some(title) = title
+        && all_seen(title)
+    {
+        return Ok(true);
+    }
+
+    let file = tokio::fs::File::open(path).await?;
+    let mut lines = tokio::io::BufReader::new(file).lines();
+    while let Some(line) = lines.next_line().await? {
+        // Cheap pre-check so most lines are never deserialized.
+        if !line.contains("\"user_message\"") && !line.contains("\"agent_message\"") {
+            continue;
+        }
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(&line) else {
+            continue;
+        };
+        let message = match rollout_line.item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => event.message,
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => event.message,
+            _ => continue,
+        };
+        if all_seen(&message) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTailSummary> {
     use tokio::io::AsyncBufReadExt;
 
@@ -404,6 +590,9 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
 
         match rollout_line.item {
             RolloutItem::SessionMeta(session_meta_line) => {
+                if summary.meta.is_none() {
+                    summary.meta = Some(session_meta_line.clone());
+                }
                 summary.source = Some(session_meta_line.meta.source.clone());
                 summary.model_provider = session_meta_line.meta.model_provider.clone();
                 summary.created_at = summary
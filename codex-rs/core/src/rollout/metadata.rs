@@ -0,0 +1,152 @@
+//! Editing of the user-visible thread metadata (title and tags) stored in the
+//! leading `SessionMeta` line of a rollout file.
+
+use std::io;
+use std::path::Path;
+
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionMeta;
+use codex_protocol::protocol::SessionMetaLine;
+
+/// Changes to apply to a rollout's `SessionMeta`. Fields left as `None` are
+/// kept as they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionMetaUpdate {
+    /// New title. An empty (or whitespace-only) title clears it.
+    pub title: Option<String>,
+    /// Replacement set of tags.
+    pub tags: Option<Vec<String>>,
+}
+
+impl SessionMetaUpdate {
+    fn apply(self, meta: &mut SessionMeta) {
+        if let Some(title) = self.title {
+            let title = title.trim();
+            meta.title = (!title.is_empty()).then(|| title.to_string());
+        }
+        if let Some(tags) = self.tags {
+            meta.tags = normalize_tags(tags);
+        }
+    }
+}
+
+/// Trims tags, drops empty ones and removes duplicates while keeping the
+/// order in which they were first given.
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// Rewrites the first line of the rollout at `path` with `update` applied and
+/// returns the updated meta line.
+///
+/// The file is replaced via rename, so a writer holding the rollout open must
+/// reopen it afterwards. For threads that are currently loaded go through
+/// [`crate::rollout::RolloutRecorder::update_session_meta`] instead.
+pub async fn update_session_meta(
+    path: &Path,
+    update: SessionMetaUpdate,
+) -> io::Result<SessionMetaLine> {
+    let text = tokio::fs::read_to_string(path).await?;
+    let (first, rest) = text.split_once('\n').unwrap_or((text.as_str(), ""));
+    let mut line: RolloutLine = serde_json::from_str(first)?;
+    let RolloutItem::SessionMeta(meta_line) = &mut line.item else {
+        return Err(io::Error::other(format!(
+            "rollout {} does not start with session metadata",
+            path.display()
+        )));
+    };
+    update.apply(&mut meta_line.meta);
+    let updated = meta_line.clone();
+
+    let mut contents = serde_json::to_string(&line)?;
+    contents.push('\n');
+    contents.push_str(rest);
+    let tmp_path = path.with_extension("jsonl.tmp");
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn normalize_tags_trims_and_dedupes() {
+        let tags = vec![
+            " infra ".to_string(),
+            "".to_string(),
+            "bug".to_string(),
+            "infra".to_string(),
+        ];
+        assert_eq!(
+            normalize_tags(tags),
+            vec!["infra".to_string(), "bug".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn update_rewrites_only_the_meta_line() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("rollout.jsonl");
+        let meta_line = RolloutLine {
+            timestamp: "2025-01-01T00:00:00.000Z".to_string(),
+            item: RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id: ThreadId::new(),
+                    title: Some("old".to_string()),
+                    ..SessionMeta::default()
+                },
+                git: None,
+            }),
+        };
+        let body = "{\"timestamp\":\"2025-01-01T00:00:01.000Z\",\"type\":\"event_msg\"}\n";
+        std::fs::write(
+            &path,
+            format!("{}\n{body}", serde_json::to_string(&meta_line)?),
+        )?;
+
+        let updated = update_session_meta(
+            &path,
+            SessionMetaUpdate {
+                title: None,
+                tags: Some(vec!["a".to_string(), " a".to_string()]),
+            },
+        )
+        .await?;
+        assert_eq!(updated.meta.title.as_deref(), Some("old"));
+        assert_eq!(updated.meta.tags, vec!["a".to_string()]);
+
+        let updated = update_session_meta(
+            &path,
+            SessionMetaUpdate {
+                title: Some(" ".to_string()),
+                tags: None,
+            },
+        )
+        .await?;
+        assert_eq!(updated.meta.title, None);
+        assert_eq!(updated.meta.tags, vec!["a".to_string()]);
+
+        let text = std::fs::read_to_string(&path)?;
+        let (first, rest) = text.split_once('\n').expect("meta line");
+        assert_eq!(rest, body);
+        let line: RolloutLine = serde_json::from_str(first)?;
+        let RolloutItem::SessionMeta(line) = line.item else {
+            panic!("expected session meta");
+        };
+        assert_eq!(line.meta.tags, vec!["a".to_string()]);
+        Ok(())
+    }
+}
@@ -8,7 +8,9 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
 pub(crate) mod error;
+pub mod export;
 pub mod list;
+pub mod metadata;
 pub(crate) mod policy;
 pub mod recorder;
 pub(crate) mod truncation;
@@ -18,6 +20,8 @@ pub(crate) use error::map_session_init_error;
 pub use list::find_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use list::find_thread_path_by_id_str as find_conversation_path_by_id_str;
+pub use metadata::SessionMetaUpdate;
+pub use metadata::update_session_meta;
 pub use recorder::RolloutRecorder;
 pub use recorder::RolloutRecorderParams;
 
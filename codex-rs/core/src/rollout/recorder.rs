@@ -20,8 +20,12 @@ use tracing::warn;
 
 use super::SESSIONS_SUBDIR;
 use super::list::Cursor;
+use super::list::ThreadSearchFilter;
 use super::list::ThreadsPage;
 use super::list::get_threads;
+use super::list::search_threads;
+use super::metadata::SessionMetaUpdate;
+use super::metadata::update_session_meta;
 use super::policy::is_persisted_response_item;
 use crate::config::Config;
 use crate::default_client::originator;
@@ -67,6 +71,11 @@ enum RolloutCmd {
     Flush {
         ack: oneshot::Sender<()>,
     },
+    /// Rewrite the leading `SessionMeta` line and keep appending afterwards.
+    UpdateSessionMeta {
+        update: SessionMetaUpdate,
+        ack: oneshot::Sender<std::io::Result<SessionMetaLine>>,
+    },
     Shutdown {
         ack: oneshot::Sender<()>,
     },
@@ -111,6 +120,28 @@ impl RolloutRecorder {
         .await
     }
 
+    /// List threads under the provided Codex home directory that match `search`.
+    pub async fn search_threads(
+        codex_home: &Path,
+        page_size: usize,
+        cursor: Option<&Cursor>,
+        allowed_sources: &[SessionSource],
+        model_providers: Option<&[String]>,
+        default_provider: &str,
+        search: &ThreadSearchFilter,
+    ) -> std::io::Result<ThreadsPage> {
+        search_threads(
+            codex_home,
+            page_size,
+            cursor,
+            allowed_sources,
+            model_providers,
+            default_provider,
+            search,
+        )
+        .await
+    }
+
     /// Attempt to create a new [`RolloutRecorder`]. If the sessions directory
     /// cannot be created or the rollout file cannot be opened we return the
     /// error so the caller can decide whether to disable persistence.
@@ -148,6 +179,8 @@ impl RolloutRecorder {
                         instructions,
                         source,
                         model_provider: Some(config.model_provider_id.clone()),
+                        title: None,
+                        tags: Vec::new(),
                     }),
                 )
             }
@@ -172,7 +205,7 @@ impl RolloutRecorder {
         // Spawn a Tokio task that owns the file handle and performs async
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
-        tokio::task::spawn(rollout_writer(file, rx, meta, cwd));
+        tokio::task::spawn(rollout_writer(file, rx, meta, cwd, rollout_path.clone()));
 
         Ok(Self { tx, rollout_path })
     }
@@ -207,6 +240,21 @@ impl RolloutRecorder {
             .map_err(|e| IoError::other(format!("failed waiting for rollout flush: {e}")))
     }
 
+    /// Apply `update` to the `SessionMeta` line of this rollout. Queued items
+    /// are written before the rewrite so nothing is lost.
+    pub async fn update_session_meta(
+        &self,
+        update: SessionMetaUpdate,
+    ) -> std::io::Result<SessionMetaLine> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .send(RolloutCmd::UpdateSessionMeta { update, ack: tx })
+            .await
+            .map_err(|e| IoError::other(format!("failed to queue session meta update: {e}")))?;
+        rx.await
+            .map_err(|e| IoError::other(format!("failed waiting for session meta update: {e}")))?
+    }
+
     pub async fn get_rollout_history(path: &Path) -> std::io::Result<InitialHistory> {
         info!("Resuming rollout from {path:?}");
         let text = tokio::fs::read_to_string(path).await?;
@@ -348,6 +396,7 @@ async fn rollout_writer(
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
     cwd: std::path::PathBuf,
+    rollout_path: PathBuf,
 ) -> std::io::Result<()> {
     let mut writer = JsonlWriter { file };
 
@@ -383,6 +432,9 @@ async fn rollout_writer(
                 }
                 let _ = ack.send(());
             }
+            RolloutCmd::UpdateSessionMeta { update, ack } => {
+                let _ = ack.send(writer.update_session_meta(&rollout_path, update).await);
+            }
             RolloutCmd::Shutdown { ack } => {
                 let _ = ack.send(());
             }
@@ -411,6 +463,22 @@ impl JsonlWriter {
         };
         self.write_line(&line).await
     }
+    /// Rewrites the meta line of the rollout at `path` and reopens it, since
+    /// the rewrite replaces the file this writer was appending to.
+    async fn update_session_meta(
+        &mut self,
+        path: &Path,
+        update: SessionMetaUpdate,
+    ) -> std::io::Result<SessionMetaLine> {
+        self.file.flush().await?;
+        let updated = update_session_meta(path, update).await?;
+        self.file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .await?;
+        Ok(updated)
+    }
+
     async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
         let mut json = serde_json::to_string(item)?;
         json.push('\n');
@@ -13,10 +13,14 @@ use time::macros::format_description;
 use uuid::Uuid;
 
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
+use crate::rollout::SessionMetaUpdate;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::ThreadItem;
+use crate::rollout::list::ThreadSearchFilter;
 use crate::rollout::list::ThreadsPage;
 use crate::rollout::list::get_threads;
+use crate::rollout::list::search_threads;
+use crate::rollout::update_session_meta;
 use anyhow::Result;
 use codex_protocol::ThreadId;
 use codex_protocol::models::ContentItem;
@@ -592,6 +596,8 @@ async fn test_updated_at_uses_file_mtime() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                title: None,
+                tags: Vec::new(),
             },
             git: None,
         }),
@@ -916,3 +922,115 @@ async fn test_model_provider_filter_selects_only_matching_sessions() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_search_filters_by_text_tags_and_dates() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+
+    let newest = Uuid::from_u128(1);
+    let middle = Uuid::from_u128(2);
+    let oldest = Uuid::from_u128(3);
+    write_session_file(home, "2025-08-03T10-00-00", newest, 0, None)?;
+    write_session_file(home, "2025-08-02T10-00-00", middle, 0, None)?;
+    write_session_file(home, "2025-08-01T10-00-00", oldest, 0, None)?;
+
+    let newest_path = home.join(format!(
+        "sessions/2025/08/03/rollout-2025-08-03T10-00-00-{newest}.jsonl"
+    ));
+    let agent_event = serde_json::json!({
+        "timestamp": "2025-08-03T10-00-01",
+        "type": "event_msg",
+        "payload": {
+            "type": "agent_message",
+            "message": "Fixed the flaky WebSocket test",
+        }
+    });
+    let mut file = fs::OpenOptions::new().append(true).open(&newest_path)?;
+    writeln!(file, "{agent_event}")?;
+
+    let middle_path = home.join(format!(
+        "sessions/2025/08/02/rollout-2025-08-02T10-00-00-{middle}.jsonl"
+    ));
+    update_session_meta(
+        &middle_path,
+        SessionMetaUpdate {
+            title: Some("Release checklist".to_string()),
+            tags: Some(vec!["ops".to_string()]),
+        },
+    )
+    .await?;
+
+    let search = |filter: ThreadSearchFilter| async move {
+        let page = search_threads(
+            home,
+            10,
+            None,
+            NO_SOURCE_FILTER,
+            None,
+            TEST_PROVIDER,
+            &filter,
+        )
+        .await?;
+        let ids: Vec<String> = page
+            .items
+            .iter()
+            .filter_map(|item| item.head.first()?.get("id")?.as_str().map(str::to_string))
+            .collect();
+        anyhow::Ok(ids)
+    };
+    let ids = |uuids: &[Uuid]| -> Vec<String> { uuids.iter().map(Uuid::to_string).collect() };
+
+    assert_eq!(
+        search(ThreadSearchFilter {
+            query: Some("websocket FLAKY".to_string()),
+            ..Default::default()
+        })
+        .await?,
+        ids(&[newest])
+    );
+    assert_eq!(
+        search(ThreadSearchFilter {
+            query: Some("release".to_string()),
+            ..Default::default()
+        })
+        .await?,
+        ids(&[middle])
+    );
+    assert_eq!(
+        search(ThreadSearchFilter {
+            tags: vec!["ops".to_string()],
+            ..Default::default()
+        })
+        .await?,
+        ids(&[middle])
+    );
+    let august_second = 1_754_092_800;
+    assert_eq!(
+        search(ThreadSearchFilter {
+            created_after: Some(august_second),
+            ..Default::default()
+        })
+        .await?,
+        ids(&[newest, middle])
+    );
+    assert_eq!(
+        search(ThreadSearchFilter {
+            query: Some("hello".to_string()),
+            created_before: Some(august_second),
+            ..Default::default()
+        })
+        .await?,
+        ids(&[oldest])
+    );
+    assert!(
+        search(ThreadSearchFilter {
+            git_branch: Some("main".to_string()),
+            ..Default::default()
+        })
+        .await?
+        .is_empty()
+    );
+
+    Ok(())
+}
@@ -6,13 +6,19 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+use crate::config::types::LinuxSandboxBackend;
+use crate::config::types::ResourceLimits;
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
+use crate::landlock::LinuxSandboxOptions;
 use crate::landlock::create_linux_sandbox_command_args;
+use crate::network_proxy::NetworkProxy;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::CommandCgroup;
+use crate::resource_limits::prepare_resource_limits;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
@@ -22,9 +28,11 @@ use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::tools::sandboxing::SandboxablePreference;
 pub use codex_protocol::models::SandboxPermissions;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct CommandSpec {
@@ -47,6 +55,11 @@ pub struct ExecEnv {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    /// Limits applied as rlimits.
+    pub resource_limits: ResourceLimits,
+    /// cgroup that enforces the remaining limits. The command must join it
+    /// before it execs, and it must be kept alive until the command is done.
+    pub cgroup: Option<Arc<CommandCgroup>>,
 }
 
 pub enum SandboxPreference {
@@ -64,6 +77,17 @@ pub(crate) enum SandboxTransformError {
     SeatbeltUnavailable,
 }
 
+/// Sandbox settings that come from the session configuration rather than the
+/// [`SandboxPolicy`].
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SandboxExtras<'a> {
+    pub network_proxy: Option<&'a NetworkProxy>,
+    pub deny_read: &'a [AbsolutePathBuf],
+    pub linux_backend: LinuxSandboxBackend,
+    pub linux_overlay_dir: Option<&'a Path>,
+    pub resource_limits: ResourceLimits,
+}
+
 #[derive(Default)]
 pub struct SandboxManager;
 
@@ -100,6 +124,7 @@ impl SandboxManager {
         sandbox: SandboxType,
         sandbox_policy_cwd: &Path,
         codex_linux_sandbox_exe: Option<&PathBuf>,
+        extras: SandboxExtras<'_>,
     ) -> Result<ExecEnv, SandboxTransformError> {
         let mut env = spec.env;
         if !policy.has_full_network_access() {
@@ -108,19 +133,35 @@ impl SandboxManager {
                 "1".to_string(),
             );
         }
+        // Only sandboxed commands are routed through the egress proxy: without a
+        // sandbox the network is not blocked in the first place.
+        let network_proxy = extras
+            .network_proxy
+            .filter(|_| sandbox != SandboxType::None && !policy.has_full_network_access());
+        if let Some(network_proxy) = network_proxy {
+            network_proxy.apply_env(&mut env);
+        }
+        let network_proxy_port = network_proxy.map(NetworkProxy::port);
 
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
         command.append(&mut spec.args);
 
+        let (cgroup, resource_limits) = prepare_resource_limits(extras.resource_limits);
+
         let (command, sandbox_env, arg0_override) = match sandbox {
             SandboxType::None => (command, HashMap::new(), None),
             #[cfg(target_os = "macos")]
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
                 seatbelt_env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-                let mut args =
-                    create_seatbelt_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_seatbelt_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    network_proxy_port,
+                    extras.deny_read,
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(MACOS_PATH_TO_SEATBELT_EXECUTABLE.to_string());
                 full_command.append(&mut args);
@@ -131,8 +172,19 @@ impl SandboxManager {
             SandboxType::LinuxSeccomp => {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
-                let mut args =
-                    create_linux_sandbox_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_linux_sandbox_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    LinuxSandboxOptions {
+                        backend: extras.linux_backend,
+                        network_proxy_port,
+                        deny_read: extras.deny_read,
+                        overlay_dir: extras.linux_overlay_dir,
+                        resource_limits,
+                        cgroup: cgroup.as_ref().map(CommandCgroup::path),
+                    },
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
                 full_command.append(&mut args);
@@ -163,6 +215,8 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limits,
+            cgroup: cgroup.map(Arc::new),
         })
     }
 
@@ -10,6 +10,7 @@ use crate::protocol::SandboxPolicy;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
+use codex_utils_absolute_path::AbsolutePathBuf;
 
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_policy.sbpl");
@@ -28,7 +29,7 @@ pub async fn spawn_command_under_seatbelt(
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
-    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd, None, &[]);
     let arg0 = None;
     env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
     spawn_child_async(
@@ -39,14 +40,22 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        None,
+        None,
     )
     .await
 }
 
+/// Builds the `sandbox-exec` arguments for `command`. When the policy blocks
+/// network access, `network_proxy_port` opens outbound connections to the
+/// Codex egress proxy listening on that loopback port. Paths in `deny_read`
+/// can be neither read nor written, even inside a writable root.
 pub(crate) fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy_port: Option<u16>,
+    deny_read: &[AbsolutePathBuf],
 ) -> Vec<String> {
     let (file_write_policy, file_write_dir_params) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -113,16 +122,40 @@ pub(crate) fn create_seatbelt_command_args(
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
-        MACOS_SEATBELT_NETWORK_POLICY
+        MACOS_SEATBELT_NETWORK_POLICY.to_string()
+    } else if let Some(port) = network_proxy_port {
+        format!("(allow network-outbound (remote ip \"localhost:{port}\"))")
     } else {
-        ""
+        String::new()
+    };
+
+    // Seatbelt gives precedence to the last matching rule, so the deny rules
+    // must follow the read and write allowances above.
+    let mut deny_read_params = Vec::new();
+    let mut deny_read_policies = Vec::new();
+    for (index, path) in deny_read.iter().enumerate() {
+        let canonical_path = path
+            .as_path()
+            .canonicalize()
+            .unwrap_or_else(|_| path.to_path_buf());
+        let param = format!("DENY_READ_{index}");
+        deny_read_policies.push(format!("(subpath (param \"{param}\"))"));
+        deny_read_params.push((param, canonical_path));
+    }
+    let deny_read_policy = if deny_read_policies.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n(deny file-read* file-write*\n{}\n)",
+            deny_read_policies.join(" ")
+        )
     };
 
     let full_policy = format!(
-        "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
+        "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}{deny_read_policy}"
     );
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [file_write_dir_params, deny_read_params, macos_dir_params()].concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -167,6 +200,7 @@ mod tests {
     use super::macos_dir_params;
     use crate::protocol::SandboxPolicy;
     use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use pretty_assertions::assert_eq;
     use std::fs;
     use std::path::Path;
@@ -217,7 +251,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd, None, &[]);
 
         // Build the expected policy text using a raw string for readability.
         // Note that the policy includes:
@@ -308,7 +342,8 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let write_hooks_file_args = create_seatbelt_command_args(shell_command_git, &policy, &cwd);
+        let write_hooks_file_args =
+            create_seatbelt_command_args(shell_command_git, &policy, &cwd, None, &[]);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_hooks_file_args)
             .current_dir(&cwd)
@@ -345,7 +380,7 @@ mod tests {
         .map(std::string::ToString::to_string)
         .collect();
         let write_allowed_file_args =
-            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd);
+            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd, None, &[]);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_allowed_file_args)
             .current_dir(&cwd)
@@ -364,6 +399,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deny_read_paths_are_unreadable() {
+        let tmp = TempDir::new().expect("tempdir");
+        let tmp_canonical = tmp.path().canonicalize().expect("canonicalize tmp");
+        let secrets = tmp_canonical.join("secrets");
+        fs::create_dir_all(&secrets).expect("create secrets");
+        fs::write(secrets.join("token"), "hunter2").expect("write token");
+        fs::write(tmp_canonical.join("public"), "hello").expect("write public");
+
+        let deny_read = vec![AbsolutePathBuf::try_from(secrets.as_path()).expect("absolute")];
+        let read_args = |path: &Path| {
+            create_seatbelt_command_args(
+                vec!["cat".to_string(), path.to_string_lossy().to_string()],
+                &SandboxPolicy::ReadOnly,
+                &tmp_canonical,
+                None,
+                &deny_read,
+            )
+        };
+
+        let args = read_args(&secrets.join("token"));
+        assert!(
+            args[1]
+                .ends_with("\n(deny file-read* file-write*\n(subpath (param \"DENY_READ_0\"))\n)")
+        );
+        assert!(args.contains(&format!("-DDENY_READ_0={}", secrets.to_string_lossy())));
+
+        let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
+            .args(&args)
+            .output()
+            .expect("execute seatbelt command");
+        assert!(
+            !output.status.success(),
+            "reading a denied path should fail"
+        );
+
+        let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
+            .args(read_args(&tmp_canonical.join("public")))
+            .output()
+            .expect("execute seatbelt command");
+        assert_eq!("hello", String::from_utf8_lossy(&output.stdout));
+    }
+
     #[test]
     fn create_seatbelt_args_for_cwd_as_git_repo() {
         // Create a temporary workspace with two writable roots: one containing
@@ -400,8 +478,12 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args =
-            create_seatbelt_command_args(shell_command.clone(), &policy, vulnerable_root.as_path());
+        let args = create_seatbelt_command_args(
+            shell_command.clone(),
+            &policy,
+            vulnerable_root.as_path(),
+            None,
+        );
 
         let tmpdir_env_var = std::env::var("TMPDIR")
             .ok()
@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::Arc;
 use tokio::process::Child;
 use tokio::process::Command;
 use tracing::trace;
 
+use crate::config::types::ResourceLimits;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::CommandCgroup;
 
 /// Experimental environment variable that will be set to some non-empty value
 /// if both of the following are true:
@@ -35,6 +38,11 @@ pub enum StdioPolicy {
 /// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
 /// we need to determine whether to set the
 /// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
+///
+/// The child joins `cgroup` and applies `resource_limits` right before exec.
+/// Pass `None` for both when the program is `codex-linux-sandbox`, which does
+/// that itself.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
@@ -43,9 +51,11 @@ pub(crate) async fn spawn_child_async(
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    #[cfg_attr(not(unix), allow(unused_variables))] resource_limits: Option<ResourceLimits>,
+    #[cfg_attr(not(unix), allow(unused_variables))] cgroup: Option<Arc<CommandCgroup>>,
 ) -> std::io::Result<Child> {
     trace!(
-        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
+        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?} {resource_limits:?} {cgroup:?}"
     );
 
     let mut cmd = Command::new(&program);
@@ -92,6 +102,13 @@ pub(crate) async fn spawn_child_async(
                     libc::raise(libc::SIGTERM);
                 }
             }
+
+            if let Some(cgroup) = &cgroup {
+                cgroup.join()?;
+            }
+            if let Some(resource_limits) = &resource_limits {
+                crate::resource_limits::apply_rlimits(resource_limits)?;
+            }
             Ok(())
         });
     }
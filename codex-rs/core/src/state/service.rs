@@ -7,6 +7,7 @@ use crate::exec_policy::ExecPolicyManager;
 use crate::hooks::HooksManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::network_proxy::NetworkProxy;
 use crate::skills::SkillsManager;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
@@ -32,4 +33,6 @@ pub(crate) struct SessionServices {
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) skills_manager: Arc<SkillsManager>,
     pub(crate) agent_control: AgentControl,
+    /// Egress proxy for `sandbox_workspace_write.allowed_domains`, if configured.
+    pub(crate) network_proxy: Option<NetworkProxy>,
 }
@@ -1,10 +1,14 @@
 //! Session-wide mutable state.
 
 use codex_protocol::models::ResponseItem;
+use codex_utils_tokenizer::Tokenizer;
+use std::collections::HashMap;
+use tokio::sync::oneshot;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
 use crate::protocol::RateLimitSnapshot;
+use crate::protocol::ReviewDecision;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::truncate::TruncationPolicy;
@@ -14,6 +18,8 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    /// Approval requests raised by sub-agents, keyed by `<agent id>:<call id>`.
+    pending_agent_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
 }
 
 impl SessionState {
@@ -24,16 +30,36 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            pending_agent_approvals: HashMap::new(),
         }
     }
 
     // History helpers
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
-    where
+    pub(crate) fn record_items<I>(
+        &mut self,
+        items: I,
+        policy: TruncationPolicy,
+        tokenizer: Option<Tokenizer>,
+    ) where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
-        self.history.record_items(items, policy);
+        self.history.record_items(items, policy, tokenizer);
+    }
+
+    pub(crate) fn insert_agent_approval(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<ReviewDecision>,
+    ) -> Option<oneshot::Sender<ReviewDecision>> {
+        self.pending_agent_approvals.insert(key, tx)
+    }
+
+    pub(crate) fn remove_agent_approval(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<ReviewDecision>> {
+        self.pending_agent_approvals.remove(key)
     }
 
     pub(crate) fn clone_history(&self) -> ContextManager {
@@ -9,6 +9,7 @@ use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::user_input::UserInput;
 use tokio::sync::oneshot;
 
 use crate::codex::TurnContext;
@@ -68,6 +69,12 @@ impl ActiveTurn {
 pub(crate) struct TurnState {
     pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
     pending_input: Vec<ResponseInputItem>,
+    /// Steers queued with `Op::SteerTurn`, in the same order as their items
+    /// in `pending_input`, so delivery can be confirmed to the client.
+    pending_steers: Vec<Vec<UserInput>>,
+    /// Set once the turn has decided to end; later steers are rejected
+    /// instead of being dropped with the turn.
+    finishing: bool,
 }
 
 impl TurnState {
@@ -89,12 +96,35 @@ impl TurnState {
     pub(crate) fn clear_pending(&mut self) {
         self.pending_approvals.clear();
         self.pending_input.clear();
+        self.pending_steers.clear();
     }
 
     pub(crate) fn push_pending_input(&mut self, input: ResponseInputItem) {
         self.pending_input.push(input);
     }
 
+    pub(crate) fn push_steer(&mut self, items: Vec<UserInput>) {
+        self.pending_input.push(items.clone().into());
+        self.pending_steers.push(items);
+    }
+
+    pub(crate) fn is_finishing(&self) -> bool {
+        self.finishing
+    }
+
+    /// Marks the turn as finishing unless steers are still waiting to be
+    /// sent to the model. Returns whether the turn may end.
+    pub(crate) fn finish_unless_steered(&mut self) -> bool {
+        if self.pending_steers.is_empty() {
+            self.finishing = true;
+        }
+        self.finishing
+    }
+
+    pub(crate) fn take_pending_steers(&mut self) -> Vec<Vec<UserInput>> {
+        std::mem::take(&mut self.pending_steers)
+    }
+
     pub(crate) fn take_pending_input(&mut self) -> Vec<ResponseInputItem> {
         if self.pending_input.is_empty() {
             Vec::with_capacity(0)
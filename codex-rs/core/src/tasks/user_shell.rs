@@ -10,6 +10,7 @@ use tracing::error;
 use uuid::Uuid;
 
 use crate::codex::TurnContext;
+use crate::config::types::ResourceLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
@@ -110,6 +111,8 @@ impl SessionTask for UserShellCommandTask {
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
             arg0: None,
+            resource_limits: ResourceLimits::default(),
+            cgroup: None,
         };
 
         let stdout_stream = Some(StdoutStream {
@@ -133,6 +136,7 @@ impl SessionTask for UserShellCommandTask {
                     aggregated_output: StreamOutput::new(aborted_message.clone()),
                     duration: Duration::ZERO,
                     timed_out: false,
+                    exceeded_limit: None,
                 };
                 let output_items = [user_shell_command_record_item(
                     &raw_command,
@@ -185,6 +189,7 @@ impl SessionTask for UserShellCommandTask {
                             formatted_output: format_exec_output_str(
                                 &output,
                                 turn_context.truncation_policy,
+                                turn_context.tokenizer,
                             ),
                         }),
                     )
@@ -209,6 +214,7 @@ impl SessionTask for UserShellCommandTask {
                     aggregated_output: StreamOutput::new(message.clone()),
                     duration: Duration::ZERO,
                     timed_out: false,
+                    exceeded_limit: None,
                 };
                 session
                     .send_event(
@@ -230,6 +236,7 @@ impl SessionTask for UserShellCommandTask {
                             formatted_output: format_exec_output_str(
                                 &exec_output,
                                 turn_context.truncation_policy,
+                                turn_context.tokenizer,
                             ),
                         }),
                     )
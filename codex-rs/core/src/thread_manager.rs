@@ -4,6 +4,7 @@ use crate::CodexAuth;
 #[cfg(any(test, feature = "test-support"))]
 use crate::ModelProviderInfo;
 use crate::agent::AgentControl;
+use crate::agent::AgentRecord;
 use crate::codex::Codex;
 use crate::codex::CodexSpawnOk;
 use crate::codex::INITIAL_SUBMIT_ID;
@@ -30,6 +31,7 @@ use std::sync::Arc;
 #[cfg(any(test, feature = "test-support"))]
 use tempfile::TempDir;
 use tokio::sync::RwLock;
+use tokio::sync::watch;
 
 /// Represents a newly created Codex thread (formerly called a conversation), including the first event
 /// (which is [`EventMsg::SessionConfigured`]).
@@ -52,6 +54,9 @@ pub struct ThreadManager {
 /// function to require an `Arc<&Self>`.
 pub(crate) struct ThreadManagerState {
     threads: Arc<RwLock<HashMap<ThreadId, Arc<CodexThread>>>>,
+    /// Lifecycle records for agents spawned through `AgentControl`, kept until the agent's thread
+    /// shuts down or is removed.
+    agent_records: RwLock<HashMap<ThreadId, watch::Sender<AgentRecord>>>,
     auth_manager: Arc<AuthManager>,
     models_manager: Arc<ModelsManager>,
     skills_manager: Arc<SkillsManager>,
@@ -67,6 +72,7 @@ impl ThreadManager {
         Self {
             state: Arc::new(ThreadManagerState {
                 threads: Arc::new(RwLock::new(HashMap::new())),
+                agent_records: RwLock::new(HashMap::new()),
                 models_manager: Arc::new(ModelsManager::new(
                     codex_home.clone(),
                     auth_manager.clone(),
@@ -103,6 +109,7 @@ impl ThreadManager {
         Self {
             state: Arc::new(ThreadManagerState {
                 threads: Arc::new(RwLock::new(HashMap::new())),
+                agent_records: RwLock::new(HashMap::new()),
                 models_manager: Arc::new(ModelsManager::with_provider(
                     codex_home.clone(),
                     auth_manager.clone(),
@@ -177,7 +184,7 @@ impl ThreadManager {
     /// as `Arc<CodexThread>`, it is possible that other references to it exist elsewhere.
     /// Returns the thread if the thread was found and removed.
     pub async fn remove_thread(&self, thread_id: &ThreadId) -> Option<Arc<CodexThread>> {
-        self.state.threads.write().await.remove(thread_id)
+        self.state.remove_thread(thread_id).await
     }
 
     /// Fork an existing thread by taking messages up to the given position (not including
@@ -216,6 +223,45 @@ impl ThreadManagerState {
             .ok_or_else(|| CodexErr::ThreadNotFound(thread_id))
     }
 
+    pub(crate) async fn remove_thread(&self, thread_id: &ThreadId) -> Option<Arc<CodexThread>> {
+        self.remove_agent_record(thread_id).await;
+        self.threads.write().await.remove(thread_id)
+    }
+
+    pub(crate) async fn register_agent_record(
+        &self,
+        thread_id: ThreadId,
+        record: watch::Sender<AgentRecord>,
+    ) {
+        self.agent_records.write().await.insert(thread_id, record);
+    }
+
+    pub(crate) async fn remove_agent_record(&self, thread_id: &ThreadId) {
+        self.agent_records.write().await.remove(thread_id);
+    }
+
+    pub(crate) async fn agent_record(
+        &self,
+        thread_id: ThreadId,
+    ) -> Option<watch::Receiver<AgentRecord>> {
+        self.agent_records
+            .read()
+            .await
+            .get(&thread_id)
+            .map(watch::Sender::subscribe)
+    }
+
+    /// Apply `modify` to the record of `thread_id`, if the agent has one.
+    pub(crate) async fn update_agent_record(
+        &self,
+        thread_id: ThreadId,
+        modify: impl FnOnce(&mut AgentRecord),
+    ) {
+        if let Some(record) = self.agent_records.read().await.get(&thread_id) {
+            record.send_modify(modify);
+        }
+    }
+
     pub(crate) async fn send_op(&self, thread_id: ThreadId, op: Op) -> CodexResult<String> {
         self.get_thread(thread_id).await?.submit(op).await
     }
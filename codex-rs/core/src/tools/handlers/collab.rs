@@ -1,7 +1,11 @@
+use crate::agent::AgentParent;
+use crate::agent::AgentRecord;
+use crate::agent::AgentStatus;
 use crate::codex::TurnContext;
 use crate::config::Config;
 use crate::error::CodexErr;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::SandboxPolicy;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -10,7 +14,12 @@ use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use async_trait::async_trait;
 use codex_protocol::ThreadId;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::TokenUsage;
 use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct CollabHandler;
 
@@ -20,6 +29,7 @@ pub(crate) const MAX_WAIT_TIMEOUT_MS: i64 = 300_000;
 #[derive(Debug, Deserialize)]
 struct SpawnAgentArgs {
     message: String,
+    agent: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,7 +40,10 @@ struct SendInputArgs {
 
 #[derive(Debug, Deserialize)]
 struct WaitArgs {
-    id: String,
+    #[serde(default)]
+    ids: Vec<String>,
+    /// Single agent id accepted from calls made against the earlier schema, which took `id`.
+    id: Option<String>,
     timeout_ms: Option<i64>,
 }
 
@@ -39,6 +52,28 @@ struct CloseAgentArgs {
     id: String,
 }
 
+/// Per-agent result reported back to the model by `wait` and `close_agent`.
+#[derive(Debug, Serialize)]
+struct AgentReport {
+    id: String,
+    status: AgentStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_agent_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_usage: Option<TokenUsage>,
+}
+
+impl AgentReport {
+    fn new(id: ThreadId, record: AgentRecord) -> Self {
+        Self {
+            id: id.to_string(),
+            status: record.status,
+            last_agent_message: record.last_agent_message,
+            token_usage: record.token_usage,
+        }
+    }
+}
+
 #[async_trait]
 impl ToolHandler for CollabHandler {
     fn kind(&self) -> ToolKind {
@@ -70,8 +105,8 @@ impl ToolHandler for CollabHandler {
         match tool_name.as_str() {
             "spawn_agent" => handle_spawn_agent(session, turn, arguments).await,
             "send_input" => handle_send_input(session, arguments).await,
-            "wait" => handle_wait(arguments).await,
-            "close_agent" => handle_close_agent(arguments).await,
+            "wait" => handle_wait(session, arguments).await,
+            "close_agent" => handle_close_agent(session, arguments).await,
             other => Err(FunctionCallError::RespondToModel(format!(
                 "unsupported collab tool {other}"
             ))),
@@ -90,11 +125,23 @@ async fn handle_spawn_agent(
             "Empty message can't be send to an agent".to_string(),
         ));
     }
-    let config = build_agent_spawn_config(turn.as_ref())?;
+    let mut config = build_agent_spawn_config(turn.as_ref())?;
+    if let Some(name) = args.agent.as_deref() {
+        apply_agent_profile(&mut config, name)?;
+    }
     let result = session
         .services
         .agent_control
-        .spawn_agent(config, args.message, true)
+        .spawn_agent(
+            config,
+            args.message,
+            true,
+            Some(AgentParent {
+                session: Arc::downgrade(&session),
+                turn: Arc::clone(&turn),
+                agent: args.agent,
+            }),
+        )
         .await
         .map_err(|err| FunctionCallError::Fatal(err.to_string()))?;
 
@@ -135,9 +182,21 @@ async fn handle_send_input(
     })
 }
 
-async fn handle_wait(arguments: String) -> Result<ToolOutput, FunctionCallError> {
+async fn handle_wait(
+    session: std::sync::Arc<crate::codex::Session>,
+    arguments: String,
+) -> Result<ToolOutput, FunctionCallError> {
     let args: WaitArgs = parse_arguments(&arguments)?;
-    let _agent_id = agent_id(&args.id)?;
+    let ids: Vec<&String> = args.id.iter().chain(&args.ids).collect();
+    if ids.is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "ids must contain at least one agent id".to_string(),
+        ));
+    }
+    let agent_ids = ids
+        .into_iter()
+        .map(|id| agent_id(id))
+        .collect::<Result<Vec<_>, _>>()?;
 
     let timeout_ms = args.timeout_ms.unwrap_or(DEFAULT_WAIT_TIMEOUT_MS);
     if timeout_ms <= 0 {
@@ -145,18 +204,56 @@ async fn handle_wait(arguments: String) -> Result<ToolOutput, FunctionCallError>
             "timeout_ms must be greater than zero".to_string(),
         ));
     }
-    let _timeout_ms = timeout_ms.min(MAX_WAIT_TIMEOUT_MS);
-    // TODO(jif): implement agent wait once lifecycle tracking is wired up.
-    Err(FunctionCallError::Fatal("wait not implemented".to_string()))
+    let timeout_ms = timeout_ms.min(MAX_WAIT_TIMEOUT_MS);
+    let records = session
+        .services
+        .agent_control
+        .wait_agents(&agent_ids, Duration::from_millis(timeout_ms as u64))
+        .await
+        .map_err(|err| FunctionCallError::Fatal(err.to_string()))?;
+
+    let timed_out = records.iter().any(|(_, record)| !record.is_final());
+    let reports = records
+        .into_iter()
+        .map(|(id, record)| AgentReport::new(id, record))
+        .collect::<Vec<_>>();
+    let content = serde_json::to_string(&reports).map_err(|err| {
+        FunctionCallError::Fatal(format!("failed to serialize agent status: {err}"))
+    })?;
+
+    Ok(ToolOutput::Function {
+        content,
+        success: Some(!timed_out),
+        content_items: None,
+    })
 }
 
-async fn handle_close_agent(arguments: String) -> Result<ToolOutput, FunctionCallError> {
+async fn handle_close_agent(
+    session: std::sync::Arc<crate::codex::Session>,
+    arguments: String,
+) -> Result<ToolOutput, FunctionCallError> {
     let args: CloseAgentArgs = parse_arguments(&arguments)?;
-    let _agent_id = agent_id(&args.id)?;
-    // TODO(jif): implement agent shutdown and return the final status.
-    Err(FunctionCallError::Fatal(
-        "close_agent not implemented".to_string(),
-    ))
+    let agent_id = agent_id(&args.id)?;
+    let record = session
+        .services
+        .agent_control
+        .close_agent(agent_id)
+        .await
+        .map_err(|err| match err {
+            CodexErr::ThreadNotFound(id) => {
+                FunctionCallError::RespondToModel(format!("agent with id {id} not found"))
+            }
+            err => FunctionCallError::Fatal(err.to_string()),
+        })?;
+    let content = serde_json::to_string(&AgentReport::new(agent_id, record)).map_err(|err| {
+        FunctionCallError::Fatal(format!("failed to serialize agent status: {err}"))
+    })?;
+
+    Ok(ToolOutput::Function {
+        content,
+        success: Some(true),
+        content_items: None,
+    })
 }
 
 fn agent_id(id: &str) -> Result<ThreadId, FunctionCallError> {
@@ -192,3 +289,180 @@ fn build_agent_spawn_config(turn: &TurnContext) -> Result<Config, FunctionCallEr
         })?;
     Ok(config)
 }
+
+/// Apply the `[agents.<name>]` profile on top of the inherited spawn config, rejecting any
+/// setting that would give the sub-agent more permissions than its parent.
+fn apply_agent_profile(config: &mut Config, name: &str) -> Result<(), FunctionCallError> {
+    let Some(profile) = config.agents.get(name).cloned() else {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "unknown agent `{name}`"
+        )));
+    };
+
+    if let Some(model) = profile.model {
+        config.model = Some(model);
+    }
+    if let Some(effort) = profile.model_reasoning_effort {
+        config.model_reasoning_effort = Some(effort);
+    }
+    if let Some(instructions) = profile.developer_instructions {
+        config.developer_instructions = Some(instructions);
+    }
+
+    if let Some(mode) = profile.sandbox_mode {
+        let Some(policy) = narrow_sandbox_policy(config.sandbox_policy.get(), mode) else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "agent `{name}` requests sandbox_mode `{mode}`, which is more permissive than this session"
+            )));
+        };
+        config.sandbox_policy.set(policy).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("sandbox_policy is invalid: {err}"))
+        })?;
+    }
+
+    if let Some(features) = profile.features {
+        let mut narrowed = config.features.clone();
+        narrowed.apply_map(&features.entries);
+        if let Some(feature) = narrowed
+            .enabled_features()
+            .into_iter()
+            .find(|feature| !config.features.enabled(*feature))
+        {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "agent `{name}` enables feature `{}`, which is disabled in this session",
+                feature.key()
+            )));
+        }
+        config.features = narrowed;
+    }
+
+    Ok(())
+}
+
+/// Resolve `mode` against the parent's sandbox, returning `None` when it would widen access.
+fn narrow_sandbox_policy(parent: &SandboxPolicy, mode: SandboxMode) -> Option<SandboxPolicy> {
+    match (mode, parent) {
+        (SandboxMode::ReadOnly, _) => Some(SandboxPolicy::new_read_only_policy()),
+        (SandboxMode::WorkspaceWrite, SandboxPolicy::ReadOnly) => None,
+        (SandboxMode::WorkspaceWrite, SandboxPolicy::WorkspaceWrite { .. }) => Some(parent.clone()),
+        (SandboxMode::WorkspaceWrite, _) => Some(SandboxPolicy::new_workspace_write_policy()),
+        (SandboxMode::DangerFullAccess, SandboxPolicy::DangerFullAccess) => Some(parent.clone()),
+        (SandboxMode::DangerFullAccess, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use crate::config::types::AgentProfile;
+    use crate::features::Feature;
+    use crate::features::FeaturesToml;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    fn config_with_agent(profile: AgentProfile) -> Config {
+        let mut config = test_config();
+        config.agents.insert("reviewer".to_string(), profile);
+        config
+    }
+
+    #[test]
+    fn wait_accepts_the_earlier_single_id_argument() {
+        let args: WaitArgs = parse_arguments(r#"{"id": "a"}"#).expect("id parses");
+        assert_eq!(args.id.as_deref(), Some("a"));
+        assert!(args.ids.is_empty());
+
+        let args: WaitArgs = parse_arguments(r#"{"ids": ["a", "b"]}"#).expect("ids parse");
+        assert_eq!(args.ids, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn agent_profile_overrides_model_and_instructions() {
+        let mut config = config_with_agent(AgentProfile {
+            model: Some("gpt-5.1-codex".to_string()),
+            developer_instructions: Some("Only review.".to_string()),
+            ..Default::default()
+        });
+
+        apply_agent_profile(&mut config, "reviewer").expect("profile applies");
+
+        assert_eq!(config.model.as_deref(), Some("gpt-5.1-codex"));
+        assert_eq!(
+            config.developer_instructions.as_deref(),
+            Some("Only review.")
+        );
+    }
+
+    #[test]
+    fn agent_profile_can_narrow_sandbox() {
+        let mut config = config_with_agent(AgentProfile {
+            sandbox_mode: Some(SandboxMode::ReadOnly),
+            ..Default::default()
+        });
+        config
+            .sandbox_policy
+            .set(SandboxPolicy::new_workspace_write_policy())
+            .expect("set sandbox policy");
+
+        apply_agent_profile(&mut config, "reviewer").expect("profile applies");
+
+        assert_eq!(
+            config.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+    }
+
+    #[test]
+    fn agent_profile_cannot_widen_sandbox() {
+        let mut config = config_with_agent(AgentProfile {
+            sandbox_mode: Some(SandboxMode::DangerFullAccess),
+            ..Default::default()
+        });
+        config
+            .sandbox_policy
+            .set(SandboxPolicy::new_workspace_write_policy())
+            .expect("set sandbox policy");
+
+        let err = apply_agent_profile(&mut config, "reviewer").expect_err("widening is rejected");
+
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(
+                "agent `reviewer` requests sandbox_mode `danger-full-access`, which is more permissive than this session"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn agent_profile_cannot_enable_disabled_feature() {
+        let mut config = config_with_agent(AgentProfile {
+            features: Some(FeaturesToml {
+                entries: BTreeMap::from([(Feature::Collab.key().to_string(), true)]),
+            }),
+            ..Default::default()
+        });
+        config.features.disable(Feature::Collab);
+
+        let err = apply_agent_profile(&mut config, "reviewer").expect_err("enabling is rejected");
+
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(format!(
+                "agent `reviewer` enables feature `{}`, which is disabled in this session",
+                Feature::Collab.key()
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_agent_profile_is_rejected() {
+        let mut config = test_config();
+        let err = apply_agent_profile(&mut config, "missing").expect_err("unknown agent");
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel("unknown agent `missing`".to_string())
+        );
+    }
+}
@@ -6,6 +6,7 @@ mod mcp;
 mod mcp_resource;
 mod plan;
 mod read_file;
+mod read_tool_output;
 mod shell;
 mod test_sync;
 mod unified_exec;
@@ -23,6 +24,7 @@ pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
+pub use read_tool_output::ReadToolOutputHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
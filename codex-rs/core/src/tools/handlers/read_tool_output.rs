@@ -0,0 +1,190 @@
+use async_trait::async_trait;
+use codex_utils_string::take_bytes_at_char_boundary;
+use regex_lite::Regex;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::spill::spill_path;
+
+pub struct ReadToolOutputHandler;
+
+const MAX_LINE_LENGTH: usize = 500;
+
+/// JSON arguments accepted by the `read_tool_output` tool handler.
+#[derive(Deserialize)]
+struct ReadToolOutputArgs {
+    /// Id named in the truncation notice.
+    id: String,
+    /// 1-indexed line number to start reading from; defaults to 1.
+    #[serde(default = "defaults::offset")]
+    offset: usize,
+    /// Maximum number of lines to return; defaults to 200.
+    #[serde(default = "defaults::limit")]
+    limit: usize,
+    /// Only return lines matching this regular expression.
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for ReadToolOutputHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_tool_output handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let ReadToolOutputArgs {
+            id,
+            offset,
+            limit,
+            pattern,
+        } = parse_arguments(&arguments)?;
+
+        if offset == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "offset must be a 1-indexed line number".to_string(),
+            ));
+        }
+
+        if limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let pattern = pattern
+            .map(|pattern| {
+                Regex::new(&pattern).map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("invalid pattern: {err}"))
+                })
+            })
+            .transpose()?;
+
+        let unknown_id =
+            || FunctionCallError::RespondToModel(format!("no saved tool output with id `{id}`"));
+        let path = spill_path(session.as_ref(), &id)
+            .await
+            .ok_or_else(unknown_id)?;
+        let bytes = tokio::fs::read(&path).await.map_err(|_| unknown_id())?;
+        let text = String::from_utf8_lossy(&bytes);
+
+        let content = read_lines(&text, offset, limit, pattern.as_ref())?;
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// Returns up to `limit` lines starting at line `offset`, keeping only lines
+/// matching `pattern` when given, and notes where to continue if more remain.
+fn read_lines(
+    text: &str,
+    offset: usize,
+    limit: usize,
+    pattern: Option<&Regex>,
+) -> Result<String, FunctionCallError> {
+    let total_lines = text.lines().count();
+    if offset > total_lines {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "offset exceeds output length ({total_lines} lines)"
+        )));
+    }
+
+    let mut collected = Vec::new();
+    let mut next_line = None;
+    for (index, line) in text.lines().enumerate().skip(offset - 1) {
+        if pattern.is_some_and(|pattern| !pattern.is_match(line)) {
+            continue;
+        }
+        if collected.len() == limit {
+            next_line = Some(index + 1);
+            break;
+        }
+        let line = take_bytes_at_char_boundary(line, MAX_LINE_LENGTH);
+        collected.push(format!("L{}: {line}", index + 1));
+    }
+
+    if collected.is_empty() {
+        return Ok(format!(
+            "No lines match from line {offset} (output has {total_lines} lines)."
+        ));
+    }
+    if let Some(next_line) = next_line {
+        collected.push(format!(
+            "[More lines available; continue with offset {next_line}.]"
+        ));
+    }
+    Ok(collected.join("\n"))
+}
+
+mod defaults {
+    pub fn offset() -> usize {
+        1
+    }
+
+    pub fn limit() -> usize {
+        200
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn numbered(count: usize) -> String {
+        (1..=count).map(|i| format!("line {i}\n")).collect()
+    }
+
+    #[test]
+    fn reads_a_window_and_points_at_the_next_line() -> anyhow::Result<()> {
+        let text = numbered(10);
+        assert_eq!(
+            read_lines(&text, 4, 2, None)?,
+            "L4: line 4\nL5: line 5\n[More lines available; continue with offset 6.]"
+        );
+        assert_eq!(read_lines(&text, 9, 5, None)?, "L9: line 9\nL10: line 10");
+        Ok(())
+    }
+
+    #[test]
+    fn filters_lines_by_pattern() -> anyhow::Result<()> {
+        let text = "ok\nerror: one\nok\nerror: two\n";
+        let pattern = Regex::new("^error")?;
+        assert_eq!(
+            read_lines(text, 1, 10, Some(&pattern))?,
+            "L2: error: one\nL4: error: two"
+        );
+        assert_eq!(
+            read_lines(text, 1, 1, Some(&pattern))?,
+            "L2: error: one\n[More lines available; continue with offset 4.]"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_offset_past_the_end() {
+        assert!(read_lines(&numbered(3), 4, 1, None).is_err());
+    }
+}
@@ -12,6 +12,8 @@ use crate::tools::handlers::apply_patch::intercept_apply_patch;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::tools::spill::spill_tool_output;
+use crate::tools::spill::with_spill_notice;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecProcessManager;
@@ -215,7 +217,13 @@ impl ToolHandler for UnifiedExecHandler {
             }
         };
 
-        let content = format_response(&response);
+        let raw_output = String::from_utf8_lossy(&response.raw_output);
+        let spill_notice = if response.output != raw_output {
+            spill_tool_output(session.as_ref(), turn.as_ref(), &call_id, &raw_output).await
+        } else {
+            None
+        };
+        let content = format_response(&response, spill_notice.as_deref());
 
         Ok(ToolOutput::Function {
             content,
@@ -237,7 +245,7 @@ fn get_command(args: &ExecCommandArgs, session_shell: Arc<Shell>) -> Vec<String>
     shell.derive_exec_args(&args.cmd, args.login)
 }
 
-fn format_response(response: &UnifiedExecResponse) -> String {
+fn format_response(response: &UnifiedExecResponse, spill_notice: Option<&str>) -> String {
     let mut sections = Vec::new();
 
     if !response.chunk_id.is_empty() {
@@ -251,6 +259,10 @@ fn format_response(response: &UnifiedExecResponse) -> String {
         sections.push(format!("Process exited with code {exit_code}"));
     }
 
+    if let Some(limit) = response.exceeded_limit {
+        sections.push(format!("Process was stopped after exceeding its {limit}"));
+    }
+
     if let Some(process_id) = &response.process_id {
         // Training still uses "session ID".
         sections.push(format!("Process running with session ID {process_id}"));
@@ -261,7 +273,7 @@ fn format_response(response: &UnifiedExecResponse) -> String {
     }
 
     sections.push("Output:".to_string());
-    sections.push(response.output.clone());
+    sections.push(with_spill_notice(response.output.clone(), spill_notice));
 
     sections.join("\n")
 }
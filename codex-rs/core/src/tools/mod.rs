@@ -8,11 +8,14 @@ pub mod router;
 pub mod runtimes;
 pub mod sandboxing;
 pub mod spec;
+pub(crate) mod spill;
 
 use crate::exec::ExecToolCallOutput;
+use crate::tools::spill::with_spill_notice;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use crate::truncate::truncate_text;
+use codex_utils_tokenizer::Tokenizer;
 pub use router::ToolRouter;
 use serde::Serialize;
 
@@ -24,9 +27,12 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
+/// `spill_notice` is appended to the output when the full body was saved.
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
+    spill_notice: Option<&str>,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -49,7 +55,10 @@ pub fn format_exec_output_for_model_structured(
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output, truncation_policy);
+    let formatted_output = with_spill_notice(
+        format_exec_output_str(exec_output, truncation_policy, tokenizer),
+        spill_notice,
+    );
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -66,6 +75,8 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
+    spill_notice: Option<&str>,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -74,7 +85,7 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = truncate_text(&content, truncation_policy, tokenizer);
 
     let mut sections = Vec::new();
 
@@ -85,7 +96,7 @@ pub fn format_exec_output_for_model_freeform(
     }
 
     sections.push("Output:".to_string());
-    sections.push(formatted_output);
+    sections.push(with_spill_notice(formatted_output, spill_notice));
 
     sections.join("\n")
 }
@@ -93,21 +104,28 @@ pub fn format_exec_output_for_model_freeform(
 pub fn format_exec_output_str(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
 ) -> String {
     let content = build_content_with_timeout(exec_output);
 
     // Truncate for model consumption before serialization.
-    formatted_truncate_text(&content, truncation_policy)
+    formatted_truncate_text(&content, truncation_policy, tokenizer)
 }
 
-/// Extracts exec output content and prepends a timeout message if the command timed out.
-fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
+/// Extracts exec output content and prepends a message if the command timed
+/// out or was stopped by a resource limit.
+pub(crate) fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
     if exec_output.timed_out {
         format!(
             "command timed out after {} milliseconds\n{}",
             exec_output.duration.as_millis(),
             exec_output.aggregated_output.text
         )
+    } else if let Some(limit) = exec_output.exceeded_limit {
+        format!(
+            "command was stopped after exceeding its {limit}\n{}",
+            exec_output.aggregated_output.text
+        )
     } else {
         exec_output.aggregated_output.text.clone()
     }
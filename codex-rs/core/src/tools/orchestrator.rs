@@ -8,6 +8,7 @@ retry without sandbox on denial (no re‑approval thanks to caching).
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::sandboxing::SandboxExtras;
 use crate::sandboxing::SandboxManager;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -20,6 +21,7 @@ use crate::tools::sandboxing::default_exec_approval_requirement;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use codex_utils_absolute_path::AbsolutePathBuf;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -101,6 +103,16 @@ impl ToolOrchestrator {
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
+            extras: SandboxExtras {
+                network_proxy: tool_ctx.session.services.network_proxy.as_ref(),
+                deny_read: &turn_ctx.sandbox_deny_read,
+                linux_backend: turn_ctx.linux_sandbox_backend,
+                linux_overlay_dir: turn_ctx
+                    .linux_sandbox_overlay_dir
+                    .as_ref()
+                    .map(AbsolutePathBuf::as_path),
+                resource_limits: turn_ctx.sandbox_resource_limits,
+            },
         };
 
         match tool.run(req, &initial_attempt, tool_ctx).await {
@@ -151,6 +163,11 @@ impl ToolOrchestrator {
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
+                    // Approval lifts the sandbox, not the resource limits.
+                    extras: SandboxExtras {
+                        resource_limits: turn_ctx.sandbox_resource_limits,
+                        ..Default::default()
+                    },
                 };
 
                 // Second attempt.
@@ -2,11 +2,15 @@ use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::hooks::HookCommandConfig;
 use crate::hooks::HookContext;
 use crate::hooks::HookDecisionKind;
-use crate::hooks::HookDecisionWithContext;
+use crate::hooks::PreToolUseOutcome;
 use crate::hooks::ToolHookPayload;
+use crate::hooks::build_additional_context_item;
 use crate::hooks::build_apply_patch_hook_payloads;
+use crate::hooks::build_apply_patch_post_tool_use_hook_payloads;
+use crate::hooks::build_post_tool_use_hook_payloads;
 use crate::protocol::EventMsg;
 use crate::protocol::HookActivityEvent;
 use crate::protocol::HookActivityHook;
@@ -22,14 +26,18 @@ use crate::tools::registry::ToolRegistry;
 use crate::tools::spec::ApplyPatchToolArgs;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::build_specs;
+use codex_apply_patch::ApplyPatchFileChange;
 use codex_apply_patch::MaybeApplyPatchVerified;
 use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::ShellToolCallParams;
 use serde_json;
+use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::instrument;
 
 #[derive(Clone, Debug)]
@@ -151,12 +159,13 @@ impl ToolRouter {
         let ToolCall {
             tool_name,
             call_id,
-            payload,
+            mut payload,
         } = call;
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
+        let is_apply_patch = tool_name == "apply_patch";
         let failure_call_id = call_id.clone();
         let hook_session = session.clone();
-        let pre_hook_payloads = build_tool_hook_payloads(
+        let mut pre_hook_payloads = build_tool_hook_payloads(
             session.as_ref(),
             turn.as_ref(),
             &tool_name,
@@ -165,9 +174,21 @@ impl ToolRouter {
         )
         .await;
 
-        if let Some(blocked) =
-            pre_tool_use_decision(session.as_ref(), pre_hook_payloads.as_deref()).await?
+        let PreToolUseOutcome {
+            blocked,
+            rewritten_payloads,
+            rewritten_by,
+            additional_context,
+        } = pre_tool_use_decision(session.as_ref(), pre_hook_payloads.as_deref()).await?;
+
+        // Queue hook context for the next model request rather than recording it
+        // now, so it lands after this call's output in the history.
+        if let Some(context_item) = build_additional_context_item("PreToolUse", &additional_context)
         {
+            let _ = session.inject_response_items(vec![context_item]).await;
+        }
+
+        if let Some(blocked) = blocked {
             let reason = blocked
                 .decision
                 .reason
@@ -178,12 +199,7 @@ impl ToolRouter {
                 .and_then(|payloads| payloads.first())
                 .map(|payload| payload.tool_name.clone())
                 .unwrap_or_else(|| tool_name.clone());
-            let hook_name = blocked
-                .hook
-                .command
-                .first()
-                .cloned()
-                .unwrap_or_else(|| "unknown".to_string());
+            let hook_name = hook_display_name(&blocked.hook);
             let hook_decision = blocked
                 .decision
                 .decision
@@ -231,6 +247,50 @@ impl ToolRouter {
             ));
         }
 
+        if let (Some(original), Some(rewritten)) =
+            (pre_hook_payloads.as_deref(), rewritten_payloads)
+        {
+            let display_tool_name = rewritten
+                .first()
+                .map(|payload| payload.tool_name.clone())
+                .unwrap_or_else(|| tool_name.clone());
+            match rewrite_tool_payload(turn.as_ref(), &tool_name, &payload, original, &rewritten) {
+                Ok(rewritten_payload) => {
+                    payload = rewritten_payload;
+                    pre_hook_payloads = Some(rewritten);
+                    session
+                        .send_event(
+                            turn.as_ref(),
+                            EventMsg::HookActivity(HookActivityEvent {
+                                status: HookActivityStatus::Rewritten,
+                                tool: Some(HookActivityTool {
+                                    name: display_tool_name.clone(),
+                                    past_tense: tool_past_tense(&display_tool_name),
+                                }),
+                                hooks: rewritten_by
+                                    .iter()
+                                    .map(|hook| HookActivityHook {
+                                        name: hook_display_name(hook),
+                                        decision: "rewrite".to_string(),
+                                    })
+                                    .collect(),
+                                reason: None,
+                            }),
+                        )
+                        .await;
+                }
+                Err(message) => {
+                    return Ok(Self::failure_response(
+                        failure_call_id,
+                        payload_outputs_custom,
+                        FunctionCallError::RespondToModel(format!(
+                            "hook rewrote {display_tool_name} input that could not be applied: {message}"
+                        )),
+                    ));
+                }
+            }
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
@@ -240,6 +300,7 @@ impl ToolRouter {
             payload,
         };
 
+        let started_at = Instant::now();
         let response = match self.registry.dispatch(invocation).await {
             Ok(response) => response,
             Err(FunctionCallError::Fatal(message)) => {
@@ -251,13 +312,15 @@ impl ToolRouter {
         if let Some(hooks) = hook_session.services.hooks.as_ref()
             && let Some(payloads) = pre_hook_payloads.as_ref()
         {
-            let post_payloads: Vec<ToolHookPayload> = payloads
-                .iter()
-                .map(|payload| ToolHookPayload {
-                    hook_event_name: "PostToolUse".to_string(),
-                    ..payload.clone()
-                })
-                .collect();
+            let post_payloads = if is_apply_patch {
+                build_apply_patch_post_tool_use_hook_payloads(
+                    payloads,
+                    &response,
+                    started_at.elapsed(),
+                )
+            } else {
+                build_post_tool_use_hook_payloads(payloads, &response, started_at.elapsed())
+            };
             let _ = hooks.run_post_tool_use(&post_payloads).await;
         }
 
@@ -343,6 +406,7 @@ async fn build_tool_hook_payloads(
         hook_event_name: context.hook_event_name,
         tool_name: tool_name.to_string(),
         tool_input,
+        tool_response: None,
     }])
 }
 
@@ -379,13 +443,13 @@ fn build_apply_patch_payloads(
 async fn pre_tool_use_decision(
     session: &Session,
     payloads: Option<&[ToolHookPayload]>,
-) -> Result<Option<HookDecisionWithContext>, FunctionCallError> {
+) -> Result<PreToolUseOutcome, FunctionCallError> {
     let Some(hooks) = session.services.hooks.as_ref() else {
-        return Ok(None);
+        return Ok(PreToolUseOutcome::default());
     };
 
     let Some(payloads) = payloads else {
-        return Ok(None);
+        return Ok(PreToolUseOutcome::default());
     };
 
     hooks
@@ -394,6 +458,220 @@ async fn pre_tool_use_decision(
         .map_err(|err| FunctionCallError::RespondToModel(format!("hook error: {err}")))
 }
 
+/// Rebuilds the `ToolPayload` for a call from hook payloads whose `tool_input`
+/// was replaced by a `PreToolUse` hook. This is the inverse of
+/// `build_tool_hook_payloads`.
+fn rewrite_tool_payload(
+    turn: &TurnContext,
+    tool_name: &str,
+    payload: &ToolPayload,
+    original: &[ToolHookPayload],
+    rewritten: &[ToolHookPayload],
+) -> Result<ToolPayload, String> {
+    if tool_name == "apply_patch" {
+        let patch = rewrite_apply_patch_input(turn, payload, original, rewritten)?;
+        return match payload {
+            ToolPayload::Function { .. } => Ok(ToolPayload::Function {
+                arguments: serde_json::json!({ "input": patch }).to_string(),
+            }),
+            ToolPayload::Custom { .. } => Ok(ToolPayload::Custom { input: patch }),
+            _ => Err("unsupported apply_patch payload".to_string()),
+        };
+    }
+
+    let [rewritten] = rewritten else {
+        return Err(format!("expected one payload, got {}", rewritten.len()));
+    };
+    let tool_input = rewritten.tool_input.clone();
+    match payload {
+        ToolPayload::Function { arguments } => {
+            let arguments = match tool_input {
+                // Arguments that are not JSON reach hooks as a plain string;
+                // hand that string back verbatim instead of re-quoting it.
+                Value::String(raw) if serde_json::from_str::<Value>(arguments).is_err() => raw,
+                other => other.to_string(),
+            };
+            Ok(ToolPayload::Function { arguments })
+        }
+        ToolPayload::Custom { .. } => match tool_input {
+            Value::String(input) => Ok(ToolPayload::Custom { input }),
+            other => Ok(ToolPayload::Custom {
+                input: other.to_string(),
+            }),
+        },
+        ToolPayload::LocalShell { .. } => serde_json::from_value::<ShellToolCallParams>(tool_input)
+            .map(|params| ToolPayload::LocalShell { params })
+            .map_err(|err| err.to_string()),
+        // Hooks may only rewrite MCP arguments; the target server and tool stay
+        // fixed so a hook cannot redirect a call to a different integration.
+        ToolPayload::Mcp { server, tool, .. } => {
+            let arguments = tool_input.get("arguments").cloned().unwrap_or(Value::Null);
+            let raw_arguments = match arguments {
+                Value::Null => String::new(),
+                Value::String(raw) => raw,
+                other => other.to_string(),
+            };
+            Ok(ToolPayload::Mcp {
+                server: server.clone(),
+                tool: tool.clone(),
+                raw_arguments,
+            })
+        }
+    }
+}
+
+/// Re-renders an apply_patch call from per-file `Write`/`Edit` hook payloads.
+/// Deletions have no hook payload and are carried over unchanged.
+fn rewrite_apply_patch_input(
+    turn: &TurnContext,
+    payload: &ToolPayload,
+    original: &[ToolHookPayload],
+    rewritten: &[ToolHookPayload],
+) -> Result<String, String> {
+    let patch_input = match payload {
+        ToolPayload::Function { arguments } => {
+            serde_json::from_str::<ApplyPatchToolArgs>(arguments)
+                .map(|args| args.input)
+                .map_err(|err| err.to_string())?
+        }
+        ToolPayload::Custom { input } => input.clone(),
+        _ => return Err("unsupported apply_patch payload".to_string()),
+    };
+    let command = vec!["apply_patch".to_string(), patch_input];
+    let action = match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &turn.cwd) {
+        MaybeApplyPatchVerified::Body(action) => action,
+        _ => return Err("original patch no longer parses".to_string()),
+    };
+
+    let rewrites: HashMap<String, &ToolHookPayload> = original
+        .iter()
+        .zip(rewritten)
+        .filter_map(|(before, after)| {
+            hook_file_path(&before.tool_input).map(|path| (path.to_string(), after))
+        })
+        .collect();
+
+    let mut changes: Vec<_> = action.changes().iter().collect();
+    changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut patch = String::from("*** Begin Patch\n");
+    for (path, change) in changes {
+        let original_target = match change {
+            ApplyPatchFileChange::Update {
+                move_path: Some(move_path),
+                ..
+            } => move_path.as_path(),
+            _ => path.as_path(),
+        };
+        let rewrite = rewrites
+            .get(original_target.to_string_lossy().as_ref())
+            .map(|payload| &payload.tool_input);
+
+        match change {
+            ApplyPatchFileChange::Delete { .. } => {
+                patch.push_str(&format!("*** Delete File: {}\n", path.display()));
+            }
+            ApplyPatchFileChange::Add { content } => {
+                let file_path = rewrite
+                    .and_then(hook_file_path)
+                    .map(Path::new)
+                    .unwrap_or(path.as_path());
+                let content = rewrite
+                    .and_then(|input| input.get("content"))
+                    .and_then(Value::as_str)
+                    .unwrap_or(content);
+                patch.push_str(&format!("*** Add File: {}\n", file_path.display()));
+                push_patch_lines(&mut patch, '+', content);
+            }
+            ApplyPatchFileChange::Update {
+                move_path,
+                new_content,
+                ..
+            } => {
+                let rewritten_target = rewrite.and_then(hook_file_path).map(Path::new);
+                let retargeted = rewritten_target.is_some_and(|target| target != original_target);
+                let old_content = match rewrite
+                    .and_then(|input| input.get("old_string"))
+                    .and_then(Value::as_str)
+                {
+                    Some(old) => old.to_string(),
+                    None => std::fs::read_to_string(path).map_err(|err| err.to_string())?,
+                };
+                let new_content = rewrite
+                    .and_then(|input| input.get("new_string"))
+                    .and_then(Value::as_str)
+                    .unwrap_or(new_content);
+
+                if retargeted {
+                    // A hook pointed the edit at a different file: edit that
+                    // file in place rather than moving the original. The hunk
+                    // replaces `old_content`, so the new target must contain it.
+                    let target = rewritten_target.unwrap_or(path.as_path());
+                    let target_content = std::fs::read_to_string(turn.cwd.join(target))
+                        .map_err(|err| format!("{}: {err}", target.display()))?;
+                    if !contains_lines(&target_content, &old_content) {
+                        return Err(format!(
+                            "cannot retarget edit of {} to {}: the target does not contain the text being replaced",
+                            original_target.display(),
+                            target.display()
+                        ));
+                    }
+                    patch.push_str(&format!("*** Update File: {}\n", target.display()));
+                } else {
+                    patch.push_str(&format!("*** Update File: {}\n", path.display()));
+                    if let Some(move_path) = move_path {
+                        patch.push_str(&format!("*** Move to: {}\n", move_path.display()));
+                    }
+                }
+                patch.push_str("@@\n");
+                push_patch_lines(&mut patch, '-', &old_content);
+                push_patch_lines(&mut patch, '+', new_content);
+            }
+        }
+    }
+    patch.push_str("*** End Patch");
+    Ok(patch)
+}
+
+fn hook_file_path(tool_input: &Value) -> Option<&str> {
+    tool_input.get("file_path").and_then(Value::as_str)
+}
+
+/// Splits `content` the way apply_patch reads files: on `\n` only, with the
+/// final newline ending the last line rather than starting an empty one.
+fn patch_lines(content: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = content.split('\n').collect();
+    if lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn contains_lines(haystack: &str, needle: &str) -> bool {
+    let needle = patch_lines(needle);
+    if needle.is_empty() {
+        return true;
+    }
+    patch_lines(haystack)
+        .windows(needle.len())
+        .any(|window| window == needle.as_slice())
+}
+
+fn push_patch_lines(patch: &mut String, prefix: char, content: &str) {
+    for line in patch_lines(content) {
+        patch.push(prefix);
+        patch.push_str(line);
+        patch.push('\n');
+    }
+}
+
+fn hook_display_name(hook: &HookCommandConfig) -> String {
+    hook.command
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 fn tool_past_tense(tool_name: &str) -> String {
     match tool_name {
         "Edit" | "MultiEdit" => "Edited",
@@ -406,3 +684,127 @@ fn tool_past_tense(tool_name: &str) -> String {
     }
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn hook_payload(tool_name: &str, tool_input: Value) -> ToolHookPayload {
+        ToolHookPayload {
+            session_id: String::new(),
+            transcript_path: String::new(),
+            hook_event_name: "PreToolUse".to_string(),
+            tool_name: tool_name.to_string(),
+            tool_input,
+            tool_response: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn non_json_function_arguments_pass_through_unchanged() {
+        let (_, turn) = make_session_and_context().await;
+        let payload = ToolPayload::Function {
+            arguments: "not json".to_string(),
+        };
+        let original = [hook_payload("custom", json!("not json"))];
+
+        let rewritten =
+            rewrite_tool_payload(&turn, "custom", &payload, &original, &original).expect("rewrite");
+
+        let ToolPayload::Function { arguments } = rewritten else {
+            panic!("expected function payload");
+        };
+        assert_eq!(arguments, "not json");
+    }
+
+    #[tokio::test]
+    async fn apply_patch_rejects_retarget_to_file_without_the_replaced_text() {
+        let (_, mut turn) = make_session_and_context().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        turn.cwd = dir.path().to_path_buf();
+        let source = dir.path().join("a.txt");
+        let other = dir.path().join("b.txt");
+        std::fs::write(&source, "original\n").expect("write a.txt");
+        std::fs::write(&other, "something else\n").expect("write b.txt");
+
+        let payload = ToolPayload::Custom {
+            input:
+                "*** Begin Patch\n*** Update File: a.txt\n@@\n-original\n+updated\n*** End Patch"
+                    .to_string(),
+        };
+        let edit = |path: &Path| {
+            hook_payload(
+                "Edit",
+                json!({
+                    "file_path": path.to_string_lossy(),
+                    "old_string": "original\n",
+                    "new_string": "updated\n",
+                }),
+            )
+        };
+        let original = [edit(&source)];
+
+        let err = rewrite_tool_payload(&turn, "apply_patch", &payload, &original, &[edit(&other)])
+            .expect_err("retarget to a file without the old text should be rejected");
+        assert!(err.contains("does not contain the text"), "{err}");
+
+        std::fs::write(&other, "header\noriginal\nfooter\n").expect("write b.txt");
+        let rewritten =
+            rewrite_tool_payload(&turn, "apply_patch", &payload, &original, &[edit(&other)])
+                .expect("retarget to a file containing the old text");
+        let ToolPayload::Custom { input } = rewritten else {
+            panic!("expected custom payload");
+        };
+        assert!(
+            input.contains(&format!("*** Update File: {}", other.display())),
+            "{input}"
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_patch_round_trips_blank_final_lines() {
+        let (_, mut turn) = make_session_and_context().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        turn.cwd = dir.path().to_path_buf();
+        std::fs::write(dir.path().join("a.txt"), "one\n\ntwo\n\n").expect("write a.txt");
+
+        let payload = ToolPayload::Custom {
+            input: "*** Begin Patch\n*** Add File: new.txt\n+first\n+\n+last\n+\n\
+                    *** Update File: a.txt\n@@\n-two\n+three\n*** End Patch"
+                .to_string(),
+        };
+        let context = HookContext {
+            session_id: String::new(),
+            transcript_path: String::new(),
+            hook_event_name: "PreToolUse".to_string(),
+        };
+        let original = build_apply_patch_payloads(&context, &turn, &payload).expect("payloads");
+
+        let rewritten = rewrite_tool_payload(&turn, "apply_patch", &payload, &original, &original)
+            .expect("rewrite");
+        let round_tripped =
+            build_apply_patch_payloads(&context, &turn, &rewritten).expect("payloads");
+
+        let by_path = |payloads: &[ToolHookPayload]| {
+            let mut inputs: Vec<Value> = payloads
+                .iter()
+                .map(|payload| payload.tool_input.clone())
+                .collect();
+            inputs.sort_by_key(|input| input["file_path"].as_str().map(str::to_string));
+            inputs
+        };
+        assert_eq!(by_path(&round_tripped), by_path(&original));
+        let contents: Vec<Value> = by_path(&original)
+            .into_iter()
+            .map(|input| input.get("content").or(input.get("new_string")).cloned())
+            .map(Option::unwrap_or_default)
+            .collect();
+        assert_eq!(
+            contents,
+            vec![json!("one\n\nthree\n\n"), json!("first\n\nlast\n\n")]
+        );
+    }
+}
@@ -109,6 +109,7 @@ impl Approvable<ShellRequest> for ShellRuntime {
                         req.exec_approval_requirement
                             .proposed_execpolicy_amendment()
                             .cloned(),
+                        req.exec_approval_requirement.explanation().cloned(),
                     )
                     .await
             })
@@ -9,6 +9,7 @@ use crate::error::SandboxErr;
 use crate::exec::ExecExpiration;
 use crate::features::Feature;
 use crate::powershell::prefix_powershell_script_with_utf8;
+use crate::resource_limits::wrap_pty_command_with_limits;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::ShellType;
 use crate::tools::runtimes::build_command_spec;
@@ -127,6 +128,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
                         req.exec_approval_requirement
                             .proposed_execpolicy_amendment()
                             .cloned(),
+                        req.exec_approval_requirement.explanation().cloned(),
                     )
                     .await
             })
@@ -185,9 +187,18 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecProcess> for UnifiedExecRunt
             req.justification.clone(),
         )
         .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
-        let exec_env = attempt
+        let mut exec_env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
+        if !exec_env.resource_limits.is_empty() || exec_env.cgroup.is_some() {
+            let codex_exe = match &ctx.turn.codex_linux_sandbox_exe {
+                Some(path) => path.clone(),
+                None => std::env::current_exe().map_err(|e| {
+                    ToolError::Rejected(format!("failed to determine codex exe: {e}"))
+                })?,
+            };
+            wrap_pty_command_with_limits(&mut exec_env, &codex_exe);
+        }
         self.manager
             .open_session_with_exec_env(&exec_env)
             .await
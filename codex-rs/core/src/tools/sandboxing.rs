@@ -9,10 +9,12 @@ use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
+use crate::sandboxing::SandboxExtras;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
 use crate::state::SessionServices;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyExplanation;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
 use std::collections::HashMap;
@@ -129,6 +131,9 @@ pub(crate) enum ExecApprovalRequirement {
         /// Proposed execpolicy amendment to skip future approvals for similar commands
         /// See core/src/exec_policy.rs for more details on how proposed_execpolicy_amendment is determined.
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        /// Which execpolicy rules or heuristic decided that approval is needed, surfaced to the
+        /// user alongside the prompt.
+        explanation: Option<ExecPolicyExplanation>,
     },
     /// Execution forbidden for this tool call.
     Forbidden { reason: String },
@@ -148,6 +153,16 @@ impl ExecApprovalRequirement {
             _ => None,
         }
     }
+
+    pub fn explanation(&self) -> Option<&ExecPolicyExplanation> {
+        match self {
+            Self::NeedsApproval {
+                explanation: Some(explanation),
+                ..
+            } => Some(explanation),
+            _ => None,
+        }
+    }
 }
 
 /// - Never, OnFailure: do not ask
@@ -170,6 +185,7 @@ pub(crate) fn default_exec_approval_requirement(
         ExecApprovalRequirement::NeedsApproval {
             reason: None,
             proposed_execpolicy_amendment: None,
+            explanation: None,
         }
     } else {
         ExecApprovalRequirement::Skip {
@@ -274,6 +290,7 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
+    pub(crate) extras: SandboxExtras<'a>,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -287,6 +304,7 @@ impl<'a> SandboxAttempt<'a> {
             self.sandbox,
             self.sandbox_cwd,
             self.codex_linux_sandbox_exe,
+            self.extras,
         )
     }
 }
@@ -320,6 +338,7 @@ mod tests {
             ExecApprovalRequirement::NeedsApproval {
                 reason: None,
                 proposed_execpolicy_amendment: None,
+                explanation: None,
             }
         );
     }
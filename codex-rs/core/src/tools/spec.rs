@@ -1,5 +1,6 @@
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::AgentProfile;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::tools::handlers::PLAN_TOOL;
@@ -8,6 +9,7 @@ use crate::tools::handlers::apply_patch::create_apply_patch_json_tool;
 use crate::tools::handlers::collab::DEFAULT_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::collab::MAX_WAIT_TIMEOUT_MS;
 use crate::tools::registry::ToolRegistryBuilder;
+use crate::tools::spill::READ_TOOL_OUTPUT_TOOL_NAME;
 use codex_protocol::models::VIEW_IMAGE_TOOL_NAME;
 use codex_protocol::openai_models::ApplyPatchToolType;
 use codex_protocol::openai_models::ConfigShellToolType;
@@ -26,6 +28,9 @@ pub(crate) struct ToolsConfig {
     pub web_search_request: bool,
     pub web_search_cached: bool,
     pub collab_tools: bool,
+    /// `[agents]` profiles offered by `spawn_agent`, as name and description.
+    pub agent_profiles: BTreeMap<String, Option<String>>,
+    pub tool_output_spill: bool,
     pub experimental_supported_tools: Vec<String>,
 }
 
@@ -44,6 +49,7 @@ impl ToolsConfig {
         let include_web_search_request = features.enabled(Feature::WebSearchRequest);
         let include_web_search_cached = features.enabled(Feature::WebSearchCached);
         let include_collab_tools = features.enabled(Feature::Collab);
+        let include_tool_output_spill = features.enabled(Feature::ToolOutputSpill);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -76,9 +82,20 @@ impl ToolsConfig {
             web_search_request: include_web_search_request,
             web_search_cached: include_web_search_cached,
             collab_tools: include_collab_tools,
+            agent_profiles: BTreeMap::new(),
+            tool_output_spill: include_tool_output_spill,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
         }
     }
+
+    /// Lists the configured `[agents]` profiles in the `spawn_agent` tool.
+    pub fn with_agent_profiles(mut self, agents: &HashMap<String, AgentProfile>) -> Self {
+        self.agent_profiles = agents
+            .iter()
+            .map(|(name, profile)| (name.clone(), profile.description.clone()))
+            .collect();
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -425,7 +442,7 @@ fn create_view_image_tool() -> ToolSpec {
     })
 }
 
-fn create_spawn_agent_tool() -> ToolSpec {
+fn create_spawn_agent_tool(agent_profiles: &BTreeMap<String, Option<String>>) -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
         "message".to_string(),
@@ -433,10 +450,37 @@ fn create_spawn_agent_tool() -> ToolSpec {
             description: Some("Initial message to send to the new agent.".to_string()),
         },
     );
+    let mut description = "Spawn a new agent and return its id.".to_string();
+    let mut agent_description =
+        "Optional name of an agent profile configured under `[agents]`. Defaults to a copy of the current agent."
+            .to_string();
+    if !agent_profiles.is_empty() {
+        description.push_str("\n\nAvailable agent profiles:");
+        for (name, profile_description) in agent_profiles {
+            match profile_description {
+                Some(profile_description) => {
+                    description.push_str(&format!("\n- {name}: {profile_description}"));
+                }
+                None => description.push_str(&format!("\n- {name}")),
+            }
+        }
+        let names = agent_profiles
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        agent_description.push_str(&format!(" One of: {names}."));
+    }
+    properties.insert(
+        "agent".to_string(),
+        JsonSchema::String {
+            description: Some(agent_description),
+        },
+    );
 
     ToolSpec::Function(ResponsesApiTool {
         name: "spawn_agent".to_string(),
-        description: "Spawn a new agent and return its id.".to_string(),
+        description,
         strict: false,
         parameters: JsonSchema::Object {
             properties,
@@ -476,9 +520,10 @@ fn create_send_input_tool() -> ToolSpec {
 fn create_wait_tool() -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
-        "id".to_string(),
-        JsonSchema::String {
-            description: Some("Identifier of the agent to wait on.".to_string()),
+        "ids".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None }),
+            description: Some("Identifiers of the agents to wait on.".to_string()),
         },
     );
     properties.insert(
@@ -492,11 +537,11 @@ fn create_wait_tool() -> ToolSpec {
 
     ToolSpec::Function(ResponsesApiTool {
         name: "wait".to_string(),
-        description: "Wait for an agent and return its status.".to_string(),
+        description: "Wait for agents to finish and return their status and final output. Returns early when every agent is done, otherwise after the timeout.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["id".to_string()]),
+            required: Some(vec!["ids".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
@@ -744,6 +789,55 @@ fn create_read_file_tool() -> ToolSpec {
     })
 }
 
+fn create_read_tool_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "id".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Id of the saved output, as given in the truncation notice.".to_string(),
+                ),
+            },
+        ),
+        (
+            "offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The line number to start reading from. Must be 1 or greater.".to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some("The maximum number of lines to return.".to_string()),
+            },
+        ),
+        (
+            "pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional regular expression; only lines matching it are returned.".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: READ_TOOL_OUTPUT_TOOL_NAME.to_string(),
+        description: "Reads the full output of an earlier tool call whose result was truncated, \
+                      with 1-indexed line numbers. Use it to inspect the omitted middle of long \
+                      logs instead of re-running the command."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_dir_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -951,6 +1045,26 @@ pub(crate) fn create_tools_json_for_chat_completions_api(
     Ok(tools_json)
 }
 
+/// Returns JSON values that are compatible with tool use in the Anthropic
+/// Messages API: https://docs.anthropic.com/en/api/messages
+pub(crate) fn create_tools_json_for_messages_api(
+    tools: &[ToolSpec],
+) -> crate::error::Result<Vec<serde_json::Value>> {
+    let responses_api_tools_json = create_tools_json_for_responses_api(tools)?;
+    let tools_json = responses_api_tools_json
+        .into_iter()
+        .filter(|tool| tool.get("type").and_then(serde_json::Value::as_str) == Some("function"))
+        .map(|tool| {
+            json!({
+                "name": tool.get("name").cloned().unwrap_or_default(),
+                "description": tool.get("description").cloned().unwrap_or_default(),
+                "input_schema": tool.get("parameters").cloned().unwrap_or_else(|| json!({"type": "object"})),
+            })
+        })
+        .collect::<Vec<serde_json::Value>>();
+    Ok(tools_json)
+}
+
 pub(crate) fn mcp_tool_to_openai_tool(
     fully_qualified_name: String,
     tool: mcp_types::Tool,
@@ -1110,6 +1224,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadToolOutputHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
     use crate::tools::handlers::TestSyncHandler;
@@ -1232,7 +1347,7 @@ pub(crate) fn build_specs(
 
     if config.collab_tools {
         let collab_handler = Arc::new(CollabHandler);
-        builder.push_spec(create_spawn_agent_tool());
+        builder.push_spec(create_spawn_agent_tool(&config.agent_profiles));
         builder.push_spec(create_send_input_tool());
         builder.push_spec(create_wait_tool());
         builder.push_spec(create_close_agent_tool());
@@ -1242,6 +1357,12 @@ pub(crate) fn build_specs(
         builder.register_handler("close_agent", collab_handler);
     }
 
+    if config.tool_output_spill {
+        let read_tool_output_handler = Arc::new(ReadToolOutputHandler);
+        builder.push_spec_with_parallel_support(create_read_tool_output_tool(), true);
+        builder.register_handler(READ_TOOL_OUTPUT_TOOL_NAME, read_tool_output_handler);
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, mcp_types::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1438,6 +1559,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_spawn_agent_lists_agent_profiles() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::Collab);
+        let agents = HashMap::from([
+            (
+                "reviewer".to_string(),
+                AgentProfile {
+                    description: Some("Reviews diffs and reports problems".to_string()),
+                    ..Default::default()
+                },
+            ),
+            ("explorer".to_string(), AgentProfile::default()),
+        ]);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+        })
+        .with_agent_profiles(&agents);
+        let (tools, _) = build_specs(&tools_config, None).build();
+
+        let ToolSpec::Function(ResponsesApiTool {
+            description,
+            parameters: JsonSchema::Object { properties, .. },
+            ..
+        }) = &find_tool(&tools, "spawn_agent").spec
+        else {
+            panic!("spawn_agent should be a function tool");
+        };
+        assert_eq!(
+            description,
+            "Spawn a new agent and return its id.\n\nAvailable agent profiles:\n- explorer\n- reviewer: Reviews diffs and reports problems"
+        );
+        let Some(JsonSchema::String {
+            description: Some(agent_description),
+        }) = properties.get("agent")
+        else {
+            panic!("spawn_agent should take an agent name");
+        };
+        assert!(
+            agent_description.ends_with("One of: explorer, reviewer."),
+            "{agent_description}"
+        );
+    }
+
+    #[test]
+    fn test_build_specs_tool_output_spill_enabled() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ToolOutputSpill);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "read_tool_output").supports_parallel_tool_calls);
+    }
+
     fn assert_model_tools(model_slug: &str, features: &Features, expected_tools: &[&str]) {
         let config = test_config();
         let model_info = ModelsManager::construct_model_info_offline(model_slug, &config);
@@ -2362,4 +2544,39 @@ Examples of valid command strings:
             })]
         );
     }
+
+    #[test]
+    fn messages_tools_use_input_schema() {
+        let properties =
+            BTreeMap::from([("foo".to_string(), JsonSchema::String { description: None })]);
+        let tools = vec![
+            ToolSpec::Function(ResponsesApiTool {
+                name: "demo".to_string(),
+                description: "A demo tool".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties,
+                    required: None,
+                    additional_properties: None,
+                },
+            }),
+            ToolSpec::LocalShell {},
+        ];
+
+        let tools_json = create_tools_json_for_messages_api(&tools).unwrap();
+
+        assert_eq!(
+            tools_json,
+            vec![json!({
+                "name": "demo",
+                "description": "A demo tool",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "foo": { "type": "string" }
+                    },
+                },
+            })]
+        );
+    }
 }
@@ -0,0 +1,142 @@
+//! Saving of truncated tool output to disk.
+//!
+//! When exec output is truncated before it reaches the model, the complete
+//! text is written next to the session's rollout file and the truncated text
+//! gains a notice naming an id that can be passed to `read_tool_output`.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use tracing::warn;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+
+pub(crate) const READ_TOOL_OUTPUT_TOOL_NAME: &str = "read_tool_output";
+
+/// Directory holding saved output for the session recorded at `rollout_path`.
+pub(crate) fn spill_dir(rollout_path: &Path) -> PathBuf {
+    rollout_path.with_extension("tool_output")
+}
+
+/// Moves the saved output of the session recorded at `from` so that it sits
+/// next to the rollout's new path `to`. Sessions without saved output are left
+/// alone.
+pub async fn move_saved_tool_output(from: &Path, to: &Path) -> std::io::Result<()> {
+    match tokio::fs::rename(spill_dir(from), spill_dir(to)).await {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Maps a tool call id to an id that is safe to use as a file name. ASCII
+/// letters, digits and `_` are kept; every other byte is written as `-` and
+/// two hex digits so that distinct call ids never share a file.
+fn spill_id(call_id: &str) -> String {
+    let mut id = String::with_capacity(call_id.len());
+    for byte in call_id.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' {
+            id.push(char::from(byte));
+        } else {
+            id.push_str(&format!("-{byte:02x}"));
+        }
+    }
+    id
+}
+
+/// Resolves `id` to the file holding its output. Returns `None` for ids that
+/// [`spill_tool_output`] could not have produced or when the session is not
+/// recorded.
+pub(crate) async fn spill_path(session: &Session, id: &str) -> Option<PathBuf> {
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return None;
+    }
+    let rollout_path = session.rollout_path().await?;
+    Some(spill_dir(&rollout_path).join(format!("{id}.log")))
+}
+
+/// Saves the untruncated `content` of `call_id` and returns the notice to
+/// append to its truncated form.
+///
+/// Returns `None` when the feature is disabled, the session has no rollout
+/// file, or the write fails; callers then fall back to plain truncation.
+pub(crate) async fn spill_tool_output(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    content: &str,
+) -> Option<String> {
+    if !turn.tools_config.tool_output_spill {
+        return None;
+    }
+    let id = spill_id(call_id);
+    let path = spill_path(session, &id).await?;
+    let write = async {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, content).await
+    };
+    if let Err(err) = write.await {
+        warn!("failed to save tool output to {}: {err}", path.display());
+        return None;
+    }
+    Some(spill_notice(&id, content.lines().count()))
+}
+
+fn spill_notice(id: &str, total_lines: usize) -> String {
+    format!(
+        "[Full output ({total_lines} lines) saved with id `{id}`. Call {READ_TOOL_OUTPUT_TOOL_NAME} with this id to read the omitted lines.]"
+    )
+}
+
+/// Appends `notice`, if any, to `text` on its own line.
+pub(crate) fn with_spill_notice(mut text: String, notice: Option<&str>) -> String {
+    if let Some(notice) = notice {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(notice);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn spill_id_escapes_path_characters() {
+        assert_eq!(spill_id("call_abc123"), "call_abc123");
+        assert_eq!(spill_id("call-1"), "call-2d1");
+        assert_eq!(spill_id("../etc/passwd"), "-2e-2e-2fetc-2fpasswd");
+        assert_ne!(spill_id("a.b"), spill_id("a_b"));
+        assert_ne!(spill_id("a.b"), spill_id("a/b"));
+    }
+
+    #[test]
+    fn spill_dir_sits_next_to_rollout() {
+        assert_eq!(
+            spill_dir(Path::new("/tmp/sessions/rollout-1.jsonl")),
+            PathBuf::from("/tmp/sessions/rollout-1.tool_output")
+        );
+    }
+
+    #[test]
+    fn notice_goes_on_its_own_line() {
+        assert_eq!(
+            with_spill_notice("head…tail".to_string(), Some("[notice]")),
+            "head…tail\n[notice]"
+        );
+        assert_eq!(
+            with_spill_notice("out\n".to_string(), Some("[notice]")),
+            "out\n[notice]"
+        );
+        assert_eq!(with_spill_notice("out".to_string(), None), "out");
+    }
+}
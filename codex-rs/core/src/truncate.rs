@@ -2,13 +2,26 @@
 //! and suffix on UTF-8 boundaries, and helpers for line/token‑based truncation
 //! used across the core crate.
 
+use std::sync::LazyLock;
+
 use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::openai_models::TokenizerEncoding;
 use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 use codex_protocol::protocol::TruncationPolicy as ProtocolTruncationPolicy;
+use codex_utils_tokenizer::EncodingKind;
+use codex_utils_tokenizer::Tokenizer;
+use codex_utils_tokenizer::encoding_for_model;
+use tracing::warn;
 
 const APPROX_BYTES_PER_TOKEN: usize = 4;
 
+/// Tokenizer for the default vocabulary, used when no model is known.
+/// `None` if the bundled vocabulary failed to load.
+static DEFAULT_TOKENIZER: LazyLock<Option<Tokenizer>> =
+    LazyLock::new(|| load_tokenizer(EncodingKind::default()));
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TruncationPolicy {
     Bytes(usize),
@@ -73,20 +86,31 @@ impl TruncationPolicy {
     }
 }
 
-pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -> String {
-    if content.len() <= policy.byte_budget() {
+/// Token budgets are measured with `tokenizer`, normally the turn's
+/// [`tokenizer_for_model`]; without one they fall back to a bytes-per-token
+/// estimate.
+pub(crate) fn formatted_truncate_text(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
+) -> String {
+    if fits_within_policy(content, policy, tokenizer) {
         return content.to_string();
     }
     let total_lines = content.lines().count();
-    let result = truncate_text(content, policy);
+    let result = truncate_text(content, policy, tokenizer);
     format!("Total output lines: {total_lines}\n\n{result}")
 }
 
-pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
+pub(crate) fn truncate_text(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
+) -> String {
     match policy {
         TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy),
         TruncationPolicy::Tokens(_) => {
-            let (truncated, _) = truncate_with_token_budget(content, policy);
+            let (truncated, _) = truncate_with_token_budget(content, policy, tokenizer);
             truncated
         }
     }
@@ -97,6 +121,7 @@ pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
 pub(crate) fn truncate_function_output_items_with_policy(
     items: &[FunctionCallOutputContentItem],
     policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
 ) -> Vec<FunctionCallOutputContentItem> {
     let mut out: Vec<FunctionCallOutputContentItem> = Vec::with_capacity(items.len());
     let mut remaining_budget = match policy {
@@ -115,7 +140,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
 
                 let cost = match policy {
                     TruncationPolicy::Bytes(_) => text.len(),
-                    TruncationPolicy::Tokens(_) => approx_token_count(text),
+                    TruncationPolicy::Tokens(_) => token_count(text, tokenizer),
                 };
 
                 if cost <= remaining_budget {
@@ -126,7 +151,7 @@ pub(crate) fn truncate_function_output_items_with_policy(
                         TruncationPolicy::Bytes(_) => TruncationPolicy::Bytes(remaining_budget),
                         TruncationPolicy::Tokens(_) => TruncationPolicy::Tokens(remaining_budget),
                     };
-                    let snippet = truncate_text(text, snippet_policy);
+                    let snippet = truncate_text(text, snippet_policy, tokenizer);
                     if snippet.is_empty() {
                         omitted_text_items += 1;
                     } else {
@@ -156,12 +181,45 @@ pub(crate) fn truncate_function_output_items_with_policy(
 /// preserving the beginning and the end. Returns the possibly truncated string
 /// and `Some(original_token_count)` if truncation occurred; otherwise returns
 /// the original string and `None`.
-fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Option<u64>) {
+fn truncate_with_token_budget(
+    s: &str,
+    policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
+) -> (String, Option<u64>) {
     if s.is_empty() {
         return (String::new(), None);
     }
     let max_tokens = policy.token_budget();
 
+    let Some(tokenizer) = tokenizer else {
+        return truncate_with_token_estimate(s, policy);
+    };
+    if tokenizer.fits_within(s, max_tokens) {
+        return (s.to_string(), None);
+    }
+
+    // Only the kept head and tail are located by tokenizing; the removed
+    // middle is counted once for the marker.
+    let (left_budget, right_budget) = split_budget(max_tokens);
+    let prefix_end = tokenizer.prefix_len(s, left_budget);
+    let suffix_start = tokenizer.suffix_start(s, right_budget).max(prefix_end);
+    let removed_tokens = tokenizer.count(&s[prefix_end..suffix_start]);
+
+    let marker =
+        format_truncation_marker(policy, u64::try_from(removed_tokens).unwrap_or(u64::MAX));
+    let total_tokens =
+        tokenizer.count(&s[..prefix_end]) + removed_tokens + tokenizer.count(&s[suffix_start..]);
+    (
+        assemble_truncated_output(&s[..prefix_end], &s[suffix_start..], &marker),
+        Some(u64::try_from(total_tokens).unwrap_or(u64::MAX)),
+    )
+}
+
+/// Byte-heuristic fallback for [`truncate_with_token_budget`] when no
+/// tokenizer is available.
+fn truncate_with_token_estimate(s: &str, policy: TruncationPolicy) -> (String, Option<u64>) {
+    let max_tokens = policy.token_budget();
+
     let byte_len = s.len();
     if max_tokens > 0 && byte_len <= approx_bytes_for_tokens(max_tokens) {
         return (s.to_string(), None);
@@ -286,6 +344,59 @@ fn assemble_truncated_output(prefix: &str, suffix: &str, marker: &str) -> String
     out
 }
 
+/// Whether `content` can be passed through `policy` without truncation.
+pub(crate) fn fits_within_policy(
+    content: &str,
+    policy: TruncationPolicy,
+    tokenizer: Option<Tokenizer>,
+) -> bool {
+    match policy {
+        TruncationPolicy::Bytes(bytes) => content.len() <= bytes,
+        TruncationPolicy::Tokens(tokens) => match tokenizer {
+            Some(tokenizer) => tokenizer.fits_within(content, tokens),
+            None => content.len() <= policy.byte_budget(),
+        },
+    }
+}
+
+fn load_tokenizer(kind: EncodingKind) -> Option<Tokenizer> {
+    match Tokenizer::new(kind) {
+        Ok(tokenizer) => Some(tokenizer),
+        Err(err) => {
+            warn!("{err}; falling back to byte-based token estimates");
+            None
+        }
+    }
+}
+
+/// Returns the tokenizer for `model_info`, preferring the vocabulary the
+/// model metadata names and otherwise inferring one from the slug.
+pub(crate) fn tokenizer_for_model(model_info: &ModelInfo) -> Option<Tokenizer> {
+    let kind = match model_info.tokenizer {
+        Some(TokenizerEncoding::O200kBase) => EncodingKind::O200kBase,
+        Some(TokenizerEncoding::Cl100kBase) => EncodingKind::Cl100kBase,
+        None => encoding_for_model(&model_info.slug),
+    };
+    if kind == EncodingKind::default() {
+        return default_tokenizer();
+    }
+    load_tokenizer(kind)
+}
+
+/// Tokenizer for text that is not tied to a model, such as error messages.
+pub(crate) fn default_tokenizer() -> Option<Tokenizer> {
+    *DEFAULT_TOKENIZER
+}
+
+/// Counts the tokens in `text` with `tokenizer`, falling back to the byte
+/// heuristic without one.
+pub(crate) fn token_count(text: &str, tokenizer: Option<Tokenizer>) -> usize {
+    match tokenizer {
+        Some(tokenizer) => tokenizer.count(text),
+        None => approx_token_count(text),
+    }
+}
+
 pub(crate) fn approx_token_count(text: &str) -> usize {
     let len = text.len();
     len.saturating_add(APPROX_BYTES_PER_TOKEN.saturating_sub(1)) / APPROX_BYTES_PER_TOKEN
@@ -305,13 +416,16 @@ pub(crate) fn approx_tokens_from_byte_count(bytes: usize) -> u64 {
 mod tests {
 
     use super::TruncationPolicy;
-    use super::approx_token_count;
+    use super::default_tokenizer;
     use super::formatted_truncate_text;
     use super::split_string;
+    use super::token_count;
     use super::truncate_function_output_items_with_policy;
     use super::truncate_text;
     use super::truncate_with_token_budget;
     use codex_protocol::models::FunctionCallOutputContentItem;
+    use codex_utils_tokenizer::EncodingKind;
+    use codex_utils_tokenizer::Tokenizer;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -355,7 +469,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\n…13 chars truncated…t",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(1)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(1), None),
         );
     }
 
@@ -364,8 +478,8 @@ mod tests {
         let content = "example output";
 
         assert_eq!(
-            "Total output lines: 1\n\nex…3 tokens truncated…ut",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(1)),
+            "Total output lines: 1\n\n…1 tokens truncated… output",
+            formatted_truncate_text(content, TruncationPolicy::Tokens(1), default_tokenizer()),
         );
     }
 
@@ -375,7 +489,7 @@ mod tests {
 
         assert_eq!(
             content,
-            formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
+            formatted_truncate_text(content, TruncationPolicy::Tokens(10), default_tokenizer()),
         );
     }
 
@@ -385,7 +499,7 @@ mod tests {
 
         assert_eq!(
             content,
-            formatted_truncate_text(content, TruncationPolicy::Bytes(20)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(20), None),
         );
     }
 
@@ -394,8 +508,8 @@ mod tests {
         let content = "this is an example of a long output that should be truncated";
 
         assert_eq!(
-            "Total output lines: 1\n\nthis is an…10 tokens truncated… truncated",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(5)),
+            "Total output lines: 1\n\nthis is…7 tokens truncated… should be truncated",
+            formatted_truncate_text(content, TruncationPolicy::Tokens(5), default_tokenizer()),
         );
     }
 
@@ -405,7 +519,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 1\n\nthis is an exam…30 chars truncated…ld be truncated",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(30)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(30), None),
         );
     }
 
@@ -416,7 +530,7 @@ mod tests {
 
         assert_eq!(
             "Total output lines: 2\n\nthis is an exam…51 chars truncated…some other line",
-            formatted_truncate_text(content, TruncationPolicy::Bytes(30)),
+            formatted_truncate_text(content, TruncationPolicy::Bytes(30), None),
         );
     }
 
@@ -426,8 +540,8 @@ mod tests {
             "this is an example of a long output that should be truncated\nalso some other line";
 
         assert_eq!(
-            "Total output lines: 2\n\nthis is an example o…11 tokens truncated…also some other line",
-            formatted_truncate_text(content, TruncationPolicy::Tokens(10)),
+            "Total output lines: 2\n\nthis is an example of…7 tokens truncated…\nalso some other line",
+            formatted_truncate_text(content, TruncationPolicy::Tokens(10), default_tokenizer()),
         );
     }
 
@@ -435,7 +549,8 @@ mod tests {
     fn truncate_with_token_budget_returns_original_when_under_limit() {
         let s = "short output";
         let limit = 100;
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit));
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(limit), default_tokenizer());
         assert_eq!(out, s);
         assert_eq!(original, None);
     }
@@ -443,30 +558,55 @@ mod tests {
     #[test]
     fn truncate_with_token_budget_reports_truncation_at_zero_limit() {
         let s = "abcdef";
-        let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0));
-        assert_eq!(out, "…2 tokens truncated…");
-        assert_eq!(original, Some(2));
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(0), default_tokenizer());
+        assert_eq!(out, "…1 tokens truncated…");
+        assert_eq!(original, Some(1));
     }
 
     #[test]
     fn truncate_middle_tokens_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8));
+        let (out, tokens) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(8), default_tokenizer());
         assert_eq!(out, "😀😀😀😀…8 tokens truncated… line with text\n");
         assert_eq!(tokens, Some(16));
     }
 
+    #[test]
+    fn token_budget_counts_dense_text_exactly() {
+        // 20 bytes but 13 tokens: the byte heuristic would let this through.
+        let s = r#"{"a":[1,2,3],"b":{}}"#;
+        assert_eq!(token_count(s, default_tokenizer()), 13);
+        let (out, original) =
+            truncate_with_token_budget(s, TruncationPolicy::Tokens(6), default_tokenizer());
+        assert_ne!(out, s);
+        assert_eq!(original, Some(13));
+    }
+
+    #[test]
+    fn token_budget_is_measured_with_the_given_vocabulary() {
+        // 8 tokens with o200k_base but 11 with cl100k_base.
+        let s = "こんにちは世界、これはテストです。";
+        let cl100k = Tokenizer::new(EncodingKind::Cl100kBase).ok();
+        assert_eq!(
+            truncate_text(s, TruncationPolicy::Tokens(9), default_tokenizer()),
+            s
+        );
+        assert_ne!(truncate_text(s, TruncationPolicy::Tokens(9), cl100k), s);
+    }
+
     #[test]
     fn truncate_middle_bytes_handles_utf8_content() {
         let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-        let out = truncate_text(s, TruncationPolicy::Bytes(20));
+        let out = truncate_text(s, TruncationPolicy::Bytes(20), None);
         assert_eq!(out, "😀😀…21 chars truncated…with text\n");
     }
 
     #[test]
     fn truncates_across_multiple_under_limit_texts_and_reports_omitted() {
         let chunk = "alpha beta gamma delta epsilon zeta eta theta iota kappa lambda mu nu xi omicron pi rho sigma tau upsilon phi chi psi omega.\n";
-        let chunk_tokens = approx_token_count(chunk);
+        let chunk_tokens = token_count(chunk, default_tokenizer());
         assert!(chunk_tokens > 0, "chunk must consume tokens");
         let limit = chunk_tokens * 3;
         let t1 = chunk.to_string();
@@ -486,8 +626,11 @@ mod tests {
             FunctionCallOutputContentItem::InputText { text: t5 },
         ];
 
-        let output =
-            truncate_function_output_items_with_policy(&items, TruncationPolicy::Tokens(limit));
+        let output = truncate_function_output_items_with_policy(
+            &items,
+            TruncationPolicy::Tokens(limit),
+            default_tokenizer(),
+        );
 
         // Expect: t1 (full), t2 (full), image, t3 (truncated), summary mentioning 2 omitted.
         assert_eq!(output.len(), 5);
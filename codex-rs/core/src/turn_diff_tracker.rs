@@ -40,6 +40,13 @@ pub struct TurnDiffTracker {
     temp_name_to_current_path: HashMap<String, PathBuf>,
     /// Cache of known git worktree roots to avoid repeated filesystem walks.
     git_root_cache: Vec<PathBuf>,
+    /// Workspace and the upper directory of the overlay the Linux namespaces
+    /// sandbox mounts over it. Sandboxed commands' changes land in the upper
+    /// directory and leave the workspace untouched.
+    overlay: Option<(PathBuf, PathBuf)>,
+    /// External path -> file in the overlay's upper directory holding its
+    /// current contents, or `None` when the overlay deletes it.
+    overlay_sources: HashMap<PathBuf, Option<PathBuf>>,
 }
 
 impl TurnDiffTracker {
@@ -47,6 +54,44 @@ impl TurnDiffTracker {
         Self::default()
     }
 
+    /// Tracker that also reports the changes waiting in the overlay whose
+    /// upper directory `upper_dir` is mounted over `workspace`.
+    pub fn with_overlay(workspace: PathBuf, upper_dir: PathBuf) -> Self {
+        Self {
+            overlay: Some((workspace, upper_dir)),
+            ..Self::default()
+        }
+    }
+
+    /// Starts tracking every file the overlay has changed, with the
+    /// workspace's copy as its baseline.
+    fn track_overlay_changes(&mut self) {
+        let Some((workspace, upper_dir)) = self.overlay.clone() else {
+            return;
+        };
+        let mut changes = HashMap::new();
+        for (relative, source) in overlay_entries(&upper_dir) {
+            let path = workspace.join(relative);
+            changes.insert(
+                path.clone(),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: None,
+                },
+            );
+            self.overlay_sources.insert(path, source);
+        }
+        self.on_patch_begin(&changes);
+    }
+
+    /// Where the current contents of `path` live, or `None` if it is deleted.
+    fn current_source(&self, path: &Path) -> Option<PathBuf> {
+        match self.overlay_sources.get(path) {
+            Some(source) => source.clone(),
+            None => Some(path.to_path_buf()),
+        }
+    }
+
     /// Front-run apply patch calls to track the starting contents of any modified files.
     /// - Creates an in-memory baseline snapshot for files that already exist on disk when first seen.
     /// - For additions, we intentionally do not create a baseline snapshot so that diffs are proper additions.
@@ -223,6 +268,7 @@ impl TurnDiffTracker {
     /// collected before the first time they were touched by apply_patch during this turn with
     /// the current repo state.
     pub fn get_unified_diff(&mut self) -> Result<Option<String>> {
+        self.track_overlay_changes();
         let mut aggregated = String::new();
 
         // Compute diffs per tracked internal file in a stable order by external path.
@@ -265,8 +311,15 @@ impl TurnDiffTracker {
             None => return aggregated,
         };
 
-        let current_mode = file_mode_for_path(&current_external_path).unwrap_or(FileMode::Regular);
-        let right_bytes = blob_bytes(&current_external_path, current_mode);
+        let current_source = self.current_source(&current_external_path);
+        let from_overlay = current_source.as_ref() != Some(&current_external_path);
+        let current_mode = current_source
+            .as_deref()
+            .and_then(file_mode_for_path)
+            .unwrap_or(FileMode::Regular);
+        let right_bytes = current_source
+            .as_deref()
+            .and_then(|source| blob_bytes(source, current_mode));
 
         // Compute displays with &mut self before borrowing any baseline content.
         let left_display = self.relative_to_git_root_str(&baseline_external_path);
@@ -274,7 +327,7 @@ impl TurnDiffTracker {
 
         // Compute right oid before borrowing baseline content.
         let right_oid = if let Some(b) = right_bytes.as_ref() {
-            if current_mode == FileMode::Symlink {
+            if current_mode == FileMode::Symlink || from_overlay {
                 format!("{:x}", git_blob_sha1_hex_bytes(b))
             } else {
                 self.git_blob_oid_for_path(&current_external_path)
@@ -454,6 +507,49 @@ fn symlink_blob_bytes(_path: &Path) -> Option<Vec<u8>> {
     None
 }
 
+/// Files in an overlay's upper directory, relative to it, each with the file
+/// holding its contents or `None` for a whiteout, which marks a deletion.
+fn overlay_entries(upper_dir: &Path) -> Vec<(PathBuf, Option<PathBuf>)> {
+    let mut entries = Vec::new();
+    let mut pending = vec![upper_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.filter_map(std::result::Result::ok) {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(upper_dir) else {
+                continue;
+            };
+            let relative = relative.to_path_buf();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if is_whiteout(&path) {
+                entries.push((relative, None));
+            } else {
+                entries.push((relative, Some(path)));
+            }
+        }
+    }
+    entries
+}
+
+#[cfg(unix)]
+fn is_whiteout(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+    fs::symlink_metadata(path)
+        .is_ok_and(|meta| meta.file_type().is_char_device() && meta.rdev() == 0)
+}
+
+#[cfg(not(unix))]
+fn is_whiteout(_path: &Path) -> bool {
+    false
+}
+
 #[cfg(windows)]
 fn is_windows_drive_or_unc_root(p: &std::path::Path) -> bool {
     use std::path::Component;
@@ -893,4 +989,49 @@ index {ZERO_OID}..{right_oid}
         };
         assert_eq!(combined, expected_combined);
     }
+
+    #[test]
+    fn reports_changes_waiting_in_overlay() {
+        let workspace = tempdir().unwrap();
+        let upper = tempdir().unwrap();
+        fs::write(workspace.path().join("kept.txt"), "same\n").unwrap();
+        fs::write(workspace.path().join("edited.txt"), "before\n").unwrap();
+        fs::write(upper.path().join("edited.txt"), "after\n").unwrap();
+        fs::create_dir(upper.path().join("src")).unwrap();
+        fs::write(upper.path().join("src").join("new.txt"), "new\n").unwrap();
+
+        let mut tracker = TurnDiffTracker::with_overlay(
+            workspace.path().to_path_buf(),
+            upper.path().to_path_buf(),
+        );
+        let diff = tracker.get_unified_diff().unwrap().unwrap();
+        let diff = normalize_diff_for_test(&diff, workspace.path());
+        let mode = file_mode_for_path(&upper.path().join("src").join("new.txt"))
+            .unwrap_or(FileMode::Regular);
+        let before_oid = git_blob_sha1_hex("before\n");
+        let after_oid = git_blob_sha1_hex("after\n");
+        let new_oid = git_blob_sha1_hex("new\n");
+        let expected = format!(
+            r#"diff --git a/<TMP>/edited.txt b/<TMP>/edited.txt
+index {before_oid}..{after_oid}
+--- a/<TMP>/edited.txt
++++ b/<TMP>/edited.txt
+@@ -1 +1 @@
+-before
++after
+diff --git a/<TMP>/src/new.txt b/<TMP>/src/new.txt
+new file mode {mode}
+index {ZERO_OID}..{new_oid}
+--- {DEV_NULL}
++++ b/<TMP>/src/new.txt
+@@ -0,0 +1 @@
++new
+"#,
+        );
+        assert_eq!(diff, expected);
+        assert_eq!(
+            fs::read_to_string(workspace.path().join("edited.txt")).unwrap(),
+            "before\n"
+        );
+    }
 }
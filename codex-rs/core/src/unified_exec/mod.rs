@@ -33,6 +33,7 @@ use tokio::sync::Mutex;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::resource_limits::ExceededResourceLimit;
 use crate::sandboxing::SandboxPermissions;
 
 mod async_watcher;
@@ -99,6 +100,8 @@ pub(crate) struct UnifiedExecResponse {
     pub raw_output: Vec<u8>,
     pub process_id: Option<String>,
     pub exit_code: Option<i32>,
+    /// Set when the process exited after running into a resource limit.
+    pub exceeded_limit: Option<ExceededResourceLimit>,
     pub original_token_count: Option<usize>,
     pub session_command: Option<Vec<String>>,
 }
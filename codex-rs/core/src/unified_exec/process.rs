@@ -9,11 +9,17 @@ use tokio::task::JoinHandle;
 use tokio::time::Duration;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::types::ResourceLimits;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::resource_limits::CommandCgroup;
+use crate::resource_limits::ExceededResourceLimit;
+use crate::resource_limits::exceeded_limit;
+use crate::sandboxing::ExecEnv;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::default_tokenizer;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
 use codex_utils_pty::SpawnedPty;
@@ -38,13 +44,16 @@ pub(crate) struct UnifiedExecProcess {
     output_drained: Arc<Notify>,
     output_task: JoinHandle<()>,
     sandbox_type: SandboxType,
+    resource_limits: ResourceLimits,
+    /// Kept alive for as long as the process may run in it.
+    cgroup: Option<Arc<CommandCgroup>>,
 }
 
 impl UnifiedExecProcess {
     pub(super) fn new(
         process_handle: ExecCommandSession,
         initial_output_rx: tokio::sync::broadcast::Receiver<Vec<u8>>,
-        sandbox_type: SandboxType,
+        env: &ExecEnv,
     ) -> Self {
         let output_buffer = Arc::new(Mutex::new(HeadTailBuffer::default()));
         let output_notify = Arc::new(Notify::new());
@@ -75,7 +84,9 @@ impl UnifiedExecProcess {
             cancellation_token,
             output_drained,
             output_task,
-            sandbox_type,
+            sandbox_type: env.sandbox,
+            resource_limits: env.resource_limits,
+            cgroup: env.cgroup.clone(),
         }
     }
 
@@ -111,6 +122,17 @@ impl UnifiedExecProcess {
         self.process_handle.exit_code()
     }
 
+    /// The resource limit that stopped the process, once it has exited.
+    pub(super) fn exceeded_limit(&self) -> Option<ExceededResourceLimit> {
+        let exit_code = self.exit_code()?;
+        exceeded_limit(
+            &self.resource_limits,
+            self.cgroup.as_deref(),
+            self.process_handle.exit_signal(),
+            exit_code,
+        )
+    }
+
     pub(super) fn terminate(&self) {
         self.process_handle.terminate();
         self.cancellation_token.cancel();
@@ -162,6 +184,7 @@ impl UnifiedExecProcess {
             let snippet = formatted_truncate_text(
                 text,
                 TruncationPolicy::Tokens(UNIFIED_EXEC_OUTPUT_MAX_TOKENS),
+                default_tokenizer(),
             );
             let message = if snippet.is_empty() {
                 format!("Process exited with code {exit_code}")
@@ -175,14 +198,14 @@ impl UnifiedExecProcess {
 
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
-        sandbox_type: SandboxType,
+        env: &ExecEnv,
     ) -> Result<Self, UnifiedExecError> {
         let SpawnedPty {
             session: process_handle,
             output_rx,
             mut exit_rx,
         } = spawned;
-        let managed = Self::new(process_handle, output_rx, sandbox_type);
+        let managed = Self::new(process_handle, output_rx, env);
 
         let exit_ready = matches!(exit_rx.try_recv(), Ok(_) | Err(TryRecvError::Closed));
 
@@ -14,6 +14,7 @@ use crate::bash::extract_bash_command;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::exec_env::create_env;
+use crate::exec_policy::workspace_match_context;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandSource;
@@ -27,8 +28,8 @@ use crate::tools::runtimes::unified_exec::UnifiedExecRequest as UnifiedExecToolR
 use crate::tools::runtimes::unified_exec::UnifiedExecRuntime;
 use crate::tools::sandboxing::ToolCtx;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::approx_token_count;
 use crate::truncate::formatted_truncate_text;
+use crate::truncate::token_count;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::MAX_UNIFIED_EXEC_PROCESSES;
 use crate::unified_exec::ProcessEntry;
@@ -183,7 +184,11 @@ impl UnifiedExecProcessManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
+        let output = formatted_truncate_text(
+            &text,
+            TruncationPolicy::Tokens(max_tokens),
+            context.turn.tokenizer,
+        );
         let exit_code = process.exit_code();
         let has_exited = process.has_exited() || exit_code.is_some();
         let chunk_id = generate_chunk_id();
@@ -203,6 +208,7 @@ impl UnifiedExecProcessManager {
                 Arc::clone(&transcript),
                 output.clone(),
                 exit,
+                process.exceeded_limit(),
                 wall_time,
             )
             .await;
@@ -228,7 +234,7 @@ impl UnifiedExecProcessManager {
             Self::emit_waiting_status(&context.session, &context.turn, &request.command).await;
         };
 
-        let original_token_count = approx_token_count(&text);
+        let original_token_count = token_count(&text, context.turn.tokenizer);
         let response = UnifiedExecResponse {
             event_call_id: context.call_id.clone(),
             chunk_id,
@@ -241,6 +247,7 @@ impl UnifiedExecProcessManager {
                 Some(request.process_id.clone())
             },
             exit_code,
+            exceeded_limit: process.exceeded_limit(),
             original_token_count: Some(original_token_count),
             session_command: Some(request.command.clone()),
         };
@@ -287,8 +294,12 @@ impl UnifiedExecProcessManager {
         let wall_time = Instant::now().saturating_duration_since(start);
 
         let text = String::from_utf8_lossy(&collected).to_string();
-        let output = formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens));
-        let original_token_count = approx_token_count(&text);
+        let output = formatted_truncate_text(
+            &text,
+            TruncationPolicy::Tokens(max_tokens),
+            turn_ref.tokenizer,
+        );
+        let original_token_count = token_count(&text, turn_ref.tokenizer);
         let chunk_id = generate_chunk_id();
 
         // After polling, refresh_process_state tells us whether the PTY is
@@ -296,15 +307,15 @@ impl UnifiedExecProcessManager {
         // that through so the handler can tag TerminalInteraction with an
         // appropriate process_id and exit_code.
         let status = self.refresh_process_state(process_id.as_str()).await;
-        let (process_id, exit_code, event_call_id) = match status {
+        let (process_id, exit_code, exceeded_limit, event_call_id) = match status {
             ProcessStatus::Alive {
                 exit_code,
                 call_id,
                 process_id,
-            } => (Some(process_id), exit_code, call_id),
+            } => (Some(process_id), exit_code, None, call_id),
             ProcessStatus::Exited { exit_code, entry } => {
                 let call_id = entry.call_id.clone();
-                (None, exit_code, call_id)
+                (None, exit_code, entry.process.exceeded_limit(), call_id)
             }
             ProcessStatus::Unknown => {
                 return Err(UnifiedExecError::UnknownProcessId {
@@ -321,6 +332,7 @@ impl UnifiedExecProcessManager {
             raw_output: collected,
             process_id,
             exit_code,
+            exceeded_limit,
             original_token_count: Some(original_token_count),
             session_command: Some(session_command.clone()),
         };
@@ -486,7 +498,7 @@ impl UnifiedExecProcessManager {
         )
         .await
         .map_err(|err| UnifiedExecError::create_process(err.to_string()))?;
-        UnifiedExecProcess::from_spawned(spawned, env.sandbox).await
+        UnifiedExecProcess::from_spawned(spawned, env).await
     }
 
     pub(super) async fn open_session_with_sandbox(
@@ -511,6 +523,11 @@ impl UnifiedExecProcessManager {
                 context.turn.approval_policy,
                 &context.turn.sandbox_policy,
                 sandbox_permissions,
+                &workspace_match_context(
+                    cwd.clone(),
+                    &context.turn.sandbox_policy,
+                    &context.turn.cwd,
+                ),
             )
             .await;
         let req = UnifiedExecToolRequest::new(
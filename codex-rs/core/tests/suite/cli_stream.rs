@@ -212,6 +212,55 @@ async fn responses_api_stream_cli() {
     assert!(stdout.contains("fixture hello"));
 }
 
+/// Records an exec run against a mock server into a cassette, then replays it
+/// with the base URL pointing nowhere.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_replays_recorded_cassette() {
+    skip_if_no_network!();
+
+    let server = MockServer::start().await;
+    let sse = concat!(
+        "data: {\"type\":\"response.created\",\"response\":{}}\n\n",
+        "data: {\"type\":\"response.output_item.done\",\"item\":{\"type\":\"message\",\"role\":\"assistant\",\"content\":[{\"type\":\"output_text\",\"text\":\"cassette hello\"}]}}\n\n",
+        "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"r1\"}}\n\n"
+    );
+    let resp_mock = core_test_support::responses::mount_sse_once(&server, sse.to_string()).await;
+
+    let cassette_dir = TempDir::new().unwrap();
+    let cassette = cassette_dir.path().join("session.jsonl");
+    let repo_root = repo_root();
+    let bin = codex_utils_cargo_bin::cargo_bin("codex").unwrap();
+
+    let run = |base_url: String, cassette_env: &str| {
+        let home = TempDir::new().unwrap();
+        let mut cmd = AssertCommand::new(&bin);
+        cmd.arg("exec")
+            .arg("--skip-git-repo-check")
+            .arg("-C")
+            .arg(&repo_root)
+            .arg("hello?");
+        cmd.env("CODEX_HOME", home.path())
+            .env("OPENAI_API_KEY", "dummy")
+            .env("OPENAI_BASE_URL", base_url)
+            .env(cassette_env, &cassette);
+        cmd.output().unwrap()
+    };
+
+    let recorded = run(format!("{}/v1", server.uri()), "CODEX_RS_HTTP_RECORD");
+    assert!(recorded.status.success());
+    assert!(String::from_utf8_lossy(&recorded.stdout).contains("cassette hello"));
+    assert_eq!(resp_mock.requests().len(), 1);
+    let contents = std::fs::read_to_string(&cassette).unwrap();
+    assert!(contents.contains("cassette hello"));
+    assert!(!contents.contains("dummy"));
+
+    let replayed = run("http://127.0.0.1:9/v1".to_string(), "CODEX_RS_HTTP_REPLAY");
+    println!("Stderr:\n{}", String::from_utf8_lossy(&replayed.stderr));
+    assert!(replayed.status.success());
+    assert!(String::from_utf8_lossy(&replayed.stdout).contains("cassette hello"));
+    assert_eq!(resp_mock.requests().len(), 1);
+}
+
 /// End-to-end: create a session (writes rollout), verify the file, then resume and confirm append.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn integration_creates_and_checks_session_file() -> anyhow::Result<()> {
@@ -0,0 +1,147 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::features::Feature;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::Request;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path_regex;
+
+const PARENT_PROMPT: &str = "parent-prompt: delegate to a helper";
+const CHILD_DELAY: Duration = Duration::from_millis(500);
+
+fn agent_id_from(body: &str) -> String {
+    let start = body.find("agent_id: ").expect("spawn output present") + "agent_id: ".len();
+    body[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit() || *c == '-')
+        .collect()
+}
+
+fn function_call_output(body: &Value, call_id: &str) -> Option<String> {
+    let item = body["input"]
+        .as_array()?
+        .iter()
+        .find(|item| item["type"] == "function_call_output" && item["call_id"] == call_id)?;
+    item["output"].as_str().map(str::to_string)
+}
+
+fn call(call_id: &str, name: &str, args: Value) -> ResponseTemplate {
+    sse_response(sse(vec![
+        ev_response_created(call_id),
+        ev_function_call(call_id, name, &args.to_string()),
+        ev_completed(call_id),
+    ]))
+}
+
+/// Scripts the parent through spawn_agent, wait, close_agent and a second
+/// wait. Requests without the parent prompt come from the child, which answers
+/// after a delay so `wait` has to block for it.
+fn respond(request: &Request) -> ResponseTemplate {
+    let body = String::from_utf8_lossy(&request.body);
+    if !body.contains(PARENT_PROMPT) {
+        return sse_response(sse(vec![
+            ev_response_created("child-resp"),
+            ev_assistant_message("child-msg", "child finished"),
+            ev_completed("child-resp"),
+        ]))
+        .set_delay(CHILD_DELAY);
+    }
+    if body.contains("wait-again") {
+        return sse_response(sse(vec![
+            ev_response_created("parent-done"),
+            ev_assistant_message("parent-msg", "done"),
+            ev_completed("parent-done"),
+        ]));
+    }
+    let id = || agent_id_from(&body);
+    if body.contains("close-call") {
+        return call(
+            "wait-again",
+            "wait",
+            json!({ "ids": [id()], "timeout_ms": 1_000 }),
+        );
+    }
+    if body.contains("wait-call") {
+        return call("close-call", "close_agent", json!({ "id": id() }));
+    }
+    if body.contains("spawn-call") {
+        return call(
+            "wait-call",
+            "wait",
+            json!({ "ids": [id()], "timeout_ms": 10_000 }),
+        );
+    }
+    call(
+        "spawn-call",
+        "spawn_agent",
+        json!({ "message": "child-task: report back" }),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn wait_blocks_until_agent_finishes_and_close_forgets_it() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let parent_bodies = Arc::new(Mutex::new(Vec::<Value>::new()));
+    let recorded = Arc::clone(&parent_bodies);
+    Mock::given(method("POST"))
+        .and(path_regex(".*/responses$"))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+            if body.to_string().contains(PARENT_PROMPT) {
+                recorded.lock().unwrap().push(body);
+            }
+            respond(request)
+        })
+        .mount(&server)
+        .await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.features.enable(Feature::Collab);
+    });
+    let test = builder.build(&server).await?;
+    test.submit_turn(PARENT_PROMPT).await?;
+
+    let bodies = parent_bodies.lock().unwrap().clone();
+    let last = bodies.last().expect("parent requests recorded");
+
+    let wait: Value = serde_json::from_str(
+        &function_call_output(last, "wait-call").expect("wait output present"),
+    )?;
+    assert_eq!(wait[0]["last_agent_message"], "child finished");
+    assert_eq!(wait[0]["status"]["completed"], "child finished");
+
+    let close: Value = serde_json::from_str(
+        &function_call_output(last, "close-call").expect("close output present"),
+    )?;
+    assert_eq!(close["last_agent_message"], "child finished");
+
+    // Once closed, the agent's record is gone and it is reported as unknown.
+    let wait_again: Value = serde_json::from_str(
+        &function_call_output(last, "wait-again").expect("second wait output present"),
+    )?;
+    assert_eq!(wait_again[0]["status"], "not_found");
+    assert_eq!(wait_again[0].get("last_agent_message"), None);
+
+    Ok(())
+}
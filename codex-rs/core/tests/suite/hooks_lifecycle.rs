@@ -7,6 +7,7 @@ use std::time::Duration;
 use anyhow::Result;
 use codex_core::hooks::HookCommandConfig;
 use codex_core::hooks::HooksConfig;
+use codex_core::protocol::Op;
 use core_test_support::fs_wait;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::TestCodexHarness;
@@ -38,6 +39,7 @@ async fn session_start_invokes_hook() -> Result<()> {
                 matcher: None,
                 command: vec![script_str],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -80,6 +82,7 @@ async fn user_prompt_submit_invokes_hook() -> Result<()> {
                 matcher: None,
                 command: vec![script_str],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -112,3 +115,221 @@ async fn user_prompt_submit_invokes_hook() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn stop_hook_block_continues_turn() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let hook_dir = TempDir::new()?;
+    let script = hook_dir.path().join("hook.sh");
+    let reason = "tests still failing";
+    std::fs::write(
+        &script,
+        format!(
+            r#"#!/bin/bash
+set -e
+if grep -q '"stop_hook_active":true'; then
+  echo '{{}}'
+else
+  echo '{{"decision":"block","reason":"{reason}"}}'
+fi
+"#
+        ),
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    let script_str = script.to_string_lossy().to_string();
+
+    let builder = test_codex().with_config(move |config| {
+        config.hooks = Some(HooksConfig {
+            stop: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![script_str],
+                timeout_ms: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+    });
+
+    let harness = TestCodexHarness::with_builder(builder).await?;
+
+    let mock = core_test_support::responses::mount_sse_sequence(
+        harness.server(),
+        vec![
+            core_test_support::responses::sse(vec![
+                core_test_support::responses::ev_response_created("resp-1"),
+                core_test_support::responses::ev_assistant_message("msg-1", "done"),
+                core_test_support::responses::ev_completed("resp-1"),
+            ]),
+            core_test_support::responses::sse(vec![
+                core_test_support::responses::ev_response_created("resp-2"),
+                core_test_support::responses::ev_assistant_message("msg-2", "tests pass now"),
+                core_test_support::responses::ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    harness.submit("fix the bug").await?;
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2, "stop hook should force one follow-up");
+    let follow_up = requests.last().expect("follow-up request");
+    let user_texts = follow_up.message_input_texts("user");
+    assert!(
+        user_texts.iter().any(|text| text == reason),
+        "expected stop hook reason to be injected as user input"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn user_prompt_submit_hook_adds_context_and_rewrites_prompt() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let hook_dir = TempDir::new()?;
+    let script = hook_dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        r#"#!/bin/bash
+set -e
+cat >/dev/null
+echo '{"prompt":"rewritten prompt","additional_context":"ticket ABC-123: fix login"}'
+"#,
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    let script_str = script.to_string_lossy().to_string();
+
+    let builder = test_codex().with_config(move |config| {
+        config.hooks = Some(HooksConfig {
+            user_prompt_submit: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![script_str],
+                timeout_ms: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+    });
+
+    let harness = TestCodexHarness::with_builder(builder).await?;
+
+    let mock = core_test_support::responses::mount_sse_sequence(
+        harness.server(),
+        vec![core_test_support::responses::sse(vec![
+            core_test_support::responses::ev_response_created("resp-1"),
+            core_test_support::responses::ev_assistant_message("msg-1", "done"),
+            core_test_support::responses::ev_completed("resp-1"),
+        ])],
+    )
+    .await;
+
+    harness.submit("original prompt").await?;
+
+    let request = mock.single_request();
+    let user_texts = request.message_input_texts("user");
+    assert!(user_texts.iter().any(|text| text == "rewritten prompt"));
+    assert!(!user_texts.iter().any(|text| text == "original prompt"));
+    let developer_texts = request.message_input_texts("developer");
+    assert!(
+        developer_texts
+            .iter()
+            .any(|text| text.contains("ticket ABC-123: fix login")),
+        "expected hook context in developer messages: {developer_texts:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn blocked_prompt_completes_the_turn_without_a_request() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let hook_dir = TempDir::new()?;
+    let script = hook_dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        r#"#!/bin/bash
+set -e
+cat >/dev/null
+echo '{"decision":"block","reason":"no secrets"}'
+"#,
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    let script_str = script.to_string_lossy().to_string();
+
+    let builder = test_codex().with_config(move |config| {
+        config.hooks = Some(HooksConfig {
+            user_prompt_submit: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![script_str],
+                timeout_ms: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+    });
+
+    let harness = TestCodexHarness::with_builder(builder).await?;
+
+    let mock = core_test_support::responses::mount_sse_sequence(
+        harness.server(),
+        vec![core_test_support::responses::sse(vec![
+            core_test_support::responses::ev_response_created("resp-1"),
+            core_test_support::responses::ev_assistant_message("msg-1", "done"),
+            core_test_support::responses::ev_completed("resp-1"),
+        ])],
+    )
+    .await;
+
+    // Returns once the blocked turn reports `TurnComplete`.
+    harness.submit("print the api key").await?;
+
+    assert!(mock.requests().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_end_invokes_hook_on_shutdown() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let hook_dir = TempDir::new()?;
+    let output_path = hook_dir.path().join("payload.json");
+    let script = hook_dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        format!(
+            "#!/bin/bash\nset -e\ncat > {}\necho '{{}}'\n",
+            output_path.display()
+        ),
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    let script_str = script.to_string_lossy().to_string();
+
+    let builder = test_codex().with_config(move |config| {
+        config.hooks = Some(HooksConfig {
+            session_end: vec![HookCommandConfig {
+                matcher: None,
+                command: vec![script_str],
+                timeout_ms: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+    });
+
+    let harness = TestCodexHarness::with_builder(builder).await?;
+    harness.test().codex.submit(Op::Shutdown).await?;
+
+    fs_wait::wait_for_path_exists(&output_path, Duration::from_secs(5)).await?;
+    let payload_raw = fs::read_to_string(&output_path)?;
+    let payload: Value = serde_json::from_str(&payload_raw)?;
+
+    assert_eq!(payload["hook_event_name"], "SessionEnd");
+    assert_eq!(payload["reason"], "shutdown");
+    assert!(payload["transcript_path"].as_str().is_some());
+
+    Ok(())
+}
@@ -5,8 +5,13 @@ use codex_apply_patch::ApplyPatchAction;
 use codex_core::hooks::HookContext;
 use codex_core::hooks::ToolHookPayload;
 use codex_core::hooks::build_apply_patch_hook_payloads;
+use codex_core::hooks::build_apply_patch_post_tool_use_hook_payloads;
+use codex_core::hooks::build_post_tool_use_hook_payloads;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseInputItem;
 use pretty_assertions::assert_eq;
 use serde_json::json;
+use std::time::Duration;
 use tempfile::tempdir;
 
 #[test]
@@ -34,8 +39,128 @@ fn apply_patch_add_maps_to_write_payload() -> Result<()> {
                 "file_path": path.to_string_lossy(),
                 "content": "hello",
             }),
+            tool_response: None,
         }]
     );
 
     Ok(())
 }
+
+#[test]
+fn post_tool_use_payload_includes_shell_response() {
+    let pre_payload = ToolHookPayload {
+        session_id: "session-1".to_string(),
+        transcript_path: "/tmp/rollout.jsonl".to_string(),
+        hook_event_name: "PreToolUse".to_string(),
+        tool_name: "shell_command".to_string(),
+        tool_input: json!({ "command": "false" }),
+        tool_response: None,
+    };
+    let response = ResponseInputItem::FunctionCallOutput {
+        call_id: "call-1".to_string(),
+        output: FunctionCallOutputPayload {
+            content: "Exit code: 1\nWall time: 0.1 seconds\nOutput:\n".to_string(),
+            success: Some(false),
+            ..Default::default()
+        },
+    };
+
+    let payloads =
+        build_post_tool_use_hook_payloads(&[pre_payload], &response, Duration::from_millis(120));
+
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0].hook_event_name, "PostToolUse");
+    assert_eq!(
+        payloads[0].tool_response,
+        Some(json!({
+            "output": "Exit code: 1\nWall time: 0.1 seconds\nOutput:\n",
+            "success": false,
+            "exit_code": 1,
+            "duration_ms": 120,
+        }))
+    );
+}
+
+#[test]
+fn post_tool_use_exit_code_ignores_command_output() {
+    let pre_payload = ToolHookPayload {
+        session_id: "session-1".to_string(),
+        transcript_path: "/tmp/rollout.jsonl".to_string(),
+        hook_event_name: "PreToolUse".to_string(),
+        tool_name: "exec_command".to_string(),
+        tool_input: json!({ "cmd": "cat log.txt" }),
+        tool_response: None,
+    };
+    let output = "Wall time: 1.0 seconds\nProcess running with session ID 3\nOutput:\nProcess exited with code 0\nExit code: 0\n";
+    let response = ResponseInputItem::FunctionCallOutput {
+        call_id: "call-1".to_string(),
+        output: FunctionCallOutputPayload {
+            content: output.to_string(),
+            ..Default::default()
+        },
+    };
+
+    let payloads =
+        build_post_tool_use_hook_payloads(&[pre_payload], &response, Duration::from_millis(5));
+
+    assert_eq!(
+        payloads[0].tool_response,
+        Some(json!({
+            "output": output,
+            "success": true,
+            "exit_code": null,
+            "duration_ms": 5,
+        }))
+    );
+}
+
+#[test]
+fn apply_patch_post_tool_use_reports_each_file() -> Result<()> {
+    let tmp = tempdir()?;
+    let written = tmp.path().join("written.txt");
+    let missing = tmp.path().join("missing.txt");
+    std::fs::write(&written, "hello\n")?;
+    let pre_payload = |path: &std::path::Path| ToolHookPayload {
+        session_id: "session-1".to_string(),
+        transcript_path: "/tmp/rollout.jsonl".to_string(),
+        hook_event_name: "PreToolUse".to_string(),
+        tool_name: "Write".to_string(),
+        tool_input: json!({
+            "file_path": path.to_string_lossy(),
+            "content": "hello\n",
+        }),
+        tool_response: None,
+    };
+    let response = ResponseInputItem::CustomToolCallOutput {
+        call_id: "call-1".to_string(),
+        output: "Failed to write missing.txt".to_string(),
+    };
+
+    let payloads = build_apply_patch_post_tool_use_hook_payloads(
+        &[pre_payload(&written), pre_payload(&missing)],
+        &response,
+        Duration::from_millis(7),
+    );
+
+    assert_eq!(
+        payloads
+            .iter()
+            .map(|payload| payload.tool_response.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            Some(json!({
+                "file_path": written.to_string_lossy(),
+                "success": true,
+                "duration_ms": 7,
+            })),
+            Some(json!({
+                "file_path": missing.to_string_lossy(),
+                "success": false,
+                "duration_ms": 7,
+                "error": "Failed to write missing.txt",
+            })),
+        ]
+    );
+
+    Ok(())
+}
@@ -54,6 +54,7 @@ fn apply_patch_update_maps_to_edit_payload() -> Result<()> {
                 "old_string": "old\n",
                 "new_string": "new\n",
             }),
+            tool_response: None,
         }]
     );
 
@@ -43,6 +43,7 @@ async fn post_tool_use_invokes_hook() -> Result<()> {
                 matcher: Some("Edit".to_string()),
                 command: vec![script_str],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -81,6 +82,12 @@ async fn post_tool_use_invokes_hook() -> Result<()> {
 
     assert_eq!(payload["hook_event_name"], "PostToolUse");
     assert_eq!(payload["tool_name"], "Edit");
+    assert_eq!(payload["tool_response"]["success"], true);
+    assert_eq!(
+        payload["tool_response"]["file_path"],
+        target.to_string_lossy().as_ref()
+    );
+    assert!(payload["tool_response"]["duration_ms"].as_u64().is_some());
 
     Ok(())
 }
@@ -58,6 +58,7 @@ echo '{"decision":"block","reason":"blocked by hook"}'
                 matcher: Some("Edit".to_string()),
                 command: vec![hook_command],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -122,6 +123,7 @@ echo '{"decision":"block","reason":"blocked by hook"}'
                 matcher: Some("shell_command".to_string()),
                 command: vec![script_str],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -158,6 +160,67 @@ echo '{"decision":"block","reason":"blocked by hook"}'
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pre_tool_use_rewrites_shell_command() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let hook_dir = TempDir::new()?;
+    let script = hook_dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        r#"#!/bin/bash
+set -e
+cat >/dev/null
+echo '{"decision":"allow","tool_input":{"command":"echo rewritten-by-hook","timeout_ms":1000}}'
+"#,
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    let script_str = script.to_string_lossy().to_string();
+
+    let builder = test_codex().with_config(move |config| {
+        config.hooks = Some(HooksConfig {
+            pre_tool_use: vec![HookCommandConfig {
+                matcher: Some("shell_command".to_string()),
+                command: vec![script_str],
+                timeout_ms: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+    });
+
+    let harness = TestCodexHarness::with_builder(builder).await?;
+    let call_id = "rewritten-shell-command";
+    let args = json!({
+        "command": "echo original",
+        "timeout_ms": 1_000,
+    });
+
+    mount_sse_sequence(
+        harness.server(),
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "shell_command", &serde_json::to_string(&args)?),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    harness.submit("run command").await?;
+
+    let output = harness.function_call_stdout(call_id).await;
+    assert!(output.contains("rewritten-by-hook"));
+    assert!(!output.contains("original"));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn pre_tool_use_block_injects_user_prompt() -> Result<()> {
     skip_if_no_network!(Ok(()));
@@ -184,6 +247,7 @@ echo '{{"decision":"block","reason":"{reason}"}}'
                 matcher: Some("shell_command".to_string()),
                 command: vec![script_str],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -257,6 +321,7 @@ echo '{{"decision":"block","reason":"{reason}"}}'
                 matcher: Some("Edit".to_string()),
                 command: vec![hook_command],
                 timeout_ms: None,
+                ..Default::default()
             }],
             ..Default::default()
         });
@@ -43,6 +43,48 @@ echo '{"decision":"block","reason":"policy"}'
         HookDecision {
             decision: Some(HookDecisionKind::Block),
             reason: Some("policy".to_string()),
+            tool_input: None,
+            prompt: None,
+            additional_context: None,
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hook_runner_parses_rewritten_tool_input() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let script = temp_dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        r#"#!/bin/bash
+set -e
+cat >/dev/null
+echo '{"decision":"allow","tool_input":{"command":"git commit"}}'
+"#,
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+
+    let runner = HookRunner::new(Duration::from_secs(2));
+    let result = runner
+        .run(
+            vec![
+                "/bin/bash".to_string(),
+                script.to_string_lossy().to_string(),
+            ],
+            json!({ "hook_event_name": "PreToolUse" }),
+            None,
+        )
+        .await?;
+
+    assert_eq!(
+        result,
+        HookDecision {
+            decision: Some(HookDecisionKind::Allow),
+            reason: None,
+            tool_input: Some(json!({ "command": "git commit" })),
+            prompt: None,
+            additional_context: None,
         }
     );
     Ok(())
@@ -77,3 +119,41 @@ echo 'not json'
     assert!(matches!(result, Err(HookError::InvalidResponse(_))));
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hook_runner_treats_exit_code_2_as_block() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let script = temp_dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        r#"#!/bin/bash
+echo 'run the tests first' >&2
+exit 2
+"#,
+    )?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+
+    let runner = HookRunner::new(Duration::from_secs(2));
+    let result = runner
+        .run(
+            vec![
+                "/bin/bash".to_string(),
+                script.to_string_lossy().to_string(),
+            ],
+            json!({ "hook_event_name": "PreToolUse" }),
+            None,
+        )
+        .await?;
+
+    assert_eq!(
+        result,
+        HookDecision {
+            decision: Some(HookDecisionKind::Block),
+            reason: Some("run the tests first".to_string()),
+            tool_input: None,
+            prompt: None,
+            additional_context: None,
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,400 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Drives full turns against a provider configured with the Messages wire API
+//! and checks both the streamed events and the request bodies sent back.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use anyhow::Result;
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::sse_response;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::Request;
+use wiremock::matchers::method;
+use wiremock::matchers::path_regex;
+
+/// Outgoing requests seen by the mock provider, in order.
+#[derive(Clone, Default)]
+struct Recorded(Arc<Mutex<Vec<Request>>>);
+
+impl Recorded {
+    fn bodies(&self) -> Vec<Value> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| serde_json::from_slice(&request.body).unwrap())
+            .collect()
+    }
+
+    fn header(&self, index: usize, name: &str) -> Option<String> {
+        let requests = self.0.lock().unwrap();
+        let value = requests.get(index)?.headers.get(name)?;
+        value.to_str().ok().map(str::to_string)
+    }
+}
+
+/// Answers each `/messages` request with the next stream in `streams`.
+async fn mount_messages_sequence(server: &MockServer, streams: Vec<String>) -> Recorded {
+    let recorded = Recorded::default();
+    let requests = Arc::clone(&recorded.0);
+    let calls = AtomicUsize::new(0);
+    Mock::given(method("POST"))
+        .and(path_regex(".*/messages$"))
+        .respond_with(move |request: &Request| {
+            requests.lock().unwrap().push(request.clone());
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            let stream = streams.get(call).expect("unexpected extra request");
+            sse_response(stream.clone())
+        })
+        .mount(server)
+        .await;
+    recorded
+}
+
+/// Renders Messages events as an SSE body; every event carries its data.
+fn messages_sse(events: Vec<Value>) -> String {
+    events
+        .into_iter()
+        .map(|event| {
+            let kind = event["type"].as_str().unwrap_or_default().to_string();
+            format!("event: {kind}\ndata: {event}\n\n")
+        })
+        .collect()
+}
+
+fn message_start(id: &str) -> Value {
+    json!({
+        "type": "message_start",
+        "message": {"id": id, "usage": {"input_tokens": 10, "output_tokens": 0}},
+    })
+}
+
+fn message_end(stop_reason: &str) -> Vec<Value> {
+    vec![
+        json!({
+            "type": "message_delta",
+            "delta": {"stop_reason": stop_reason},
+            "usage": {"output_tokens": 5},
+        }),
+        json!({"type": "message_stop"}),
+    ]
+}
+
+fn text_block(index: u32, deltas: &[&str]) -> Vec<Value> {
+    let mut events = vec![json!({
+        "type": "content_block_start",
+        "index": index,
+        "content_block": {"type": "text", "text": ""},
+    })];
+    events.extend(deltas.iter().map(|text| {
+        json!({
+            "type": "content_block_delta",
+            "index": index,
+            "delta": {"type": "text_delta", "text": text},
+        })
+    }));
+    events.push(json!({"type": "content_block_stop", "index": index}));
+    events
+}
+
+fn thinking_block(index: u32, thinking: &str, signature: &str) -> Vec<Value> {
+    vec![
+        json!({
+            "type": "content_block_start",
+            "index": index,
+            "content_block": {"type": "thinking", "thinking": ""},
+        }),
+        json!({
+            "type": "content_block_delta",
+            "index": index,
+            "delta": {"type": "thinking_delta", "thinking": thinking},
+        }),
+        json!({
+            "type": "content_block_delta",
+            "index": index,
+            "delta": {"type": "signature_delta", "signature": signature},
+        }),
+        json!({"type": "content_block_stop", "index": index}),
+    ]
+}
+
+fn tool_use_block(index: u32, id: &str, name: &str, input: &Value) -> Vec<Value> {
+    let input = input.to_string();
+    let (first, rest) = input.split_at(input.len() / 2);
+    vec![
+        json!({
+            "type": "content_block_start",
+            "index": index,
+            "content_block": {"type": "tool_use", "id": id, "name": name, "input": {}},
+        }),
+        json!({
+            "type": "content_block_delta",
+            "index": index,
+            "delta": {"type": "input_json_delta", "partial_json": first},
+        }),
+        json!({
+            "type": "content_block_delta",
+            "index": index,
+            "delta": {"type": "input_json_delta", "partial_json": rest},
+        }),
+        json!({"type": "content_block_stop", "index": index}),
+    ]
+}
+
+fn stream(id: &str, blocks: Vec<Vec<Value>>, stop_reason: &str) -> String {
+    let mut events = vec![message_start(id)];
+    events.extend(blocks.into_iter().flatten());
+    events.extend(message_end(stop_reason));
+    messages_sse(events)
+}
+
+async fn messages_codex(server: &MockServer) -> Result<TestCodex> {
+    test_codex()
+        .with_model("gpt-5.1")
+        .with_config(|config| {
+            config.model_provider.wire_api = WireApi::Messages;
+        })
+        .build(server)
+        .await
+}
+
+/// Content blocks of the last message with `role` in a request body.
+fn last_message_content(body: &Value, role: &str) -> Vec<Value> {
+    body["messages"]
+        .as_array()
+        .expect("messages array")
+        .iter()
+        .rev()
+        .find(|message| message["role"] == role)
+        .and_then(|message| message["content"].as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn tool_result(body: &Value, tool_use_id: &str) -> Value {
+    last_message_content(body, "user")
+        .into_iter()
+        .find(|block| block["type"] == "tool_result" && block["tool_use_id"] == tool_use_id)
+        .unwrap_or_else(|| panic!("tool_result for {tool_use_id} missing from {body}"))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn text_deltas_stream_into_the_agent_message() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let recorded = mount_messages_sequence(
+        &server,
+        vec![stream(
+            "msg_1",
+            vec![text_block(0, &["Hel", "lo ", "there"])],
+            "end_turn",
+        )],
+    )
+    .await;
+    let test = messages_codex(&server).await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "say hello".into(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut deltas = Vec::new();
+    let mut message = None;
+    wait_for_event(&test.codex, |event| {
+        match event {
+            EventMsg::AgentMessageDelta(event) => deltas.push(event.delta.clone()),
+            EventMsg::AgentMessage(event) => message = Some(event.message.clone()),
+            _ => {}
+        }
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    assert_eq!(deltas, vec!["Hel", "lo ", "there"]);
+    assert_eq!(message.as_deref(), Some("Hello there"));
+
+    let bodies = recorded.bodies();
+    assert_eq!(bodies.len(), 1);
+    let body = &bodies[0];
+    assert_eq!(body["model"], "gpt-5.1");
+    assert_eq!(body["stream"], true);
+    assert!(body["system"].as_str().is_some_and(|s| !s.is_empty()));
+    assert!(body["max_tokens"].as_u64().is_some_and(|n| n > 0));
+    assert_eq!(
+        last_message_content(body, "user").last(),
+        Some(&json!({"type": "text", "text": "say hello"}))
+    );
+    assert_eq!(
+        recorded.header(0, "anthropic-version").as_deref(),
+        Some("2023-06-01")
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn tool_use_round_trips_as_tool_result() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let input = json!({"command": "echo messages-tool-output", "timeout_ms": 5_000});
+    let recorded = mount_messages_sequence(
+        &server,
+        vec![
+            stream(
+                "msg_1",
+                vec![
+                    text_block(0, &["Running it."]),
+                    tool_use_block(1, "toolu_shell", "shell_command", &input),
+                ],
+                "tool_use",
+            ),
+            stream("msg_2", vec![text_block(0, &["done"])], "end_turn"),
+        ],
+    )
+    .await;
+    let test = messages_codex(&server).await?;
+
+    test.submit_turn("run echo").await?;
+
+    let bodies = recorded.bodies();
+    assert_eq!(bodies.len(), 2);
+    let second = &bodies[1];
+
+    assert_eq!(
+        last_message_content(second, "assistant"),
+        vec![
+            json!({"type": "text", "text": "Running it."}),
+            json!({
+                "type": "tool_use",
+                "id": "toolu_shell",
+                "name": "shell_command",
+                "input": input,
+            }),
+        ]
+    );
+
+    let result = tool_result(second, "toolu_shell");
+    assert_eq!(result.get("is_error"), None);
+    let content = result["content"].as_str().expect("text tool result");
+    assert!(
+        content.contains("messages-tool-output"),
+        "expected command output in tool result: {content}"
+    );
+
+    // The tool definitions use the Messages shape.
+    let tools = second["tools"].as_array().expect("tools array");
+    let shell = tools
+        .iter()
+        .find(|tool| tool["name"] == "shell_command")
+        .expect("shell_command tool");
+    assert_eq!(shell["input_schema"]["type"], "object");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn thinking_signature_is_sent_back_on_the_next_request() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let input = json!({"command": "echo thinking", "timeout_ms": 5_000});
+    let recorded = mount_messages_sequence(
+        &server,
+        vec![
+            stream(
+                "msg_1",
+                vec![
+                    thinking_block(0, "Echo first.", "sig-abc"),
+                    tool_use_block(1, "toolu_think", "shell_command", &input),
+                ],
+                "tool_use",
+            ),
+            stream("msg_2", vec![text_block(0, &["done"])], "end_turn"),
+        ],
+    )
+    .await;
+    let test = messages_codex(&server).await?;
+
+    test.submit_turn("think, then echo").await?;
+
+    let bodies = recorded.bodies();
+    assert_eq!(bodies.len(), 2);
+    let assistant = last_message_content(&bodies[1], "assistant");
+    assert_eq!(
+        assistant.first(),
+        Some(&json!({
+            "type": "thinking",
+            "thinking": "Echo first.",
+            "signature": "sig-abc",
+        }))
+    );
+    assert_eq!(
+        assistant.get(1).map(|block| &block["type"]),
+        Some(&json!("tool_use"))
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn failed_tool_call_is_marked_as_error() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let recorded = mount_messages_sequence(
+        &server,
+        vec![
+            stream(
+                "msg_1",
+                vec![tool_use_block(
+                    0,
+                    "toolu_missing",
+                    "no_such_tool",
+                    &json!({"arg": 1}),
+                )],
+                "tool_use",
+            ),
+            stream("msg_2", vec![text_block(0, &["ok"])], "end_turn"),
+        ],
+    )
+    .await;
+    let test = messages_codex(&server).await?;
+
+    test.submit_turn("call a missing tool").await?;
+
+    let bodies = recorded.bodies();
+    assert_eq!(bodies.len(), 2);
+    let result = tool_result(&bodies[1], "toolu_missing");
+    assert_eq!(result["is_error"], true);
+    assert!(
+        result["content"]
+            .as_str()
+            .is_some_and(|content| content.contains("no_such_tool")),
+        "expected error naming the tool: {result}"
+    );
+
+    Ok(())
+}
@@ -22,6 +22,7 @@ mod auth_refresh;
 mod cli_stream;
 mod client;
 mod codex_delegate;
+mod collab;
 mod compact;
 mod compact_remote;
 mod compact_resume_fork;
@@ -42,12 +43,14 @@ mod json_result;
 mod list_dir;
 mod list_models;
 mod live_cli;
+mod messages_api;
 mod model_info_overrides;
 mod model_overrides;
 mod model_tools;
 mod models_etag_responses;
 mod otel;
 mod prompt_caching;
+mod provider_fallback;
 mod quota_exceeded;
 mod read_file;
 mod remote_models;
@@ -66,6 +69,7 @@ mod stream_error_allows_next_turn;
 mod stream_no_completed;
 mod text_encoding_fix;
 mod tool_harness;
+mod tool_output_spill;
 mod tool_parallelism;
 mod tools;
 mod truncation;
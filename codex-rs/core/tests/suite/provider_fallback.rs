@@ -0,0 +1,131 @@
+//! Verifies that a turn fails over to the configured fallback provider when
+//! the primary provider is unavailable.
+
+use anyhow::Result;
+use codex_core::ModelProviderInfo;
+use codex_core::ProviderFallback;
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::RolloutItem;
+use codex_core::protocol::RolloutLine;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_response_once;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::ResponseTemplate;
+
+fn provider(base_url: String) -> ModelProviderInfo {
+    ModelProviderInfo {
+        name: "backup".into(),
+        base_url: Some(base_url),
+        env_key: None,
+        env_key_instructions: None,
+        experimental_bearer_token: None,
+        wire_api: WireApi::Responses,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2_000),
+        requires_openai_auth: false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn server_error_fails_over_to_fallback_provider() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let primary = start_mock_server().await;
+    let backup = start_mock_server().await;
+
+    let primary_mock = mount_response_once(&primary, ResponseTemplate::new(500)).await;
+    let backup_mock = mount_sse_once(
+        &backup,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "served by backup"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let backup_url = format!("{}/v1", backup.uri());
+    let test = test_codex()
+        .with_config(move |config| {
+            config.model_provider.request_max_retries = Some(0);
+            config.model_provider.stream_max_retries = Some(0);
+            config.model_provider_fallbacks = vec![ProviderFallback {
+                provider_id: "backup".to_string(),
+                provider: provider(backup_url),
+                model: Some("backup-model".to_string()),
+            }];
+        })
+        .build(&primary)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let message = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::BackgroundEvent(event) if event.message.contains("switching to") => {
+            Some(event.message.clone())
+        }
+        _ => None,
+    })
+    .await;
+    assert!(
+        message.contains("switching to `backup` (backup-model)"),
+        "unexpected background event: {message}"
+    );
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    assert_eq!(primary_mock.requests().len(), 1);
+    let backup_request = backup_mock.single_request().body_json();
+    assert_eq!(backup_request["model"], "backup-model");
+    // The request is built from the fallback model's metadata: unlike the
+    // primary model, the unknown `backup-model` does not support reasoning.
+    assert!(!primary_mock.requests()[0].body_json()["reasoning"].is_null());
+    assert!(backup_request["reasoning"].is_null());
+
+    test.codex.submit(Op::Shutdown).await?;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::ShutdownComplete)
+    })
+    .await;
+
+    let rollout = std::fs::read_to_string(&test.session_configured.rollout_path)?;
+    let providers: Vec<Option<String>> = rollout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RolloutLine>(line).ok())
+        .filter_map(|line| match line.item {
+            RolloutItem::TurnContext(item) => Some(item.model_provider),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        providers,
+        vec![Some("openai".to_string()), Some("backup".to_string())]
+    );
+
+    Ok(())
+}
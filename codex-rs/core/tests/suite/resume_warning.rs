@@ -26,6 +26,7 @@ fn resume_history(
         approval_policy: config.approval_policy.value(),
         sandbox_policy: config.sandbox_policy.get().clone(),
         model: previous_model.to_string(),
+        model_provider: None,
         effort: config.model_reasoning_effort,
         summary: config.model_reasoning_summary,
         base_instructions: None,
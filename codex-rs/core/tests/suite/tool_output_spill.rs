@@ -0,0 +1,101 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::features::Feature;
+use codex_core::protocol::SandboxPolicy;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+// Truncated shell output is saved, and read_tool_output returns the elided lines.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn truncated_shell_output_can_be_read_back() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let mut builder = test_codex().with_model("gpt-5.1").with_config(|config| {
+        config.features.enable(Feature::ToolOutputSpill);
+        config.tool_output_token_limit = Some(100);
+    });
+    let fixture = builder.build(&server).await?;
+
+    let shell_call_id = "shell-spill";
+    let read_call_id = "read-spill";
+    let shell_args = json!({
+        "command": "seq 1 400",
+        "timeout_ms": 5_000,
+    });
+    let read_args = json!({
+        "id": shell_call_id,
+        "offset": 200,
+        "limit": 2,
+    });
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(
+                    shell_call_id,
+                    "shell_command",
+                    &serde_json::to_string(&shell_args)?,
+                ),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_function_call(
+                    read_call_id,
+                    "read_tool_output",
+                    &serde_json::to_string(&read_args)?,
+                ),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-3"),
+            ]),
+        ],
+    )
+    .await;
+
+    fixture
+        .submit_turn_with_policy("run a noisy command", SandboxPolicy::DangerFullAccess)
+        .await?;
+
+    let shell_output = mock
+        .function_call_output_text(shell_call_id)
+        .context("shell output present")?;
+    assert!(
+        shell_output.contains("tokens truncated"),
+        "expected truncated shell output: {shell_output}"
+    );
+    assert!(
+        shell_output.contains("saved with id `shell-spill`"),
+        "expected spill notice in shell output: {shell_output}"
+    );
+    assert!(
+        !shell_output.contains("\n200\n"),
+        "line 200 should have been elided: {shell_output}"
+    );
+
+    let read_output = mock
+        .function_call_output_text(read_call_id)
+        .context("read_tool_output output present")?;
+    assert_eq!(
+        read_output,
+        "L200: 200\nL201: 201\n[More lines available; continue with offset 202.]"
+    );
+
+    Ok(())
+}
@@ -65,6 +65,7 @@ use codex_core::config::find_codex_home;
 use codex_core::is_dangerous_command::command_might_be_dangerous;
 use codex_core::sandboxing::SandboxPermissions;
 use codex_execpolicy::Decision;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::RuleMatch;
 use rmcp::ErrorData as McpError;
@@ -173,10 +174,12 @@ pub async fn main_execve_wrapper() -> anyhow::Result<()> {
 ///
 /// `file` is the absolute, canonical path to the executable to run, i.e. the first arg to exec.
 /// `argv` is the argv, including the program name (`argv[0]`).
+/// `match_context` is what `workspace_path()` matchers in the policy check paths against.
 pub(crate) fn evaluate_exec_policy(
     policy: &Policy,
     file: &Path,
     argv: &[String],
+    match_context: &MatchContext,
     preserve_program_paths: bool,
 ) -> Result<ExecPolicyOutcome, McpError> {
     let program_name = format_program_name(file, preserve_program_paths).ok_or_else(|| {
@@ -189,7 +192,7 @@ pub(crate) fn evaluate_exec_policy(
         // Use the normalized program name instead of argv[0].
         .chain(argv.iter().skip(1).cloned())
         .collect();
-    let evaluation = policy.check(&command, &|cmd| {
+    let evaluation = policy.check_with_context(&command, match_context, &|cmd| {
         if command_might_be_dangerous(cmd) {
             Decision::Prompt
         } else {
@@ -264,7 +267,8 @@ mod tests {
         let file = Path::new("/bin/rm");
         let argv = vec!["rm".to_string(), "-rf".to_string(), "/".to_string()];
 
-        let outcome = evaluate_exec_policy(&policy, file, &argv, false).expect("policy evaluation");
+        let outcome = evaluate_exec_policy(&policy, file, &argv, &MatchContext::default(), false)
+            .expect("policy evaluation");
 
         assert_eq!(
             outcome,
@@ -293,7 +297,8 @@ mod tests {
             "value".to_string(),
         ];
 
-        let outcome = evaluate_exec_policy(&policy, file, &argv, true).expect("policy evaluation");
+        let outcome = evaluate_exec_policy(&policy, file, &argv, &MatchContext::default(), true)
+            .expect("policy evaluation");
 
         assert_eq!(
             outcome,
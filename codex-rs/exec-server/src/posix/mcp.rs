@@ -126,6 +126,7 @@ impl ExecTool {
                     sandbox_policy: SandboxPolicy::ReadOnly,
                     codex_linux_sandbox_exe: None,
                     sandbox_cwd: PathBuf::from(&params.workdir),
+                    deny_read: Vec::new(),
                 });
         let escalate_server = EscalateServer::new(
             self.bash_path.clone(),
@@ -134,6 +135,7 @@ impl ExecTool {
                 self.policy.clone(),
                 context,
                 stopwatch.clone(),
+                sandbox_state.clone(),
                 self.preserve_program_paths,
             ),
         );
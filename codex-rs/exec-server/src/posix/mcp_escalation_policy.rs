@@ -1,6 +1,8 @@
 use std::path::Path;
 
+use codex_core::SandboxState;
 use codex_core::sandboxing::SandboxPermissions;
+use codex_core::workspace_match_context;
 use codex_execpolicy::Policy;
 use rmcp::ErrorData as McpError;
 use rmcp::RoleServer;
@@ -34,6 +36,8 @@ pub(crate) struct McpEscalationPolicy {
     policy: Arc<RwLock<Policy>>,
     context: RequestContext<RoleServer>,
     stopwatch: Stopwatch,
+    /// Sandbox the shell runs in, which determines the workspace for `workspace_path()` rules.
+    sandbox_state: SandboxState,
     preserve_program_paths: bool,
 }
 
@@ -42,12 +46,14 @@ impl McpEscalationPolicy {
         policy: Arc<RwLock<Policy>>,
         context: RequestContext<RoleServer>,
         stopwatch: Stopwatch,
+        sandbox_state: SandboxState,
         preserve_program_paths: bool,
     ) -> Self {
         Self {
             policy,
             context,
             stopwatch,
+            sandbox_state,
             preserve_program_paths,
         }
     }
@@ -104,9 +110,19 @@ impl EscalationPolicy for McpEscalationPolicy {
         argv: &[String],
         workdir: &Path,
     ) -> Result<EscalateAction, rmcp::ErrorData> {
+        let match_context = workspace_match_context(
+            workdir.to_path_buf(),
+            &self.sandbox_state.sandbox_policy,
+            &self.sandbox_state.sandbox_cwd,
+        );
         let policy = self.policy.read().await;
-        let outcome =
-            crate::posix::evaluate_exec_policy(&policy, file, argv, self.preserve_program_paths)?;
+        let outcome = crate::posix::evaluate_exec_policy(
+            &policy,
+            file,
+            argv,
+            &match_context,
+            self.preserve_program_paths,
+        )?;
         let action = match outcome {
             ExecPolicyOutcome::Allow {
                 sandbox_permissions,
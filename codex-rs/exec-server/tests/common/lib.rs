@@ -91,6 +91,7 @@ where
         sandbox_policy: SandboxPolicy::ReadOnly,
         codex_linux_sandbox_exe,
         sandbox_cwd: sandbox_cwd.as_ref().to_path_buf(),
+        deny_read: Vec::new(),
     };
     send_sandbox_state_update(sandbox_state, service).await
 }
@@ -118,6 +119,7 @@ where
         },
         codex_linux_sandbox_exe,
         sandbox_cwd: writable_folder.as_ref().to_path_buf(),
+        deny_read: Vec::new(),
     };
     send_sandbox_state_update(sandbox_state, service).await
 }
@@ -14,6 +14,7 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::NetworkAccessDeniedEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -173,6 +174,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
+            EventMsg::NetworkAccessDenied(NetworkAccessDeniedEvent { host, port }) => {
+                ts_msg!(
+                    self,
+                    "{} sandbox blocked network access to {host}:{port}",
+                    "warning:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -597,7 +605,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
             | EventMsg::ThreadRolledBack(_)
-            | EventMsg::HookActivity(_) => {}
+            | EventMsg::TurnSteered(_)
+            | EventMsg::HookActivity(_)
+            | EventMsg::SubAgentActivity(_) => {}
         }
         CodexStatus::Running
     }
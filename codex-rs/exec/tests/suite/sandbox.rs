@@ -50,6 +50,7 @@ async fn spawn_command_under_sandbox(
         command_cwd,
         sandbox_policy,
         sandbox_cwd,
+        Default::default(),
         stdio_policy,
         env,
     )
@@ -7,6 +7,7 @@ use clap::Parser;
 use serde::Serialize;
 
 use crate::Decision;
+use crate::MatchContext;
 use crate::Policy;
 use crate::PolicyParser;
 use crate::RuleMatch;
@@ -22,6 +23,11 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub pretty: bool,
 
+    /// Workspace root that `workspace_path()` matchers resolve against (defaults to the
+    /// current directory).
+    #[arg(long = "workspace", value_name = "DIR")]
+    pub workspace: Option<PathBuf>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -36,7 +42,10 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
-        let matched_rules = policy.matches_for_command(&self.command, None);
+        let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+        let workspace = self.workspace.clone().unwrap_or_else(|| cwd.clone());
+        let context = MatchContext::new(cwd, vec![workspace]);
+        let matched_rules = policy.matches_for_command_with_context(&self.command, &context, None);
 
         let json = format_matches_json(&matched_rules, self.pretty)?;
         println!("{json}");
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use clap::ValueEnum;
+
+use crate::lint::LintReport;
+use crate::lint::lint_policy_files;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum LintFormat {
+    #[default]
+    Text,
+    Json,
+    Junit,
+}
+
+/// Arguments for linting a set of execpolicy files.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyLintCommand {
+    /// Paths to execpolicy rule files to lint (repeatable), lowest precedence first. When
+    /// omitted, `codex execpolicy lint` lints the rules loaded from the config layer stack.
+    #[arg(short = 'r', long = "rules", value_name = "PATH")]
+    pub rules: Vec<PathBuf>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+    pub format: LintFormat,
+
+    /// Exit with a failure status when there are warnings, not just errors.
+    #[arg(long)]
+    pub deny_warnings: bool,
+}
+
+impl ExecPolicyLintCommand {
+    /// Lint `--rules`, print the report, and return whether the policy passed.
+    pub fn run(&self) -> Result<bool> {
+        if self.rules.is_empty() {
+            anyhow::bail!("pass at least one `--rules` file to lint");
+        }
+        self.run_with_policy_files(&self.rules)
+    }
+
+    /// Lint `policy_paths` instead of `--rules`, print the report, and return whether the policy
+    /// passed.
+    pub fn run_with_policy_files(&self, policy_paths: &[PathBuf]) -> Result<bool> {
+        let report = lint_policy_files(policy_paths);
+        println!("{}", self.render(&report)?);
+        Ok(report.passed(self.deny_warnings))
+    }
+
+    fn render(&self, report: &LintReport) -> Result<String> {
+        Ok(match self.format {
+            LintFormat::Text => report.to_text(),
+            LintFormat::Json => serde_json::to_string_pretty(report)?,
+            LintFormat::Junit => report.to_junit(self.deny_warnings),
+        })
+    }
+}
@@ -2,6 +2,9 @@ pub mod amend;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
+pub mod execpolicylint;
+pub mod lint;
+pub mod matcher;
 pub mod parser;
 pub mod policy;
 pub mod rule;
@@ -12,6 +15,10 @@ pub use decision::Decision;
 pub use error::Error;
 pub use error::Result;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use execpolicylint::ExecPolicyLintCommand;
+pub use lint::LintReport;
+pub use lint::lint_policy_files;
+pub use matcher::MatchContext;
 pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::Policy;
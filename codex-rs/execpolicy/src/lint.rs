@@ -0,0 +1,497 @@
+use std::any::Any;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use shlex::try_join;
+
+use crate::matcher::FlagConstraints;
+use crate::matcher::MatchContext;
+use crate::parser::PolicyParser;
+use crate::parser::RuleDefinition;
+use crate::rule::PatternToken;
+use crate::rule::PrefixRule;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintCode {
+    /// The file could not be read or parsed, or one of its examples failed.
+    ParseError,
+    /// The rule has no `match` examples.
+    UncoveredRule,
+    /// A `not_match` example names a different program, so it can never exercise the rule.
+    UnusedExample,
+    /// Another rule matches everything this rule matches with an equal or stricter decision.
+    ShadowedRule,
+    /// Rules with the same pattern disagree on the decision.
+    ConflictingDecision,
+}
+
+impl LintCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ParseError => "parse-error",
+            Self::UncoveredRule => "uncovered-rule",
+            Self::UnusedExample => "unused-example",
+            Self::ShadowedRule => "shadowed-rule",
+            Self::ConflictingDecision => "conflicting-decision",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintDiagnostic {
+    pub severity: LintSeverity,
+    pub code: LintCode,
+    pub message: String,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Index into [`LintReport::rules`] of the rule definition the diagnostic is about.
+    #[serde(skip)]
+    pub rule: Option<usize>,
+}
+
+/// A `prefix_rule(...)` call as reported by the linter.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintedRule {
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub pattern: String,
+    pub examples: usize,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub files: Vec<String>,
+    pub rules: Vec<LintedRule>,
+    pub covered_rules: usize,
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintReport {
+    pub fn error_count(&self) -> usize {
+        self.count(LintSeverity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(LintSeverity::Warning)
+    }
+
+    fn count(&self, severity: LintSeverity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+
+    /// Whether the policy passes, treating warnings as failures when `deny_warnings` is set.
+    pub fn passed(&self, deny_warnings: bool) -> bool {
+        self.error_count() == 0 && (!deny_warnings || self.warning_count() == 0)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for diagnostic in &self.diagnostics {
+            let location = match diagnostic.line {
+                Some(line) => format!("{}:{line}", diagnostic.source),
+                None => diagnostic.source.clone(),
+            };
+            let severity = match diagnostic.severity {
+                LintSeverity::Warning => "warning",
+                LintSeverity::Error => "error",
+            };
+            let _ = writeln!(
+                out,
+                "{location}: {severity}[{}]: {}",
+                diagnostic.code.as_str(),
+                diagnostic.message
+            );
+        }
+        let examples: usize = self.rules.iter().map(|rule| rule.examples).sum();
+        let _ = write!(
+            out,
+            "{} files, {} rules ({} with examples), {examples} examples: {} errors, {} warnings",
+            self.files.len(),
+            self.rules.len(),
+            self.covered_rules,
+            self.error_count(),
+            self.warning_count(),
+        );
+        out
+    }
+
+    /// Render the report as JUnit XML: one test case per rule definition plus one per file that
+    /// failed to parse. Warnings only fail a test case when `deny_warnings` is set.
+    pub fn to_junit(&self, deny_warnings: bool) -> String {
+        let is_failure = |diagnostic: &LintDiagnostic| {
+            diagnostic.severity == LintSeverity::Error || deny_warnings
+        };
+        let file_errors: Vec<&LintDiagnostic> = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.rule.is_none())
+            .collect();
+        let tests = self.rules.len() + file_errors.len();
+        let failures = file_errors.len()
+            + (0..self.rules.len())
+                .filter(|index| {
+                    self.diagnostics
+                        .iter()
+                        .any(|d| d.rule == Some(*index) && is_failure(d))
+                })
+                .count();
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<testsuites name=\"execpolicy\" tests=\"{tests}\" failures=\"{failures}\">"
+        );
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"execpolicy lint\" tests=\"{tests}\" failures=\"{failures}\">"
+        );
+        for diagnostic in file_errors {
+            let _ = writeln!(
+                out,
+                "    <testcase classname=\"{}\" name=\"parse\">",
+                xml_escape(&diagnostic.source)
+            );
+            write_junit_diagnostic(&mut out, diagnostic, true);
+            out.push_str("    </testcase>\n");
+        }
+        for (index, rule) in self.rules.iter().enumerate() {
+            let name = match rule.line {
+                Some(line) => format!("line {line}: {}", rule.pattern),
+                None => rule.pattern.clone(),
+            };
+            let _ = writeln!(
+                out,
+                "    <testcase classname=\"{}\" name=\"{}\">",
+                xml_escape(&rule.source),
+                xml_escape(&name)
+            );
+            for diagnostic in self.diagnostics.iter().filter(|d| d.rule == Some(index)) {
+                write_junit_diagnostic(&mut out, diagnostic, is_failure(diagnostic));
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n</testsuites>\n");
+        out
+    }
+}
+
+fn write_junit_diagnostic(out: &mut String, diagnostic: &LintDiagnostic, failure: bool) {
+    let message = xml_escape(&diagnostic.message);
+    let code = diagnostic.code.as_str();
+    if failure {
+        let _ = writeln!(
+            out,
+            "      <failure type=\"{code}\" message=\"{message}\">{message}</failure>"
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "      <system-out>warning[{code}]: {message}</system-out>"
+        );
+    }
+}
+
+fn xml_escape(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Parse every policy file (in precedence order, lowest first), run their examples, and report
+/// rules that can never decide an outcome, conflicting decisions, and gaps in example coverage.
+pub fn lint_policy_files(policy_paths: &[PathBuf]) -> LintReport {
+    let mut report = LintReport::default();
+    let mut definitions = Vec::new();
+
+    for policy_path in policy_paths {
+        let identifier = policy_path.to_string_lossy().to_string();
+        report.files.push(identifier.clone());
+        let parsed = fs::read_to_string(policy_path)
+            .map_err(|err| format!("failed to read policy: {err}"))
+            .and_then(|contents| {
+                // Parse each file on its own so one broken file does not hide the others.
+                let mut parser = PolicyParser::new();
+                parser
+                    .parse(&identifier, &contents)
+                    .map(|()| parser.rule_definitions())
+                    .map_err(|err| err.to_string())
+            });
+        match parsed {
+            Ok(file_definitions) => definitions.extend(file_definitions),
+            Err(message) => report.diagnostics.push(LintDiagnostic {
+                severity: LintSeverity::Error,
+                code: LintCode::ParseError,
+                message,
+                source: identifier,
+                line: None,
+                rule: None,
+            }),
+        }
+    }
+
+    lint_definitions(&mut report, &definitions);
+    report
+}
+
+fn lint_definitions(report: &mut LintReport, definitions: &[RuleDefinition]) {
+    let prefix_rules: Vec<Vec<&PrefixRule>> = definitions
+        .iter()
+        .map(|definition| {
+            definition
+                .rules
+                .iter()
+                .filter_map(|rule| (rule.as_ref() as &dyn Any).downcast_ref::<PrefixRule>())
+                .collect()
+        })
+        .collect();
+
+    for (index, definition) in definitions.iter().enumerate() {
+        let rules = &prefix_rules[index];
+        report.rules.push(LintedRule {
+            source: definition.source.clone(),
+            line: definition.line,
+            pattern: rules
+                .first()
+                .map(|rule| render_rule(rule))
+                .unwrap_or_default(),
+            examples: definition.matches.len() + definition.not_matches.len(),
+        });
+
+        if definition.matches.is_empty() {
+            report.push_rule_diagnostic(
+                definition,
+                index,
+                LintSeverity::Warning,
+                LintCode::UncoveredRule,
+                "rule has no `match` examples".to_string(),
+            );
+        } else {
+            report.covered_rules += 1;
+        }
+
+        for example in &definition.not_matches {
+            let program = example.first().map(String::as_str);
+            if !rules
+                .iter()
+                .any(|rule| program == Some(rule.pattern.first.as_ref()))
+            {
+                report.push_rule_diagnostic(
+                    definition,
+                    index,
+                    LintSeverity::Warning,
+                    LintCode::UnusedExample,
+                    format!(
+                        "`not_match` example `{}` runs a different program, so it never exercises this rule",
+                        render_command(example)
+                    ),
+                );
+            }
+        }
+    }
+
+    for (index, definition) in definitions.iter().enumerate() {
+        if let Some((code, message)) = find_override(definitions, &prefix_rules, index) {
+            report.push_rule_diagnostic(definition, index, LintSeverity::Warning, code, message);
+        }
+    }
+}
+
+impl LintReport {
+    fn push_rule_diagnostic(
+        &mut self,
+        definition: &RuleDefinition,
+        index: usize,
+        severity: LintSeverity,
+        code: LintCode,
+        message: String,
+    ) {
+        self.diagnostics.push(LintDiagnostic {
+            severity,
+            code,
+            message,
+            source: definition.source.clone(),
+            line: definition.line,
+            rule: Some(index),
+        });
+    }
+}
+
+/// Find another definition that makes every rule of `definitions[index]` irrelevant: either the
+/// same pattern with a different decision (a conflict), or a more general pattern with an equal
+/// or stricter decision (shadowing). Identical duplicates are reported on the later definition.
+fn find_override(
+    definitions: &[RuleDefinition],
+    prefix_rules: &[Vec<&PrefixRule>],
+    index: usize,
+) -> Option<(LintCode, String)> {
+    let rules = &prefix_rules[index];
+    if rules.is_empty() {
+        return None;
+    }
+
+    for (other_index, other_rules) in prefix_rules.iter().enumerate() {
+        if other_index == index {
+            continue;
+        }
+        let other = &definitions[other_index];
+        let location = match other.line {
+            Some(line) => format!("{}:{line}", other.source),
+            None => other.source.clone(),
+        };
+
+        let conflicting = rules.iter().all(|rule| {
+            other_rules.iter().any(|other_rule| {
+                same_pattern(other_rule, rule) && other_rule.decision != rule.decision
+            })
+        });
+        if conflicting {
+            // Report each conflicting pair once, on the rule that loses.
+            let other_decision = other_rules[0].decision;
+            if other_decision > rules[0].decision {
+                return Some((
+                    LintCode::ConflictingDecision,
+                    format!(
+                        "rule decides `{}` but the same pattern at {location} decides `{}`; the stricter decision always wins",
+                        rules[0].decision.as_str(),
+                        other_decision.as_str(),
+                    ),
+                ));
+            }
+            continue;
+        }
+
+        let shadowed = rules.iter().all(|rule| {
+            other_rules.iter().any(|other_rule| {
+                other_rule.decision >= rule.decision
+                    && subsumes(other_rule, rule)
+                    && (!subsumes(rule, other_rule) || other_index < index)
+            })
+        });
+        if shadowed {
+            return Some((
+                LintCode::ShadowedRule,
+                format!(
+                    "rule never changes the outcome: `{}` at {location} matches every command it matches with an equal or stricter decision",
+                    render_rule(other_rules[0])
+                ),
+            ));
+        }
+    }
+    None
+}
+
+fn same_pattern(a: &PrefixRule, b: &PrefixRule) -> bool {
+    a.pattern == b.pattern && a.flags == b.flags
+}
+
+/// Whether every command matched by `specific` is also matched by `general`.
+fn subsumes(general: &PrefixRule, specific: &PrefixRule) -> bool {
+    if general.pattern.first != specific.pattern.first
+        || !flags_subsume(&general.flags, &specific.flags)
+    {
+        return false;
+    }
+
+    for (position, general_token) in general.pattern.rest.iter().enumerate() {
+        let specific_token = specific.pattern.rest.get(position);
+        match (general_token, specific_token) {
+            (PatternToken::AnyArgs { workspace_paths }, specific_token) => {
+                return !*workspace_paths
+                    || matches!(
+                        specific_token,
+                        Some(PatternToken::AnyArgs {
+                            workspace_paths: true
+                        })
+                    );
+            }
+            (_, None | Some(PatternToken::AnyArgs { .. })) => return false,
+            (general_token, Some(specific_token)) => {
+                if !token_subsumes(general_token, specific_token) {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+fn token_subsumes(general: &PatternToken, specific: &PatternToken) -> bool {
+    if general == specific {
+        return true;
+    }
+    match specific {
+        PatternToken::Single(_) | PatternToken::Alts(_) => {
+            // Path matchers depend on where the command runs, so only compare them to themselves.
+            !matches!(general, PatternToken::WorkspacePath)
+                && specific
+                    .alternatives()
+                    .iter()
+                    .all(|literal| general.matches(literal, &MatchContext::default()))
+        }
+        PatternToken::Regex(_)
+        | PatternToken::Glob(_)
+        | PatternToken::WorkspacePath
+        | PatternToken::AnyArgs { .. } => false,
+    }
+}
+
+fn flags_subsume(general: &FlagConstraints, specific: &FlagConstraints) -> bool {
+    general
+        .required
+        .iter()
+        .all(|flag| specific.required.contains(flag))
+        && general
+            .rejected
+            .iter()
+            .all(|flag| specific.rejected.contains(flag))
+}
+
+fn render_rule(rule: &PrefixRule) -> String {
+    let mut tokens = vec![rule.pattern.first.to_string()];
+    tokens.extend(rule.pattern.rest.iter().map(|token| match token {
+        PatternToken::Single(value) => value.clone(),
+        PatternToken::Alts(values) => format!("[{}]", values.join("|")),
+        PatternToken::Regex(regex) => format!("regex({:?})", regex.as_str()),
+        PatternToken::Glob(pattern) => format!("glob({pattern:?})"),
+        PatternToken::WorkspacePath => "workspace_path()".to_string(),
+        PatternToken::AnyArgs {
+            workspace_paths: false,
+        } => "any_args()".to_string(),
+        PatternToken::AnyArgs {
+            workspace_paths: true,
+        } => "any_args(workspace_paths = True)".to_string(),
+    }));
+    tokens.join(" ")
+}
+
+fn render_command(command: &[String]) -> String {
+    try_join(command.iter().map(String::as_str))
+        .unwrap_or_else(|_| "unable to render example".to_string())
+}
@@ -0,0 +1,373 @@
+use std::fmt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use allocative::Allocative;
+use regex_lite::Regex;
+use starlark::any::ProvidesStaticType;
+use starlark::values::AllocValue;
+use starlark::values::Heap;
+use starlark::values::NoSerialize;
+use starlark::values::StarlarkValue;
+use starlark::values::Value;
+use starlark::values::starlark_value;
+use wildmatch::WildMatch;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Workspace that examples in `match` / `not_match` are evaluated against. Relative paths in
+/// examples resolve against this directory.
+pub const EXAMPLE_WORKSPACE: &str = "/workspace";
+
+/// Argument matcher returned by the `regex()`, `glob()`, `workspace_path()` and `any_args()`
+/// builtins. The parser lowers it into a [`crate::rule::PatternToken`].
+#[derive(Clone, Debug, Eq, PartialEq, NoSerialize, ProvidesStaticType, Allocative)]
+pub enum ArgPattern {
+    Regex(String),
+    Glob(String),
+    WorkspacePath,
+    AnyArgs { workspace_paths: bool },
+}
+
+impl fmt::Display for ArgPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regex(pattern) => write!(f, "regex({pattern:?})"),
+            Self::Glob(pattern) => write!(f, "glob({pattern:?})"),
+            Self::WorkspacePath => write!(f, "workspace_path()"),
+            Self::AnyArgs {
+                workspace_paths: false,
+            } => write!(f, "any_args()"),
+            Self::AnyArgs {
+                workspace_paths: true,
+            } => write!(f, "any_args(workspace_paths = True)"),
+        }
+    }
+}
+
+impl<'v> AllocValue<'v> for ArgPattern {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
+
+#[starlark_value(type = "ArgPattern")]
+impl<'v> StarlarkValue<'v> for ArgPattern {
+    type Canonical = ArgPattern;
+}
+
+/// Regular expression that must match an entire command token.
+#[derive(Clone, Debug)]
+pub struct TokenRegex {
+    source: String,
+    regex: Regex,
+}
+
+impl TokenRegex {
+    pub fn new(source: &str) -> Result<Self> {
+        let regex = Regex::new(&format!("^(?:{source})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{source}`: {err}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_match(&self, token: &str) -> bool {
+        self.regex.is_match(token)
+    }
+}
+
+impl PartialEq for TokenRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for TokenRegex {}
+
+pub(crate) fn glob_matches(pattern: &str, token: &str) -> bool {
+    WildMatch::new(pattern).matches(token)
+}
+
+/// Flags that must be present on, or absent from, a command for a rule to match.
+///
+/// Flags are looked up anywhere after the program name and before a `--` separator. A long flag
+/// (`--force`) also matches its `--force=value` form. A rejected single-letter flag (`-f`) also
+/// matches when combined with other short flags (`-fu`); a required one must appear on its own,
+/// since a cluster such as `-of` may be `-o` with the value `f`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FlagConstraints {
+    pub required: Vec<String>,
+    pub rejected: Vec<String>,
+}
+
+impl FlagConstraints {
+    pub fn is_satisfied_by(&self, cmd: &[String]) -> bool {
+        let args = cmd.get(1..).unwrap_or_default();
+        self.required
+            .iter()
+            .all(|flag| has_flag(args, |arg| flag_matches(arg, flag)))
+            && !self
+                .rejected
+                .iter()
+                .any(|flag| has_flag(args, |arg| flag_may_match(arg, flag)))
+    }
+}
+
+fn has_flag(args: &[String], matches: impl Fn(&str) -> bool) -> bool {
+    args.iter()
+        .take_while(|arg| arg.as_str() != "--")
+        .any(|arg| matches(arg))
+}
+
+/// Whether `arg` is certainly `flag`: the flag itself or, for a long flag, its `--flag=value`
+/// form.
+fn flag_matches(arg: &str, flag: &str) -> bool {
+    if arg == flag {
+        return true;
+    }
+    flag.starts_with("--")
+        && arg
+            .strip_prefix(flag)
+            .is_some_and(|value| value.starts_with('='))
+}
+
+/// Whether `arg` could set `flag`. On top of [`flag_matches`], a single-letter flag matches any
+/// short-flag cluster containing its letter.
+fn flag_may_match(arg: &str, flag: &str) -> bool {
+    if flag_matches(arg, flag) {
+        return true;
+    }
+    if flag.starts_with("--") {
+        return false;
+    }
+
+    let (Some(short), Some(cluster)) = (flag.strip_prefix('-'), arg.strip_prefix('-')) else {
+        return false;
+    };
+    let mut short_chars = short.chars();
+    match (short_chars.next(), short_chars.next()) {
+        (Some(letter), None) if letter.is_ascii_alphanumeric() => {
+            !cluster.starts_with('-')
+                && cluster.chars().all(|c| c.is_ascii_alphanumeric())
+                && cluster.contains(letter)
+        }
+        _ => false,
+    }
+}
+
+/// Environment a command is evaluated in, for matchers that inspect path arguments.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MatchContext {
+    /// Directory that relative path arguments resolve against.
+    pub cwd: Option<PathBuf>,
+    /// Directories that `workspace_path()` arguments must stay within.
+    pub workspace_roots: Vec<PathBuf>,
+}
+
+impl MatchContext {
+    pub fn new(cwd: PathBuf, workspace_roots: Vec<PathBuf>) -> Self {
+        Self {
+            cwd: Some(cwd),
+            workspace_roots,
+        }
+    }
+
+    /// Context used to validate `match` / `not_match` examples at load time.
+    pub(crate) fn for_examples() -> Self {
+        let workspace = PathBuf::from(EXAMPLE_WORKSPACE);
+        Self::new(workspace.clone(), vec![workspace])
+    }
+
+    /// Whether `arg` names a path inside one of the workspace roots. Symlinks in the part of
+    /// the path that exists are resolved; the rest is resolved lexically, so the path does not
+    /// need to exist.
+    pub fn is_workspace_path(&self, arg: &str) -> bool {
+        if arg.is_empty() || arg.starts_with('~') {
+            return false;
+        }
+        let path = Path::new(arg);
+        let resolved = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            match &self.cwd {
+                Some(cwd) => cwd.join(path),
+                None => return false,
+            }
+        };
+        let Some(resolved) = resolve_path(&resolved) else {
+            return false;
+        };
+        self.workspace_roots
+            .iter()
+            .any(|root| resolve_path(root).is_some_and(|root| resolved.starts_with(root)))
+    }
+
+    /// Whether every non-flag argument in `args` is a workspace path. The value of a
+    /// `--flag=value` argument is checked too, as is everything after the first letter of a
+    /// `-X<value>` argument, since the flag may take a glued value. Arguments after `--` are
+    /// always treated as paths.
+    pub fn are_workspace_paths(&self, args: &[String]) -> bool {
+        let mut after_separator = false;
+        args.iter().all(|arg| {
+            if after_separator {
+                return self.is_workspace_path(arg);
+            }
+            if arg == "--" {
+                after_separator = true;
+                return true;
+            }
+            if let Some(long) = arg.strip_prefix("--") {
+                return long
+                    .split_once('=')
+                    .is_none_or(|(_, value)| self.is_workspace_path(value));
+            }
+            if let Some(short) = arg.strip_prefix('-')
+                && let Some(letter) = short.chars().next()
+            {
+                let value = &short[letter.len_utf8()..];
+                return value.is_empty() || self.is_workspace_path(value);
+            }
+            self.is_workspace_path(arg)
+        })
+    }
+}
+
+/// Resolves `path` through the filesystem as far as it exists, following symlinks, and
+/// lexically for the remainder.
+fn resolve_path(path: &Path) -> Option<PathBuf> {
+    let (existing, remainder) = path.ancestors().find_map(|ancestor| {
+        let canonical = std::fs::canonicalize(ancestor).ok()?;
+        let remainder = path.strip_prefix(ancestor).ok()?;
+        Some((canonical, remainder))
+    })?;
+    normalize_lexically(&existing.join(remainder))
+}
+
+fn normalize_lexically(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn args(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn flags_match_long_values_and_short_clusters() {
+        assert!(flag_matches("--force", "--force"));
+        assert!(flag_matches("--force=yes", "--force"));
+        assert!(!flag_matches("--force-with-lease", "--force"));
+        assert!(flag_may_match("-fu", "-f"));
+        assert!(!flag_may_match("--f", "-f"));
+        assert!(!flag_may_match("origin", "-f"));
+    }
+
+    #[test]
+    fn required_short_flags_do_not_match_clusters() {
+        let constraints = FlagConstraints {
+            required: vec!["-f".to_string()],
+            rejected: Vec::new(),
+        };
+        assert!(constraints.is_satisfied_by(&args(&["rm", "-f", "x"])));
+        assert!(!constraints.is_satisfied_by(&args(&["rm", "-xf", "x"])));
+        assert!(!constraints.is_satisfied_by(&args(&["tar", "-ofoo", "x"])));
+        assert!(!flag_matches("-fu", "-f"));
+    }
+
+    #[test]
+    fn rejected_short_flags_match_clusters() {
+        let constraints = FlagConstraints {
+            required: Vec::new(),
+            rejected: vec!["-f".to_string()],
+        };
+        assert!(!constraints.is_satisfied_by(&args(&["rm", "-rf", "x"])));
+        assert!(constraints.is_satisfied_by(&args(&["rm", "-r", "--", "-f"])));
+    }
+
+    #[test]
+    fn flags_after_separator_are_ignored() {
+        let constraints = FlagConstraints {
+            required: Vec::new(),
+            rejected: vec!["--force".to_string()],
+        };
+        assert!(constraints.is_satisfied_by(&args(&["git", "push", "--", "--force"])));
+        assert!(!constraints.is_satisfied_by(&args(&["git", "push", "--force", "origin"])));
+    }
+
+    #[test]
+    fn workspace_paths_resolve_lexically() {
+        let context = MatchContext::new(PathBuf::from("/repo/src"), vec![PathBuf::from("/repo")]);
+        assert_eq!(
+            vec![true, true, true, false, false, false],
+            [
+                "main.rs",
+                "../README.md",
+                "/repo/a/../b",
+                "../../etc",
+                "/etc",
+                "~/x"
+            ]
+            .iter()
+            .map(|arg| context.is_workspace_path(arg))
+            .collect::<Vec<_>>()
+        );
+        assert!(context.are_workspace_paths(&args(&["-rf", "target", "--", "-weird"])));
+        assert!(!context.are_workspace_paths(&args(&["-rf", "/tmp"])));
+        let outside_cwd = MatchContext::new(PathBuf::from("/tmp"), vec![PathBuf::from("/repo")]);
+        assert!(outside_cwd.are_workspace_paths(&args(&["-v", "--force", "/repo/a"])));
+        assert!(!MatchContext::default().is_workspace_path("main.rs"));
+    }
+
+    #[test]
+    fn flag_values_must_be_workspace_paths() {
+        let context = MatchContext::new(PathBuf::from("/repo"), vec![PathBuf::from("/repo")]);
+        assert!(context.are_workspace_paths(&args(&["--target-directory=out", "a"])));
+        assert!(!context.are_workspace_paths(&args(&["--target-directory=/etc", "a"])));
+        assert!(!context.are_workspace_paths(&args(&["--output=../x", "a"])));
+        assert!(context.are_workspace_paths(&args(&["-oout", "a"])));
+        assert!(!context.are_workspace_paths(&args(&["-o/etc/passwd", "a"])));
+        assert!(!context.are_workspace_paths(&args(&["-o../x", "a"])));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_the_workspace_are_rejected() -> anyhow::Result<()> {
+        let workspace = tempfile::tempdir()?;
+        let outside = tempfile::tempdir()?;
+        std::os::unix::fs::symlink(outside.path(), workspace.path().join("escape"))?;
+        std::fs::create_dir(workspace.path().join("src"))?;
+
+        let context = MatchContext::new(
+            workspace.path().to_path_buf(),
+            vec![workspace.path().to_path_buf()],
+        );
+        assert!(context.is_workspace_path("src/new.rs"));
+        assert!(!context.is_workspace_path("escape"));
+        assert!(!context.is_workspace_path("escape/new.rs"));
+        Ok(())
+    }
+}
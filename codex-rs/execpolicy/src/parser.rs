@@ -18,6 +18,9 @@ use std::sync::Arc;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::matcher::ArgPattern;
+use crate::matcher::FlagConstraints;
+use crate::matcher::TokenRegex;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -53,6 +56,7 @@ impl PolicyParser {
             &dialect,
         )
         .map_err(Error::Starlark)?;
+        self.builder.borrow_mut().source = Some(policy_identifier.to_string());
         let globals = GlobalsBuilder::standard().with(policy_builtins).build();
         let module = Module::new();
         {
@@ -66,17 +70,40 @@ impl PolicyParser {
     pub fn build(self) -> crate::policy::Policy {
         self.builder.into_inner().build()
     }
+
+    /// Every `prefix_rule(...)` call parsed so far, in evaluation order.
+    pub fn rule_definitions(&self) -> Vec<RuleDefinition> {
+        self.builder.borrow().definitions.clone()
+    }
+}
+
+/// A single `prefix_rule(...)` call together with where it was defined and its examples.
+#[derive(Clone, Debug)]
+pub struct RuleDefinition {
+    /// Identifier the policy was parsed with, usually its path.
+    pub source: String,
+    /// 1-based line of the `prefix_rule` call, when known.
+    pub line: Option<usize>,
+    /// One rule per alternative of the first pattern element.
+    pub rules: Vec<RuleRef>,
+    pub matches: Vec<Vec<String>>,
+    pub not_matches: Vec<Vec<String>>,
 }
 
 #[derive(Debug, ProvidesStaticType)]
 struct PolicyBuilder {
     rules_by_program: MultiMap<String, RuleRef>,
+    definitions: Vec<RuleDefinition>,
+    /// Identifier of the policy currently being parsed.
+    source: Option<String>,
 }
 
 impl PolicyBuilder {
     fn new() -> Self {
         Self {
             rules_by_program: MultiMap::new(),
+            definitions: Vec::new(),
+            source: None,
         }
     }
 
@@ -97,15 +124,30 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
         .map(parse_pattern_token)
         .collect::<Result<_>>()?;
     if tokens.is_empty() {
-        Err(Error::InvalidPattern("pattern cannot be empty".to_string()))
-    } else {
-        Ok(tokens)
+        return Err(Error::InvalidPattern("pattern cannot be empty".to_string()));
+    }
+    if tokens[0].alternatives().is_empty() {
+        return Err(Error::InvalidPattern(
+            "first pattern element must be a literal program name".to_string(),
+        ));
     }
+    if let Some(position) = tokens
+        .iter()
+        .position(|token| matches!(token, PatternToken::AnyArgs { .. }))
+        && position + 1 != tokens.len()
+    {
+        return Err(Error::InvalidPattern(
+            "any_args() must be the last pattern element".to_string(),
+        ));
+    }
+    Ok(tokens)
 }
 
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if let Some(arg_pattern) = value.downcast_ref::<ArgPattern>() {
+        parse_arg_pattern(arg_pattern)
     } else if let Some(list) = ListRef::from_value(value) {
         let tokens: Vec<String> = list
             .content()
@@ -132,12 +174,43 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, list of strings or argument matcher (got {})",
             value.get_type()
         )))
     }
 }
 
+fn parse_arg_pattern(arg_pattern: &ArgPattern) -> Result<PatternToken> {
+    match arg_pattern {
+        ArgPattern::Regex(pattern) => TokenRegex::new(pattern).map(PatternToken::Regex),
+        ArgPattern::Glob(pattern) if pattern.is_empty() => Err(Error::InvalidPattern(
+            "glob pattern cannot be empty".to_string(),
+        )),
+        ArgPattern::Glob(pattern) => Ok(PatternToken::Glob(pattern.clone())),
+        ArgPattern::WorkspacePath => Ok(PatternToken::WorkspacePath),
+        ArgPattern::AnyArgs { workspace_paths } => Ok(PatternToken::AnyArgs {
+            workspace_paths: *workspace_paths,
+        }),
+    }
+}
+
+fn parse_flags<'v>(flags: Option<UnpackList<&'v str>>) -> Result<Vec<String>> {
+    flags
+        .map(|flags| flags.items)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|flag| {
+            if flag.len() > 1 && flag.starts_with('-') {
+                Ok(flag.to_string())
+            } else {
+                Err(Error::InvalidRule(format!(
+                    "flag `{flag}` must start with `-`"
+                )))
+            }
+        })
+        .collect()
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -213,6 +286,8 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         r#match: Option<UnpackList<Value<'v>>>,
         not_match: Option<UnpackList<Value<'v>>>,
         justification: Option<&'v str>,
+        with_flags: Option<UnpackList<&'v str>>,
+        without_flags: Option<UnpackList<&'v str>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let decision = match decision {
@@ -229,6 +304,10 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         };
 
         let pattern_tokens = parse_pattern(pattern)?;
+        let flags = FlagConstraints {
+            required: parse_flags(with_flags)?,
+            rejected: parse_flags(without_flags)?,
+        };
 
         let matches: Vec<Vec<String>> =
             r#match.map(parse_examples).transpose()?.unwrap_or_default();
@@ -237,6 +316,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             .transpose()?
             .unwrap_or_default();
 
+        let location = eval.call_stack_top_location();
         let mut builder = policy_builder(eval);
 
         let (first_token, remaining_tokens) = pattern_tokens
@@ -254,8 +334,10 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                         first: Arc::from(head.as_str()),
                         rest: rest.clone(),
                     },
+                    flags: flags.clone(),
                     decision,
                     justification: justification.clone(),
+                    source: builder.source.clone(),
                 }) as RuleRef
             })
             .collect();
@@ -263,7 +345,39 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         validate_not_match_examples(&rules, &not_matches)?;
         validate_match_examples(&rules, &matches)?;
 
-        rules.into_iter().for_each(|rule| builder.add_rule(rule));
+        rules
+            .iter()
+            .cloned()
+            .for_each(|rule| builder.add_rule(rule));
+        builder.definitions.push(RuleDefinition {
+            source: builder.source.clone().unwrap_or_default(),
+            line: location.map(|location| location.resolve_span().begin.line + 1),
+            rules,
+            matches,
+            not_matches,
+        });
         Ok(NoneType)
     }
+
+    /// Matches one token fully matched by the regular expression `pattern`.
+    fn regex(pattern: &str) -> anyhow::Result<ArgPattern> {
+        Ok(ArgPattern::Regex(pattern.to_string()))
+    }
+
+    /// Matches one token against the shell-style glob `pattern`.
+    fn glob(pattern: &str) -> anyhow::Result<ArgPattern> {
+        Ok(ArgPattern::Glob(pattern.to_string()))
+    }
+
+    /// Matches one token naming a path inside the workspace.
+    fn workspace_path() -> anyhow::Result<ArgPattern> {
+        Ok(ArgPattern::WorkspacePath)
+    }
+
+    /// Matches all remaining tokens, optionally requiring non-flag tokens to be workspace paths.
+    fn any_args(
+        #[starlark(require = named, default = false)] workspace_paths: bool,
+    ) -> anyhow::Result<ArgPattern> {
+        Ok(ArgPattern::AnyArgs { workspace_paths })
+    }
 }
@@ -1,6 +1,8 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::matcher::FlagConstraints;
+use crate::matcher::MatchContext;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -45,19 +47,35 @@ impl Policy {
                     .collect::<Vec<_>>()
                     .into(),
             },
+            flags: FlagConstraints::default(),
             decision,
             justification: None,
+            source: None,
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
         Ok(())
     }
 
+    /// Evaluates `cmd` without a workspace, so `workspace_path()` matchers never match.
     pub fn check<F>(&self, cmd: &[String], heuristics_fallback: &F) -> Evaluation
     where
         F: Fn(&[String]) -> Decision,
     {
-        let matched_rules = self.matches_for_command(cmd, Some(heuristics_fallback));
+        self.check_with_context(cmd, &MatchContext::default(), heuristics_fallback)
+    }
+
+    pub fn check_with_context<F>(
+        &self,
+        cmd: &[String],
+        context: &MatchContext,
+        heuristics_fallback: &F,
+    ) -> Evaluation
+    where
+        F: Fn(&[String]) -> Decision,
+    {
+        let matched_rules =
+            self.matches_for_command_with_context(cmd, context, Some(heuristics_fallback));
         Evaluation::from_matches(matched_rules)
     }
 
@@ -66,6 +84,20 @@ impl Policy {
         commands: Commands,
         heuristics_fallback: &F,
     ) -> Evaluation
+    where
+        Commands: IntoIterator,
+        Commands::Item: AsRef<[String]>,
+        F: Fn(&[String]) -> Decision,
+    {
+        self.check_multiple_with_context(commands, &MatchContext::default(), heuristics_fallback)
+    }
+
+    pub fn check_multiple_with_context<Commands, F>(
+        &self,
+        commands: Commands,
+        context: &MatchContext,
+        heuristics_fallback: &F,
+    ) -> Evaluation
     where
         Commands: IntoIterator,
         Commands::Item: AsRef<[String]>,
@@ -74,23 +106,42 @@ impl Policy {
         let matched_rules: Vec<RuleMatch> = commands
             .into_iter()
             .flat_map(|command| {
-                self.matches_for_command(command.as_ref(), Some(heuristics_fallback))
+                self.matches_for_command_with_context(
+                    command.as_ref(),
+                    context,
+                    Some(heuristics_fallback),
+                )
             })
             .collect();
 
         Evaluation::from_matches(matched_rules)
     }
 
+    /// Matches `cmd` without a workspace, so `workspace_path()` matchers never match.
     pub fn matches_for_command(
         &self,
         cmd: &[String],
         heuristics_fallback: HeuristicsFallback<'_>,
+    ) -> Vec<RuleMatch> {
+        self.matches_for_command_with_context(cmd, &MatchContext::default(), heuristics_fallback)
+    }
+
+    pub fn matches_for_command_with_context(
+        &self,
+        cmd: &[String],
+        context: &MatchContext,
+        heuristics_fallback: HeuristicsFallback<'_>,
     ) -> Vec<RuleMatch> {
         let mut matched_rules: Vec<RuleMatch> = match cmd.first() {
             Some(first) => self
                 .rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches(cmd, context))
+                        .collect()
+                })
                 .unwrap_or_default(),
             None => Vec::new(),
         };
@@ -1,6 +1,10 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::matcher::FlagConstraints;
+use crate::matcher::MatchContext;
+use crate::matcher::TokenRegex;
+use crate::matcher::glob_matches;
 use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
@@ -8,25 +12,43 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-/// Matches a single command token, either a fixed string or one of several allowed alternatives.
+/// Matches a single command token, either a fixed string or one of several allowed alternatives,
+/// or, via `AnyArgs`, every remaining token.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    /// Token fully matched by a regular expression.
+    Regex(TokenRegex),
+    /// Token matched by a shell-style glob (`*`, `?`).
+    Glob(String),
+    /// Token naming a path inside the workspace.
+    WorkspacePath,
+    /// Zero or more remaining tokens. Only valid as the last element of a pattern; with
+    /// `workspace_paths`, every non-flag remaining token must name a path inside the workspace.
+    AnyArgs {
+        workspace_paths: bool,
+    },
 }
 
 impl PatternToken {
-    fn matches(&self, token: &str) -> bool {
+    pub(crate) fn matches(&self, token: &str, context: &MatchContext) -> bool {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Regex(regex) => regex.is_match(token),
+            Self::Glob(pattern) => glob_matches(pattern, token),
+            Self::WorkspacePath => context.is_workspace_path(token),
+            Self::AnyArgs { .. } => true,
         }
     }
 
+    /// Literal strings this token accepts; empty for tokens that match on argument content.
     pub fn alternatives(&self) -> &[String] {
         match self {
             Self::Single(expected) => std::slice::from_ref(expected),
             Self::Alts(alternatives) => alternatives,
+            Self::Regex(_) | Self::Glob(_) | Self::WorkspacePath | Self::AnyArgs { .. } => &[],
         }
     }
 }
@@ -40,19 +62,29 @@ pub struct PrefixPattern {
 }
 
 impl PrefixPattern {
-    pub fn matches_prefix(&self, cmd: &[String]) -> Option<Vec<String>> {
-        let pattern_length = self.rest.len() + 1;
-        if cmd.len() < pattern_length || cmd[0] != self.first.as_ref() {
+    pub fn matches_prefix(&self, cmd: &[String], context: &MatchContext) -> Option<Vec<String>> {
+        if cmd.first().map(String::as_str) != Some(self.first.as_ref()) {
             return None;
         }
 
-        for (pattern_token, cmd_token) in self.rest.iter().zip(&cmd[1..pattern_length]) {
-            if !pattern_token.matches(cmd_token) {
+        let mut matched = 1;
+        for pattern_token in self.rest.iter() {
+            if let PatternToken::AnyArgs { workspace_paths } = pattern_token {
+                let remaining = &cmd[matched..];
+                if *workspace_paths && !context.are_workspace_paths(remaining) {
+                    return None;
+                }
+                return Some(cmd.to_vec());
+            }
+
+            let cmd_token = cmd.get(matched)?;
+            if !pattern_token.matches(cmd_token, context) {
                 return None;
             }
+            matched += 1;
         }
 
-        Some(cmd[..pattern_length].to_vec())
+        Some(cmd[..matched].to_vec())
     }
 }
 
@@ -69,6 +101,9 @@ pub enum RuleMatch {
         /// (e.g., prompt reasons or rejection messages).
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
+        /// Identifier (usually the path) of the policy file that defined the rule.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
     },
     HeuristicsRuleMatch {
         command: Vec<String>,
@@ -88,14 +123,17 @@ impl RuleMatch {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PrefixRule {
     pub pattern: PrefixPattern,
+    pub flags: FlagConstraints,
     pub decision: Decision,
     pub justification: Option<String>,
+    /// Identifier of the policy file the rule was parsed from; `None` for rules added at runtime.
+    pub source: Option<String>,
 }
 
 pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+    fn matches(&self, cmd: &[String], context: &MatchContext) -> Option<RuleMatch>;
 }
 
 pub type RuleRef = Arc<dyn Rule>;
@@ -105,23 +143,31 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+    fn matches(&self, cmd: &[String], context: &MatchContext) -> Option<RuleMatch> {
+        if !self.flags.is_satisfied_by(cmd) {
+            return None;
+        }
         self.pattern
-            .matches_prefix(cmd)
+            .matches_prefix(cmd, context)
             .map(|matched_prefix| RuleMatch::PrefixRuleMatch {
                 matched_prefix,
                 decision: self.decision,
                 justification: self.justification.clone(),
+                source: self.source.clone(),
             })
     }
 }
 
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(rules: &[RuleRef], matches: &[Vec<String>]) -> Result<()> {
+    let context = MatchContext::for_examples();
     let mut unmatched_examples = Vec::new();
 
     for example in matches {
-        if rules.iter().any(|rule| rule.matches(example).is_some()) {
+        if rules
+            .iter()
+            .any(|rule| rule.matches(example, &context).is_some())
+        {
             continue;
         }
 
@@ -146,8 +192,12 @@ pub(crate) fn validate_not_match_examples(
     rules: &[RuleRef],
     not_matches: &[Vec<String>],
 ) -> Result<()> {
+    let context = MatchContext::for_examples();
     for example in not_matches {
-        if let Some(rule) = rules.iter().find(|rule| rule.matches(example).is_some()) {
+        if let Some(rule) = rules
+            .iter()
+            .find(|rule| rule.matches(example, &context).is_some())
+        {
             return Err(Error::ExampleDidMatch {
                 rule: format!("{rule:?}"),
                 example: try_join(example.iter().map(String::as_str))
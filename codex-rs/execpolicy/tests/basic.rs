@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -6,10 +7,12 @@ use anyhow::Result;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
+use codex_execpolicy::matcher::FlagConstraints;
 use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
 use codex_execpolicy::rule::PrefixRule;
@@ -65,6 +68,7 @@ prefix_rule(
                 matched_prefix: tokens(&["git", "status"]),
                 decision: Decision::Allow,
                 justification: None,
+                source: Some("test.rules".to_string()),
             }],
         },
         evaluation
@@ -96,6 +100,7 @@ prefix_rule(
                 matched_prefix: tokens(&["rm"]),
                 decision: Decision::Forbidden,
                 justification: Some("destructive command".to_string()),
+                source: Some("test.rules".to_string()),
             }],
         },
         evaluation
@@ -124,6 +129,7 @@ prefix_rule(
                 matched_prefix: tokens(&["ls"]),
                 decision: Decision::Allow,
                 justification: Some("safe and commonly used".to_string()),
+                source: Some("test.rules".to_string()),
             }],
         },
         evaluation
@@ -162,8 +168,10 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
                 first: Arc::from("ls"),
                 rest: vec![PatternToken::Single(String::from("-l"))].into(),
             },
+            flags: FlagConstraints::default(),
             decision: Decision::Prompt,
             justification: None,
+            source: None,
         })],
         rules
     );
@@ -176,6 +184,7 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
                 matched_prefix: tokens(&["ls", "-l"]),
                 decision: Decision::Prompt,
                 justification: None,
+                source: None,
             }],
         },
         evaluation
@@ -222,16 +231,20 @@ prefix_rule(
                     first: Arc::from("git"),
                     rest: Vec::<PatternToken>::new().into(),
                 },
+                flags: FlagConstraints::default(),
                 decision: Decision::Prompt,
                 justification: None,
+                source: Some("first.rules".to_string()),
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
                     first: Arc::from("git"),
                     rest: vec![PatternToken::Single("commit".to_string())].into(),
                 },
+                flags: FlagConstraints::default(),
                 decision: Decision::Forbidden,
                 justification: None,
+                source: Some("second.rules".to_string()),
             }),
         ],
         git_rules
@@ -245,6 +258,7 @@ prefix_rule(
                 matched_prefix: tokens(&["git"]),
                 decision: Decision::Prompt,
                 justification: None,
+                source: Some("first.rules".to_string()),
             }],
         },
         status_eval
@@ -259,11 +273,13 @@ prefix_rule(
                     matched_prefix: tokens(&["git"]),
                     decision: Decision::Prompt,
                     justification: None,
+                    source: Some("first.rules".to_string()),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git", "commit"]),
                     decision: Decision::Forbidden,
                     justification: None,
+                    source: Some("second.rules".to_string()),
                 },
             ],
         },
@@ -296,8 +312,10 @@ prefix_rule(
                 first: Arc::from("bash"),
                 rest: vec![PatternToken::Alts(vec!["-c".to_string(), "-l".to_string()])].into(),
             },
+            flags: FlagConstraints::default(),
             decision: Decision::Allow,
             justification: None,
+            source: Some("test.rules".to_string()),
         })],
         bash_rules
     );
@@ -307,8 +325,10 @@ prefix_rule(
                 first: Arc::from("sh"),
                 rest: vec![PatternToken::Alts(vec!["-c".to_string(), "-l".to_string()])].into(),
             },
+            flags: FlagConstraints::default(),
             decision: Decision::Allow,
             justification: None,
+            source: Some("test.rules".to_string()),
         })],
         sh_rules
     );
@@ -321,6 +341,7 @@ prefix_rule(
                 matched_prefix: tokens(&["bash", "-c"]),
                 decision: Decision::Allow,
                 justification: None,
+                source: Some("test.rules".to_string()),
             }],
         },
         bash_eval
@@ -334,6 +355,7 @@ prefix_rule(
                 matched_prefix: tokens(&["sh", "-l"]),
                 decision: Decision::Allow,
                 justification: None,
+                source: Some("test.rules".to_string()),
             }],
         },
         sh_eval
@@ -366,8 +388,10 @@ prefix_rule(
                 ]
                 .into(),
             },
+            flags: FlagConstraints::default(),
             decision: Decision::Allow,
             justification: None,
+            source: Some("test.rules".to_string()),
         })],
         rules
     );
@@ -380,6 +404,7 @@ prefix_rule(
                 matched_prefix: tokens(&["npm", "i", "--legacy-peer-deps"]),
                 decision: Decision::Allow,
                 justification: None,
+                source: Some("test.rules".to_string()),
             }],
         },
         npm_i
@@ -396,6 +421,7 @@ prefix_rule(
                 matched_prefix: tokens(&["npm", "install", "--no-save"]),
                 decision: Decision::Allow,
                 justification: None,
+                source: Some("test.rules".to_string()),
             }],
         },
         npm_install
@@ -426,6 +452,7 @@ prefix_rule(
                 matched_prefix: tokens(&["git", "status"]),
                 decision: Decision::Allow,
                 justification: None,
+                source: Some("test.rules".to_string()),
             }],
         },
         match_eval
@@ -473,11 +500,13 @@ prefix_rule(
                     matched_prefix: tokens(&["git"]),
                     decision: Decision::Prompt,
                     justification: None,
+                    source: Some("test.rules".to_string()),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git", "commit"]),
                     decision: Decision::Forbidden,
                     justification: None,
+                    source: Some("test.rules".to_string()),
                 },
             ],
         },
@@ -516,16 +545,19 @@ prefix_rule(
                     matched_prefix: tokens(&["git"]),
                     decision: Decision::Prompt,
                     justification: None,
+                    source: Some("test.rules".to_string()),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git"]),
                     decision: Decision::Prompt,
                     justification: None,
+                    source: Some("test.rules".to_string()),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git", "commit"]),
                     decision: Decision::Forbidden,
                     justification: None,
+                    source: Some("test.rules".to_string()),
                 },
             ],
         },
@@ -551,3 +583,115 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn argument_matchers_match_token_content() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "checkout", regex("v[0-9]+\\.[0-9]+")],
+    match = ["git checkout v1.2"],
+    not_match = ["git checkout v1.2-rc", "git checkout main"],
+)
+prefix_rule(
+    pattern = ["cat", glob("*.md")],
+    match = ["cat README.md"],
+    not_match = ["cat main.rs"],
+)
+prefix_rule(
+    pattern = ["cargo", "test", any_args()],
+    match = ["cargo test", "cargo test -p codex-core -- --nocapture"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["cargo", "test", "--all"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["cargo", "test", "--all"]),
+                decision: Decision::Allow,
+                justification: None,
+                source: Some("test.rules".to_string()),
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn flag_constraints_gate_matches() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    without_flags = ["--force", "-f"],
+    match = ["git push origin main"],
+    not_match = ["git push --force origin", "git push --force=yes", "git push -fu origin"],
+)
+prefix_rule(
+    pattern = ["git", "commit"],
+    with_flags = ["--dry-run"],
+    decision = "allow",
+    match = ["git commit --dry-run -m hi"],
+    not_match = ["git commit -m hi"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["git", "push", "-f"]), &prompt_all);
+    assert_eq!(Decision::Prompt, evaluation.decision);
+    assert!(!evaluation.is_match());
+    Ok(())
+}
+
+#[test]
+fn workspace_path_matchers_use_match_context() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", any_args(workspace_paths = True)],
+    match = ["rm -rf target", "rm src/../build"],
+    not_match = ["rm -rf /", "rm ../outside"],
+)
+prefix_rule(
+    pattern = ["touch", workspace_path()],
+    match = ["touch notes.txt"],
+    not_match = ["touch ../notes.txt"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let context = MatchContext::new(PathBuf::from("/repo/src"), vec![PathBuf::from("/repo")]);
+    let inside =
+        policy.check_with_context(&tokens(&["rm", "-rf", "../target"]), &context, &prompt_all);
+    assert_eq!(Decision::Allow, inside.decision);
+    let outside =
+        policy.check_with_context(&tokens(&["rm", "-rf", "../../tmp"]), &context, &prompt_all);
+    assert_eq!(Decision::Prompt, outside.decision);
+
+    // Without a workspace, path matchers never match.
+    let no_workspace = policy.check(&tokens(&["touch", "notes.txt"]), &prompt_all);
+    assert!(!no_workspace.is_match());
+    Ok(())
+}
+
+#[test]
+fn any_args_must_be_last_pattern_element() {
+    let policy_src = r#"
+prefix_rule(pattern = ["git", any_args(), "status"])
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("any_args() must be the last pattern element")
+    );
+}
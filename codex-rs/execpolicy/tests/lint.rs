@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use codex_execpolicy::lint::LintCode;
+use codex_execpolicy::lint::LintSeverity;
+use codex_execpolicy::lint_policy_files;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn write_policy(dir: &TempDir, name: &str, contents: &str) -> Result<PathBuf> {
+    let path = dir.path().join(name);
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+#[test]
+fn reports_shadowed_conflicting_and_uncovered_rules() -> Result<()> {
+    let dir = TempDir::new()?;
+    let user = write_policy(
+        &dir,
+        "user.rules",
+        r#"
+prefix_rule(
+    pattern = ["git"],
+    decision = "prompt",
+    match = ["git status"],
+)
+prefix_rule(
+    pattern = ["rm", "-rf"],
+    decision = "forbidden",
+    match = ["rm -rf /"],
+)
+"#,
+    )?;
+    let project = write_policy(
+        &dir,
+        "project.rules",
+        r#"
+prefix_rule(
+    pattern = ["git", "status"],
+    match = ["git status"],
+    not_match = ["ls status"],
+)
+prefix_rule(
+    pattern = ["rm", "-rf"],
+    decision = "allow",
+)
+"#,
+    )?;
+
+    let report = lint_policy_files(&[user.clone(), project.clone()]);
+    let project_source = project.to_string_lossy().to_string();
+    let diagnostics: Vec<(LintCode, String, Option<usize>)> = report
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.code, diagnostic.source.clone(), diagnostic.line))
+        .collect();
+    assert_eq!(
+        vec![
+            (LintCode::UnusedExample, project_source.clone(), Some(2)),
+            (LintCode::UncoveredRule, project_source.clone(), Some(7)),
+            (LintCode::ShadowedRule, project_source.clone(), Some(2)),
+            (LintCode::ConflictingDecision, project_source, Some(7)),
+        ],
+        diagnostics
+    );
+    assert_eq!(4, report.rules.len());
+    assert_eq!(3, report.covered_rules);
+    assert!(report.passed(false));
+    assert!(!report.passed(true));
+    Ok(())
+}
+
+#[test]
+fn parse_errors_are_reported_per_file() -> Result<()> {
+    let dir = TempDir::new()?;
+    let broken = write_policy(
+        &dir,
+        "broken.rules",
+        r#"
+prefix_rule(
+    pattern = ["git", "status"],
+    match = ["git commit"],
+)
+"#,
+    )?;
+    let fine = write_policy(
+        &dir,
+        "fine.rules",
+        r#"prefix_rule(pattern = ["ls"], match = ["ls -l"])"#,
+    )?;
+
+    let report = lint_policy_files(&[broken, fine]);
+    assert_eq!(1, report.error_count());
+    assert_eq!(LintSeverity::Error, report.diagnostics[0].severity);
+    assert_eq!(LintCode::ParseError, report.diagnostics[0].code);
+    assert_eq!(1, report.rules.len());
+    assert!(!report.passed(false));
+
+    let junit = report.to_junit(false);
+    assert!(junit.contains(r#"<testsuites name="execpolicy" tests="2" failures="1">"#));
+    assert!(junit.contains(r#"<failure type="parse-error""#));
+    Ok(())
+}
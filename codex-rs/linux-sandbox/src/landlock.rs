@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
@@ -27,12 +28,19 @@ use seccompiler::apply_filter;
 
 /// Apply sandbox policies inside this thread so only the child inherits
 /// them, not the entire CLI process.
+///
+/// With `proxy_bridged`, the thread already sits in a private network
+/// namespace whose loopback relays to the egress proxy, so TCP sockets stay
+/// allowed; they cannot reach anything else. Paths in `deny_read` are neither
+/// readable nor writable.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    proxy_bridged: bool,
+    deny_read: &[PathBuf],
 ) -> Result<()> {
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        install_network_seccomp_filter_on_current_thread(proxy_bridged)?;
     }
 
     if !sandbox_policy.has_full_disk_write_access() {
@@ -41,38 +49,71 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, deny_read)?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
     Ok(())
 }
 
 /// Installs Landlock file-system rules on the current thread allowing read
 /// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// `/dev/null` and the provided list of `writable_roots`. Nothing beneath a
+/// `deny_read` path is granted beyond listing its entries and, inside a
+/// writable root, creating, renaming and removing them.
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    deny_read: &[PathBuf],
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
     let access_ro = AccessFs::from_read(abi);
 
+    let deny_read: Vec<PathBuf> = deny_read
+        .iter()
+        .map(|path| canonicalize_existing_prefix(path))
+        .collect();
+    let mut readable = CarvedPaths::default();
+    readable.carve(Path::new("/"), &deny_read);
+    let mut writable = CarvedPaths::default();
+    for root in &writable_roots {
+        writable.carve(root.as_path(), &deny_read);
+    }
+
     let mut ruleset = Ruleset::default()
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
         .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .add_rules(landlock::path_beneath_rules(&readable.beneath, access_ro))?
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
-    if !writable_roots.is_empty() {
-        ruleset = ruleset.add_rules(landlock::path_beneath_rules(&writable_roots, access_rw))?;
+    // Directories holding a denied path can still be listed. Landlock rules
+    // apply to whole hierarchies, so this also lists the names inside the
+    // denied directories, but none of their contents can be read.
+    if !readable.ancestors.is_empty() {
+        ruleset = ruleset.add_rules(landlock::path_beneath_rules(
+            &readable.ancestors,
+            AccessFs::ReadDir,
+        ))?;
+    }
+    // Directories inside a writable root that hold a denied path keep their
+    // directory-level write rights, so files can still be created, renamed
+    // and removed next to the denied path. The same hierarchy rule applies
+    // here: entries inside the denied directories can be created or removed,
+    // but their contents can neither be read nor written.
+    if !writable.ancestors.is_empty() {
+        let access_dir_write = AccessFs::from_write(abi)
+            & !(AccessFs::WriteFile | AccessFs::Truncate | AccessFs::IoctlDev);
+        ruleset = ruleset.add_rules(landlock::path_beneath_rules(
+            &writable.ancestors,
+            access_dir_write,
+        ))?;
+    }
+    if !writable.beneath.is_empty() {
+        ruleset = ruleset.add_rules(landlock::path_beneath_rules(&writable.beneath, access_rw))?;
     }
 
     let status = ruleset.restrict_self()?;
@@ -84,9 +125,80 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+/// Canonicalizes the part of `path` that exists, so symlinked spellings cannot
+/// slip through, and keeps the rest as given: a denied path that does not
+/// exist yet stays denied once it is created.
+fn canonicalize_existing_prefix(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find_map(|ancestor| {
+            let canonical = ancestor.canonicalize().ok()?;
+            let remainder = path.strip_prefix(ancestor).ok()?;
+            Some(canonical.join(remainder))
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Paths that together cover everything beneath some roots except the denied
+/// paths.
+///
+/// Landlock can only grant access, so a denied path inside a root is carved
+/// out by granting its siblings, and its ancestors' siblings, one by one. The
+/// ancestors themselves are collected separately so they can be granted less.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CarvedPaths {
+    /// Paths whose whole hierarchy is granted.
+    beneath: Vec<PathBuf>,
+    /// Directories that contain a denied path.
+    ancestors: Vec<PathBuf>,
+}
+
+impl CarvedPaths {
+    fn carve(&mut self, root: &Path, deny: &[PathBuf]) {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        if deny.iter().any(|denied| root.starts_with(denied)) {
+            return;
+        }
+        if !deny.iter().any(|denied| denied.starts_with(&root)) {
+            self.beneath.push(root);
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(&root) else {
+            return;
+        };
+        self.ancestors.push(root);
+        for entry in entries.filter_map(std::result::Result::ok) {
+            let path = entry.path();
+            if entry
+                .file_type()
+                .is_ok_and(|file_type| file_type.is_symlink())
+            {
+                // Landlock rules follow symlinks. A link leading to or into a
+                // denied path is skipped; its target is handled where it lives.
+                let Ok(target) = path.canonicalize() else {
+                    continue;
+                };
+                if !deny
+                    .iter()
+                    .any(|denied| denied.starts_with(&target) || target.starts_with(denied))
+                {
+                    self.beneath.push(target);
+                }
+                continue;
+            }
+            self.carve(&path, deny);
+        }
+    }
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
 /// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+///
+/// With `proxy_only`, TCP sockets may also be created and connected; the
+/// caller must already have moved the thread into a network namespace where
+/// only the proxy relay is reachable.
+fn install_network_seccomp_filter_on_current_thread(
+    proxy_only: bool,
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -95,22 +207,24 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
+    if !proxy_only {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+    }
     deny_syscall(libc::SYS_accept);
     deny_syscall(libc::SYS_accept4);
     deny_syscall(libc::SYS_bind);
     deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
     deny_syscall(libc::SYS_sendmmsg);
     // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
     // with their socketpair + child processes for sub-proc management
     // deny_syscall(libc::SYS_recvfrom);
     deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
 
     // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
@@ -121,7 +235,12 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         libc::AF_UNIX as u64,
     )?])?;
 
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
+    let socket_rules = if proxy_only {
+        proxy_only_socket_rules()?
+    } else {
+        vec![unix_only_rule.clone()]
+    };
+    rules.insert(libc::SYS_socket, socket_rules);
     rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
 
     let filter = SeccompFilter::new(
@@ -143,3 +262,47 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
     Ok(())
 }
+
+/// Rules matching `socket` calls that are denied in proxy-only mode: any
+/// domain other than AF_UNIX/AF_INET/AF_INET6, and any non-stream INET socket
+/// (UDP, raw, ...), since the proxy relay only carries TCP.
+fn proxy_only_socket_rules() -> std::result::Result<Vec<SeccompRule>, SandboxErr> {
+    let mut rules = vec![SeccompRule::new(vec![
+        SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?,
+        SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_INET as u64,
+        )?,
+        SeccompCondition::new(
+            0,
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_INET6 as u64,
+        )?,
+    ])?];
+
+    // The low four bits of `type` hold the socket type; the rest are flags
+    // such as SOCK_CLOEXEC.
+    for domain in [libc::AF_INET, libc::AF_INET6] {
+        for socket_type in (0..16).filter(|t| *t != libc::SOCK_STREAM) {
+            rules.push(SeccompRule::new(vec![
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, domain as u64)?,
+                SeccompCondition::new(
+                    1,
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::MaskedEq(0xf),
+                    socket_type as u64,
+                )?,
+            ])?);
+        }
+    }
+
+    Ok(rules)
+}
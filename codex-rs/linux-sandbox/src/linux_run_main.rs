@@ -1,8 +1,26 @@
 use clap::Parser;
+use clap::ValueEnum;
+use codex_core::config::types::ResourceLimits;
+use codex_core::resource_limits::apply_rlimits;
+use codex_core::resource_limits::join_cgroup;
 use std::ffi::CString;
 use std::path::PathBuf;
 
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::namespaces::NamespaceSandbox;
+use crate::namespaces::enter_namespaces;
+use crate::namespaces::unshare_with_user_namespace;
+use crate::proxy_bridge::spawn_proxy_bridge;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// Landlock and seccomp applied to the current process.
+    #[default]
+    Landlock,
+    /// Fresh user, mount, PID and network namespaces.
+    #[value(alias = "ns")]
+    Namespaces,
+}
 
 #[derive(Debug, Parser)]
 pub struct LandlockCommand {
@@ -14,6 +32,47 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    /// Loopback port of the Codex egress proxy. When set and the policy
+    /// blocks network access, the command runs in a private network namespace
+    /// in which this loopback port is relayed to the proxy and nothing else
+    /// is reachable.
+    #[arg(long = "network-proxy-port")]
+    pub network_proxy_port: Option<u16>,
+
+    /// Paths the command may not access at all. May be repeated.
+    #[arg(long = "deny-read")]
+    pub deny_read: Vec<PathBuf>,
+
+    /// Isolation mechanism used to enforce the policy.
+    #[arg(long = "backend", value_enum, default_value_t = Backend::Landlock)]
+    pub backend: Backend,
+
+    /// With the namespaces backend, mount the workspace as an overlay whose
+    /// upper layer lives in `DIR/upper` instead of binding it read-write.
+    #[arg(long = "overlay-dir", value_name = "DIR")]
+    pub overlay_dir: Option<PathBuf>,
+
+    /// Maximum private writable memory per process, in megabytes.
+    #[arg(long = "max-memory-mb")]
+    pub max_memory_mb: Option<u64>,
+
+    /// Maximum CPU time per process, in seconds.
+    #[arg(long = "max-cpu-seconds")]
+    pub max_cpu_seconds: Option<u64>,
+
+    /// Maximum number of processes owned by the user (`RLIMIT_NPROC`).
+    #[arg(long = "max-processes")]
+    pub max_processes: Option<u64>,
+
+    /// Maximum size of any file the command writes, in megabytes.
+    #[arg(long = "max-file-size-mb")]
+    pub max_file_size_mb: Option<u64>,
+
+    /// cgroup v2 directory that enforces the command's memory and process
+    /// limits. The helper joins it before anything else.
+    #[arg(long = "cgroup", value_name = "DIR")]
+    pub cgroup: Option<PathBuf>,
+
     /// Full command args to run under landlock.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -23,11 +82,75 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        network_proxy_port,
+        deny_read,
+        backend,
+        overlay_dir,
+        max_memory_mb,
+        max_cpu_seconds,
+        max_processes,
+        max_file_size_mb,
+        cgroup,
         command,
     } = LandlockCommand::parse();
 
-    if let Err(e) = apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd) {
-        panic!("error running landlock: {e:?}");
+    if let Some(cgroup) = &cgroup
+        && let Err(e) = join_cgroup(cgroup)
+    {
+        panic!("error joining cgroup {}: {e}", cgroup.display());
+    }
+
+    // The relay to the egress proxy must be forked while the helper is still
+    // in the host network namespace.
+    let proxy_bridge = match network_proxy_port {
+        Some(port) if !sandbox_policy.has_full_network_access() => match spawn_proxy_bridge(port) {
+            Ok(proxy_bridge) => Some(proxy_bridge),
+            Err(e) => panic!("error starting the network proxy relay: {e}"),
+        },
+        _ => None,
+    };
+
+    match backend {
+        Backend::Landlock => {
+            // Without a network namespace (e.g. user namespaces are disabled)
+            // the proxy stays unreachable and the command has no network.
+            let proxy_bridged = proxy_bridge.is_some_and(|proxy_bridge| {
+                unshare_with_user_namespace(libc::CLONE_NEWNET)
+                    .and_then(|()| proxy_bridge.listen())
+                    .is_ok()
+            });
+            if let Err(e) = apply_sandbox_policy_to_current_thread(
+                &sandbox_policy,
+                &sandbox_policy_cwd,
+                proxy_bridged,
+                &deny_read,
+            ) {
+                panic!("error running landlock: {e:?}");
+            }
+        }
+        Backend::Namespaces => {
+            let sandbox = NamespaceSandbox {
+                sandbox_policy: &sandbox_policy,
+                sandbox_policy_cwd: &sandbox_policy_cwd,
+                deny_read: &deny_read,
+                overlay_dir: overlay_dir.as_deref(),
+                proxy_bridge,
+            };
+            if let Err(e) = enter_namespaces(sandbox) {
+                panic!("error entering sandbox namespaces: {e}");
+            }
+        }
+    }
+
+    // Applied last so the namespace setup above is not subject to them.
+    let resource_limits = ResourceLimits {
+        max_memory_mb,
+        max_cpu_seconds,
+        max_processes,
+        max_file_size_mb,
+    };
+    if let Err(e) = apply_rlimits(&resource_limits) {
+        panic!("error applying resource limits: {e}");
     }
 
     if command.is_empty() {
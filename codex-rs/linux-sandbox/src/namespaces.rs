@@ -0,0 +1,495 @@
+//! Namespace backend: runs the command in fresh user, mount and PID
+//! namespaces, plus a network namespace when the policy blocks network
+//! access. With an egress proxy, that network namespace reaches the proxy
+//! through [`crate::proxy_bridge`].
+//!
+//! The command sees a read-only bind of the host file system with:
+//! - its writable roots bound read-write (or the workspace mounted as an
+//!   overlay whose upper dir collects every change),
+//! - a private tmpfs on `/tmp`,
+//! - an empty, unreadable mount over each `deny_read` path, and a read-only
+//!   copy of the directory a missing `deny_read` path would be created in,
+//! - a `/proc` for its own PID namespace, so processes it leaves behind are
+//!   killed when it exits.
+
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::protocol::SandboxPolicy;
+
+use crate::proxy_bridge::ProxyBridge;
+
+/// The new root is assembled on a tmpfs mounted here. Inside the private
+/// mount namespace this only hides `/tmp` from the helper itself.
+const STAGING_DIR: &str = "/tmp";
+const NEW_ROOT: &str = "/newroot";
+const OLD_ROOT: &str = "/oldroot";
+
+/// `mount_setattr(2)` has the same number on every architecture; the libc
+/// crate does not expose it everywhere yet.
+const SYS_MOUNT_SETATTR: libc::c_long = 442;
+const MOUNT_ATTR_RDONLY: u64 = 0x1;
+const AT_RECURSIVE: libc::c_uint = 0x8000;
+
+#[repr(C)]
+struct MountAttr {
+    attr_set: u64,
+    attr_clr: u64,
+    propagation: u64,
+    userns_fd: u64,
+}
+
+pub(crate) struct NamespaceSandbox<'a> {
+    pub sandbox_policy: &'a SandboxPolicy,
+    pub sandbox_policy_cwd: &'a Path,
+    pub deny_read: &'a [PathBuf],
+    pub overlay_dir: Option<&'a Path>,
+    /// Relay to the egress proxy, set up inside the command's network
+    /// namespace.
+    pub proxy_bridge: Option<ProxyBridge>,
+}
+
+/// Moves the current process into the sandbox.
+///
+/// Returns only in the process that should exec the command. The helper's
+/// original process and the namespace's PID 1 stay behind to wait for it
+/// and exit with its status.
+pub(crate) fn enter_namespaces(sandbox: NamespaceSandbox) -> io::Result<()> {
+    // Resolve every path while the host file system is still the root, so
+    // absolute symlinks resolve the way the caller expects.
+    let mounts = MountPlan::new(&sandbox)?;
+
+    let mut flags = libc::CLONE_NEWNS | libc::CLONE_NEWPID;
+    if !sandbox.sandbox_policy.has_full_network_access() {
+        flags |= libc::CLONE_NEWNET;
+    }
+    unshare_with_user_namespace(flags)?;
+    if let Some(proxy_bridge) = sandbox.proxy_bridge {
+        proxy_bridge.listen()?;
+    }
+
+    // The first child becomes PID 1 of the new PID namespace.
+    let init = fork()?;
+    if init != 0 {
+        wait_and_exit(init);
+    }
+    kill_with_parent()?;
+    mounts.apply()?;
+
+    // PID 1 stays a minimal init: it reaps orphans and, by exiting, tears
+    // down everything the command left running.
+    let command = fork()?;
+    if command != 0 {
+        wait_and_exit(command);
+    }
+    kill_with_parent()?;
+    check(unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) })?;
+    Ok(())
+}
+
+/// Moves the current process into a new user namespace, mapping the caller's
+/// uid and gid to themselves, together with the namespaces in `flags`.
+pub(crate) fn unshare_with_user_namespace(flags: libc::c_int) -> io::Result<()> {
+    let uid = unsafe { libc::getuid() };
+    let gid = unsafe { libc::getgid() };
+    check(unsafe { libc::unshare(libc::CLONE_NEWUSER | flags) })?;
+    fs::write("/proc/self/setgroups", "deny")?;
+    fs::write("/proc/self/uid_map", format!("{uid} {uid} 1\n"))?;
+    fs::write("/proc/self/gid_map", format!("{gid} {gid} 1\n"))?;
+    Ok(())
+}
+
+struct MountPlan {
+    command_cwd: PathBuf,
+    /// Writable roots with their read-only subpaths.
+    writable_roots: Vec<(PathBuf, Vec<PathBuf>)>,
+    /// Workspace mounted as an overlay, with the overlay directory.
+    overlay: Option<(PathBuf, PathBuf)>,
+    deny_read: Vec<PathBuf>,
+    /// Existing directories that would hold a missing `deny_read` path,
+    /// parents first.
+    shadowed_dirs: Vec<PathBuf>,
+}
+
+impl MountPlan {
+    fn new(sandbox: &NamespaceSandbox) -> io::Result<Self> {
+        let command_cwd = std::env::current_dir()?;
+        let workspace = canonicalize(sandbox.sandbox_policy_cwd);
+
+        let overlay = match sandbox.overlay_dir {
+            Some(overlay_dir) => {
+                fs::create_dir_all(overlay_dir.join("upper"))?;
+                fs::create_dir_all(overlay_dir.join("work"))?;
+                Some((workspace.clone(), canonicalize(overlay_dir)))
+            }
+            None => None,
+        };
+
+        let writable_roots = if sandbox.sandbox_policy.has_full_disk_write_access() {
+            Vec::new()
+        } else {
+            sandbox
+                .sandbox_policy
+                .get_writable_roots_with_cwd(sandbox.sandbox_policy_cwd)
+                .into_iter()
+                .map(|writable_root| {
+                    let read_only_subpaths = writable_root
+                        .read_only_subpaths
+                        .iter()
+                        .map(|path| canonicalize(path.as_path()))
+                        .collect();
+                    (
+                        canonicalize(writable_root.root.as_path()),
+                        read_only_subpaths,
+                    )
+                })
+                .collect()
+        };
+
+        let mut deny_read = Vec::new();
+        let mut shadowed_dirs = Vec::new();
+        for path in sandbox.deny_read {
+            if let Ok(path) = path.canonicalize() {
+                deny_read.push(path);
+                continue;
+            }
+            // A missing path can still be created while the command runs, so
+            // freeze the directory it would appear in.
+            if let Some(dir) = path
+                .ancestors()
+                .skip(1)
+                .find_map(|ancestor| ancestor.canonicalize().ok())
+                .filter(|dir| dir.is_dir())
+                && !shadowed_dirs.contains(&dir)
+            {
+                shadowed_dirs.push(dir);
+            }
+        }
+        shadowed_dirs.sort_by_key(|dir| dir.components().count());
+
+        Ok(Self {
+            command_cwd,
+            writable_roots,
+            overlay,
+            deny_read,
+            shadowed_dirs,
+        })
+    }
+
+    fn apply(&self) -> io::Result<()> {
+        // Keep every mount below private to this namespace.
+        mount(
+            None,
+            Path::new("/"),
+            None,
+            libc::MS_REC | libc::MS_PRIVATE,
+            None,
+        )?;
+
+        // Pivot onto a scratch tmpfs first: the host tree stays reachable
+        // under OLD_ROOT while the new root is assembled under NEW_ROOT.
+        mount(
+            Some(Path::new("tmpfs")),
+            Path::new(STAGING_DIR),
+            Some("tmpfs"),
+            libc::MS_NOSUID | libc::MS_NODEV,
+            None,
+        )?;
+        fs::create_dir(Path::new(STAGING_DIR).join(&NEW_ROOT[1..]))?;
+        fs::create_dir(Path::new(STAGING_DIR).join(&OLD_ROOT[1..]))?;
+        pivot_root(
+            Path::new(STAGING_DIR),
+            &Path::new(STAGING_DIR).join(&OLD_ROOT[1..]),
+        )?;
+        std::env::set_current_dir("/")?;
+
+        let new_root = Path::new(NEW_ROOT);
+        bind(Path::new(OLD_ROOT), new_root)?;
+        make_read_only(new_root)?;
+
+        mount(
+            Some(Path::new("tmpfs")),
+            &new_root.join("tmp"),
+            Some("tmpfs"),
+            libc::MS_NOSUID | libc::MS_NODEV,
+            None,
+        )?;
+        // Device nodes such as /dev/null must stay writable.
+        bind(&host_path("/dev"), &new_root.join("dev"))?;
+        let proc_dir = new_root.join("proc");
+        let proc_flags = libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC;
+        if mount(
+            Some(Path::new("proc")),
+            &proc_dir,
+            Some("proc"),
+            proc_flags,
+            None,
+        )
+        .is_err()
+        {
+            // The kernel refuses a fresh procfs when the host's is partly
+            // masked (e.g. inside a container); fall back to the host view.
+            bind(&host_path("/proc"), &proc_dir)?;
+        }
+
+        for (root, read_only_subpaths) in &self.writable_roots {
+            let target = under(new_root, root);
+            if root == Path::new("/tmp") {
+                // Already replaced by the private tmpfs.
+            } else if let Some((workspace, overlay_dir)) = &self.overlay
+                && workspace == root
+            {
+                mount_overlay(root, overlay_dir, &target)?;
+            } else {
+                create_mount_point(&host_path(root), &target)?;
+                bind(&host_path(root), &target)?;
+            }
+            for subpath in read_only_subpaths {
+                let target = under(new_root, subpath);
+                if target.exists() {
+                    bind(&target, &target)?;
+                    make_read_only(&target)?;
+                }
+            }
+        }
+
+        for (index, dir) in self.shadowed_dirs.iter().enumerate() {
+            let target = under(new_root, dir);
+            if target.is_dir() {
+                shadow_dir(&target, &Path::new("/").join(format!("shadow{index}")))?;
+            }
+        }
+
+        for path in &self.deny_read {
+            let target = under(new_root, path);
+            if !target.exists() {
+                continue;
+            }
+            if target.is_dir() {
+                mount(
+                    Some(Path::new("tmpfs")),
+                    &target,
+                    Some("tmpfs"),
+                    libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                    Some("mode=000"),
+                )?;
+            } else {
+                bind(&new_root.join("dev/null"), &target)?;
+            }
+        }
+
+        // Swap in the new root and drop the host tree stacked underneath it.
+        std::env::set_current_dir(new_root)?;
+        pivot_root(Path::new("."), Path::new("."))?;
+        check(unsafe { libc::umount2(c".".as_ptr(), libc::MNT_DETACH) })?;
+        std::env::set_current_dir(&self.command_cwd)?;
+        Ok(())
+    }
+}
+
+/// Replaces the directory `target` with a read-only tmpfs holding binds of
+/// its current entries, so no new entry can appear in it, whether created by
+/// the command or by the host while the command runs. `stash` is a scratch
+/// directory on the staging tmpfs.
+fn shadow_dir(target: &Path, stash: &Path) -> io::Result<()> {
+    let mode = fs::metadata(target)?.permissions().mode() & 0o7777;
+    fs::create_dir(stash)?;
+    bind(target, stash)?;
+    let flags = libc::MS_NOSUID | libc::MS_NODEV;
+    mount(
+        Some(Path::new("tmpfs")),
+        target,
+        Some("tmpfs"),
+        flags,
+        Some(&format!("mode={mode:o}")),
+    )?;
+    for entry in fs::read_dir(stash)? {
+        let entry = entry?;
+        let source = entry.path();
+        let entry_target = target.join(entry.file_name());
+        if entry.file_type()?.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(&source)?, &entry_target)?;
+        } else {
+            create_mount_point(&source, &entry_target)?;
+            bind(&source, &entry_target)?;
+        }
+    }
+    // Only the tmpfs itself: the binds keep their own flags.
+    mount(
+        None,
+        target,
+        None,
+        libc::MS_REMOUNT | libc::MS_RDONLY | flags,
+        None,
+    )?;
+    let stash = to_cstring(stash)?;
+    check(unsafe { libc::umount2(stash.as_ptr(), libc::MNT_DETACH) })
+}
+
+fn mount_overlay(workspace: &Path, overlay_dir: &Path, target: &Path) -> io::Result<()> {
+    let options = format!(
+        "lowerdir={},upperdir={},workdir={},userxattr",
+        escape_overlay_path(&host_path(workspace)),
+        escape_overlay_path(&host_path(&overlay_dir.join("upper"))),
+        escape_overlay_path(&host_path(&overlay_dir.join("work"))),
+    );
+    mount(
+        Some(Path::new("overlay")),
+        target,
+        Some("overlay"),
+        0,
+        Some(&options),
+    )
+}
+
+/// Overlayfs splits its options on `,` and lower dirs on `:`.
+fn escape_overlay_path(path: &Path) -> String {
+    let mut escaped = String::new();
+    for ch in path.to_string_lossy().chars() {
+        if matches!(ch, ',' | ':' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Location of the absolute host `path` while the host tree is at OLD_ROOT.
+fn host_path(path: impl AsRef<Path>) -> PathBuf {
+    under(Path::new(OLD_ROOT), path.as_ref())
+}
+
+fn under(root: &Path, path: &Path) -> PathBuf {
+    root.join(path.strip_prefix("/").unwrap_or(path))
+}
+
+/// Creates the mount point for `source` at `target` when it is missing, which
+/// only happens inside the private `/tmp` and in shadowed directories.
+fn create_mount_point(source: &Path, target: &Path) -> io::Result<()> {
+    if target.exists() {
+        return Ok(());
+    }
+    if source.is_dir() {
+        fs::create_dir_all(target)
+    } else {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, "")
+    }
+}
+
+fn bind(source: &Path, target: &Path) -> io::Result<()> {
+    mount(
+        Some(source),
+        target,
+        None,
+        libc::MS_BIND | libc::MS_REC,
+        None,
+    )
+}
+
+fn make_read_only(path: &Path) -> io::Result<()> {
+    let path = to_cstring(path)?;
+    let attr = MountAttr {
+        attr_set: MOUNT_ATTR_RDONLY,
+        attr_clr: 0,
+        propagation: 0,
+        userns_fd: 0,
+    };
+    let rc = unsafe {
+        libc::syscall(
+            SYS_MOUNT_SETATTR,
+            libc::AT_FDCWD,
+            path.as_ptr(),
+            AT_RECURSIVE,
+            &attr as *const MountAttr,
+            std::mem::size_of::<MountAttr>(),
+        )
+    };
+    check(rc)
+}
+
+fn mount(
+    source: Option<&Path>,
+    target: &Path,
+    fstype: Option<&str>,
+    flags: libc::c_ulong,
+    data: Option<&str>,
+) -> io::Result<()> {
+    let source = source.map(to_cstring).transpose()?;
+    let target = to_cstring(target)?;
+    let fstype = fstype.map(to_cstring).transpose()?;
+    let data = data.map(to_cstring).transpose()?;
+    let rc = unsafe {
+        libc::mount(
+            source.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            target.as_ptr(),
+            fstype.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            flags,
+            data.as_ref()
+                .map_or(std::ptr::null(), |s| s.as_ptr().cast::<libc::c_void>()),
+        )
+    };
+    check(rc)
+}
+
+fn pivot_root(new_root: &Path, put_old: &Path) -> io::Result<()> {
+    let new_root = to_cstring(new_root)?;
+    let put_old = to_cstring(put_old)?;
+    check(unsafe { libc::syscall(libc::SYS_pivot_root, new_root.as_ptr(), put_old.as_ptr()) })
+}
+
+pub(crate) fn fork() -> io::Result<libc::pid_t> {
+    let pid = unsafe { libc::fork() };
+    check(pid)?;
+    Ok(pid)
+}
+
+/// Makes sure the current process dies with the one that forked it.
+pub(crate) fn kill_with_parent() -> io::Result<()> {
+    check(unsafe { libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL, 0, 0, 0) })
+}
+
+/// Waits for `child`, reaping any other process that exits meanwhile (only
+/// relevant for PID 1), and exits with the child's status.
+pub(crate) fn wait_and_exit(child: libc::pid_t) -> ! {
+    loop {
+        let mut status = 0;
+        let pid = unsafe { libc::waitpid(-1, &mut status, 0) };
+        if pid == child {
+            if libc::WIFEXITED(status) {
+                std::process::exit(libc::WEXITSTATUS(status));
+            }
+            if libc::WIFSIGNALED(status) {
+                // Mirror the shell's convention; PID 1 cannot re-raise the
+                // signal on itself.
+                std::process::exit(128 + libc::WTERMSIG(status));
+            }
+        } else if pid < 0 && io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+            std::process::exit(1);
+        }
+    }
+}
+
+fn to_cstring(value: impl AsRef<std::ffi::OsStr>) -> io::Result<CString> {
+    CString::new(value.as_ref().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+}
+
+pub(crate) fn check<T: Default + PartialOrd>(rc: T) -> io::Result<()> {
+    if rc < T::default() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
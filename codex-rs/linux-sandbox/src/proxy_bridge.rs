@@ -0,0 +1,193 @@
+//! Relay that keeps the egress proxy reachable from a private network
+//! namespace.
+//!
+//! When the policy blocks network access but an egress proxy is configured,
+//! the command runs in a network namespace that only has a loopback
+//! interface, so no remote host is reachable directly. Inside it, the helper
+//! listens on the proxy's port; a process left behind in the host namespace
+//! accepts on that listener and relays every connection to the real proxy on
+//! the host's loopback.
+
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsFd;
+use std::os::fd::AsRawFd;
+use std::os::fd::BorrowedFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+
+use crate::namespaces::check;
+use crate::namespaces::fork;
+use crate::namespaces::kill_with_parent;
+use crate::namespaces::wait_and_exit;
+
+/// Sandbox side of the relay: hands the in-namespace listener to the relay
+/// process.
+pub(crate) struct ProxyBridge {
+    control: OwnedFd,
+    port: u16,
+}
+
+/// Forks the relay while the helper is still in the host network namespace.
+///
+/// Returns only in the child, which goes on to set up the sandbox and exec
+/// the command. The parent relays connections until the child exits and then
+/// exits with its status.
+pub(crate) fn spawn_proxy_bridge(port: u16) -> io::Result<ProxyBridge> {
+    let (relay_end, sandbox_end) = seqpacket_pair()?;
+    let child = fork()?;
+    if child != 0 {
+        drop(sandbox_end);
+        std::thread::spawn(move || relay(&relay_end, port));
+        wait_and_exit(child);
+    }
+    drop(relay_end);
+    kill_with_parent()?;
+    Ok(ProxyBridge {
+        control: sandbox_end,
+        port,
+    })
+}
+
+impl ProxyBridge {
+    /// Listens on the proxy port in the current network namespace, which
+    /// must be the command's, and passes the listener to the relay.
+    pub(crate) fn listen(self) -> io::Result<()> {
+        bring_up_loopback()?;
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, self.port))?;
+        send_fd(&self.control, listener.as_fd())
+    }
+}
+
+fn relay(control: &OwnedFd, port: u16) {
+    // The sandbox closes its end without sending a listener when it cannot
+    // set one up; the command then has no network at all.
+    let Ok(listener) = recv_fd(control) else {
+        return;
+    };
+    let listener = TcpListener::from(listener);
+    for inbound in listener.incoming().flatten() {
+        std::thread::spawn(move || forward(inbound, port));
+    }
+}
+
+fn forward(inbound: TcpStream, port: u16) -> io::Result<()> {
+    let outbound = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+    let mut upstream_reader = inbound.try_clone()?;
+    let mut upstream_writer = outbound.try_clone()?;
+    let upstream = std::thread::spawn(move || {
+        let _ = io::copy(&mut upstream_reader, &mut upstream_writer);
+        let _ = upstream_writer.shutdown(Shutdown::Write);
+    });
+    let (mut downstream_reader, mut downstream_writer) = (outbound, inbound);
+    let _ = io::copy(&mut downstream_reader, &mut downstream_writer);
+    let _ = downstream_writer.shutdown(Shutdown::Write);
+    let _ = upstream.join();
+    Ok(())
+}
+
+/// `struct ifreq` with the `ifr_flags` member of its union.
+#[repr(C)]
+struct InterfaceFlagsRequest {
+    name: [libc::c_char; libc::IFNAMSIZ],
+    flags: libc::c_short,
+    _padding: [u8; 22],
+}
+
+/// A new network namespace starts with its loopback interface down.
+fn bring_up_loopback() -> io::Result<()> {
+    let socket = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    check(socket)?;
+    let socket = unsafe { OwnedFd::from_raw_fd(socket) };
+    let mut request = InterfaceFlagsRequest {
+        name: [0; libc::IFNAMSIZ],
+        flags: 0,
+        _padding: [0; 22],
+    };
+    for (dst, src) in request.name.iter_mut().zip(b"lo") {
+        *dst = *src as libc::c_char;
+    }
+    check(unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCGIFFLAGS as _, &mut request) })?;
+    request.flags |= libc::IFF_UP as libc::c_short;
+    check(unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCSIFFLAGS as _, &request) })
+}
+
+fn seqpacket_pair() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    check(unsafe {
+        libc::socketpair(
+            libc::AF_UNIX,
+            libc::SOCK_SEQPACKET | libc::SOCK_CLOEXEC,
+            0,
+            fds.as_mut_ptr(),
+        )
+    })?;
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Room for one `SCM_RIGHTS` message carrying a single descriptor, aligned
+/// for `cmsghdr`.
+type ControlBuffer = [u64; 4];
+
+fn one_byte_iovec(byte: &mut [u8; 1]) -> libc::iovec {
+    libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: byte.len(),
+    }
+}
+
+/// Builds a message carrying one byte from `payload` plus `control`.
+fn fd_message(payload: &mut libc::iovec, control: &mut ControlBuffer) -> libc::msghdr {
+    let mut message: libc::msghdr = unsafe { std::mem::zeroed() };
+    message.msg_iov = payload;
+    message.msg_iovlen = 1;
+    message.msg_control = control.as_mut_ptr().cast();
+    message.msg_controllen =
+        unsafe { libc::CMSG_SPACE(std::mem::size_of::<libc::c_int>() as u32) } as _;
+    message
+}
+
+fn send_fd(socket: &OwnedFd, fd: BorrowedFd<'_>) -> io::Result<()> {
+    let mut byte = [0u8; 1];
+    let mut payload = one_byte_iovec(&mut byte);
+    let mut control: ControlBuffer = [0; 4];
+    let message = fd_message(&mut payload, &mut control);
+    unsafe {
+        let header = libc::CMSG_FIRSTHDR(&message);
+        (*header).cmsg_level = libc::SOL_SOCKET;
+        (*header).cmsg_type = libc::SCM_RIGHTS;
+        (*header).cmsg_len = libc::CMSG_LEN(std::mem::size_of::<libc::c_int>() as u32) as _;
+        std::ptr::write_unaligned(
+            libc::CMSG_DATA(header).cast::<libc::c_int>(),
+            fd.as_raw_fd(),
+        );
+    }
+    check(unsafe { libc::sendmsg(socket.as_raw_fd(), &message, 0) })
+}
+
+fn recv_fd(socket: &OwnedFd) -> io::Result<OwnedFd> {
+    let mut byte = [0u8; 1];
+    let mut payload = one_byte_iovec(&mut byte);
+    let mut control: ControlBuffer = [0; 4];
+    let mut message = fd_message(&mut payload, &mut control);
+    let received =
+        unsafe { libc::recvmsg(socket.as_raw_fd(), &mut message, libc::MSG_CMSG_CLOEXEC) };
+    check(received)?;
+    let header = unsafe { libc::CMSG_FIRSTHDR(&message) };
+    if received == 0
+        || header.is_null()
+        || unsafe {
+            (*header).cmsg_level != libc::SOL_SOCKET || (*header).cmsg_type != libc::SCM_RIGHTS
+        }
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "sandbox closed the relay channel without a listener",
+        ));
+    }
+    let fd = unsafe { std::ptr::read_unaligned(libc::CMSG_DATA(header).cast::<libc::c_int>()) };
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
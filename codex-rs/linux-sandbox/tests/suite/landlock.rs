@@ -3,6 +3,7 @@ use codex_core::config::types::ShellEnvironmentPolicy;
 use codex_core::error::CodexErr;
 use codex_core::error::SandboxErr;
 use codex_core::exec::ExecParams;
+use codex_core::exec::ExecToolCallOutput;
 use codex_core::exec::process_exec_tool_call;
 use codex_core::exec_env::create_env;
 use codex_core::protocol::SandboxPolicy;
@@ -34,8 +35,34 @@ fn create_env_from_core_vars() -> HashMap<String, String> {
     create_env(&policy)
 }
 
-#[expect(clippy::print_stdout, clippy::expect_used, clippy::unwrap_used)]
 async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
+    run_cmd_with_deny_read(cmd, writable_roots, &[], timeout_ms).await;
+}
+
+#[expect(clippy::print_stdout)]
+async fn run_cmd_with_deny_read(
+    cmd: &[&str],
+    writable_roots: &[PathBuf],
+    deny_read: &[PathBuf],
+    timeout_ms: u64,
+) {
+    let res = run_cmd_output(cmd, writable_roots, deny_read, timeout_ms).await;
+    if res.exit_code != 0 {
+        println!("stdout:\n{}", res.stdout.text);
+        println!("stderr:\n{}", res.stderr.text);
+        panic!("exit code: {}", res.exit_code);
+    }
+}
+
+/// Runs `cmd` under a workspace-write policy and returns its output whether or
+/// not it succeeds.
+#[expect(clippy::expect_used, clippy::unwrap_used)]
+async fn run_cmd_output(
+    cmd: &[&str],
+    writable_roots: &[PathBuf],
+    deny_read: &[PathBuf],
+    timeout_ms: u64,
+) -> ExecToolCallOutput {
     let cwd = std::env::current_dir().expect("cwd should exist");
     let sandbox_cwd = cwd.clone();
     let params = ExecParams {
@@ -62,20 +89,23 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
     };
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
-    let res = process_exec_tool_call(
+    let deny_read: Vec<AbsolutePathBuf> = deny_read
+        .iter()
+        .map(|p| AbsolutePathBuf::try_from(p.as_path()).unwrap())
+        .collect();
+    match process_exec_tool_call(
         params,
         &sandbox_policy,
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
+        &deny_read,
         None,
     )
     .await
-    .unwrap();
-
-    if res.exit_code != 0 {
-        println!("stdout:\n{}", res.stdout.text);
-        println!("stderr:\n{}", res.stderr.text);
-        panic!("exit code: {}", res.exit_code);
+    {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        Err(err) => panic!("failed to run {cmd:?}: {err:?}"),
     }
 }
 
@@ -127,6 +157,149 @@ async fn test_writable_root() {
     .await;
 }
 
+#[tokio::test]
+async fn test_deny_read_blocks_read() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let secret = tmpdir.path().join("secret");
+    std::fs::write(&secret, "hunter2").unwrap();
+    let output = run_cmd_output(
+        &["cat", &secret.to_string_lossy()],
+        &[],
+        &[tmpdir.path().to_path_buf()],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_ne!(output.exit_code, 0);
+    assert!(!output.stdout.text.contains("hunter2"));
+}
+
+#[tokio::test]
+async fn test_deny_read_keeps_parent_listable() {
+    // Mirrors denying `~/.ssh`: `ls ~` works while `cat ~/.ssh/x` fails.
+    let home = tempfile::tempdir().unwrap();
+    let ssh = home.path().join(".ssh");
+    std::fs::create_dir(&ssh).unwrap();
+    std::fs::write(ssh.join("x"), "hunter2").unwrap();
+    std::fs::write(home.path().join("notes.txt"), "hello").unwrap();
+    let deny_read = [ssh.clone()];
+
+    let listing = run_cmd_output(
+        &["ls", "-a", &home.path().to_string_lossy()],
+        &[],
+        &deny_read,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_eq!(listing.exit_code, 0, "{}", listing.stderr.text);
+    assert!(listing.stdout.text.contains("notes.txt"));
+    assert!(listing.stdout.text.contains(".ssh"));
+
+    let read = run_cmd_output(
+        &["cat", &ssh.join("x").to_string_lossy()],
+        &[],
+        &deny_read,
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_ne!(read.exit_code, 0);
+    assert!(!read.stdout.text.contains("hunter2"));
+}
+
+#[tokio::test]
+async fn test_deny_read_covers_paths_created_later() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let missing = tmpdir.path().join("missing");
+    let output = run_cmd_output(
+        &[
+            "bash",
+            "-c",
+            &format!(
+                "mkdir {0}; echo hunter2 > {0}/secret; cat {0}/secret",
+                missing.display()
+            ),
+        ],
+        &[tmpdir.path().to_path_buf()],
+        &[missing.clone()],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_ne!(output.exit_code, 0);
+    assert!(!output.stdout.text.contains("hunter2"));
+    let written = std::fs::read_to_string(missing.join("secret")).unwrap_or_default();
+    assert!(!written.contains("hunter2"));
+}
+
+#[tokio::test]
+async fn test_deny_read_keeps_siblings_readable() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let denied = tmpdir.path().join("denied");
+    std::fs::create_dir(&denied).unwrap();
+    let sibling = tmpdir.path().join("sibling");
+    std::fs::write(&sibling, "hello").unwrap();
+    run_cmd_with_deny_read(
+        &["cat", &sibling.to_string_lossy()],
+        &[],
+        &[denied],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_deny_read_inside_writable_root() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let denied = tmpdir.path().join("denied");
+    std::fs::create_dir(&denied).unwrap();
+    std::fs::write(denied.join("secret"), "hunter2").unwrap();
+    let output = run_cmd_output(
+        &["cat", &denied.join("secret").to_string_lossy()],
+        &[tmpdir.path().to_path_buf()],
+        &[denied],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_ne!(output.exit_code, 0);
+    assert!(!output.stdout.text.contains("hunter2"));
+}
+
+#[tokio::test]
+async fn test_deny_read_keeps_writable_root_editable() {
+    // Mirrors a workspace root holding a denied `.env`: files directly in the
+    // root can still be created, renamed and removed.
+    let tmpdir = tempfile::tempdir().unwrap();
+    let denied = tmpdir.path().join(".env");
+    std::fs::write(&denied, "hunter2").unwrap();
+    let root = tmpdir.path().display();
+    run_cmd_with_deny_read(
+        &[
+            "bash",
+            "-c",
+            &format!(
+                "echo hello > {root}/new.txt && mv {root}/new.txt {root}/moved.txt \
+                 && mkdir {root}/dir && rmdir {root}/dir && cat {root}/moved.txt"
+            ),
+        ],
+        &[tmpdir.path().to_path_buf()],
+        &[denied.clone()],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_eq!(
+        std::fs::read_to_string(tmpdir.path().join("moved.txt")).unwrap(),
+        "hello\n"
+    );
+
+    let read = run_cmd_output(
+        &["cat", &denied.to_string_lossy()],
+        &[tmpdir.path().to_path_buf()],
+        &[denied.clone()],
+        LONG_TIMEOUT_MS,
+    )
+    .await;
+    assert_ne!(read.exit_code, 0);
+    assert!(!read.stdout.text.contains("hunter2"));
+}
+
 #[tokio::test]
 #[should_panic(expected = "Sandbox(Timeout")]
 async fn test_timeout() {
@@ -161,6 +334,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         &sandbox_policy,
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
+        &[],
         None,
     )
     .await;
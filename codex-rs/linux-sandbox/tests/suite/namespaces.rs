@@ -0,0 +1,246 @@
+#![cfg(target_os = "linux")]
+#![expect(clippy::expect_used, clippy::unwrap_used)]
+use codex_core::protocol::SandboxPolicy;
+use std::io::Read;
+use std::net::IpAddr;
+use std::net::TcpListener;
+use std::net::UdpSocket;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use tempfile::TempDir;
+
+/// Some CI runners disable unprivileged user namespaces entirely.
+fn user_namespaces_available() -> bool {
+    let mut probe = Command::new("true");
+    unsafe {
+        probe.pre_exec(|| {
+            if libc::unshare(libc::CLONE_NEWUSER) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+    probe.status().is_ok_and(|status| status.success())
+}
+
+fn run_in_namespaces(
+    workspace: &Path,
+    overlay_dir: Option<&Path>,
+    deny_read: &[&Path],
+    script: &str,
+) -> Output {
+    let policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let mut command = Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"));
+    command
+        .arg("--sandbox-policy-cwd")
+        .arg(workspace)
+        .arg("--sandbox-policy")
+        .arg(serde_json::to_string(&policy).unwrap())
+        .arg("--backend")
+        .arg("namespaces");
+    if let Some(overlay_dir) = overlay_dir {
+        command.arg("--overlay-dir").arg(overlay_dir);
+    }
+    for path in deny_read {
+        command.arg("--deny-read").arg(path);
+    }
+    command
+        .arg("--")
+        .args(["bash", "-c", script])
+        .current_dir(workspace)
+        .output()
+        .expect("failed to run codex-linux-sandbox")
+}
+
+#[test]
+fn command_is_not_pid_one() {
+    if !user_namespaces_available() {
+        eprintln!("skipping: user namespaces unavailable");
+        return;
+    }
+    let workspace = TempDir::new().unwrap();
+    let output = run_in_namespaces(workspace.path(), None, &[], "echo $$");
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+}
+
+#[test]
+fn tmp_is_private() {
+    if !user_namespaces_available() {
+        eprintln!("skipping: user namespaces unavailable");
+        return;
+    }
+    let workspace = TempDir::new().unwrap();
+    let marker = tempfile::Builder::new()
+        .prefix("codex-ns-marker")
+        .tempfile_in("/tmp")
+        .unwrap();
+    let script = format!("test ! -e {} && touch /tmp/inside", marker.path().display());
+    let output = run_in_namespaces(workspace.path(), None, &[], &script);
+    assert!(output.status.success(), "{output:?}");
+    assert!(!Path::new("/tmp/inside").exists());
+}
+
+#[test]
+fn workspace_is_writable_and_rest_is_read_only() {
+    if !user_namespaces_available() {
+        eprintln!("skipping: user namespaces unavailable");
+        return;
+    }
+    let workspace = TempDir::new().unwrap();
+    // Not under /tmp, which the sandbox replaces with a private tmpfs.
+    let outside = TempDir::new_in(env!("CARGO_TARGET_TMPDIR")).unwrap();
+    let script = format!(
+        "echo ok > inside.txt && ! touch {}/outside.txt",
+        outside.path().display()
+    );
+    let output = run_in_namespaces(workspace.path(), None, &[], &script);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        std::fs::read_to_string(workspace.path().join("inside.txt")).unwrap(),
+        "ok\n"
+    );
+    assert!(!outside.path().join("outside.txt").exists());
+}
+
+#[test]
+fn overlay_collects_writes_in_upper_dir() {
+    if !user_namespaces_available() {
+        eprintln!("skipping: user namespaces unavailable");
+        return;
+    }
+    let workspace = TempDir::new().unwrap();
+    std::fs::write(workspace.path().join("existing.txt"), "before\n").unwrap();
+    let overlay = TempDir::new_in(env!("CARGO_TARGET_TMPDIR")).unwrap();
+    let output = run_in_namespaces(
+        workspace.path(),
+        Some(overlay.path()),
+        &[],
+        "echo after > existing.txt && echo new > created.txt && cat existing.txt",
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "after\n");
+    assert_eq!(
+        std::fs::read_to_string(workspace.path().join("existing.txt")).unwrap(),
+        "before\n"
+    );
+    assert!(!workspace.path().join("created.txt").exists());
+    let upper = overlay.path().join("upper");
+    assert_eq!(
+        std::fs::read_to_string(upper.join("created.txt")).unwrap(),
+        "new\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(upper.join("existing.txt")).unwrap(),
+        "after\n"
+    );
+}
+
+#[test]
+fn deny_read_covers_paths_created_later() {
+    if !user_namespaces_available() {
+        eprintln!("skipping: user namespaces unavailable");
+        return;
+    }
+    let workspace = TempDir::new().unwrap();
+    std::fs::write(workspace.path().join("existing.txt"), "before\n").unwrap();
+    let missing = workspace.path().join("missing");
+    let output = run_in_namespaces(
+        workspace.path(),
+        None,
+        &[&missing],
+        "mkdir missing; echo hunter2 > missing/secret; cat missing/secret",
+    );
+    assert!(!output.status.success(), "{output:?}");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("hunter2"));
+    assert!(!missing.exists());
+
+    // Entries already next to the missing path stay readable and writable.
+    let output = run_in_namespaces(
+        workspace.path(),
+        None,
+        &[&missing],
+        "echo after > existing.txt && cat existing.txt",
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        std::fs::read_to_string(workspace.path().join("existing.txt")).unwrap(),
+        "after\n"
+    );
+}
+
+/// Runs `script` under a read-only policy whose egress proxy listens on
+/// `proxy_port`.
+fn run_with_proxy(backend: &str, proxy_port: u16, script: &str) -> Output {
+    let workspace = TempDir::new().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .arg("--sandbox-policy-cwd")
+        .arg(workspace.path())
+        .arg("--sandbox-policy")
+        .arg(serde_json::to_string(&SandboxPolicy::new_read_only_policy()).unwrap())
+        .arg("--backend")
+        .arg(backend)
+        .arg("--network-proxy-port")
+        .arg(proxy_port.to_string())
+        .arg("--")
+        .args(["bash", "-c", script])
+        .current_dir(workspace.path())
+        .output()
+        .expect("failed to run codex-linux-sandbox")
+}
+
+/// An address of this host other than loopback, if it has one.
+fn non_loopback_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    // Connecting a UDP socket only selects a route; nothing is sent.
+    socket.connect("192.0.2.1:9").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+#[test]
+fn proxy_port_is_only_reachable_through_loopback() {
+    if !user_namespaces_available() {
+        eprintln!("skipping: user namespaces unavailable");
+        return;
+    }
+    let Some(host_ip) = non_loopback_address() else {
+        eprintln!("skipping: no non-loopback address");
+        return;
+    };
+    for backend in ["landlock", "namespaces"] {
+        // Stands in for the egress proxy. It listens on every interface, so a
+        // direct connection to `host_ip` would reach it if it were allowed.
+        let proxy = TcpListener::bind("0.0.0.0:0").unwrap();
+        let port = proxy.local_addr().unwrap().port();
+        let received = std::thread::spawn(move || {
+            let (mut stream, _) = proxy.accept().unwrap();
+            let mut text = String::new();
+            stream.read_to_string(&mut text).unwrap();
+            text
+        });
+
+        let direct = run_with_proxy(
+            backend,
+            port,
+            &format!("echo direct > /dev/tcp/{host_ip}/{port}"),
+        );
+        assert!(!direct.status.success(), "{backend}: {direct:?}");
+
+        let relayed = run_with_proxy(
+            backend,
+            port,
+            &format!("echo relayed > /dev/tcp/127.0.0.1/{port}"),
+        );
+        assert!(relayed.status.success(), "{backend}: {relayed:?}");
+        assert_eq!(received.join().unwrap(), "relayed\n", "{backend}");
+    }
+}
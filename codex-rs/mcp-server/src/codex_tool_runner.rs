@@ -176,6 +176,7 @@ async fn run_codex_tool_session_inner(
                         call_id,
                         reason: _,
                         proposed_execpolicy_amendment: _,
+                        explanation: _,
                         parsed_cmd,
                     }) => {
                         handle_exec_approval_request(
@@ -306,8 +307,11 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::ThreadRolledBack(_)
+                    | EventMsg::TurnSteered(_)
                     | EventMsg::DeprecationNotice(_)
-                    | EventMsg::HookActivity(_) => {
+                    | EventMsg::HookActivity(_)
+                    | EventMsg::SubAgentActivity(_)
+                    | EventMsg::NetworkAccessDenied(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
                         // send(codex_event_to_notification(&event)) above has
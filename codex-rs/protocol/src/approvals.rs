@@ -37,6 +37,52 @@ impl From<Vec<String>> for ExecPolicyAmendment {
     }
 }
 
+/// Decision an execpolicy rule assigns to the commands it matches.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecPolicyDecision {
+    Allow,
+    Prompt,
+    Forbidden,
+}
+
+/// Config layer an execpolicy rules file was loaded from.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecPolicyLayer {
+    System,
+    User,
+    Project,
+}
+
+/// An execpolicy rule that matched the command being approved.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ExecPolicyRuleMatch {
+    /// Leading command tokens the rule matched.
+    pub matched_prefix: Vec<String>,
+    pub decision: ExecPolicyDecision,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub justification: Option<String>,
+    /// Rules file that defined the rule; absent for rules added during the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub source: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub layer: Option<ExecPolicyLayer>,
+}
+
+/// Why a command needs approval: the execpolicy rules that decided, or, when no rule matched,
+/// a note on the built-in safety heuristic that did.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ExecPolicyExplanation {
+    pub matched_rules: Vec<ExecPolicyRuleMatch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub heuristic: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecApprovalRequestEvent {
     /// Identifier for the associated exec call, if available.
@@ -56,6 +102,10 @@ pub struct ExecApprovalRequestEvent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    /// Which execpolicy rules or heuristic decided that the command needs approval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub explanation: Option<ExecPolicyExplanation>,
     pub parsed_cmd: Vec<ParsedCommand>,
 }
 
@@ -155,6 +155,14 @@ impl TruncationPolicyConfig {
     }
 }
 
+/// BPE vocabulary the model uses to split text into tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, TS, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerEncoding {
+    O200kBase,
+    Cl100kBase,
+}
+
 /// Semantic version triple encoded as an array in JSON (e.g. [0, 62, 0]).
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS, JsonSchema)]
 pub struct ClientVersion(pub i32, pub i32, pub i32);
@@ -195,6 +203,10 @@ pub struct ModelInfo {
     #[serde(default = "default_effective_context_window_percent")]
     pub effective_context_window_percent: i64,
     pub experimental_supported_tools: Vec<String>,
+    /// Vocabulary used to count tokens locally. When omitted, core picks one
+    /// from the slug.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<TokenizerEncoding>,
 }
 
 impl ModelInfo {
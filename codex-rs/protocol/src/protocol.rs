@@ -42,6 +42,10 @@ pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
+pub use crate::approvals::ExecPolicyDecision;
+pub use crate::approvals::ExecPolicyExplanation;
+pub use crate::approvals::ExecPolicyLayer;
+pub use crate::approvals::ExecPolicyRuleMatch;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
 /// duplicated hardcoded strings.
@@ -79,6 +83,17 @@ pub enum Op {
         final_output_json_schema: Option<Value>,
     },
 
+    /// Append user input to the turn that is currently running. The input is
+    /// delivered to the model at the next request boundary, and the server
+    /// sends [`EventMsg::TurnSteered`] once it has been recorded. Fails with
+    /// [`CodexErrorInfo::TurnSteerFailed`] when no turn is running.
+    SteerTurn {
+        items: Vec<UserInput>,
+        /// When set, the steer is rejected unless this is the running turn.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expected_turn_id: Option<String>,
+    },
+
     /// Similar to [`Op::UserInput`], but contains additional context required
     /// for a turn of a [`crate::codex_thread::CodexThread`].
     UserTurn {
@@ -546,12 +561,21 @@ pub enum EventMsg {
 
     HookActivity(HookActivityEvent),
 
+    /// Activity from a sub-agent spawned by this session, tagged with the sub-agent's thread id.
+    SubAgentActivity(SubAgentActivityEvent),
+
+    /// The sandbox egress proxy refused a connection to a host outside the allowlist.
+    NetworkAccessDenied(NetworkAccessDeniedEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
+    /// User input passed via [`Op::SteerTurn`] was delivered to the model.
+    TurnSteered(TurnSteeredEvent),
+
     /// Agent has started a turn.
     /// v1 wire format uses `task_started`; accept `turn_started` for v2 interop.
     #[serde(rename = "task_started", alias = "turn_started")]
@@ -734,6 +758,7 @@ pub enum CodexErrorInfo {
         http_status_code: Option<u16>,
     },
     ThreadRollbackFailed,
+    TurnSteerFailed,
     Other,
 }
 
@@ -875,6 +900,10 @@ pub struct WarningEvent {
 #[ts(rename_all = "snake_case")]
 pub enum HookActivityStatus {
     Blocked,
+    /// A `PreToolUse` hook replaced the tool input before execution.
+    Rewritten,
+    /// A `Stop` hook blocked the agent from finishing and the turn continued.
+    Continued,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -902,6 +931,27 @@ pub struct HookActivityEvent {
     pub reason: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SubAgentActivityEvent {
+    /// Thread id of the sub-agent that emitted `msg`.
+    pub agent_id: ThreadId,
+    /// Sub-agent turn (submission) id that `msg` belongs to.
+    pub turn_id: String,
+    /// Name of the `[agents]` profile the sub-agent was spawned with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub agent: Option<String>,
+    /// The event emitted by the sub-agent.
+    pub msg: Box<EventMsg>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct NetworkAccessDeniedEvent {
+    /// Host a sandboxed command tried to reach.
+    pub host: String,
+    pub port: u16,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -1335,6 +1385,13 @@ pub struct SessionMeta {
     #[serde(default)]
     pub source: SessionSource,
     pub model_provider: Option<String>,
+    /// User-visible title set via `thread/update`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// User-assigned tags set via `thread/update`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Default for SessionMeta {
@@ -1348,6 +1405,8 @@ impl Default for SessionMeta {
             instructions: None,
             source: SessionSource::default(),
             model_provider: None,
+            title: None,
+            tags: Vec::new(),
         }
     }
 }
@@ -1395,6 +1454,10 @@ pub struct TurnContextItem {
     pub approval_policy: AskForApproval,
     pub sandbox_policy: SandboxPolicy,
     pub model: String,
+    /// Provider that served the turn; differs from the configured one after a
+    /// failover.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<ReasoningEffortConfig>,
     pub summary: ReasoningSummaryConfig,
@@ -1675,6 +1738,12 @@ pub struct ThreadRolledBackEvent {
     pub num_turns: u32,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnSteeredEvent {
+    /// The input that was appended to the running turn.
+    pub items: Vec<UserInput>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -403,6 +403,7 @@ async fn make_chatwidget_manual(
         rate_limit_poller: None,
         stream_controller: None,
         running_commands: HashMap::new(),
+        sub_agent_cells: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
         last_unified_wait: None,
         task_complete_pending: false,
@@ -744,6 +745,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -788,6 +790,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -838,6 +841,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         proposed_execpolicy_amendment: None,
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -1096,6 +1100,44 @@ async fn streaming_final_answer_keeps_task_running_state() {
     assert!(chat.bottom_pane.ctrl_c_quit_hint_visible());
 }
 
+#[tokio::test]
+async fn steer_command_sends_input_to_running_turn() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.on_task_started();
+    chat.bottom_pane
+        .set_composer_text("/steer keep the public API".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    match op_rx.try_recv() {
+        Ok(Op::SteerTurn {
+            items,
+            expected_turn_id: None,
+        }) => assert_eq!(
+            items,
+            vec![UserInput::Text {
+                text: "keep the public API".to_string(),
+            }]
+        ),
+        other => panic!("expected Op::SteerTurn, got {other:?}"),
+    }
+    assert!(chat.queued_user_messages.is_empty());
+    drain_insert_history(&mut rx);
+
+    chat.handle_codex_event(Event {
+        id: "turn".into(),
+        msg: EventMsg::TurnSteered(TurnSteeredEvent {
+            items: vec![UserInput::Text {
+                text: "keep the public API".to_string(),
+            }],
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1);
+    assert!(lines_to_single_string(&cells[0]).contains("keep the public API"));
+    assert!(chat.bottom_pane.is_task_running());
+}
+
 #[tokio::test]
 async fn ctrl_c_shutdown_ignores_caps_lock() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
@@ -2476,6 +2518,7 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
             "hello".into(),
             "world".into(),
         ])),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2529,6 +2572,7 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
             "hello".into(),
             "world".into(),
         ])),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2569,6 +2613,7 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2812,6 +2857,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "echo".into(),
             "hello world".into(),
         ])),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -28,11 +28,14 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::protocol::EventMsg;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::HookActivityEvent;
+use codex_core::protocol::HookActivityStatus;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
@@ -1315,12 +1318,19 @@ pub(crate) fn new_warning_event(message: String) -> PrefixedWrappedHistoryCell {
 }
 
 pub(crate) fn new_hook_activity_event(event: HookActivityEvent) -> PlainHistoryCell {
+    let (status_label, hook_prefix, fallback_label) = match event.status {
+        HookActivityStatus::Blocked => (" (blocked)", "blocked by hook", "Blocked tool"),
+        HookActivityStatus::Rewritten => {
+            (" (input rewritten)", "rewritten by hook", "Rewrote tool")
+        }
+        HookActivityStatus::Continued => ("", "stop blocked by hook", "Continued turn"),
+    };
     let tool_label = event
         .tool
         .map(|tool| format!("{} {}", tool.past_tense, tool.name))
-        .unwrap_or_else(|| "Blocked tool".to_string());
+        .unwrap_or_else(|| fallback_label.to_string());
     let mut lines: Vec<Line<'static>> =
-        vec![vec!["• ".dim(), tool_label.bold(), " (blocked)".dim()].into()];
+        vec![vec!["• ".dim(), tool_label.bold(), status_label.dim()].into()];
 
     let hook_names = if event.hooks.is_empty() {
         None
@@ -1335,8 +1345,8 @@ pub(crate) fn new_hook_activity_event(event: HookActivityEvent) -> PlainHistoryC
         )
     };
     let hook_line = match hook_names {
-        Some(names) => format!("blocked by hook: {names}"),
-        None => "blocked by hook".to_string(),
+        Some(names) => format!("{hook_prefix}: {names}"),
+        None => hook_prefix.to_string(),
     };
     lines.push(vec!["  └ ".dim(), hook_line.dim()].into());
 
@@ -1354,6 +1364,165 @@ pub(crate) fn new_hook_activity_event(event: HookActivityEvent) -> PlainHistoryC
     PlainHistoryCell { lines }
 }
 
+/// Activity lines shown inline for a sub-agent; the transcript shows all of them.
+const SUB_AGENT_ACTIVITY_PREVIEW_LINES: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+enum SubAgentOutcome {
+    Running,
+    Completed,
+    Interrupted,
+    Failed(String),
+    Shutdown,
+}
+
+/// Summary of one sub-agent turn. Inline it shows the latest activity and the first line of the
+/// sub-agent's final message; the transcript view expands every line.
+#[derive(Debug)]
+pub(crate) struct SubAgentCell {
+    label: String,
+    activity: Vec<String>,
+    last_message: Option<String>,
+    outcome: SubAgentOutcome,
+}
+
+impl SubAgentCell {
+    pub(crate) fn new(agent_id: ThreadId, agent: Option<String>) -> Self {
+        let short_id: String = agent_id.to_string().chars().take(8).collect();
+        let label = match agent {
+            Some(agent) => format!("{agent} ({short_id})"),
+            None => short_id,
+        };
+        Self {
+            label,
+            activity: Vec::new(),
+            last_message: None,
+            outcome: SubAgentOutcome::Running,
+        }
+    }
+
+    /// Fold a sub-agent event into the cell. Returns `true` once the sub-agent's turn has ended
+    /// and the cell is ready to be inserted into history.
+    pub(crate) fn apply_event(&mut self, msg: EventMsg, cwd: &Path) -> bool {
+        match msg {
+            EventMsg::ExecCommandBegin(ev) => {
+                self.activity
+                    .push(format!("Ran {}", strip_bash_lc_and_escape(&ev.command)));
+            }
+            EventMsg::PatchApplyBegin(ev) => {
+                let mut paths = ev
+                    .changes
+                    .keys()
+                    .map(|path| display_path_for(path, cwd))
+                    .collect::<Vec<_>>();
+                paths.sort();
+                self.activity
+                    .extend(paths.into_iter().map(|path| format!("Edited {path}")));
+            }
+            EventMsg::McpToolCallBegin(ev) => {
+                self.activity.push(format!(
+                    "Called {}.{}",
+                    ev.invocation.server, ev.invocation.tool
+                ));
+            }
+            EventMsg::WebSearchEnd(ev) => {
+                self.activity.push(format!("Searched {}", ev.query));
+            }
+            EventMsg::AgentMessage(ev) => {
+                self.last_message = Some(ev.message);
+            }
+            EventMsg::TurnComplete(ev) => {
+                if let Some(message) = ev.last_agent_message {
+                    self.last_message = Some(message);
+                }
+                self.outcome = SubAgentOutcome::Completed;
+                return true;
+            }
+            EventMsg::TurnAborted(_) => {
+                self.outcome = SubAgentOutcome::Interrupted;
+                return true;
+            }
+            EventMsg::Error(ev) => {
+                self.outcome = SubAgentOutcome::Failed(ev.message);
+                return true;
+            }
+            EventMsg::ShutdownComplete => {
+                self.outcome = SubAgentOutcome::Shutdown;
+                return !self.activity.is_empty() || self.last_message.is_some();
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn lines(&self, preview: bool) -> Vec<Line<'static>> {
+        let status = match &self.outcome {
+            SubAgentOutcome::Running => "running",
+            SubAgentOutcome::Completed => "completed",
+            SubAgentOutcome::Interrupted => "interrupted",
+            SubAgentOutcome::Failed(_) => "failed",
+            SubAgentOutcome::Shutdown => "shut down",
+        };
+        let mut lines: Vec<Line<'static>> = vec![
+            vec![
+                "• ".dim(),
+                "Sub-agent ".bold(),
+                self.label.clone().bold(),
+                format!(" {status}").dim(),
+            ]
+            .into(),
+        ];
+
+        let omitted = if preview {
+            self.activity
+                .len()
+                .saturating_sub(SUB_AGENT_ACTIVITY_PREVIEW_LINES)
+        } else {
+            0
+        };
+        let mut details: Vec<Line<'static>> = Vec::new();
+        if omitted > 0 {
+            details.push(format!("… +{omitted} earlier").dim().into());
+        }
+        details.extend(
+            self.activity[omitted..]
+                .iter()
+                .map(|activity| Line::from(activity.clone().dim())),
+        );
+        if let SubAgentOutcome::Failed(message) = &self.outcome {
+            details.push(message.clone().red().into());
+        }
+        if let Some(message) = &self.last_message {
+            let mut message_lines = message.lines();
+            if preview {
+                if let Some(first) = message_lines.next() {
+                    let ellipsis = if message_lines.next().is_some() {
+                        " …"
+                    } else {
+                        ""
+                    };
+                    details.push(format!("{first}{ellipsis}").into());
+                }
+            } else {
+                details.extend(message_lines.map(|line| Line::from(line.to_string())));
+            }
+        }
+
+        lines.extend(prefix_lines(details, "  └ ".dim(), "    ".into()));
+        lines
+    }
+}
+
+impl HistoryCell for SubAgentCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines(true)
+    }
+
+    fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines(false)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct DeprecationNoticeCell {
     summary: String,
@@ -1794,6 +1963,8 @@ mod tests {
     use codex_core::protocol::HookActivityStatus;
     use codex_core::protocol::HookActivityTool;
     use codex_core::protocol::McpAuthStatus;
+    use codex_core::protocol::TurnCompleteEvent;
+    use codex_core::protocol::WebSearchEndEvent;
     use codex_protocol::parse_command::ParsedCommand;
     use dirs::home_dir;
     use pretty_assertions::assert_eq;
@@ -1889,6 +2060,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hook_activity_history_cell_renders_rewritten_tree() {
+        let cell = new_hook_activity_event(HookActivityEvent {
+            status: HookActivityStatus::Rewritten,
+            reason: None,
+            tool: Some(HookActivityTool {
+                name: "shell_command".into(),
+                past_tense: "Ran".into(),
+            }),
+            hooks: vec![HookActivityHook {
+                name: "redact-secrets".into(),
+                decision: "rewrite".into(),
+            }],
+        });
+
+        let rendered = render_lines(&cell.display_lines(64));
+        assert_eq!(
+            rendered,
+            vec![
+                "• Ran shell_command (input rewritten)".to_string(),
+                "  └ rewritten by hook: redact-secrets".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn hook_activity_history_cell_renders_continued_turn() {
+        let cell = new_hook_activity_event(HookActivityEvent {
+            status: HookActivityStatus::Continued,
+            reason: Some("tests still failing".into()),
+            tool: None,
+            hooks: vec![HookActivityHook {
+                name: "require-tests".into(),
+                decision: "block".into(),
+            }],
+        });
+
+        let rendered = render_lines(&cell.display_lines(64));
+        assert_eq!(
+            rendered,
+            vec![
+                "• Continued turn".to_string(),
+                "  └ stop blocked by hook: require-tests".to_string(),
+                "    └ tests still failing".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn sub_agent_cell_collapses_activity_until_transcript() {
+        let agent_id = ThreadId::new();
+        let short_id: String = agent_id.to_string().chars().take(8).collect();
+        let mut cell = SubAgentCell::new(agent_id, Some("reviewer".to_string()));
+        for query in ["one", "two", "three", "four"] {
+            let done = cell.apply_event(
+                EventMsg::WebSearchEnd(WebSearchEndEvent {
+                    call_id: format!("call-{query}"),
+                    query: query.to_string(),
+                }),
+                Path::new("/repo"),
+            );
+            assert!(!done);
+        }
+        let done = cell.apply_event(
+            EventMsg::TurnComplete(TurnCompleteEvent {
+                last_agent_message: Some("Looks good.\nNo issues found.".to_string()),
+            }),
+            Path::new("/repo"),
+        );
+        assert!(done);
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                format!("• Sub-agent reviewer ({short_id}) completed"),
+                "  └ … +1 earlier".to_string(),
+                "    Searched two".to_string(),
+                "    Searched three".to_string(),
+                "    Searched four".to_string(),
+                "    Looks good. …".to_string(),
+            ]
+        );
+        assert_eq!(
+            render_transcript(&cell),
+            vec![
+                format!("• Sub-agent reviewer ({short_id}) completed"),
+                "  └ Searched one".to_string(),
+                "    Searched two".to_string(),
+                "    Searched three".to_string(),
+                "    Searched four".to_string(),
+                "    Looks good.".to_string(),
+                "    No issues found.".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn ps_output_empty_snapshot() {
         let cell = new_unified_exec_processes_output(Vec::new());
@@ -19,6 +19,7 @@ pub enum SlashCommand {
     Experimental,
     Skills,
     Review,
+    Steer,
     New,
     Resume,
     Init,
@@ -46,6 +47,7 @@ impl SlashCommand {
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Steer => "add guidance to the running turn without interrupting it",
             SlashCommand::Resume => "resume a saved chat",
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
@@ -86,6 +88,7 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Steer
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
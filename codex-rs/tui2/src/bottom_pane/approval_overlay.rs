@@ -20,6 +20,9 @@ use codex_core::features::Feature;
 use codex_core::features::Features;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
+use codex_core::protocol::ExecPolicyDecision;
+use codex_core::protocol::ExecPolicyExplanation;
+use codex_core::protocol::ExecPolicyLayer;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
@@ -44,6 +47,7 @@ pub(crate) enum ApprovalRequest {
         command: Vec<String>,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        explanation: Option<ExecPolicyExplanation>,
     },
     ApplyPatch {
         id: String,
@@ -343,12 +347,20 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 command,
                 reason,
                 proposed_execpolicy_amendment,
+                explanation,
             } => {
                 let mut header: Vec<Line<'static>> = Vec::new();
                 if let Some(reason) = reason {
                     header.push(Line::from(vec!["Reason: ".into(), reason.italic()]));
                     header.push(Line::from(""));
                 }
+                if let Some(explanation) = explanation {
+                    let explanation_lines = explanation_lines(&explanation);
+                    if !explanation_lines.is_empty() {
+                        header.extend(explanation_lines);
+                        header.push(Line::from(""));
+                    }
+                }
                 let full_cmd = strip_bash_lc_and_escape(&command);
                 let mut full_cmd_lines = highlight_bash_to_lines(&full_cmd);
                 if let Some(first) = full_cmd_lines.first_mut() {
@@ -409,6 +421,56 @@ impl From<ApprovalRequest> for ApprovalRequestState {
     }
 }
 
+/// Describe the execpolicy rules (or, failing that, the heuristic) that required approval.
+/// Rules that merely allowed part of the command are left out.
+fn explanation_lines(explanation: &ExecPolicyExplanation) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = explanation
+        .matched_rules
+        .iter()
+        .filter(|rule| rule.decision != ExecPolicyDecision::Allow)
+        .map(|rule| {
+            let decision = match rule.decision {
+                ExecPolicyDecision::Allow => "allow",
+                ExecPolicyDecision::Prompt => "prompt",
+                ExecPolicyDecision::Forbidden => "forbidden",
+            };
+            let mut spans: Vec<Span<'static>> = vec![
+                "Decided by: ".into(),
+                format!("`{}`", rule.matched_prefix.join(" ")).cyan(),
+                format!(" ({decision})").dim(),
+            ];
+            let layer = rule.layer.map(|layer| match layer {
+                ExecPolicyLayer::System => "system",
+                ExecPolicyLayer::User => "user",
+                ExecPolicyLayer::Project => "project",
+            });
+            match (layer, &rule.source) {
+                (Some(layer), Some(source)) => {
+                    spans.push(format!(" in {layer} rules {}", source.display()).dim());
+                }
+                (None, Some(source)) => {
+                    spans.push(format!(" in {}", source.display()).dim());
+                }
+                (_, None) => spans.push(" added this session".dim()),
+            }
+            if let Some(justification) = &rule.justification {
+                spans.push(": ".into());
+                spans.push(justification.clone().italic());
+            }
+            Line::from(spans)
+        })
+        .collect();
+    if lines.is_empty()
+        && let Some(heuristic) = &explanation.heuristic
+    {
+        lines.push(Line::from(vec![
+            "Decided by: ".into(),
+            heuristic.clone().italic(),
+        ]));
+    }
+    lines
+}
+
 #[derive(Clone)]
 enum ApprovalVariant {
     Exec {
@@ -540,6 +602,7 @@ fn elicitation_options() -> Vec<ApprovalOption> {
 mod tests {
     use super::*;
     use crate::app_event::AppEvent;
+    use codex_core::protocol::ExecPolicyRuleMatch;
     use pretty_assertions::assert_eq;
     use tokio::sync::mpsc::unbounded_channel;
 
@@ -549,6 +612,7 @@ mod tests {
             command: vec!["echo".to_string(), "hi".to_string()],
             reason: Some("reason".to_string()),
             proposed_execpolicy_amendment: None,
+            explanation: None,
         }
     }
 
@@ -593,6 +657,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                explanation: None,
             },
             tx,
             Features::with_defaults(),
@@ -631,6 +696,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                explanation: None,
             },
             tx,
             {
@@ -655,6 +721,7 @@ mod tests {
             command,
             reason: None,
             proposed_execpolicy_amendment: None,
+            explanation: None,
         };
 
         let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
@@ -676,6 +743,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn header_includes_policy_explanation() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let exec_request = ApprovalRequest::Exec {
+            id: "test".into(),
+            command: vec!["git".into(), "push".into()],
+            reason: None,
+            proposed_execpolicy_amendment: None,
+            explanation: Some(ExecPolicyExplanation {
+                matched_rules: vec![ExecPolicyRuleMatch {
+                    matched_prefix: vec!["git".into(), "push".into()],
+                    decision: ExecPolicyDecision::Prompt,
+                    justification: Some("pushes need review".into()),
+                    source: Some(PathBuf::from("/repo/.codex/rules/git.rules")),
+                    layer: Some(ExecPolicyLayer::Project),
+                }],
+                heuristic: None,
+            }),
+        };
+
+        let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
+        let width = 120;
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, view.desired_height(width)));
+        view.render(Rect::new(0, 0, width, view.desired_height(width)), &mut buf);
+
+        let rendered: Vec<String> = (0..buf.area.height)
+            .map(|row| {
+                (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        assert!(
+            rendered.iter().any(|line| line.contains(
+                "Decided by: `git push` (prompt) in project rules /repo/.codex/rules/git.rules: \
+                 pushes need review"
+            )),
+            "expected header to include the deciding rule, got {rendered:?}"
+        );
+    }
+
+    #[test]
+    fn explanation_falls_back_to_heuristic() {
+        let explanation = ExecPolicyExplanation {
+            matched_rules: vec![ExecPolicyRuleMatch {
+                matched_prefix: vec!["cat".into()],
+                decision: ExecPolicyDecision::Allow,
+                justification: None,
+                source: None,
+                layer: None,
+            }],
+            heuristic: Some("`rm -rf build` looks dangerous".into()),
+        };
+        let rendered: Vec<String> = explanation_lines(&explanation)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect();
+        assert_eq!(
+            rendered,
+            vec!["Decided by: `rm -rf build` looks dangerous".to_string()]
+        );
+    }
+
     #[test]
     fn exec_history_cell_wraps_with_two_space_indent() {
         let command = vec![
@@ -23,6 +23,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CodexErrorInfo;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -42,6 +43,7 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::NetworkAccessDeniedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -49,12 +51,14 @@ use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillsListEntry;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::SubAgentActivityEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnSteeredEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::UserMessageEvent;
@@ -110,6 +114,7 @@ use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
+use crate::history_cell::SubAgentCell;
 use crate::markdown::append_markdown;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
@@ -302,6 +307,8 @@ pub(crate) struct ChatWidget {
     // Stream lifecycle controller
     stream_controller: Option<StreamController>,
     running_commands: HashMap<String, RunningCommand>,
+    // Activity of sub-agents whose current turn has not ended yet, keyed by sub-agent thread id.
+    sub_agent_cells: HashMap<ThreadId, SubAgentCell>,
     suppressed_exec_calls: HashSet<String>,
     last_unified_wait: Option<UnifiedExecWaitState>,
     task_complete_pending: bool,
@@ -872,6 +879,34 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_network_access_denied(&mut self, event: NetworkAccessDeniedEvent) {
+        let NetworkAccessDeniedEvent { host, port } = event;
+        self.on_warning(format!(
+            "Sandbox blocked network access to {host}:{port}. Add it to \
+             `sandbox_workspace_write.allowed_domains` to allow it."
+        ));
+    }
+
+    fn on_sub_agent_activity(&mut self, event: SubAgentActivityEvent) {
+        let SubAgentActivityEvent {
+            agent_id,
+            agent,
+            msg,
+            ..
+        } = event;
+        let cell = self
+            .sub_agent_cells
+            .entry(agent_id)
+            .or_insert_with(|| SubAgentCell::new(agent_id, agent));
+        if cell.apply_event(*msg, &self.config.cwd)
+            && let Some(cell) = self.sub_agent_cells.remove(&agent_id)
+        {
+            self.flush_answer_stream_with_separator();
+            self.add_to_history(cell);
+            self.request_redraw();
+        }
+    }
+
     fn on_patch_apply_end(&mut self, event: codex_core::protocol::PatchApplyEndEvent) {
         let ev2 = event.clone();
         self.defer_or_handle(
@@ -1124,6 +1159,7 @@ impl ChatWidget {
             command: ev.command,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
+            explanation: ev.explanation,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -1327,6 +1363,7 @@ impl ChatWidget {
             rate_limit_poller: None,
             stream_controller: None,
             running_commands: HashMap::new(),
+            sub_agent_cells: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
             task_complete_pending: false,
@@ -1411,6 +1448,7 @@ impl ChatWidget {
             rate_limit_poller: None,
             stream_controller: None,
             running_commands: HashMap::new(),
+            sub_agent_cells: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
             task_complete_pending: false,
@@ -1653,6 +1691,12 @@ impl ChatWidget {
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
+            SlashCommand::Steer => {
+                self.add_info_message(
+                    "Usage: /steer <message>".to_string(),
+                    Some("Adds guidance to the running turn without interrupting it.".to_string()),
+                );
+            }
             SlashCommand::Skills => {
                 self.insert_str("$");
             }
@@ -1736,6 +1780,7 @@ impl ChatWidget {
                     },
                 });
             }
+            SlashCommand::Steer if !trimmed.is_empty() => self.steer_turn(trimmed.to_string()),
             _ => self.dispatch_command(cmd),
         }
     }
@@ -1926,6 +1971,11 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
+            // A rejected steer does not end the running turn.
+            EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::TurnSteerFailed),
+            }) => self.on_warning(format!("Steer not delivered: {message}")),
             EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
@@ -1959,6 +2009,8 @@ impl ChatWidget {
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::HookActivity(ev) => self.on_hook_activity(ev),
+            EventMsg::SubAgentActivity(ev) => self.on_sub_agent_activity(ev),
+            EventMsg::NetworkAccessDenied(ev) => self.on_network_access_denied(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
@@ -1996,6 +2048,7 @@ impl ChatWidget {
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
+            EventMsg::TurnSteered(ev) => self.on_turn_steered(ev),
             EventMsg::RawResponseItem(_)
             | EventMsg::ThreadRolledBack(_)
             | EventMsg::ItemStarted(_)
@@ -2055,6 +2108,37 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Sends `text` to the running turn, or starts a new turn when idle.
+    fn steer_turn(&mut self, text: String) {
+        if !self.bottom_pane.is_task_running() {
+            self.submit_user_message(text.into());
+            return;
+        }
+        self.submit_op(Op::SteerTurn {
+            items: vec![UserInput::Text { text }],
+            expected_turn_id: None,
+        });
+        self.add_info_message(
+            "Steer queued; it will be sent with the next model request.".to_string(),
+            None,
+        );
+    }
+
+    fn on_turn_steered(&mut self, event: TurnSteeredEvent) {
+        let message = event
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                UserInput::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !message.trim().is_empty() {
+            self.add_to_history(history_cell::new_user_prompt(message));
+        }
+    }
+
     fn on_user_message_event(&mut self, event: UserMessageEvent) {
         let message = event.message.trim();
         if !message.is_empty() {
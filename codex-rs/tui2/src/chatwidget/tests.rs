@@ -392,6 +392,7 @@ async fn make_chatwidget_manual(
         rate_limit_poller: None,
         stream_controller: None,
         running_commands: HashMap::new(),
+        sub_agent_cells: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
         last_unified_wait: None,
         task_complete_pending: false,
@@ -731,6 +732,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -775,6 +777,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         proposed_execpolicy_amendment: None,
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -825,6 +828,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         proposed_execpolicy_amendment: None,
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2132,6 +2136,7 @@ async fn approval_modal_exec_snapshot() {
             "hello".into(),
             "world".into(),
         ])),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2183,6 +2188,7 @@ async fn approval_modal_exec_without_reason_snapshot() {
             "hello".into(),
             "world".into(),
         ])),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2220,6 +2226,7 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2439,6 +2446,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "echo".into(),
             "hello world".into(),
         ])),
+        explanation: None,
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
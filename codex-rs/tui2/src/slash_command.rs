@@ -18,6 +18,7 @@ pub enum SlashCommand {
     ElevateSandbox,
     Skills,
     Review,
+    Steer,
     New,
     Resume,
     Init,
@@ -44,6 +45,7 @@ impl SlashCommand {
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Steer => "add guidance to the running turn without interrupting it",
             SlashCommand::Resume => "resume a saved chat",
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
@@ -81,6 +83,7 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Steer
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
@@ -38,6 +38,7 @@ pub struct ExecCommandSession {
     wait_handle: StdMutex<Option<JoinHandle<()>>>,
     exit_status: Arc<AtomicBool>,
     exit_code: Arc<StdMutex<Option<i32>>>,
+    exit_signal: Arc<StdMutex<Option<i32>>>,
     // PtyPair must be preserved because the process will receive Control+C if the
     // slave is closed
     _pair: StdMutex<PtyPairWrapper>,
@@ -61,6 +62,7 @@ impl ExecCommandSession {
         wait_handle: JoinHandle<()>,
         exit_status: Arc<AtomicBool>,
         exit_code: Arc<StdMutex<Option<i32>>>,
+        exit_signal: Arc<StdMutex<Option<i32>>>,
         pair: PtyPairWrapper,
     ) -> (Self, broadcast::Receiver<Vec<u8>>) {
         (
@@ -73,6 +75,7 @@ impl ExecCommandSession {
                 wait_handle: StdMutex::new(Some(wait_handle)),
                 exit_status,
                 exit_code,
+                exit_signal,
                 _pair: StdMutex::new(pair),
             },
             initial_output_rx,
@@ -95,6 +98,11 @@ impl ExecCommandSession {
         self.exit_code.lock().ok().and_then(|guard| *guard)
     }
 
+    /// The signal that terminated the process, if it was killed by one.
+    pub fn exit_signal(&self) -> Option<i32> {
+        self.exit_signal.lock().ok().and_then(|guard| *guard)
+    }
+
     pub fn terminate(&self) {
         if let Ok(mut killer_opt) = self.killer.lock() {
             if let Some(mut killer) = killer_opt.take() {
@@ -152,6 +160,22 @@ fn platform_native_pty_system() -> Box<dyn portable_pty::PtySystem + Send> {
     native_pty_system()
 }
 
+/// portable-pty only reports the `strsignal(3)` description of the signal that
+/// killed a child, so look up the signal with that description.
+#[cfg(unix)]
+fn signal_number(description: &str) -> Option<i32> {
+    (1..32).find(|&signal| {
+        let text = unsafe { libc::strsignal(signal) };
+        !text.is_null()
+            && unsafe { std::ffi::CStr::from_ptr(text) }.to_string_lossy() == description
+    })
+}
+
+#[cfg(not(unix))]
+fn signal_number(_description: &str) -> Option<i32> {
+    None
+}
+
 pub async fn spawn_pty_process(
     program: &str,
     args: &[String],
@@ -228,11 +252,19 @@ pub async fn spawn_pty_process(
     let wait_exit_status = Arc::clone(&exit_status);
     let exit_code = Arc::new(StdMutex::new(None));
     let wait_exit_code = Arc::clone(&exit_code);
+    let exit_signal = Arc::new(StdMutex::new(None));
+    let wait_exit_signal = Arc::clone(&exit_signal);
     let wait_handle: JoinHandle<()> = tokio::task::spawn_blocking(move || {
-        let code = match child.wait() {
-            Ok(status) => status.exit_code() as i32,
-            Err(_) => -1,
+        let (code, signal) = match child.wait() {
+            Ok(status) => (
+                status.exit_code() as i32,
+                status.signal().and_then(signal_number),
+            ),
+            Err(_) => (-1, None),
         };
+        if let Ok(mut guard) = wait_exit_signal.lock() {
+            *guard = signal;
+        }
         wait_exit_status.store(true, std::sync::atomic::Ordering::SeqCst);
         if let Ok(mut guard) = wait_exit_code.lock() {
             *guard = Some(code);
@@ -260,6 +292,7 @@ pub async fn spawn_pty_process(
         wait_handle,
         exit_status,
         exit_code,
+        exit_signal,
         pair,
     );
 
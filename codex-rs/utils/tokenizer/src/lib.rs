@@ -0,0 +1,288 @@
+//! Offline BPE token counting.
+//!
+//! Wraps the vocabularies bundled with `tiktoken-rs` so token budgets can be
+//! enforced without a network round-trip. Vocabularies are parsed once per
+//! process and shared by every [`Tokenizer`].
+
+use std::sync::LazyLock;
+
+use thiserror::Error;
+use tiktoken_rs::CoreBPE;
+
+/// BPE vocabulary used to split text into tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EncodingKind {
+    /// Vocabulary used by GPT-4o and later OpenAI models.
+    #[default]
+    O200kBase,
+    /// Vocabulary used by GPT-4 and GPT-3.5 era models.
+    Cl100kBase,
+}
+
+#[derive(Debug, Error)]
+pub enum TokenizerError {
+    #[error("failed to load {kind:?} vocabulary: {message}")]
+    Load { kind: EncodingKind, message: String },
+}
+
+/// Generous bytes-per-token bound used to size the windows encoded by
+/// [`Tokenizer::prefix_len`] and [`Tokenizer::suffix_start`]. Windows double
+/// until they hold more than the requested number of tokens.
+const WINDOW_BYTES_PER_TOKEN: usize = 8;
+
+static O200K_BASE: LazyLock<Result<CoreBPE, String>> =
+    LazyLock::new(|| tiktoken_rs::o200k_base().map_err(|err| err.to_string()));
+static CL100K_BASE: LazyLock<Result<CoreBPE, String>> =
+    LazyLock::new(|| tiktoken_rs::cl100k_base().map_err(|err| err.to_string()));
+
+/// Cheap, copyable handle to a shared vocabulary.
+#[derive(Clone, Copy)]
+pub struct Tokenizer {
+    kind: EncodingKind,
+    bpe: &'static CoreBPE,
+}
+
+impl std::fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tokenizer")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Tokenizer {
+    /// Returns the tokenizer for `kind`, loading its vocabulary on first use.
+    pub fn new(kind: EncodingKind) -> Result<Self, TokenizerError> {
+        let vocab = match kind {
+            EncodingKind::O200kBase => &*O200K_BASE,
+            EncodingKind::Cl100kBase => &*CL100K_BASE,
+        };
+        match vocab {
+            Ok(bpe) => Ok(Self { kind, bpe }),
+            Err(message) => Err(TokenizerError::Load {
+                kind,
+                message: message.clone(),
+            }),
+        }
+    }
+
+    /// Returns the tokenizer for a model slug, falling back to `o200k_base`
+    /// for models `tiktoken` does not know about.
+    pub fn for_model(model: &str) -> Result<Self, TokenizerError> {
+        Self::new(encoding_for_model(model))
+    }
+
+    pub fn kind(&self) -> EncodingKind {
+        self.kind
+    }
+
+    /// Encodes `text` without recognizing special tokens, matching how the
+    /// API counts user-provided content.
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        self.bpe.encode_ordinary(text)
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        self.encode(text).len()
+    }
+
+    /// Whether `text` is at most `budget` tokens.
+    ///
+    /// Every token covers at least one byte, so text no longer than the budget
+    /// in bytes fits without being tokenized; most tool output and messages
+    /// take this path.
+    pub fn fits_within(&self, text: &str, budget: usize) -> bool {
+        text.len() <= budget || self.count(text) <= budget
+    }
+
+    /// Byte length of the prefix of `text` covered by its first `budget`
+    /// tokens, shortened to end on a char boundary. Only a window at the start of
+    /// `text` is encoded, so this stays cheap for very large inputs.
+    pub fn prefix_len(&self, text: &str, budget: usize) -> usize {
+        if budget == 0 {
+            return 0;
+        }
+        let mut window = budget.saturating_mul(WINDOW_BYTES_PER_TOKEN);
+        loop {
+            let end = floor_char_boundary(text, window.min(text.len()));
+            let tokens = self.encode(&text[..end]);
+            if tokens.len() > budget || end == text.len() {
+                return self.leading_text_len(&tokens[..budget.min(tokens.len())]);
+            }
+            window = window.saturating_mul(2);
+        }
+    }
+
+    /// Byte offset where the suffix of `text` covered by its last `budget`
+    /// tokens starts, moved forward to a char boundary. Like
+    /// [`Tokenizer::prefix_len`], only a window at the end of `text` is
+    /// encoded.
+    pub fn suffix_start(&self, text: &str, budget: usize) -> usize {
+        if budget == 0 {
+            return text.len();
+        }
+        let mut window = budget.saturating_mul(WINDOW_BYTES_PER_TOKEN);
+        loop {
+            let start = ceil_char_boundary(text, text.len().saturating_sub(window));
+            let tokens = self.encode(&text[start..]);
+            if tokens.len() > budget || start == 0 {
+                let kept = &tokens[tokens.len().saturating_sub(budget)..];
+                return text.len() - self.trailing_text_len(kept);
+            }
+            window = window.saturating_mul(2);
+        }
+    }
+
+    /// Byte length of the longest leading run of `tokens` that decodes to
+    /// whole characters. A character split across tokens is dropped when
+    /// only part of it is in `tokens`.
+    fn leading_text_len(&self, mut tokens: &[u32]) -> usize {
+        loop {
+            match self.bpe.decode(tokens.to_vec()) {
+                Ok(text) => return text.len(),
+                Err(_) if !tokens.is_empty() => tokens = &tokens[..tokens.len() - 1],
+                Err(_) => return 0,
+            }
+        }
+    }
+
+    /// Like [`Tokenizer::leading_text_len`], for the trailing run of `tokens`.
+    fn trailing_text_len(&self, mut tokens: &[u32]) -> usize {
+        loop {
+            match self.bpe.decode(tokens.to_vec()) {
+                Ok(text) => return text.len(),
+                Err(_) if !tokens.is_empty() => tokens = &tokens[1..],
+                Err(_) => return 0,
+            }
+        }
+    }
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+/// Picks the vocabulary for a model slug.
+pub fn encoding_for_model(model: &str) -> EncodingKind {
+    match tiktoken_rs::tokenizer::get_tokenizer(model) {
+        Some(tiktoken_rs::tokenizer::Tokenizer::Cl100kBase) => EncodingKind::Cl100kBase,
+        _ => EncodingKind::O200kBase,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Instant;
+
+    fn tokenizer() -> Tokenizer {
+        Tokenizer::new(EncodingKind::O200kBase).expect("o200k vocabulary")
+    }
+
+    #[test]
+    fn counts_tokens_with_o200k() {
+        let tokenizer = tokenizer();
+        assert_eq!(tokenizer.count(""), 0);
+        assert_eq!(tokenizer.count("hello world"), 2);
+        assert_eq!(
+            tokenizer.encode("hello world").len(),
+            tokenizer.count("hello world")
+        );
+    }
+
+    #[test]
+    fn dense_punctuation_exceeds_byte_estimate() {
+        // JSON punctuation and short numbers mostly tokenize one byte at a
+        // time, so a bytes/4 estimate undercounts them.
+        let text = r#"{"a":[1,2,3],"b":{}}"#;
+        assert_eq!(text.len(), 20);
+        assert_eq!(tokenizer().count(text), 13);
+    }
+
+    #[test]
+    fn fits_within_short_circuits_on_byte_length() {
+        let tokenizer = tokenizer();
+        assert!(tokenizer.fits_within("abc", 3));
+        assert!(tokenizer.fits_within("hello world", 2));
+        assert!(!tokenizer.fits_within("hello world", 1));
+    }
+
+    #[test]
+    fn prefix_and_suffix_follow_token_boundaries() {
+        let tokenizer = tokenizer();
+        let text = "this is an example of a long output that should be truncated";
+        assert_eq!(&text[..tokenizer.prefix_len(text, 3)], "this is an");
+        assert_eq!(&text[tokenizer.suffix_start(text, 2)..], " be truncated");
+        assert_eq!(tokenizer.prefix_len(text, 100), text.len());
+        assert_eq!(tokenizer.suffix_start(text, 100), 0);
+        assert_eq!(tokenizer.prefix_len(text, 0), 0);
+        assert_eq!(tokenizer.suffix_start(text, 0), text.len());
+    }
+
+    #[test]
+    fn prefix_and_suffix_respect_char_boundaries() {
+        let tokenizer = tokenizer();
+        let text = "😀😀😀😀";
+        let prefix = tokenizer.prefix_len(text, 1);
+        let suffix = tokenizer.suffix_start(text, 1);
+        assert!(text.is_char_boundary(prefix));
+        assert!(text.is_char_boundary(suffix));
+        assert!(prefix <= suffix);
+    }
+
+    #[test]
+    fn picks_encoding_per_model() {
+        assert_eq!(encoding_for_model("gpt-4-0613"), EncodingKind::Cl100kBase);
+        assert_eq!(encoding_for_model("gpt-4o"), EncodingKind::O200kBase);
+        assert_eq!(encoding_for_model("gpt-5.1-codex"), EncodingKind::O200kBase);
+        assert_eq!(encoding_for_model("unknown-model"), EncodingKind::O200kBase);
+    }
+
+    /// Rough throughput check for the counting paths; run with
+    /// `cargo test -p codex-utils-tokenizer --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_count() {
+        let tokenizer = tokenizer();
+        let line = "fn main() { println!(\"{}\", std::env::args().count()); } // ok\n";
+        let text = line.repeat(16 * 1024);
+
+        let start = Instant::now();
+        let tokens = tokenizer.count(&text);
+        let elapsed = start.elapsed();
+        println!(
+            "count: {} bytes -> {tokens} tokens in {elapsed:?} ({:.1} MiB/s)",
+            text.len(),
+            text.len() as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64()
+        );
+
+        let start = Instant::now();
+        for _ in 0..1_000 {
+            assert!(tokenizer.fits_within(line, 1_000));
+        }
+        println!("fits_within fast path: 1000 calls in {:?}", start.elapsed());
+
+        let start = Instant::now();
+        let prefix = tokenizer.prefix_len(&text, 5_000);
+        let suffix = tokenizer.suffix_start(&text, 5_000);
+        println!(
+            "prefix/suffix windows: kept {} bytes in {:?}",
+            prefix + (text.len() - suffix),
+            start.elapsed()
+        );
+    }
+}